@@ -6,6 +6,8 @@ mod lib_on;
 #[cfg(any(not(feature = "hotpath"), feature = "hotpath-off"))]
 mod lib_off;
 
+mod heap_size;
+
 /// Initializes the hotpath profiling system and generates a performance report on program exit.
 ///
 /// This attribute macro should be applied to your program's main (or other entry point) function to enable profiling.
@@ -16,9 +18,10 @@ mod lib_off;
 /// # Parameters
 ///
 /// * `percentiles` - Array of percentile values (0-100) to display in the report. Default: `[95]`
-/// * `format` - Output format as a string: `"table"` (default), `"json"`, or `"json-pretty"`
+/// * `format` - Output format as a string: `"table"` (default), `"json"`, `"json-pretty"`, or `"folded"`
 /// * `limit` - Maximum number of functions to display in the report (0 = show all). Default: `15`
 /// * `timeout` - Optional timeout in milliseconds. If specified, the program will print the report and exit after the timeout.
+/// * `report_every` - Optional interval in milliseconds. If specified, a report of the measurements since the previous one is printed periodically while the program runs.
 ///
 /// # Examples
 ///
@@ -177,6 +180,9 @@ pub fn measure(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// # Parameters
 ///
 /// * `log` - If `true`, logs the result value when the future completes (requires `Debug` on return type)
+/// * `label` - Custom label shown instead of the function path
+/// * `tags` - Key/value pairs attached to every call, e.g. `tags(request_id = req.id)`. Values
+///   can use the function arguments and are formatted with `Display`.
 ///
 /// # Examples
 ///
@@ -200,6 +206,15 @@ pub fn measure(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// }
 /// ```
 ///
+/// With a label and the request id of every call:
+///
+/// ```rust,no_run
+/// #[hotpath::future_fn(label = "handle_request", tags(request_id = id))]
+/// async fn handle_request(id: u64) -> u64 {
+///     id * 2
+/// }
+/// ```
+///
 /// # See Also
 ///
 /// * [`measure`](macro@measure) - Attribute macro for instrumenting sync/async function timing
@@ -307,3 +322,30 @@ pub fn measure_all(attr: TokenStream, item: TokenStream) -> TokenStream {
         lib_off::measure_all_impl(attr, item)
     }
 }
+
+/// Derives [`HeapSize`](../hotpath/trait.HeapSize.html) by summing the heap size of every field.
+///
+/// Used by channels created with `channel!(…, size = true)` and streams created with
+/// `stream!(…, size = true)` to record the real memory held by each message. Type parameters
+/// get a `HeapSize` bound. The impl is generated with profiling disabled as well, so code
+/// relying on it compiles in both modes.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// #[derive(hotpath::HeapSize)]
+/// struct Request {
+///     path: String,
+///     body: Vec<u8>,
+/// }
+///
+/// #[derive(hotpath::HeapSize)]
+/// enum Event {
+///     Tick,
+///     Message(String),
+/// }
+/// ```
+#[proc_macro_derive(HeapSize)]
+pub fn derive_heap_size(item: TokenStream) -> TokenStream {
+    heap_size::derive_heap_size_impl(item)
+}
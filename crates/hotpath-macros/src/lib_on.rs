@@ -8,6 +8,7 @@ pub(crate) enum Format {
     Table,
     Json,
     JsonPretty,
+    Folded,
 }
 
 impl Format {
@@ -16,6 +17,7 @@ impl Format {
             Format::Table => quote!(hotpath::Format::Table),
             Format::Json => quote!(hotpath::Format::Json),
             Format::JsonPretty => quote!(hotpath::Format::JsonPretty),
+            Format::Folded => quote!(hotpath::Format::Folded),
         }
     }
 }
@@ -30,9 +32,10 @@ impl Format {
 /// # Parameters
 ///
 /// * `percentiles` - Array of percentile values (0-100) to display in the report. Default: `[95]`
-/// * `format` - Output format as a string: `"table"` (default), `"json"`, or `"json-pretty"`
+/// * `format` - Output format as a string: `"table"` (default), `"json"`, `"json-pretty"`, or `"folded"`
 /// * `limit` - Maximum number of functions to display in the report (0 = show all). Default: `15`
 /// * `timeout` - Optional timeout in milliseconds. If specified, the program will print the report and exit after the timeout.
+/// * `report_every` - Optional interval in milliseconds. If specified, a report of the measurements since the previous one is printed periodically while the program runs.
 ///
 /// # Examples
 ///
@@ -115,6 +118,7 @@ pub fn main_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
     let mut format = Format::Table;
     let mut limit: usize = 15;
     let mut timeout: Option<u64> = None;
+    let mut report_every: Option<u64> = None;
 
     // Parse named args like: percentiles=[..], format=".."
     if !attr.is_empty() {
@@ -152,8 +156,9 @@ pub fn main_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
                         "table" => Format::Table,
                         "json" => Format::Json,
                         "json-pretty" => Format::JsonPretty,
+                        "folded" => Format::Folded,
                         other => return Err(meta.error(format!(
-                            "Unknown format {:?}. Expected one of: \"table\", \"json\", \"json-pretty\"",
+                            "Unknown format {:?}. Expected one of: \"table\", \"json\", \"json-pretty\", \"folded\"",
                             other
                         ))),
                     };
@@ -174,8 +179,15 @@ pub fn main_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
                 return Ok(());
             }
 
+            if meta.path.is_ident("report_every") {
+                meta.input.parse::<syn::Token![=]>()?;
+                let li: LitInt = meta.input.parse()?;
+                report_every = Some(li.base10_parse()?);
+                return Ok(());
+            }
+
             Err(meta.error(
-                "Unknown parameter. Supported: percentiles=[..], format=\"..\", limit=N, timeout=N",
+                "Unknown parameter. Supported: percentiles=[..], format=\"..\", limit=N, timeout=N, report_every=N",
             ))
         });
 
@@ -190,6 +202,10 @@ pub fn main_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
     let asyncness = sig.asyncness.is_some();
     let fn_name = &sig.ident;
 
+    let report_every_call = report_every.map(|interval_ms| {
+        quote! { .report_every(std::time::Duration::from_millis(#interval_ms)) }
+    });
+
     let base_builder = quote! {
         let caller_name: &'static str =
             concat!(module_path!(), "::", stringify!(#fn_name));
@@ -198,6 +214,7 @@ pub fn main_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
             .percentiles(#percentiles_array)
             .limit(#limit)
             .format(#format_token)
+            #report_every_call
     };
 
     let guard_init = if let Some(timeout_ms) = timeout {
@@ -342,7 +359,16 @@ pub fn measure_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
         };
 
         if asyncness {
-            quote! { async { #guard_init }.await }
+            quote! {
+                hotpath::functions::time_polls(
+                    concat!(module_path!(), "::", #name),
+                    hotpath::functions::call_tree::track_frame(
+                        concat!(module_path!(), "::", #name),
+                        async { #guard_init },
+                    ),
+                )
+                .await
+            }
         } else {
             guard_init
         }
@@ -367,6 +393,9 @@ pub fn measure_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// # Parameters
 ///
 /// * `log` - If `true`, logs the result value when the future completes (requires `Debug` on return type)
+/// * `label` - Custom label shown instead of the function path
+/// * `tags` - Key/value pairs attached to every call, e.g. `tags(request_id = req.id)`. Values
+///   can use the function arguments and are formatted with `Display`.
 ///
 /// # Examples
 ///
@@ -412,8 +441,10 @@ pub fn future_fn_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
         .into();
     }
 
-    // Parse optional `log = true` attribute
+    // Parse optional `log = true`, `label = "..."` and `tags(key = value, ...)` attributes
     let mut log_result = false;
+    let mut label: Option<LitStr> = None;
+    let mut tags: Vec<(syn::Ident, syn::Expr)> = Vec::new();
 
     if !attr.is_empty() {
         let parser = syn::meta::parser(|meta| {
@@ -424,7 +455,23 @@ pub fn future_fn_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
                 return Ok(());
             }
 
-            Err(meta.error("Unknown parameter. Supported: log = true"))
+            if meta.path.is_ident("label") {
+                label = Some(meta.value()?.parse()?);
+                return Ok(());
+            }
+
+            if meta.path.is_ident("tags") {
+                return meta.parse_nested_meta(|tag| {
+                    let key = tag.path.require_ident()?.clone();
+                    let value: syn::Expr = tag.value()?.parse()?;
+                    tags.push((key, value));
+                    Ok(())
+                });
+            }
+
+            Err(meta.error(
+                "Unknown parameter. Supported: log = true, label = \"...\", tags(key = value, ...)",
+            ))
         });
 
         if let Err(e) = parser.parse2(proc_macro2::TokenStream::from(attr)) {
@@ -434,15 +481,31 @@ pub fn future_fn_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
 
     let fn_name = &sig.ident;
 
+    let label = match label {
+        Some(label) => quote! { Some(#label.to_string()) },
+        None => quote! { None },
+    };
+    let tag_keys = tags.iter().map(|(key, _)| key.to_string());
+    let tag_values = tags.iter().map(|(_, value)| value);
+    // Tags are evaluated before the body borrows the arguments, under a name that can't
+    // shadow one of them
+    let tags = quote! {
+        let __hotpath_tags: hotpath::futures::FutureTags =
+            vec![#((#tag_keys, (#tag_values).to_string())),*];
+    };
+
     // Generate the wrapped body using the future! macro pattern
     let wrapped_body = if log_result {
         quote! {
             {
                 const FUTURE_LOC: &'static str = concat!(module_path!(), "::", stringify!(#fn_name));
                 hotpath::futures::init_futures_state();
+                #tags
                 hotpath::InstrumentFutureLog::instrument_future_log(
                     async #block,
-                    FUTURE_LOC
+                    FUTURE_LOC,
+                    #label,
+                    __hotpath_tags
                 ).await
             }
         }
@@ -451,9 +514,12 @@ pub fn future_fn_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
             {
                 const FUTURE_LOC: &'static str = concat!(module_path!(), "::", stringify!(#fn_name));
                 hotpath::futures::init_futures_state();
+                #tags
                 hotpath::InstrumentFuture::instrument_future(
                     async #block,
-                    FUTURE_LOC
+                    FUTURE_LOC,
+                    #label,
+                    __hotpath_tags
                 ).await
             }
         }
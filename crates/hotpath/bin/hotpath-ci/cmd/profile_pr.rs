@@ -3,11 +3,10 @@ mod comment;
 use clap::Parser;
 use comment::upsert_pr_comment;
 use eyre::Result;
-use hotpath::{format_bytes, FunctionsJson};
+use hotpath::diff::{compare_metrics, MetricsComparison};
+use hotpath::FunctionsJson;
 use prettytable::{Cell, Row, Table};
 use std::env;
-use std::fmt;
-use std::time::Duration;
 
 #[derive(Debug, Parser)]
 pub struct ProfilePrArgs {
@@ -90,238 +89,6 @@ impl ProfilePrArgs {
     }
 }
 
-#[derive(Debug, Clone)]
-pub enum MetricDiff {
-    CallsCount(u64, u64), // (before, after)
-    DurationNs(u64, u64), // (before, after) - Duration in nanoseconds
-    Alloc(u64, u64),      // (before, after) - Bytes allocated
-    Percentage(u64, u64), // (before, after)
-}
-
-impl fmt::Display for MetricDiff {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.format_with_emoji(None))
-    }
-}
-
-impl MetricDiff {
-    fn format_with_emoji(&self, emoji_threshold: Option<u32>) -> String {
-        match self {
-            MetricDiff::CallsCount(before, after) => {
-                let diff_percent = calculate_percentage_diff(*before, *after);
-                let emoji = get_emoji_for_diff(diff_percent, emoji_threshold);
-                format!("{} → {} ({:+.1}%){}", before, after, diff_percent, emoji)
-            }
-            MetricDiff::DurationNs(before, after) => {
-                let diff_percent = calculate_percentage_diff(*before, *after);
-                let before_duration = Duration::from_nanos(*before);
-                let after_duration = Duration::from_nanos(*after);
-                let emoji = get_emoji_for_diff(diff_percent, emoji_threshold);
-                format!(
-                    "{:.2?} → {:.2?} ({:+.1}%){}",
-                    before_duration, after_duration, diff_percent, emoji
-                )
-            }
-            MetricDiff::Alloc(before, after) => {
-                let diff_percent = calculate_percentage_diff(*before, *after);
-                let emoji = get_emoji_for_diff(diff_percent, emoji_threshold);
-                format!(
-                    "{} → {} ({:+.1}%){}",
-                    format_bytes(*before),
-                    format_bytes(*after),
-                    diff_percent,
-                    emoji
-                )
-            }
-            MetricDiff::Percentage(before, after) => {
-                let diff_percent = calculate_percentage_diff(*before, *after);
-                let before_percent = *before as f64 / 100.0;
-                let after_percent = *after as f64 / 100.0;
-                let emoji = get_emoji_for_diff(diff_percent, emoji_threshold);
-                format!(
-                    "{:.2}% → {:.2}% ({:+.1}%){}",
-                    before_percent, after_percent, diff_percent, emoji
-                )
-            }
-        }
-    }
-}
-
-fn get_emoji_for_diff(diff_percent: f64, threshold: Option<u32>) -> &'static str {
-    if let Some(threshold_val) = threshold {
-        let threshold = threshold_val as f64;
-        if diff_percent > threshold {
-            " ⚠️ "
-        } else if diff_percent < -threshold {
-            " 🚀 "
-        } else {
-            "   "
-        }
-    } else {
-        ""
-    }
-}
-
-#[derive(Debug, Clone)]
-pub struct MetricsComparison {
-    pub total_elapsed_diff: MetricDiff,
-    pub function_diffs: Vec<FunctionMetricsDiff>,
-}
-
-#[derive(Debug, Clone)]
-pub struct FunctionMetricsDiff {
-    pub function_name: String,
-    pub metrics: Vec<MetricDiff>,
-    pub is_removed: bool, // True if function was removed (no longer measured)
-    pub is_new: bool,     // True if function is new (not in base)
-}
-
-fn calculate_percentage_diff(before: u64, after: u64) -> f64 {
-    if before == 0 {
-        if after == 0 {
-            0.0
-        } else {
-            100.0 // 100% increase from 0
-        }
-    } else {
-        ((after as f64 - before as f64) / before as f64) * 100.0
-    }
-}
-
-fn find_function<'a>(
-    data: &'a [(String, Vec<hotpath::MetricType>)],
-    name: &str,
-) -> Option<&'a Vec<hotpath::MetricType>> {
-    data.iter().find(|(n, _)| n == name).map(|(_, row)| row)
-}
-
-fn compare_metrics(
-    before_metrics: &FunctionsJson,
-    after_metrics: &FunctionsJson,
-) -> MetricsComparison {
-    use hotpath::MetricType;
-
-    let total_elapsed_diff =
-        MetricDiff::DurationNs(before_metrics.total_elapsed, after_metrics.total_elapsed);
-
-    let mut function_diffs = Vec::new();
-    let mut new_functions = Vec::new();
-
-    for (function_name, after_row) in &after_metrics.data {
-        if let Some(before_row) = find_function(&before_metrics.data, function_name) {
-            let mut metrics = Vec::new();
-
-            for (metric_idx, after_metric) in after_row.iter().enumerate() {
-                if let Some(before_metric) = before_row.get(metric_idx) {
-                    let diff = match (before_metric, after_metric) {
-                        (MetricType::CallsCount(before_val), MetricType::CallsCount(after_val)) => {
-                            MetricDiff::CallsCount(*before_val, *after_val)
-                        }
-                        (MetricType::DurationNs(before_val), MetricType::DurationNs(after_val)) => {
-                            MetricDiff::DurationNs(*before_val, *after_val)
-                        }
-                        (MetricType::Alloc(before_val, _), MetricType::Alloc(after_val, _)) => {
-                            MetricDiff::Alloc(*before_val, *after_val)
-                        }
-                        (MetricType::Percentage(before_val), MetricType::Percentage(after_val)) => {
-                            MetricDiff::Percentage(*before_val, *after_val)
-                        }
-                        _ => continue,
-                    };
-                    metrics.push(diff);
-                }
-            }
-
-            function_diffs.push(FunctionMetricsDiff {
-                function_name: function_name.clone(),
-                metrics,
-                is_removed: false,
-                is_new: false,
-            });
-        } else {
-            let mut metrics = Vec::new();
-
-            for after_metric in after_row.iter() {
-                let diff = match after_metric {
-                    MetricType::CallsCount(after_val) => MetricDiff::CallsCount(0, *after_val),
-                    MetricType::DurationNs(after_val) => MetricDiff::DurationNs(0, *after_val),
-                    MetricType::Alloc(after_val, _) => MetricDiff::Alloc(0, *after_val),
-                    MetricType::Percentage(after_val) => MetricDiff::Percentage(0, *after_val),
-                    MetricType::Unsupported => continue,
-                };
-                metrics.push(diff);
-            }
-
-            new_functions.push(FunctionMetricsDiff {
-                function_name: function_name.clone(),
-                metrics,
-                is_removed: false,
-                is_new: true,
-            });
-        }
-    }
-
-    for (function_name, before_row) in &before_metrics.data {
-        if find_function(&after_metrics.data, function_name).is_none() {
-            let mut metrics = Vec::new();
-
-            for before_metric in before_row.iter() {
-                let diff = match before_metric {
-                    MetricType::CallsCount(before_val) => MetricDiff::CallsCount(*before_val, 0),
-                    MetricType::DurationNs(before_val) => MetricDiff::DurationNs(*before_val, 0),
-                    MetricType::Alloc(before_val, _) => MetricDiff::Alloc(*before_val, 0),
-                    MetricType::Percentage(before_val) => MetricDiff::Percentage(*before_val, 0),
-                    MetricType::Unsupported => continue,
-                };
-                metrics.push(diff);
-            }
-
-            function_diffs.push(FunctionMetricsDiff {
-                function_name: function_name.clone(),
-                metrics,
-                is_removed: true,
-                is_new: false,
-            });
-        }
-    }
-
-    function_diffs.extend(new_functions);
-
-    // Sort by percent_total in head branch (after value), descending order
-    function_diffs.sort_by(|a, b| {
-        let a_percent = a
-            .metrics
-            .iter()
-            .find_map(|m| {
-                if let MetricDiff::Percentage(_, after) = m {
-                    Some(*after)
-                } else {
-                    None
-                }
-            })
-            .unwrap_or(0);
-
-        let b_percent = b
-            .metrics
-            .iter()
-            .find_map(|m| {
-                if let MetricDiff::Percentage(_, after) = m {
-                    Some(*after)
-                } else {
-                    None
-                }
-            })
-            .unwrap_or(0);
-
-        b_percent.cmp(&a_percent)
-    });
-
-    MetricsComparison {
-        total_elapsed_diff,
-        function_diffs,
-    }
-}
-
 fn format_comparison_markdown(
     comparison: &MetricsComparison,
     metrics: &FunctionsJson,
@@ -370,6 +137,13 @@ fn format_comparison_markdown(
     }
     header_cells.push(Cell::new("Total"));
     header_cells.push(Cell::new("% Total"));
+    let has_self_column = comparison
+        .function_diffs
+        .iter()
+        .any(|diff| diff.metrics.len() > 4 + metrics.percentiles.len());
+    if has_self_column {
+        header_cells.push(Cell::new("Self"));
+    }
     table.add_row(Row::new(header_cells));
 
     for func_diff in &comparison.function_diffs {
@@ -445,6 +219,7 @@ mod test {
             percentiles: vec![95],
             description: "Time metrics".to_string(),
             data: pr_data,
+            call_tree: Vec::new(),
         };
 
         let main_data = vec![
@@ -487,6 +262,7 @@ mod test {
             percentiles: vec![95],
             description: "Time metrics".to_string(),
             data: main_data,
+            call_tree: Vec::new(),
         };
 
         let comparison = compare_metrics(&main_metrics, &pr_metrics);
@@ -525,6 +301,7 @@ mod test {
             percentiles: vec![95],
             description: "Time metrics".to_string(),
             data: pr_data,
+            call_tree: Vec::new(),
         };
 
         let main_data = vec![
@@ -557,6 +334,7 @@ mod test {
             percentiles: vec![95],
             description: "Time metrics".to_string(),
             data: main_data,
+            call_tree: Vec::new(),
         };
 
         let comparison = compare_metrics(&main_metrics, &pr_metrics);
@@ -615,6 +393,7 @@ mod test {
             percentiles: vec![95],
             description: "Time metrics".to_string(),
             data: pr_data,
+            call_tree: Vec::new(),
         };
 
         let main_data = vec![(
@@ -635,6 +414,7 @@ mod test {
             percentiles: vec![95],
             description: "Time metrics".to_string(),
             data: main_data,
+            call_tree: Vec::new(),
         };
 
         let comparison = compare_metrics(&main_metrics, &pr_metrics);
@@ -693,6 +473,7 @@ mod test {
             percentiles: vec![95],
             description: "Time metrics".to_string(),
             data: pr_data,
+            call_tree: Vec::new(),
         };
 
         let main_data = vec![
@@ -725,6 +506,7 @@ mod test {
             percentiles: vec![95],
             description: "Time metrics".to_string(),
             data: main_data,
+            call_tree: Vec::new(),
         };
 
         let comparison = compare_metrics(&main_metrics, &pr_metrics);
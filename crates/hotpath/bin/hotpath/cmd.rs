@@ -1 +1,3 @@
 pub mod console;
+pub mod diff;
+pub mod record;
@@ -3,14 +3,18 @@ mod constants;
 #[cfg(feature = "hotpath")]
 pub mod demo;
 mod events;
-mod http_worker;
+pub(crate) mod http_worker;
 mod input;
+mod replay;
+pub(crate) mod session;
 mod views;
 mod widgets;
 
 use app::App;
 use clap::Parser;
 use eyre::Result;
+use session::Session;
+use std::path::PathBuf;
 
 #[derive(Debug, Parser)]
 pub struct ConsoleArgs {
@@ -31,6 +35,13 @@ pub struct ConsoleArgs {
 
     #[arg(long, default_value_t = 500, help = "Refresh interval in milliseconds")]
     pub refresh_interval: u64,
+
+    #[arg(
+        long,
+        value_name = "FILE",
+        help = "Replay a session recorded with `hotpath record` instead of connecting to a running program"
+    )]
+    pub replay: Option<PathBuf>,
 }
 
 #[hotpath::measure_all]
@@ -41,7 +52,10 @@ impl ConsoleArgs {
         #[cfg(feature = "hotpath")]
         demo::init();
 
-        let mut app = App::new(&self.metrics_host, self.metrics_port, self.refresh_interval);
+        let mut app = match &self.replay {
+            Some(path) => App::replay(Session::load(path)?),
+            None => App::new(&self.metrics_host, self.metrics_port, self.refresh_interval),
+        };
 
         // Use modern ratatui initialization
         let mut terminal = ratatui::init();
@@ -60,8 +74,10 @@ fn init_logging() {
     use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt};
 
     let offset = time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC);
-    let time_format =
-        time::format_description::parse("[year]-[month]-[day]T[hour]:[minute]:[second]").unwrap();
+    let time_format = time::format_description::parse_borrowed::<1>(
+        "[year]-[month]-[day]T[hour]:[minute]:[second]",
+    )
+    .unwrap();
     let timer = tracing_subscriber::fmt::time::OffsetTime::new(offset, time_format);
     let env_filter = tracing_subscriber::EnvFilter::try_from_default_env()
         .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("error"));
@@ -88,18 +104,18 @@ fn init_logging() {
 #[cfg(not(feature = "dev"))]
 fn init_logging() {}
 
-fn default_metrics_port() -> u16 {
+pub(crate) fn default_metrics_port() -> u16 {
     std::env::var("HOTPATH_METRICS_PORT")
         .ok()
         .and_then(|s| s.parse().ok())
         .unwrap_or(6770)
 }
 
-fn default_metrics_host() -> String {
+pub(crate) fn default_metrics_host() -> String {
     std::env::var("HOTPATH_METRICS_HOST").unwrap_or_else(|_| "http://localhost".to_string())
 }
 
-fn validate_metrics_host(s: &str) -> Result<String, String> {
+pub(crate) fn validate_metrics_host(s: &str) -> Result<String, String> {
     let s = s.trim();
 
     if s.is_empty() {
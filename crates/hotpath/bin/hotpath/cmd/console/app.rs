@@ -2,14 +2,17 @@
 
 use crossbeam_channel::{Receiver, Sender};
 use hotpath::json::{
-    ChannelLogs, ChannelsJson, FunctionLogsJson, FunctionsJson, FutureCall, FutureCalls,
-    FuturesJson as FuturesJsonData, LogEntry, StreamLogs, StreamsJson, ThreadsJson,
+    ChannelLogs, ChannelsGraphJson, ChannelsJson, FunctionLogsJson, FunctionsJson, FutureCall,
+    FutureCalls, FuturesJson as FuturesJsonData, LogEntry, RuntimeJson, SlowPollsJson, StatsWindow,
+    StreamLogs, StreamsJson, TasksJson, ThreadsJson,
 };
 use ratatui::widgets::TableState;
 use std::collections::HashMap;
 use std::time::{Duration, Instant};
 
 use super::events::{AppEvent, DataRequest};
+use super::replay::ReplayState;
+use super::session::Session;
 
 mod data;
 mod keys;
@@ -24,6 +27,8 @@ pub(crate) enum SelectedTab {
     Channels,
     Streams,
     Threads,
+    Runtime,
+    Tasks,
 }
 
 impl SelectedTab {
@@ -35,6 +40,8 @@ impl SelectedTab {
             SelectedTab::Channels => 4,
             SelectedTab::Streams => 5,
             SelectedTab::Threads => 6,
+            SelectedTab::Runtime => 7,
+            SelectedTab::Tasks => 8,
         }
     }
 
@@ -46,6 +53,8 @@ impl SelectedTab {
             SelectedTab::Channels => "Channels",
             SelectedTab::Streams => "Streams",
             SelectedTab::Threads => "Threads",
+            SelectedTab::Runtime => "Runtime",
+            SelectedTab::Tasks => "Tasks",
         }
     }
 
@@ -126,6 +135,8 @@ pub(crate) struct App {
     pub(crate) streams_table_state: TableState,
     pub(crate) selected_tab: SelectedTab,
     pub(crate) paused: bool,
+    /// Time window applied to function timings and channel rates
+    pub(crate) stats_window: StatsWindow,
 
     pub(crate) last_refresh: Instant,
     pub(crate) last_successful_fetch: Option<Instant>,
@@ -148,6 +159,8 @@ pub(crate) struct App {
     pub(crate) loading_channels: bool,
     pub(crate) loading_streams: bool,
     pub(crate) loading_threads: bool,
+    pub(crate) loading_runtime: bool,
+    pub(crate) loading_tasks: bool,
     pub(crate) loading_futures: bool,
 
     pub(crate) channel_logs_table_state: TableState,
@@ -155,6 +168,9 @@ pub(crate) struct App {
     pub(crate) show_logs: bool,
     pub(crate) logs: Option<CachedLogs>,
     pub(crate) inspected_log: Option<LogEntry>,
+    /// Whether the Channels tab shows the producer -> channel -> consumer graph
+    pub(crate) show_channels_graph: bool,
+    pub(crate) channels_graph: ChannelsGraphJson,
 
     pub(crate) stream_logs_table_state: TableState,
     pub(crate) streams_focus: StreamsFocus,
@@ -163,6 +179,12 @@ pub(crate) struct App {
     pub(crate) inspected_stream_log: Option<LogEntry>,
     pub(crate) threads: ThreadsJson,
     pub(crate) threads_table_state: TableState,
+    /// Tokio runtime metrics, None until the first successful fetch
+    pub(crate) runtime: Option<RuntimeJson>,
+    pub(crate) runtime_available: bool,
+    pub(crate) runtime_table_state: TableState,
+    pub(crate) tasks: TasksJson,
+    pub(crate) tasks_table_state: TableState,
 
     pub(crate) futures: FuturesJsonData,
     pub(crate) futures_table_state: TableState,
@@ -171,6 +193,10 @@ pub(crate) struct App {
     pub(crate) future_calls_table_state: TableState,
     pub(crate) future_calls: Option<FutureCalls>,
     pub(crate) inspected_future_call: Option<FutureCall>,
+    pub(crate) slow_polls: SlowPollsJson,
+
+    /// Playback state when replaying a recorded session instead of polling a live process
+    pub(crate) replay: Option<ReplayState>,
 }
 
 #[hotpath::measure_all]
@@ -183,6 +209,38 @@ impl App {
         super::http_worker::spawn_http_worker(request_rx, event_tx.clone(), base_url.clone());
         super::input::spawn_input_reader(event_tx);
 
+        Self::with_channels(
+            request_tx,
+            event_rx,
+            Duration::from_millis(refresh_interval_ms),
+            base_url,
+            None,
+        )
+    }
+
+    pub(crate) fn replay(session: Session) -> Self {
+        let (request_tx, request_rx) = crossbeam_channel::unbounded();
+        let (event_tx, event_rx) = crossbeam_channel::unbounded();
+
+        let replay = super::replay::spawn_replay_worker(request_rx, event_tx.clone(), session);
+        super::input::spawn_input_reader(event_tx);
+
+        Self::with_channels(
+            request_tx,
+            event_rx,
+            replay.refresh_interval(),
+            replay.metrics_url().to_string(),
+            Some(replay),
+        )
+    }
+
+    fn with_channels(
+        request_tx: Sender<DataRequest>,
+        event_rx: Receiver<AppEvent>,
+        refresh_interval: Duration,
+        metrics_host: String,
+        replay: Option<ReplayState>,
+    ) -> Self {
         let empty_functions = FunctionsJson {
             hotpath_profiling_mode: hotpath::ProfilingMode::Timing,
             total_elapsed: 0,
@@ -190,6 +248,7 @@ impl App {
             caller_name: "unknown".to_string(),
             percentiles: vec![95],
             data: Vec::new(),
+            call_tree: Vec::new(),
         };
 
         Self {
@@ -199,6 +258,7 @@ impl App {
             channels: hotpath::json::ChannelsJson {
                 current_elapsed_ns: 0,
                 channels: vec![],
+                queue_warn_threshold: None,
             },
             streams: StreamsJson {
                 current_elapsed_ns: 0,
@@ -210,6 +270,7 @@ impl App {
             streams_table_state: TableState::default().with_selected(0),
             selected_tab: SelectedTab::default(),
             paused: false,
+            stats_window: StatsWindow::default(),
             last_refresh: Instant::now(),
             last_successful_fetch: None,
             error_message: None,
@@ -221,19 +282,23 @@ impl App {
             inspected_function_log: None,
             request_tx,
             event_rx,
-            refresh_interval: Duration::from_millis(refresh_interval_ms),
-            metrics_host: base_url,
+            refresh_interval,
+            metrics_host,
             exit: false,
             loading_functions: false,
             loading_channels: false,
             loading_streams: false,
             loading_threads: false,
+            loading_runtime: false,
+            loading_tasks: false,
             loading_futures: false,
             channel_logs_table_state: TableState::default(),
             channels_focus: ChannelsFocus::Channels,
             show_logs: false,
             logs: None,
             inspected_log: None,
+            show_channels_graph: false,
+            channels_graph: ChannelsGraphJson::default(),
             stream_logs_table_state: TableState::default(),
             streams_focus: StreamsFocus::Streams,
             show_stream_logs: false,
@@ -247,6 +312,11 @@ impl App {
                 rss_bytes: None,
             },
             threads_table_state: TableState::default().with_selected(0),
+            runtime: None,
+            runtime_available: true,
+            runtime_table_state: TableState::default().with_selected(0),
+            tasks: TasksJson::default(),
+            tasks_table_state: TableState::default().with_selected(0),
             futures: FuturesJsonData {
                 current_elapsed_ns: 0,
                 futures: vec![],
@@ -257,6 +327,8 @@ impl App {
             future_calls_table_state: TableState::default(),
             future_calls: None,
             inspected_future_call: None,
+            slow_polls: SlowPollsJson::default(),
+            replay,
         }
     }
 
@@ -279,6 +351,8 @@ impl App {
             SelectedTab::Channels => &mut self.channels_table_state,
             SelectedTab::Streams => &mut self.streams_table_state,
             SelectedTab::Threads => &mut self.threads_table_state,
+            SelectedTab::Runtime => &mut self.runtime_table_state,
+            SelectedTab::Tasks => &mut self.tasks_table_state,
             SelectedTab::Futures => &mut self.futures_table_state,
         }
     }
@@ -305,6 +379,7 @@ impl App {
                 }
                 default(self.refresh_interval) => {
                     if !self.paused {
+                        self.advance_replay();
                         self.request_refresh_for_current_tab();
                     }
                 }
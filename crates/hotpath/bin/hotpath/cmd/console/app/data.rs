@@ -4,7 +4,7 @@ use super::{App, CachedLogs, CachedStreamLogs, SelectedTab};
 use crate::cmd::console::events::{DataRequest, DataResponse};
 use hotpath::json::{
     ChannelLogs, FunctionLogsJson, FunctionsJson, FutureCalls, FuturesJson as FuturesJsonData,
-    StreamLogs, StreamsJson, ThreadsJson,
+    RuntimeJson, StreamLogs, StreamsJson, TasksJson, ThreadsJson,
 };
 use std::collections::HashMap;
 use std::time::Instant;
@@ -299,6 +299,46 @@ impl App {
         }
     }
 
+    pub(crate) fn update_runtime(&mut self, runtime: RuntimeJson) {
+        // Workers are fixed for the lifetime of a runtime, so the selected index stays valid
+        if let Some(selected) = self.runtime_table_state.selected() {
+            if selected >= runtime.workers.len() && !runtime.workers.is_empty() {
+                self.runtime_table_state
+                    .select(Some(runtime.workers.len() - 1));
+            }
+        }
+
+        self.runtime = Some(runtime);
+        self.runtime_available = true;
+        self.last_successful_fetch = Some(Instant::now());
+        self.error_message = None;
+    }
+
+    pub(crate) fn update_tasks(&mut self, tasks: TasksJson) {
+        // Finished tasks move below the live ones, so keep the selection on the same task id
+        let selected_task_id = self
+            .tasks_table_state
+            .selected()
+            .and_then(|idx| self.tasks.tasks.get(idx))
+            .map(|task| task.id);
+
+        self.tasks = tasks;
+        self.last_successful_fetch = Some(Instant::now());
+        self.error_message = None;
+
+        let new_idx = selected_task_id
+            .and_then(|task_id| self.tasks.tasks.iter().position(|task| task.id == task_id));
+        match new_idx {
+            Some(new_idx) => self.tasks_table_state.select(Some(new_idx)),
+            None if !self.tasks.tasks.is_empty() => {
+                let selected = self.tasks_table_state.selected().unwrap_or(0);
+                self.tasks_table_state
+                    .select(Some(selected.min(self.tasks.tasks.len() - 1)));
+            }
+            None => {}
+        }
+    }
+
     pub(crate) fn request_stream_logs(&self) {
         if self.paused {
             return;
@@ -332,7 +372,7 @@ impl App {
         let request = match self.selected_tab {
             SelectedTab::Timing => {
                 self.loading_functions = true;
-                DataRequest::RefreshTiming
+                DataRequest::RefreshTiming(self.stats_window)
             }
             SelectedTab::Memory => {
                 self.loading_functions = true;
@@ -340,18 +380,30 @@ impl App {
             }
             SelectedTab::Channels => {
                 self.loading_channels = true;
-                DataRequest::RefreshChannels
+                if self.show_channels_graph {
+                    let _ = self.request_tx.send(DataRequest::RefreshChannelsGraph);
+                }
+                DataRequest::RefreshChannels(self.stats_window)
             }
             SelectedTab::Streams => {
                 self.loading_streams = true;
-                DataRequest::RefreshStreams
+                DataRequest::RefreshStreams(self.stats_window)
             }
             SelectedTab::Threads => {
                 self.loading_threads = true;
                 DataRequest::RefreshThreads
             }
+            SelectedTab::Runtime => {
+                self.loading_runtime = true;
+                DataRequest::RefreshRuntime
+            }
+            SelectedTab::Tasks => {
+                self.loading_tasks = true;
+                DataRequest::RefreshTasks
+            }
             SelectedTab::Futures => {
                 self.loading_futures = true;
+                let _ = self.request_tx.send(DataRequest::RefreshSlowPolls);
                 DataRequest::RefreshFutures
             }
         };
@@ -368,6 +420,9 @@ impl App {
                 self.update_timing_metrics(data);
                 self.request_function_logs_if_open();
             }
+            DataResponse::FunctionsTimingTree(data) => {
+                trace!("Received timing tree: {} roots", data.tree.len());
+            }
             DataResponse::FunctionsAlloc(data) => {
                 trace!("Received alloc data: {} functions", data.data.len());
                 self.loading_functions = false;
@@ -408,6 +463,14 @@ impl App {
                 self.loading_channels = false;
                 self.update_channels(data);
             }
+            DataResponse::ChannelsGraph(data) => {
+                trace!(
+                    "Received channels graph: {} nodes, {} edges",
+                    data.nodes.len(),
+                    data.edges.len()
+                );
+                self.channels_graph = data;
+            }
             DataResponse::ChannelLogs { channel_id, logs } => {
                 trace!(
                     "Received channel {} logs: {} sent, {} received",
@@ -435,11 +498,30 @@ impl App {
                 self.loading_threads = false;
                 self.update_threads(data);
             }
+            DataResponse::Runtime(data) => {
+                trace!("Received runtime data: {} workers", data.workers.len());
+                self.loading_runtime = false;
+                self.update_runtime(data);
+            }
+            DataResponse::RuntimeUnavailable => {
+                trace!("Runtime metrics unavailable");
+                self.loading_runtime = false;
+                self.runtime_available = false;
+            }
+            DataResponse::Tasks(data) => {
+                trace!("Received tasks data: {} tasks", data.tasks.len());
+                self.loading_tasks = false;
+                self.update_tasks(data);
+            }
             DataResponse::Futures(data) => {
                 trace!("Received futures data: {} futures", data.futures.len());
                 self.loading_futures = false;
                 self.update_futures(data);
             }
+            DataResponse::SlowPolls(data) => {
+                trace!("Received slow polls: {} total", data.total);
+                self.slow_polls = data;
+            }
             DataResponse::FutureCalls { future_id, calls } => {
                 trace!(
                     "Received future {} calls: {} entries",
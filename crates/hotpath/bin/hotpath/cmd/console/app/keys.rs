@@ -14,6 +14,16 @@ impl App {
                 self.exit();
             }
             KeyCode::Char('p') | KeyCode::Char('P') => self.toggle_pause(),
+            KeyCode::Char('w') | KeyCode::Char('W') => self.cycle_stats_window(),
+            KeyCode::Char('g') | KeyCode::Char('G')
+                if self.selected_tab == SelectedTab::Channels =>
+            {
+                self.toggle_channels_graph()
+            }
+            KeyCode::Char(',') => self.step_replay(-1),
+            KeyCode::Char('.') => self.step_replay(1),
+            KeyCode::Char('[') => self.seek_replay(false),
+            KeyCode::Char(']') => self.seek_replay(true),
             KeyCode::Char('1') => {
                 self.switch_to_tab(SelectedTab::Timing);
             }
@@ -32,6 +42,12 @@ impl App {
             KeyCode::Char('6') => {
                 self.switch_to_tab(SelectedTab::Threads);
             }
+            KeyCode::Char('7') => {
+                self.switch_to_tab(SelectedTab::Runtime);
+            }
+            KeyCode::Char('8') => {
+                self.switch_to_tab(SelectedTab::Tasks);
+            }
             KeyCode::Char('o') | KeyCode::Char('O') => {
                 if self.selected_tab == SelectedTab::Channels {
                     match self.channels_focus {
@@ -51,8 +67,11 @@ impl App {
                         FuturesFocus::Calls => self.hide_future_calls(),
                         FuturesFocus::Futures => self.toggle_future_calls(),
                     }
-                } else if self.selected_tab == SelectedTab::Threads {
-                    // No logs panel for threads tab - do nothing
+                } else if matches!(
+                    self.selected_tab,
+                    SelectedTab::Threads | SelectedTab::Runtime | SelectedTab::Tasks
+                ) {
+                    // No logs panel for threads, runtime and tasks tabs - do nothing
                 } else if self.selected_tab.is_functions_tab() {
                     match self.functions_focus {
                         FunctionsFocus::Inspect => {
@@ -137,6 +156,10 @@ impl App {
                     }
                 } else if self.selected_tab == SelectedTab::Threads {
                     self.select_next_thread();
+                } else if self.selected_tab == SelectedTab::Runtime {
+                    self.select_next_worker();
+                } else if self.selected_tab == SelectedTab::Tasks {
+                    self.select_next_task();
                 } else if self.selected_tab.is_functions_tab() {
                     match self.functions_focus {
                         FunctionsFocus::Functions => {
@@ -171,6 +194,10 @@ impl App {
                     }
                 } else if self.selected_tab == SelectedTab::Threads {
                     self.select_previous_thread();
+                } else if self.selected_tab == SelectedTab::Runtime {
+                    self.select_previous_worker();
+                } else if self.selected_tab == SelectedTab::Tasks {
+                    self.select_previous_task();
                 } else if self.selected_tab.is_functions_tab() {
                     match self.functions_focus {
                         FunctionsFocus::Functions => {
@@ -4,8 +4,11 @@ use super::{
     App, ChannelsFocus, FunctionsFocus, FuturesFocus, InspectedFunctionLog, SelectedTab,
     StreamsFocus,
 };
+use std::time::Duration;
 use tracing::{debug, info};
 
+const REPLAY_SEEK_STEP: Duration = Duration::from_secs(10);
+
 #[hotpath::measure_all]
 impl App {
     pub(crate) fn next_function(&mut self) {
@@ -39,6 +42,46 @@ impl App {
     pub(crate) fn toggle_pause(&mut self) {
         self.paused = !self.paused;
         info!("Paused: {}", self.paused);
+
+        // Resuming a finished replay starts it over
+        if let Some(replay) = &self.replay {
+            if !self.paused && replay.is_at_end() {
+                replay.rewind();
+                self.request_refresh_for_current_tab();
+            }
+        }
+    }
+
+    /// Moves replay playback forward by one snapshot, pausing at the end of the recording.
+    pub(crate) fn advance_replay(&mut self) {
+        if let Some(replay) = &self.replay {
+            if replay.is_at_end() {
+                self.paused = true;
+            } else {
+                replay.step(1);
+            }
+        }
+    }
+
+    pub(crate) fn step_replay(&mut self, delta: isize) {
+        if let Some(replay) = &self.replay {
+            replay.step(delta);
+            self.request_refresh_for_current_tab();
+        }
+    }
+
+    pub(crate) fn seek_replay(&mut self, forward: bool) {
+        if let Some(replay) = &self.replay {
+            replay.seek(REPLAY_SEEK_STEP, forward);
+            self.request_refresh_for_current_tab();
+        }
+    }
+
+    /// Cycles the stats window, replay reads it from the recorded windowed routes.
+    pub(crate) fn cycle_stats_window(&mut self) {
+        self.stats_window = self.stats_window.next();
+        info!("Stats window: {}", self.stats_window.label());
+        self.request_refresh_for_current_tab();
     }
 
     pub(crate) fn switch_to_tab(&mut self, tab: SelectedTab) {
@@ -106,6 +149,14 @@ impl App {
         }
     }
 
+    pub(crate) fn toggle_channels_graph(&mut self) {
+        self.show_channels_graph = !self.show_channels_graph;
+        if self.show_channels_graph {
+            self.hide_logs();
+        }
+        self.request_refresh_for_current_tab();
+    }
+
     pub(crate) fn hide_logs(&mut self) {
         self.show_logs = false;
         self.logs = None;
@@ -516,6 +567,57 @@ impl App {
         self.threads_table_state.select(Some(i));
     }
 
+    pub(crate) fn select_previous_worker(&mut self) {
+        let count = self.runtime.as_ref().map_or(0, |r| r.workers.len());
+        if count == 0 {
+            return;
+        }
+
+        let i = match self.runtime_table_state.selected() {
+            Some(i) => i.saturating_sub(1),
+            None => 0,
+        };
+        self.runtime_table_state.select(Some(i));
+    }
+
+    pub(crate) fn select_next_worker(&mut self) {
+        let count = self.runtime.as_ref().map_or(0, |r| r.workers.len());
+        if count == 0 {
+            return;
+        }
+
+        let i = match self.runtime_table_state.selected() {
+            Some(i) => (i + 1).min(count - 1),
+            None => 0,
+        };
+        self.runtime_table_state.select(Some(i));
+    }
+
+    pub(crate) fn select_previous_task(&mut self) {
+        if self.tasks.tasks.is_empty() {
+            return;
+        }
+
+        let i = match self.tasks_table_state.selected() {
+            Some(i) => i.saturating_sub(1),
+            None => 0,
+        };
+        self.tasks_table_state.select(Some(i));
+    }
+
+    pub(crate) fn select_next_task(&mut self) {
+        let count = self.tasks.tasks.len();
+        if count == 0 {
+            return;
+        }
+
+        let i = match self.tasks_table_state.selected() {
+            Some(i) => (i + 1).min(count - 1),
+            None => 0,
+        };
+        self.tasks_table_state.select(Some(i));
+    }
+
     pub(crate) fn select_previous_future(&mut self) {
         let count = self.futures.futures.len();
         if count == 0 {
@@ -2,18 +2,23 @@
 
 use crossterm::event::KeyCode;
 use hotpath::json::{
-    ChannelLogs, ChannelsJson, FunctionLogsJson, FunctionsJson, FutureCalls, FuturesJson, Route,
-    StreamLogs, StreamsJson, ThreadsJson,
+    ChannelLogs, ChannelsGraphJson, ChannelsJson, FunctionLogsJson, FunctionsJson,
+    FunctionsTreeJson, FutureCalls, FuturesJson, GraphFormat, Route, RuntimeJson, SlowPollsJson,
+    StatsWindow, StreamLogs, StreamsJson, TasksJson, ThreadsJson,
 };
 
 #[derive(Debug)]
 pub(crate) enum DataRequest {
-    RefreshTiming,
+    RefreshTiming(StatsWindow),
     RefreshMemory,
-    RefreshChannels,
-    RefreshStreams,
+    RefreshChannels(StatsWindow),
+    RefreshChannelsGraph,
+    RefreshStreams(StatsWindow),
     RefreshThreads,
+    RefreshRuntime,
+    RefreshTasks,
     RefreshFutures,
+    RefreshSlowPolls,
     FetchFunctionLogsTiming(String),
     FetchFunctionLogsAlloc(String),
     FetchChannelLogs(u64),
@@ -24,12 +29,18 @@ pub(crate) enum DataRequest {
 impl DataRequest {
     pub(crate) fn to_route(&self) -> Route {
         match self {
-            DataRequest::RefreshTiming => Route::FunctionsTiming,
+            DataRequest::RefreshTiming(window) => Route::FunctionsTiming { window: *window },
             DataRequest::RefreshMemory => Route::FunctionsAlloc,
-            DataRequest::RefreshChannels => Route::Channels,
-            DataRequest::RefreshStreams => Route::Streams,
+            DataRequest::RefreshChannels(window) => Route::Channels { window: *window },
+            DataRequest::RefreshChannelsGraph => Route::ChannelsGraph {
+                format: GraphFormat::Json,
+            },
+            DataRequest::RefreshStreams(window) => Route::Streams { window: *window },
             DataRequest::RefreshThreads => Route::Threads,
+            DataRequest::RefreshRuntime => Route::Runtime,
+            DataRequest::RefreshTasks => Route::Tasks,
             DataRequest::RefreshFutures => Route::Futures,
+            DataRequest::RefreshSlowPolls => Route::SlowPolls,
             DataRequest::FetchFunctionLogsTiming(name) => Route::FunctionTimingLogs {
                 function_name: name.clone(),
             },
@@ -47,6 +58,7 @@ impl DataRequest {
 #[allow(dead_code)]
 pub(crate) enum DataResponse {
     FunctionsTiming(FunctionsJson),
+    FunctionsTimingTree(FunctionsTreeJson),
     FunctionsAlloc(FunctionsJson),
     FunctionsAllocUnavailable,
     FunctionLogsTiming {
@@ -60,6 +72,7 @@ pub(crate) enum DataResponse {
     },
     FunctionLogsAllocNotFound(String),
     Channels(ChannelsJson),
+    ChannelsGraph(ChannelsGraphJson),
     ChannelLogs {
         channel_id: u64,
         logs: ChannelLogs,
@@ -70,7 +83,11 @@ pub(crate) enum DataResponse {
         logs: StreamLogs,
     },
     Threads(ThreadsJson),
+    Runtime(RuntimeJson),
+    RuntimeUnavailable,
+    Tasks(TasksJson),
     Futures(FuturesJson),
+    SlowPolls(SlowPollsJson),
     FutureCalls {
         future_id: u64,
         calls: FutureCalls,
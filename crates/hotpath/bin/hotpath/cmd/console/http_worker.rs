@@ -2,8 +2,9 @@
 
 use crossbeam_channel::{Receiver, Sender};
 use hotpath::json::{
-    ChannelLogs, ChannelsJson, FunctionLogsJson, FunctionsJson, FutureCalls, FuturesJson, Route,
-    StreamLogs, StreamsJson, ThreadsJson,
+    ChannelLogs, ChannelsGraphJson, ChannelsJson, FunctionLogsJson, FunctionsJson,
+    FunctionsTreeJson, FutureCalls, FuturesJson, GraphFormat, Route, RuntimeJson, SlowPollsJson,
+    StreamLogs, StreamsJson, TasksJson, ThreadsJson,
 };
 use reqwest::StatusCode;
 use serde::de::DeserializeOwned;
@@ -20,9 +21,13 @@ enum RequestKey {
     Timing,
     Memory,
     Channels,
+    ChannelsGraph,
     Streams,
     Threads,
+    Runtime,
+    Tasks,
     Futures,
+    SlowPolls,
     FunctionLogsTiming,
     FunctionLogsAlloc,
     ChannelLogs,
@@ -33,12 +38,16 @@ enum RequestKey {
 impl DataRequest {
     fn key(&self) -> RequestKey {
         match self {
-            DataRequest::RefreshTiming => RequestKey::Timing,
+            DataRequest::RefreshTiming(_) => RequestKey::Timing,
             DataRequest::RefreshMemory => RequestKey::Memory,
-            DataRequest::RefreshChannels => RequestKey::Channels,
-            DataRequest::RefreshStreams => RequestKey::Streams,
+            DataRequest::RefreshChannels(_) => RequestKey::Channels,
+            DataRequest::RefreshChannelsGraph => RequestKey::ChannelsGraph,
+            DataRequest::RefreshStreams(_) => RequestKey::Streams,
             DataRequest::RefreshThreads => RequestKey::Threads,
+            DataRequest::RefreshRuntime => RequestKey::Runtime,
+            DataRequest::RefreshTasks => RequestKey::Tasks,
             DataRequest::RefreshFutures => RequestKey::Futures,
+            DataRequest::RefreshSlowPolls => RequestKey::SlowPolls,
             DataRequest::FetchFunctionLogsTiming(_) => RequestKey::FunctionLogsTiming,
             DataRequest::FetchFunctionLogsAlloc(_) => RequestKey::FunctionLogsAlloc,
             DataRequest::FetchChannelLogs(_) => RequestKey::ChannelLogs,
@@ -90,58 +99,72 @@ pub(crate) fn spawn_http_worker(
     });
 }
 
-trait RouteExt {
+pub(crate) trait RouteExt {
     async fn fetch(&self, client: &reqwest::Client, base_url: &str) -> DataResponse;
     fn not_found_response(&self) -> Option<DataResponse>;
     fn parse_bytes(&self, bytes: &[u8]) -> DataResponse;
 }
 
-impl RouteExt for Route {
-    async fn fetch(&self, client: &reqwest::Client, base_url: &str) -> DataResponse {
-        let url = format!("{}{}", base_url, self.to_path());
-        trace!("Fetching {}", url);
-
-        let resp = match client.get(&url).send().await {
-            Ok(resp) => resp,
-            Err(e) => {
-                warn!("Request failed for {}: {}", url, e);
-                return DataResponse::Error(format!("Request failed: {}", e));
-            }
-        };
+/// Fetches the raw response body of a route. Returns `Ok(None)` on 404.
+pub(crate) async fn fetch_body(
+    route: &Route,
+    client: &reqwest::Client,
+    base_url: &str,
+) -> Result<Option<Vec<u8>>, String> {
+    let url = format!("{}{}", base_url, route.to_path());
+    trace!("Fetching {}", url);
+
+    let resp = match client.get(&url).send().await {
+        Ok(resp) => resp,
+        Err(e) => {
+            warn!("Request failed for {}: {}", url, e);
+            return Err(format!("Request failed: {}", e));
+        }
+    };
 
-        let status = resp.status();
-        trace!("Response status {} for {}", status, url);
+    let status = resp.status();
+    trace!("Response status {} for {}", status, url);
 
-        if status == StatusCode::NOT_FOUND {
-            if let Some(not_found) = self.not_found_response() {
-                trace!("Resource not found: {}", url);
-                return not_found;
-            }
-        }
+    if status == StatusCode::NOT_FOUND {
+        trace!("Resource not found: {}", url);
+        return Ok(None);
+    }
 
-        let resp = match resp.error_for_status() {
-            Ok(resp) => resp,
-            Err(e) => {
-                error!("HTTP error for {}: {}", url, e);
-                return DataResponse::Error(format!("HTTP error: {}", e));
-            }
-        };
+    let resp = match resp.error_for_status() {
+        Ok(resp) => resp,
+        Err(e) => {
+            error!("HTTP error for {}: {}", url, e);
+            return Err(format!("HTTP error: {}", e));
+        }
+    };
 
-        let bytes = match resp.bytes().await {
-            Ok(bytes) => bytes,
-            Err(e) => {
-                error!("Read error for {}: {}", url, e);
-                return DataResponse::Error(format!("Read error: {}", e));
-            }
-        };
+    match resp.bytes().await {
+        Ok(bytes) => {
+            trace!("Received {} bytes from {}", bytes.len(), url);
+            Ok(Some(bytes.to_vec()))
+        }
+        Err(e) => {
+            error!("Read error for {}: {}", url, e);
+            Err(format!("Read error: {}", e))
+        }
+    }
+}
 
-        trace!("Received {} bytes from {}", bytes.len(), url);
-        self.parse_bytes(&bytes)
+impl RouteExt for Route {
+    async fn fetch(&self, client: &reqwest::Client, base_url: &str) -> DataResponse {
+        match fetch_body(self, client, base_url).await {
+            Ok(Some(bytes)) => self.parse_bytes(&bytes),
+            Ok(None) => self.not_found_response().unwrap_or_else(|| {
+                DataResponse::Error(format!("HTTP error: {} not found", self.to_path()))
+            }),
+            Err(e) => DataResponse::Error(e),
+        }
     }
 
     fn not_found_response(&self) -> Option<DataResponse> {
         match self {
             Route::FunctionsAlloc => Some(DataResponse::FunctionsAllocUnavailable),
+            Route::Runtime => Some(DataResponse::RuntimeUnavailable),
             Route::FunctionTimingLogs { function_name } => Some(
                 DataResponse::FunctionLogsTimingNotFound(function_name.clone()),
             ),
@@ -154,16 +177,30 @@ impl RouteExt for Route {
 
     fn parse_bytes(&self, bytes: &[u8]) -> DataResponse {
         match self {
-            Route::FunctionsTiming => {
+            Route::FunctionsTiming { .. } => {
                 parse_json::<FunctionsJson>(bytes).map(DataResponse::FunctionsTiming)
             }
+            Route::FunctionsTimingTree => {
+                parse_json::<FunctionsTreeJson>(bytes).map(DataResponse::FunctionsTimingTree)
+            }
             Route::FunctionsAlloc => {
                 parse_json::<FunctionsJson>(bytes).map(DataResponse::FunctionsAlloc)
             }
-            Route::Channels => parse_json::<ChannelsJson>(bytes).map(DataResponse::Channels),
-            Route::Streams => parse_json::<StreamsJson>(bytes).map(DataResponse::Streams),
+            Route::Channels { .. } => parse_json::<ChannelsJson>(bytes).map(DataResponse::Channels),
+            Route::ChannelsGraph {
+                format: GraphFormat::Json,
+            } => parse_json::<ChannelsGraphJson>(bytes).map(DataResponse::ChannelsGraph),
+            Route::ChannelsGraph {
+                format: GraphFormat::Dot,
+            } => Ok(DataResponse::Error(
+                "DOT graph route is not supported by the console".to_string(),
+            )),
+            Route::Streams { .. } => parse_json::<StreamsJson>(bytes).map(DataResponse::Streams),
             Route::Threads => parse_json::<ThreadsJson>(bytes).map(DataResponse::Threads),
+            Route::Runtime => parse_json::<RuntimeJson>(bytes).map(DataResponse::Runtime),
+            Route::Tasks => parse_json::<TasksJson>(bytes).map(DataResponse::Tasks),
             Route::Futures => parse_json::<FuturesJson>(bytes).map(DataResponse::Futures),
+            Route::SlowPolls => parse_json::<SlowPollsJson>(bytes).map(DataResponse::SlowPolls),
             Route::FunctionTimingLogs { function_name } => parse_json::<FunctionLogsJson>(bytes)
                 .map(|logs| DataResponse::FunctionLogsTiming {
                     function_name: function_name.clone(),
@@ -192,6 +229,9 @@ impl RouteExt for Route {
                     calls,
                 })
             }
+            Route::Metrics => Ok(DataResponse::Error(
+                "OpenMetrics route is not supported by the console".to_string(),
+            )),
         }
         .unwrap_or_else(|e| DataResponse::Error(format!("JSON parse error: {}", e)))
     }
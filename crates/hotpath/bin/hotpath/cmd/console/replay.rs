@@ -0,0 +1,119 @@
+//! Replay worker serving data requests from a recorded session
+
+use crossbeam_channel::{Receiver, Sender};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, trace};
+
+use crate::cmd::console::events::{AppEvent, DataRequest, DataResponse};
+use crate::cmd::console::http_worker::RouteExt;
+use crate::cmd::console::session::Session;
+
+/// Playback position shared between the app and the replay worker
+pub(crate) struct ReplayState {
+    session: Arc<Session>,
+    position: Arc<AtomicUsize>,
+}
+
+impl ReplayState {
+    pub(crate) fn position(&self) -> usize {
+        self.position.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.session.snapshots.len()
+    }
+
+    pub(crate) fn is_at_end(&self) -> bool {
+        self.position() + 1 >= self.len()
+    }
+
+    pub(crate) fn elapsed(&self) -> Duration {
+        self.session.elapsed_at(self.position())
+    }
+
+    pub(crate) fn total(&self) -> Duration {
+        self.session.elapsed_at(self.len() - 1)
+    }
+
+    pub(crate) fn metrics_url(&self) -> &str {
+        &self.session.header.metrics_url
+    }
+
+    pub(crate) fn refresh_interval(&self) -> Duration {
+        Duration::from_millis(self.session.header.refresh_interval_ms)
+    }
+
+    /// Moves by `delta` snapshots, clamped to the recording.
+    pub(crate) fn step(&self, delta: isize) {
+        let position = self
+            .position()
+            .saturating_add_signed(delta)
+            .min(self.len() - 1);
+        self.position.store(position, Ordering::Relaxed);
+    }
+
+    /// Jumps to the snapshot closest to `offset` away from the current one.
+    pub(crate) fn seek(&self, offset: Duration, forward: bool) {
+        let elapsed = self.elapsed();
+        let target = if forward {
+            elapsed + offset
+        } else {
+            elapsed.saturating_sub(offset)
+        };
+        self.position
+            .store(self.session.position_at(target), Ordering::Relaxed);
+    }
+
+    pub(crate) fn rewind(&self) {
+        self.position.store(0, Ordering::Relaxed);
+    }
+}
+
+pub(crate) fn spawn_replay_worker(
+    request_rx: Receiver<DataRequest>,
+    event_tx: Sender<AppEvent>,
+    session: Session,
+) -> ReplayState {
+    let session = Arc::new(session);
+    let position = Arc::new(AtomicUsize::new(0));
+
+    let state = ReplayState {
+        session: session.clone(),
+        position: position.clone(),
+    };
+
+    std::thread::spawn(move || {
+        info!(
+            "Replay worker started, {} snapshots of {}",
+            session.snapshots.len(),
+            session.header.metrics_url
+        );
+
+        while let Ok(request) = request_rx.recv() {
+            let snapshot = &session.snapshots[position.load(Ordering::Relaxed)];
+            let route = request.to_route();
+            let path = route.to_path();
+            trace!("Replaying {} at {}ms", path, snapshot.elapsed_ms);
+
+            let response = match snapshot.responses.get(&path) {
+                Some(serde_json::Value::Null) => route.not_found_response().unwrap_or_else(|| {
+                    DataResponse::Error(format!("{} was not found when recorded", path))
+                }),
+                Some(body) => match serde_json::to_vec(body) {
+                    Ok(bytes) => route.parse_bytes(&bytes),
+                    Err(e) => DataResponse::Error(format!("JSON parse error: {}", e)),
+                },
+                None => DataResponse::Error(format!("{} was not recorded", path)),
+            };
+
+            if event_tx.send(AppEvent::Data(response)).is_err() {
+                break;
+            }
+        }
+        info!("Replay worker shutting down");
+    });
+
+    state
+}
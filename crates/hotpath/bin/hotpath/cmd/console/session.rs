@@ -0,0 +1,197 @@
+//! Recorded console sessions (`.hpr` files)
+//!
+//! A session is a JSON Lines file: a header line followed by one line per snapshot.
+//! Each snapshot maps route paths to the JSON body the metrics server returned at that
+//! moment, or `null` if the route responded with 404. Snapshots are flushed one line at
+//! a time, so a recording interrupted with Ctrl-C is still readable.
+
+use eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::time::Duration;
+
+const SESSION_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct SessionHeader {
+    pub(crate) version: u32,
+    pub(crate) metrics_url: String,
+    pub(crate) refresh_interval_ms: u64,
+}
+
+impl SessionHeader {
+    pub(crate) fn new(metrics_url: String, refresh_interval_ms: u64) -> Self {
+        Self {
+            version: SESSION_VERSION,
+            metrics_url,
+            refresh_interval_ms,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct Snapshot {
+    /// Milliseconds since the recording started
+    pub(crate) elapsed_ms: u64,
+    /// Response bodies keyed by route path, `null` for 404
+    pub(crate) responses: BTreeMap<String, serde_json::Value>,
+}
+
+pub(crate) struct Session {
+    pub(crate) header: SessionHeader,
+    pub(crate) snapshots: Vec<Snapshot>,
+}
+
+impl Session {
+    pub(crate) fn load(path: &Path) -> Result<Self> {
+        let file = File::open(path)
+            .map_err(|e| eyre::eyre!("Failed to open {}: {}", path.display(), e))?;
+        Self::from_reader(BufReader::new(file))
+            .map_err(|e| eyre::eyre!("Failed to load session {}: {}", path.display(), e))
+    }
+
+    fn from_reader(reader: impl BufRead) -> Result<Self> {
+        let mut lines = reader.lines();
+
+        let header_line = lines.next().ok_or_else(|| eyre::eyre!("file is empty"))??;
+        let header: SessionHeader =
+            serde_json::from_str(&header_line).map_err(|e| eyre::eyre!("invalid header: {}", e))?;
+        if header.version != SESSION_VERSION {
+            eyre::bail!("unsupported session version {}", header.version);
+        }
+
+        let lines = lines.collect::<std::io::Result<Vec<_>>>()?;
+        let mut snapshots = Vec::with_capacity(lines.len());
+        for (idx, line) in lines.iter().enumerate() {
+            match serde_json::from_str::<Snapshot>(line) {
+                Ok(snapshot) => snapshots.push(snapshot),
+                // The last line may be cut short if the recorder was killed mid-write
+                Err(_) if idx + 1 == lines.len() => break,
+                Err(e) => eyre::bail!("invalid snapshot on line {}: {}", idx + 2, e),
+            }
+        }
+
+        if snapshots.is_empty() {
+            eyre::bail!("no snapshots recorded");
+        }
+
+        Ok(Self { header, snapshots })
+    }
+
+    /// Time of the snapshot at `position` since the recording started.
+    pub(crate) fn elapsed_at(&self, position: usize) -> Duration {
+        self.snapshots
+            .get(position)
+            .map(|snapshot| Duration::from_millis(snapshot.elapsed_ms))
+            .unwrap_or_default()
+    }
+
+    /// Index of the last snapshot taken at or before `elapsed`.
+    pub(crate) fn position_at(&self, elapsed: Duration) -> usize {
+        let elapsed_ms = elapsed.as_millis() as u64;
+        self.snapshots
+            .partition_point(|snapshot| snapshot.elapsed_ms <= elapsed_ms)
+            .saturating_sub(1)
+    }
+}
+
+pub(crate) struct SessionWriter {
+    writer: BufWriter<File>,
+}
+
+impl SessionWriter {
+    pub(crate) fn create(path: &Path, header: &SessionHeader) -> Result<Self> {
+        let file = File::create(path)
+            .map_err(|e| eyre::eyre!("Failed to create {}: {}", path.display(), e))?;
+        let mut writer = Self {
+            writer: BufWriter::new(file),
+        };
+        writer.write_line(header)?;
+        Ok(writer)
+    }
+
+    pub(crate) fn write_snapshot(&mut self, snapshot: &Snapshot) -> Result<()> {
+        self.write_line(snapshot)
+    }
+
+    fn write_line(&mut self, value: &impl Serialize) -> Result<()> {
+        serde_json::to_writer(&mut self.writer, value)?;
+        self.writer.write_all(b"\n")?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(elapsed_ms: u64) -> Snapshot {
+        let mut responses = BTreeMap::new();
+        responses.insert("/functions_alloc".to_string(), serde_json::Value::Null);
+        responses.insert(
+            "/threads".to_string(),
+            serde_json::json!({ "elapsed_ms": elapsed_ms }),
+        );
+        Snapshot {
+            elapsed_ms,
+            responses,
+        }
+    }
+
+    #[test]
+    fn test_session_roundtrip_with_truncated_tail() {
+        let path = std::env::temp_dir().join(format!("hotpath-session-{}.hpr", std::process::id()));
+        let mut writer = SessionWriter::create(
+            &path,
+            &SessionHeader::new("http://localhost:6770".into(), 500),
+        )
+        .unwrap();
+        for elapsed_ms in [0, 500, 1000] {
+            writer.write_snapshot(&snapshot(elapsed_ms)).unwrap();
+        }
+        drop(writer);
+
+        let mut content = std::fs::read_to_string(&path).unwrap();
+        content.push_str("{\"elapsed_ms\":1500,\"respon");
+        std::fs::write(&path, content).unwrap();
+
+        let session = Session::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(session.header.metrics_url, "http://localhost:6770");
+        assert_eq!(session.header.refresh_interval_ms, 500);
+        assert_eq!(session.snapshots.len(), 3);
+        assert_eq!(
+            session.snapshots[1].responses["/functions_alloc"],
+            serde_json::Value::Null
+        );
+    }
+
+    #[test]
+    fn test_invalid_snapshot_in_the_middle_is_an_error() {
+        let content = "{\"version\":1,\"metrics_url\":\"x\",\"refresh_interval_ms\":500}\n\
+                       {\"elapsed_ms\":0,\"responses\":{}}\n\
+                       garbage\n\
+                       {\"elapsed_ms\":1000,\"responses\":{}}\n";
+
+        assert!(Session::from_reader(content.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_position_at() {
+        let session = Session {
+            header: SessionHeader::new("x".into(), 500),
+            snapshots: vec![snapshot(0), snapshot(500), snapshot(1000)],
+        };
+
+        assert_eq!(session.position_at(Duration::ZERO), 0);
+        assert_eq!(session.position_at(Duration::from_millis(499)), 0);
+        assert_eq!(session.position_at(Duration::from_millis(500)), 1);
+        assert_eq!(session.position_at(Duration::from_secs(60)), 2);
+        assert_eq!(session.elapsed_at(2), Duration::from_secs(1));
+    }
+}
@@ -5,7 +5,9 @@ pub(crate) mod functions_memory;
 pub(crate) mod functions_timing;
 pub(crate) mod futures;
 pub(crate) mod main_view;
+pub(crate) mod runtime;
 pub(crate) mod streams;
+pub(crate) mod tasks;
 pub(crate) mod threads;
 pub(crate) mod top_bar;
 pub(crate) use main_view::render_ui;
@@ -24,6 +24,14 @@ const CLOSE_LABEL: &str = " | Close ";
 const CLOSE_KEYS: &str = "<i/o/h> ";
 const TOGGLE_CALLS_LABEL: &str = " | Toggle Calls ";
 const TOGGLE_CALLS_KEY: &str = "<o> ";
+const STEP_LABEL: &str = " | Step ";
+const STEP_KEYS: &str = "<,/.> ";
+const SEEK_LABEL: &str = " | Seek 10s ";
+const SEEK_KEYS: &str = "<[/]> ";
+const WINDOW_LABEL: &str = " | Window ";
+const WINDOW_KEY: &str = "<w> ";
+const GRAPH_LABEL: &str = " | Graph ";
+const GRAPH_KEY: &str = "<g> ";
 
 /// Renders the bottom controls bar showing context-aware keybindings
 #[hotpath::measure]
@@ -36,9 +44,13 @@ pub(crate) fn render_help_bar(
     streams_focus: StreamsFocus,
     functions_focus: FunctionsFocus,
     futures_focus: FuturesFocus,
+    replaying: bool,
 ) {
-    let controls_line = if selected_tab == SelectedTab::Threads {
-        // Threads tab - simple controls, no logs
+    let mut controls_line = if matches!(
+        selected_tab,
+        SelectedTab::Threads | SelectedTab::Runtime | SelectedTab::Tasks
+    ) {
+        // Threads, Runtime and Tasks tabs - simple controls, no logs
         Line::from(vec![
             NAV_KEYS_FULL.blue().bold(),
             PAUSE_LABEL.into(),
@@ -185,6 +197,31 @@ pub(crate) fn render_help_bar(
         }
     };
 
+    if selected_tab == SelectedTab::Channels {
+        controls_line
+            .spans
+            .extend([GRAPH_LABEL.into(), GRAPH_KEY.blue().bold()]);
+    }
+
+    let windowed_tab = matches!(
+        selected_tab,
+        SelectedTab::Timing | SelectedTab::Channels | SelectedTab::Streams
+    );
+    if windowed_tab {
+        controls_line
+            .spans
+            .extend([WINDOW_LABEL.into(), WINDOW_KEY.blue().bold()]);
+    }
+
+    if replaying {
+        controls_line.spans.extend([
+            STEP_LABEL.into(),
+            STEP_KEYS.blue().bold(),
+            SEEK_LABEL.into(),
+            SEEK_KEYS.blue().bold(),
+        ]);
+    }
+
     let block = Block::bordered().border_set(border::PLAIN);
 
     let paragraph = Paragraph::new(controls_line).block(block).left_aligned();
@@ -1,15 +1,17 @@
+pub(crate) mod graph;
 pub(crate) mod inspect;
 pub(crate) mod logs;
 
 use super::common_styles;
 use crate::cmd::console::app::ChannelsFocus;
-use crate::cmd::console::widgets::formatters::{queue_status, truncate_left};
+use crate::cmd::console::widgets::formatters::{queue_status, sparkline, truncate_left};
 use hotpath::format_bytes;
-use hotpath::json::{ChannelState, ChannelType, SerializableChannelStats};
+use hotpath::json::{ChannelState, ChannelType, SerializableChannelStats, StatsWindow};
 use ratatui::{
     layout::{Constraint, Rect},
     style::{Color, Style},
     symbols::border,
+    text::Line,
     widgets::{Block, Cell, HighlightSpacing, Row, Table, TableState},
     Frame,
 };
@@ -26,9 +28,12 @@ pub(crate) fn render_channels_panel(
     focus: ChannelsFocus,
     channel_position: usize,
     total_channels: usize,
+    stats_window: StatsWindow,
+    queue_warn_threshold: Option<u64>,
 ) {
     let available_width = area.width.saturating_sub(10);
     let channel_width = ((available_width as f32 * 0.22) as usize).max(36);
+    let trend_width = (available_width as f32 * 0.11) as usize;
 
     let header = Row::new(vec![
         Cell::from("Channel"),
@@ -36,6 +41,10 @@ pub(crate) fn render_channels_panel(
         Cell::from("State"),
         Cell::from("Sent"),
         Cell::from("Receive"),
+        Cell::from("Rate"),
+        Cell::from("Bytes/s"),
+        Cell::from("Trend"),
+        Cell::from("Subs"),
         Cell::from("Queue"),
         Cell::from("Mem"),
     ])
@@ -62,7 +71,13 @@ pub(crate) fn render_channels_panel(
                 ChannelType::Unbounded => Cell::from("N/A"),
                 _ => Cell::from(format_bytes(stat.queued_bytes)),
             };
-            let queue_cell = queue_status(stat.queued, &stat.channel_type, 8);
+            let queue_cell = queue_status(
+                stat.queued,
+                stat.max_queued,
+                &stat.channel_type,
+                queue_warn_threshold,
+                8,
+            );
 
             Row::new(vec![
                 Cell::from(truncate_left(&stat.label, channel_width)),
@@ -70,6 +85,14 @@ pub(crate) fn render_channels_panel(
                 Cell::from(state_text).style(state_style),
                 Cell::from(stat.sent_count.to_string()),
                 Cell::from(stat.received_count.to_string()),
+                Cell::from(format!("{:.1}/s", stat.sent_rate)),
+                Cell::from(format_bytes(stat.sent_bytes_rate as u64)),
+                Cell::from(sparkline(&stat.sent_history, trend_width))
+                    .style(Style::default().fg(Color::Cyan)),
+                Cell::from(
+                    stat.subscribers
+                        .map_or_else(|| "-".to_string(), |subs| subs.to_string()),
+                ),
                 queue_cell,
                 mem_cell,
             ])
@@ -77,15 +100,36 @@ pub(crate) fn render_channels_panel(
         .collect();
 
     let widths = [
-        Constraint::Percentage(30), // Channel
-        Constraint::Percentage(14), // Type
+        Constraint::Percentage(20), // Channel
+        Constraint::Percentage(10), // Type
         Constraint::Percentage(10), // State
-        Constraint::Percentage(9),  // Sent
-        Constraint::Percentage(11), // Received
-        Constraint::Percentage(16), // Queue
-        Constraint::Percentage(10), // Mem
+        Constraint::Percentage(7),  // Sent
+        Constraint::Percentage(7),  // Received
+        Constraint::Percentage(8),  // Rate
+        Constraint::Percentage(8),  // Bytes/s
+        Constraint::Percentage(9),  // Trend
+        Constraint::Percentage(5),  // Subs
+        Constraint::Percentage(10), // Queue
+        Constraint::Percentage(6),  // Mem
     ];
 
+    let rate_title = format!(" Rate: {} ", stats_window.label());
+    let over_threshold = queue_warn_threshold.map_or(0, |threshold| {
+        stats.iter().filter(|stat| stat.queued >= threshold).count()
+    });
+    let warn_title = if over_threshold > 0 {
+        let noun = if over_threshold == 1 {
+            "channel"
+        } else {
+            "channels"
+        };
+        Line::from(format!(" ⚠ {} {} over queue limit ", over_threshold, noun))
+            .style(Style::default().fg(Color::Red))
+            .right_aligned()
+    } else {
+        Line::default()
+    };
+
     let table_block = if show_logs {
         let border_set = if focus == ChannelsFocus::Channels {
             border::THICK
@@ -94,6 +138,8 @@ pub(crate) fn render_channels_panel(
         };
         Block::bordered()
             .title(format!(" [{}/{}] ", channel_position, total_channels))
+            .title(rate_title)
+            .title(warn_title)
             .border_set(border_set)
             .border_style(if focus == ChannelsFocus::Channels {
                 Style::default()
@@ -103,6 +149,8 @@ pub(crate) fn render_channels_panel(
     } else {
         Block::bordered()
             .title(format!(" [{}/{}] ", channel_position, total_channels))
+            .title(rate_title)
+            .title(warn_title)
             .border_set(border::THICK)
     };
 
@@ -0,0 +1,88 @@
+use super::super::common_styles;
+use hotpath::json::{ChannelsGraphJson, GraphEdge, GraphNode, GraphNodeKind};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style, Stylize},
+    symbols::border,
+    text::{Line, Span},
+    widgets::{Block, Paragraph, Wrap},
+    Frame,
+};
+use std::collections::HashMap;
+
+/// Renders one line per channel: its producers, the channel, and its consumers
+#[hotpath::measure]
+pub(crate) fn render_graph_panel(graph: &ChannelsGraphJson, area: Rect, frame: &mut Frame) {
+    let nodes: HashMap<&str, &GraphNode> = graph
+        .nodes
+        .iter()
+        .map(|node| (node.id.as_str(), node))
+        .collect();
+
+    let lines: Vec<Line> = graph
+        .nodes
+        .iter()
+        .filter(|node| node.kind == GraphNodeKind::Channel)
+        .map(|channel| {
+            let producers = graph.edges.iter().filter(|edge| edge.to == channel.id);
+            let consumers = graph.edges.iter().filter(|edge| edge.from == channel.id);
+
+            let mut spans = endpoint_spans(producers, |edge| &edge.from, &nodes);
+            spans.push(" ─▶ ".dark_gray());
+            spans.push(format!("[{}]", channel.label).yellow().bold());
+            spans.push(" ─▶ ".dark_gray());
+            spans.extend(endpoint_spans(consumers, |edge| &edge.to, &nodes));
+            Line::from(spans)
+        })
+        .collect();
+
+    let block = Block::bordered()
+        .title(" Channel graph ")
+        .title_bottom(
+            Line::from(" Producers and consumers are reported by proxy = false channels ")
+                .style(common_styles::PLACEHOLDER_STYLE),
+        )
+        .border_set(border::THICK);
+
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false }),
+        area,
+    );
+}
+
+/// Comma separated endpoints with their message counts, or a placeholder if there are none
+fn endpoint_spans<'a>(
+    edges: impl Iterator<Item = &'a GraphEdge>,
+    endpoint_id: impl Fn(&GraphEdge) -> &String,
+    nodes: &HashMap<&str, &GraphNode>,
+) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    for edge in edges {
+        if !spans.is_empty() {
+            spans.push(Span::raw(", "));
+        }
+
+        let id = endpoint_id(edge);
+        let (label, style) = match nodes.get(id.as_str()) {
+            Some(node) if node.kind == GraphNodeKind::Function => {
+                (node.label.clone(), Style::default().fg(Color::Cyan))
+            }
+            Some(node) => (node.label.clone(), Style::default()),
+            None => (id.clone(), Style::default()),
+        };
+        let threads = nodes.get(id.as_str()).map_or(0, |node| node.tids.len());
+
+        spans.push(Span::styled(label, style));
+        if threads > 1 {
+            spans.push(format!(" ×{}", threads).dark_gray());
+        }
+        spans.push(format!(" ({})", edge.messages).dark_gray());
+    }
+
+    if spans.is_empty() {
+        spans.push(Span::styled("?", common_styles::PLACEHOLDER_STYLE));
+    }
+    spans
+}
@@ -1,10 +1,12 @@
 use super::super::common_styles;
 use crate::cmd::console::app::CachedLogs;
 use crate::cmd::console::widgets::formatters::{format_delay, format_time_ago, truncate_message};
+use hotpath::json::SerializableChannelStats;
 use ratatui::{
     layout::Rect,
-    style::Style,
+    style::{Color, Style},
     symbols::border,
+    text::Line,
     widgets::{Block, HighlightSpacing, Row, Table, TableState},
     Frame,
 };
@@ -35,9 +37,11 @@ pub(crate) fn render_logs_placeholder(
 }
 
 /// Renders the logs panel with sent and received log entries
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn render_logs_panel(
     cached_logs: &CachedLogs,
     channel_label: &str,
+    selected_stat: Option<&SerializableChannelStats>,
     area: Rect,
     frame: &mut Frame,
     table_state: &mut TableState,
@@ -50,7 +54,7 @@ pub(crate) fn render_logs_panel(
         border::PLAIN
     };
 
-    let block = Block::bordered()
+    let mut block = Block::bordered()
         .title(format!(" {} ", channel_label))
         .border_set(border_set)
         .border_style(if is_focused {
@@ -59,6 +63,51 @@ pub(crate) fn render_logs_panel(
             common_styles::UNFOCUSED_BORDER_STYLE
         });
 
+    if let Some(stat) = selected_stat.filter(|stat| stat.max_queued > 0) {
+        let ago =
+            |timestamp_ns: u64| format_time_ago(current_elapsed_ns.saturating_sub(timestamp_ns));
+        let high_water = match stat.queue_warnings.last() {
+            Some(warning) => Line::from(format!(
+                " ⚠ Max queued {} ({}) | over {} {}x, last {} ",
+                stat.max_queued,
+                ago(stat.max_queued_at_ns),
+                warning.threshold,
+                stat.queue_warnings.len(),
+                ago(warning.timestamp_ns),
+            ))
+            .style(Style::default().fg(Color::Red)),
+            None => Line::from(format!(
+                " Max queued {} ({}) ",
+                stat.max_queued,
+                ago(stat.max_queued_at_ns)
+            )),
+        };
+        block = block.title(high_water.right_aligned());
+    }
+
+    if let Some(wait) = selected_stat.and_then(|stat| stat.queue_wait.as_ref()) {
+        block = block.title_bottom(format!(
+            " Queue wait avg {} | p50 {} | p95 {} | p99 {} | max {} ",
+            format_delay(wait.avg_ns),
+            format_delay(wait.p50_ns),
+            format_delay(wait.p95_ns),
+            format_delay(wait.p99_ns),
+            format_delay(wait.max_ns),
+        ));
+    }
+
+    if let Some(blocked) = selected_stat.and_then(|stat| stat.send_blocked.as_ref()) {
+        block = block.title_bottom(
+            Line::from(format!(
+                " Send blocked {}x | total {} | max {} ",
+                blocked.durations.count,
+                format_delay(blocked.total_ns),
+                format_delay(blocked.durations.max_ns),
+            ))
+            .right_aligned(),
+        );
+    }
+
     let inner_area = block.inner(area);
     frame.render_widget(block, area);
 
@@ -69,7 +69,11 @@ pub(crate) fn render_functions_table(frame: &mut Frame, app: &mut App, area: Rec
             .map(|p| format!("P{}", p))
             .collect::<Vec<_>>(),
     )
-    .chain(vec!["Total".to_string(), "% Total".to_string()])
+    .chain(vec![
+        "Total".to_string(),
+        "% Total".to_string(),
+        "Self".to_string(),
+    ])
     .map(|h| Cell::from(h).style(common_styles::HEADER_STYLE_CYAN))
     .collect::<Vec<_>>();
 
@@ -100,7 +104,7 @@ pub(crate) fn render_functions_table(frame: &mut Frame, app: &mut App, area: Rec
 
     let function_pct: u16 = 35;
     let remaining_pct: u16 = 100 - function_pct;
-    let num_other_cols = (4 + num_percentiles) as u16; // Calls, Avg, P95s, Total, % Total
+    let num_other_cols = (5 + num_percentiles) as u16; // Calls, Avg, P95s, Total, % Total, Self
     let col_pct: u16 = remaining_pct / num_other_cols;
 
     let table = Table::new(
@@ -115,6 +119,7 @@ pub(crate) fn render_functions_table(frame: &mut Frame, app: &mut App, area: Rec
             .chain(vec![
                 Constraint::Percentage(col_pct), // Total
                 Constraint::Percentage(col_pct), // % Total
+                Constraint::Percentage(col_pct), // Self
             ])
             .collect::<Vec<_>>(),
     )
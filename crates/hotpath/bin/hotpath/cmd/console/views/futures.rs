@@ -1,18 +1,33 @@
 pub(crate) mod calls;
 pub(crate) mod inspect;
+pub(crate) mod slow_polls;
 
 use super::common_styles;
 use crate::cmd::console::app::FuturesFocus;
 use crate::cmd::console::widgets::formatters::truncate_left;
-use hotpath::json::SerializableFutureStats;
+use hotpath::format_duration;
+use hotpath::json::{DurationStats, SerializableFutureStats, WakeStats};
 use ratatui::{
     layout::{Constraint, Rect},
-    style::Style,
+    style::{Color, Style},
     symbols::border,
     widgets::{Block, Cell, HighlightSpacing, Row, Table, TableState},
     Frame,
 };
 
+fn avg_cell(stats: Option<DurationStats>) -> Cell<'static> {
+    Cell::from(stats.map_or_else(|| "-".to_string(), |stats| format_duration(stats.avg_ns)))
+}
+
+/// Wake count, marked when any call used its waker suspiciously
+pub(crate) fn wakes_cell(wakes: &WakeStats) -> Cell<'static> {
+    if wakes.warnings().is_empty() {
+        Cell::from(wakes.wakes.to_string())
+    } else {
+        Cell::from(format!("⚠ {}", wakes.wakes)).style(Style::default().fg(Color::Red))
+    }
+}
+
 /// Renders the futures table with future statistics
 #[hotpath::measure]
 #[allow(clippy::too_many_arguments)]
@@ -27,12 +42,17 @@ pub(crate) fn render_futures_panel(
     total_futures: usize,
 ) {
     let available_width = area.width.saturating_sub(10);
-    let future_width = ((available_width as f32 * 0.50) as usize).max(30);
+    let future_width = ((available_width as f32 * 0.30) as usize).max(30);
 
     let header = Row::new(vec![
         Cell::from("Future"),
         Cell::from("Calls"),
         Cell::from("Polls"),
+        Cell::from("Busy avg"),
+        Cell::from("Idle avg"),
+        Cell::from("Total p95"),
+        Cell::from("Longest poll"),
+        Cell::from("Wakes"),
     ])
     .style(common_styles::HEADER_STYLE)
     .height(1);
@@ -44,14 +64,30 @@ pub(crate) fn render_futures_panel(
                 Cell::from(truncate_left(&stat.label, future_width)),
                 Cell::from(stat.call_count.to_string()),
                 Cell::from(stat.total_polls.to_string()),
+                avg_cell(stat.busy),
+                avg_cell(stat.idle),
+                Cell::from(
+                    stat.total
+                        .map_or_else(|| "-".to_string(), |total| format_duration(total.p95_ns)),
+                ),
+                Cell::from(stat.longest_poll.map_or_else(
+                    || "-".to_string(),
+                    |longest| format_duration(longest.duration_ns),
+                )),
+                wakes_cell(&stat.wakes),
             ])
         })
         .collect();
 
     let widths = [
-        Constraint::Percentage(50), // Future
-        Constraint::Percentage(25), // Calls
-        Constraint::Percentage(25), // Polls
+        Constraint::Percentage(30), // Future
+        Constraint::Percentage(7),  // Calls
+        Constraint::Percentage(7),  // Polls
+        Constraint::Percentage(12), // Busy avg
+        Constraint::Percentage(12), // Idle avg
+        Constraint::Percentage(12), // Total p95
+        Constraint::Percentage(12), // Longest poll
+        Constraint::Percentage(8),  // Wakes
     ];
 
     let table_block = if show_calls {
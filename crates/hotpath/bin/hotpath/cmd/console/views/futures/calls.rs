@@ -1,6 +1,8 @@
 use super::super::common_styles;
+use super::wakes_cell;
 use crate::cmd::console::widgets::formatters::truncate_message;
-use hotpath::json::{FutureCall, FutureCalls, FutureState};
+use hotpath::format_duration;
+use hotpath::json::{FutureCall, FutureCalls, FutureState, LongestPoll};
 use ratatui::{
     layout::Rect,
     style::{Color, Style},
@@ -43,22 +45,57 @@ fn state_style(state: &FutureState) -> Style {
     }
 }
 
-fn render_call_row(call: &FutureCall, result_width: usize) -> Row<'static> {
+/// Tags of a call as `key=value` pairs
+fn format_tags(call: &FutureCall) -> String {
+    call.tags
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn render_call_row(
+    call: &FutureCall,
+    tags_width: usize,
+    result_width: usize,
+    longest_poll: Option<LongestPoll>,
+) -> Row<'static> {
     let state_text = call.state.as_str().to_string();
+    let tags_text = if call.tags.is_empty() {
+        "-".to_string()
+    } else {
+        truncate_message(&format_tags(call), tags_width)
+    };
     let result = call.result.as_deref().unwrap_or("-");
     let result_text = truncate_message(result, result_width);
+    let idle_text = call
+        .idle_ns()
+        .map_or_else(|| "-".to_string(), format_duration);
+    // Flag the call holding the future's longest poll
+    let max_poll_cell = if longest_poll.is_some_and(|longest| longest.call_id == call.id) {
+        Cell::from(format!("⚠ {}", format_duration(call.max_poll_ns)))
+            .style(Style::default().fg(Color::Red))
+    } else {
+        Cell::from(format_duration(call.max_poll_ns))
+    };
 
     Row::new(vec![
         Cell::from(call.id.to_string()),
         Cell::from(state_text).style(state_style(&call.state)),
+        Cell::from(tags_text).style(Style::default().fg(Color::Cyan)),
         Cell::from(result_text),
         Cell::from(call.poll_count.to_string()),
+        wakes_cell(&call.wakes),
+        Cell::from(format_duration(call.busy_ns)),
+        Cell::from(idle_text),
+        max_poll_cell,
     ])
 }
 
 pub(crate) fn render_calls_panel(
     future_calls: &FutureCalls,
     future_label: &str,
+    longest_poll: Option<LongestPoll>,
     area: Rect,
     frame: &mut Frame,
     table_state: &mut TableState,
@@ -83,23 +120,39 @@ pub(crate) fn render_calls_panel(
     frame.render_widget(block, area);
 
     let available_width = inner_area.width.saturating_sub(4);
-    let result_width = (available_width.saturating_sub(25) as usize).max(10);
+    let has_tags = future_calls.calls.iter().any(|call| !call.tags.is_empty());
+    // Calls without tags only need room for the placeholder
+    let tags_width = if has_tags {
+        (available_width.saturating_sub(66) / 2) as usize
+    } else {
+        4
+    };
+    let result_width = (available_width.saturating_sub(66) as usize)
+        .saturating_sub(tags_width)
+        .max(10);
 
-    let header = Row::new(vec!["ID", "State", "Result", "Polls"])
-        .style(common_styles::HEADER_STYLE)
-        .height(1);
+    let header = Row::new(vec![
+        "ID", "State", "Tags", "Result", "Polls", "Wakes", "Busy", "Idle", "Max poll",
+    ])
+    .style(common_styles::HEADER_STYLE)
+    .height(1);
 
     let rows: Vec<Row> = future_calls
         .calls
         .iter()
-        .map(|call| render_call_row(call, result_width))
+        .map(|call| render_call_row(call, tags_width, result_width, longest_poll))
         .collect();
 
     let widths = [
-        ratatui::layout::Constraint::Length(8), // ID
-        ratatui::layout::Constraint::Length(9), // State
-        ratatui::layout::Constraint::Min(10),   // Result
-        ratatui::layout::Constraint::Length(6), // Polls
+        ratatui::layout::Constraint::Length(8),                 // ID
+        ratatui::layout::Constraint::Length(9),                 // State
+        ratatui::layout::Constraint::Length(tags_width as u16), // Tags
+        ratatui::layout::Constraint::Min(10),                   // Result
+        ratatui::layout::Constraint::Length(6),                 // Polls
+        ratatui::layout::Constraint::Length(7),                 // Wakes
+        ratatui::layout::Constraint::Length(10),                // Busy
+        ratatui::layout::Constraint::Length(10),                // Idle
+        ratatui::layout::Constraint::Length(12),                // Max poll
     ];
 
     let table = Table::new(rows, widths)
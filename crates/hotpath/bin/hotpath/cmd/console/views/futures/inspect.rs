@@ -1,6 +1,8 @@
+use hotpath::format_duration;
 use hotpath::json::FutureCall;
 use ratatui::{
     layout::Rect,
+    style::Stylize,
     symbols::border,
     text::Line,
     widgets::{Block, Clear, Paragraph, Wrap},
@@ -39,28 +41,74 @@ pub(crate) fn render_inspect_popup(call: &FutureCall, area: Rect, frame: &mut Fr
 
     frame.render_widget(block, popup_area);
 
-    let text_lines: Vec<Line> = result
-        .lines()
-        .flat_map(|line| {
-            let max_width = inner_area.width.saturating_sub(2) as usize;
-            if line.len() <= max_width {
-                vec![Line::from(line)]
-            } else {
-                let mut wrapped = Vec::new();
-                let mut remaining = line;
-                while !remaining.is_empty() {
-                    let split_at = remaining
-                        .char_indices()
-                        .nth(max_width)
-                        .map(|(i, _)| i)
-                        .unwrap_or(remaining.len());
-                    wrapped.push(Line::from(&remaining[..split_at]));
-                    remaining = &remaining[split_at..];
-                }
-                wrapped
+    let lifetime = call
+        .lifetime_ns()
+        .map_or_else(|| "alive".to_string(), format_duration);
+    let idle = call
+        .idle_ns()
+        .map_or_else(|| "-".to_string(), format_duration);
+    let first_poll = call.first_poll_at_ns.map_or_else(
+        || "never polled".to_string(),
+        |first_poll_at_ns| {
+            format!(
+                "first poll after {}",
+                format_duration(first_poll_at_ns.saturating_sub(call.created_at_ns))
+            )
+        },
+    );
+    let mut text_lines: Vec<Line> = vec![
+        Line::from(format!(
+            "Total: {}  Busy: {}  Idle: {}  Max poll: {}  Wakes: {}  ({})",
+            lifetime,
+            format_duration(call.busy_ns),
+            idle,
+            format_duration(call.max_poll_ns),
+            call.wakes.wakes,
+            first_poll
+        ))
+        .dark_gray(),
+        Line::default(),
+    ];
+
+    let warnings = call.wakes.warnings();
+    if !warnings.is_empty() {
+        text_lines.extend(
+            warnings
+                .into_iter()
+                .map(|(warning, count)| Line::from(format!("⚠ {} ({}x)", warning, count)).red()),
+        );
+        text_lines.push(Line::default());
+    }
+
+    // Tags, separated from the result by an empty line
+    if !call.tags.is_empty() {
+        text_lines.extend(
+            call.tags
+                .iter()
+                .map(|(key, value)| Line::from(format!("{}: {}", key, value)).cyan()),
+        );
+        text_lines.push(Line::default());
+    }
+
+    text_lines.extend(result.lines().flat_map(|line| {
+        let max_width = inner_area.width.saturating_sub(2) as usize;
+        if line.len() <= max_width {
+            vec![Line::from(line)]
+        } else {
+            let mut wrapped = Vec::new();
+            let mut remaining = line;
+            while !remaining.is_empty() {
+                let split_at = remaining
+                    .char_indices()
+                    .nth(max_width)
+                    .map(|(i, _)| i)
+                    .unwrap_or(remaining.len());
+                wrapped.push(Line::from(&remaining[..split_at]));
+                remaining = &remaining[split_at..];
             }
-        })
-        .collect();
+            wrapped
+        }
+    }));
 
     let paragraph = Paragraph::new(text_lines).wrap(Wrap { trim: false });
 
@@ -0,0 +1,74 @@
+use crate::cmd::console::widgets::formatters::{format_time_ago, truncate_left};
+use hotpath::format_duration;
+use hotpath::json::SlowPollsJson;
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Style},
+    symbols::border,
+    widgets::{Block, Cell, Row, Table},
+    Frame,
+};
+
+use super::super::common_styles;
+
+/// Slow poll alerts shown at most, newest first
+pub(crate) const MAX_VISIBLE_SLOW_POLLS: usize = 6;
+
+/// Renders the most recent polls that took longer than `HOTPATH_SLOW_POLL_MS`
+pub(crate) fn render_slow_polls_panel(slow_polls: &SlowPollsJson, area: Rect, frame: &mut Frame) {
+    let label_width = (area.width.saturating_sub(44) as usize).max(20);
+
+    let header = Row::new(vec!["When", "Kind", "Label", "Thread", "Duration"])
+        .style(common_styles::HEADER_STYLE)
+        .height(1);
+
+    let rows: Vec<Row> = slow_polls
+        .slow_polls
+        .iter()
+        .take(MAX_VISIBLE_SLOW_POLLS)
+        .map(|slow_poll| {
+            let label = match slow_poll.call_id {
+                Some(call_id) => format!("{} (call {})", slow_poll.label, call_id),
+                None => slow_poll.label.clone(),
+            };
+            Row::new(vec![
+                Cell::from(format_time_ago(
+                    slow_polls
+                        .current_elapsed_ns
+                        .saturating_sub(slow_poll.timestamp_ns),
+                )),
+                Cell::from(slow_poll.kind.to_string()),
+                Cell::from(truncate_left(&label, label_width)),
+                Cell::from(slow_poll.tid.to_string()),
+                Cell::from(format_duration(slow_poll.duration_ns))
+                    .style(Style::default().fg(Color::Red)),
+            ])
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Length(8),  // When
+        Constraint::Length(9),  // Kind
+        Constraint::Min(20),    // Label
+        Constraint::Length(10), // Thread
+        Constraint::Length(12), // Duration
+    ];
+
+    let threshold = slow_polls
+        .threshold_ms
+        .map_or_else(String::new, |threshold_ms| {
+            format!(" over {}ms", threshold_ms)
+        });
+    let block = Block::bordered()
+        .title(format!(" ⚠ Slow polls{}: {} ", threshold, slow_polls.total))
+        .title_style(Style::default().fg(Color::Red))
+        .border_set(border::PLAIN)
+        .border_style(common_styles::UNFOCUSED_BORDER_STYLE);
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(block)
+        .column_spacing(1);
+
+    frame.render_widget(table, area);
+}
@@ -1,13 +1,16 @@
 use super::super::app::{
     App, ChannelsFocus, FunctionsFocus, FuturesFocus, SelectedTab, StreamsFocus,
 };
-use super::channels::{inspect, logs as channel_logs};
+use super::channels::{graph as channel_graph, inspect, logs as channel_logs};
 use super::functions_memory::{inspect as memory_inspect, logs as memory_logs};
 use super::functions_timing::{inspect as timing_inspect, logs as timing_logs};
-use super::futures::{calls as future_calls, inspect as future_inspect};
+use super::futures::{
+    calls as future_calls, inspect as future_inspect, slow_polls as future_slow_polls,
+};
 use super::streams::{inspect as stream_inspect, logs as stream_logs};
 use super::{
-    bottom_bar, channels, functions_memory, functions_timing, futures, streams, threads, top_bar,
+    bottom_bar, channels, functions_memory, functions_timing, futures, runtime, streams, tasks,
+    threads, top_bar,
 };
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
@@ -36,6 +39,8 @@ pub(crate) fn render_ui(frame: &mut Frame, app: &mut App) {
         SelectedTab::Channels => !app.channels.channels.is_empty(),
         SelectedTab::Streams => !app.streams.streams.is_empty(),
         SelectedTab::Threads => !app.threads.threads.is_empty(),
+        SelectedTab::Runtime => app.runtime.is_some(),
+        SelectedTab::Tasks => !app.tasks.tasks.is_empty(),
         SelectedTab::Futures => !app.futures.futures.is_empty(),
     };
 
@@ -46,6 +51,7 @@ pub(crate) fn render_ui(frame: &mut Frame, app: &mut App) {
         app.last_successful_fetch,
         app.error_message.is_some(),
         has_data,
+        app.replay.as_ref(),
     );
 
     render_tabs(frame, main_chunks[0], app.selected_tab);
@@ -127,6 +133,12 @@ pub(crate) fn render_ui(frame: &mut Frame, app: &mut App) {
         SelectedTab::Threads => {
             render_threads_view(frame, app, main_chunks[2]);
         }
+        SelectedTab::Runtime => {
+            render_runtime_view(frame, app, main_chunks[2]);
+        }
+        SelectedTab::Tasks => {
+            render_tasks_view(frame, app, main_chunks[2]);
+        }
         SelectedTab::Futures => {
             render_futures_view(frame, app, main_chunks[2]);
         }
@@ -140,6 +152,7 @@ pub(crate) fn render_ui(frame: &mut Frame, app: &mut App) {
         app.streams_focus,
         app.functions_focus,
         app.futures_focus,
+        app.replay.is_some(),
     );
 }
 
@@ -184,6 +197,11 @@ fn render_channels_view(frame: &mut Frame, app: &mut App, area: Rect) {
         return;
     }
 
+    if app.show_channels_graph {
+        channel_graph::render_graph_panel(&app.channels_graph, area, frame);
+        return;
+    }
+
     // Split the area if logs are being shown
     let (table_area, logs_area) = if app.show_logs {
         let chunks = Layout::default()
@@ -208,14 +226,17 @@ fn render_channels_view(frame: &mut Frame, app: &mut App, area: Rect) {
         app.channels_focus,
         channel_position,
         total_channels,
+        app.stats_window,
+        app.channels.queue_warn_threshold,
     );
 
     // Render logs panel if visible
     if let Some(logs_area) = logs_area {
-        let channel_label = app
+        let selected_stat = app
             .channels_table_state
             .selected()
-            .and_then(|i| stats.get(i))
+            .and_then(|i| stats.get(i));
+        let channel_label = selected_stat
             .map(|stat| {
                 if stat.label.is_empty() {
                     stat.id.to_string()
@@ -239,6 +260,7 @@ fn render_channels_view(frame: &mut Frame, app: &mut App, area: Rect) {
             channel_logs::render_logs_panel(
                 cached_logs,
                 &display_label,
+                selected_stat,
                 logs_area,
                 frame,
                 &mut app.channel_logs_table_state,
@@ -327,6 +349,7 @@ fn render_streams_view(frame: &mut Frame, app: &mut App, area: Rect) {
         app.streams_focus,
         stream_position,
         total_streams,
+        app.stats_window,
     );
 
     // Render logs panel if visible
@@ -438,8 +461,113 @@ fn render_threads_view(frame: &mut Frame, app: &mut App, area: Rect) {
     );
 }
 
+#[hotpath::measure]
+fn render_runtime_view(frame: &mut Frame, app: &mut App, area: Rect) {
+    if !app.runtime_available {
+        let message_text = vec![
+            Line::from(""),
+            Line::from("Tokio runtime metrics are not available")
+                .yellow()
+                .bold()
+                .centered(),
+            Line::from(""),
+            Line::from("Enable the hotpath 'tokio-runtime-metrics' feature and run #[hotpath::main] inside a tokio runtime").centered(),
+        ];
+
+        let block = Block::bordered().border_set(border::THICK);
+        frame.render_widget(Paragraph::new(message_text).block(block), area);
+        return;
+    }
+
+    let Some(ref runtime_metrics) = app.runtime else {
+        let (title, hint) = match app.error_message {
+            Some(ref error_msg) => (
+                Line::from("Error").red().bold().centered(),
+                Line::from(error_msg.as_str()).red().centered(),
+            ),
+            None => (
+                Line::from("No runtime metrics found").yellow().centered(),
+                Line::from("Waiting for the first sample").centered(),
+            ),
+        };
+        let text = vec![
+            Line::from(""),
+            title,
+            Line::from(""),
+            hint,
+            Line::from(""),
+            Line::from(format!(
+                "Make sure the metrics server is running on {}",
+                app.metrics_host
+            ))
+            .yellow()
+            .centered(),
+        ];
+
+        let block = Block::bordered().border_set(border::THICK);
+        frame.render_widget(Paragraph::new(text).block(block), area);
+        return;
+    };
+
+    runtime::render_runtime_panel(runtime_metrics, area, frame, &mut app.runtime_table_state);
+}
+
+#[hotpath::measure]
+fn render_tasks_view(frame: &mut Frame, app: &mut App, area: Rect) {
+    if app.tasks.tasks.is_empty() {
+        let (title, hint) = match app.error_message {
+            Some(ref error_msg) => (
+                Line::from("Error").red().bold().centered(),
+                Line::from(error_msg.as_str()).red().centered(),
+            ),
+            None => (
+                Line::from("No tasks found").yellow().centered(),
+                Line::from("Spawn tasks with hotpath::spawn! or hotpath::spawn_blocking!")
+                    .centered(),
+            ),
+        };
+        let text = vec![
+            Line::from(""),
+            title,
+            Line::from(""),
+            hint,
+            Line::from(""),
+            Line::from(format!(
+                "Make sure the metrics server is running on {}",
+                app.metrics_host
+            ))
+            .yellow()
+            .centered(),
+        ];
+
+        let block = Block::bordered().border_set(border::THICK);
+        frame.render_widget(Paragraph::new(text).block(block), area);
+        return;
+    }
+
+    tasks::render_tasks_panel(&app.tasks, area, frame, &mut app.tasks_table_state);
+}
+
 #[hotpath::measure]
 fn render_futures_view(frame: &mut Frame, app: &mut App, area: Rect) {
+    // Slow polls may come from measured async functions too, so they are listed even
+    // without instrumented futures
+    let area = if app.slow_polls.slow_polls.is_empty() {
+        area
+    } else {
+        let visible = app
+            .slow_polls
+            .slow_polls
+            .len()
+            .min(future_slow_polls::MAX_VISIBLE_SLOW_POLLS) as u16;
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(5), Constraint::Length(visible + 3)])
+            .split(area);
+        future_slow_polls::render_slow_polls_panel(&app.slow_polls, chunks[1], frame);
+        chunks[0]
+    };
+
     let stats = &app.futures.futures;
 
     if let Some(ref error_msg) = app.error_message {
@@ -505,10 +633,11 @@ fn render_futures_view(frame: &mut Frame, app: &mut App, area: Rect) {
 
     // Render calls panel if visible
     if let Some(calls_area) = calls_area {
-        let future_label = app
+        let selected_stat = app
             .futures_table_state
             .selected()
-            .and_then(|i| stats.get(i))
+            .and_then(|i| stats.get(i));
+        let future_label = selected_stat
             .map(|stat| {
                 if stat.label.is_empty() {
                     stat.id.to_string()
@@ -522,6 +651,7 @@ fn render_futures_view(frame: &mut Frame, app: &mut App, area: Rect) {
             future_calls::render_calls_panel(
                 calls,
                 &future_label,
+                selected_stat.and_then(|stat| stat.longest_poll),
                 calls_area,
                 frame,
                 &mut app.future_calls_table_state,
@@ -572,6 +702,8 @@ fn render_tabs(frame: &mut Frame, area: ratatui::layout::Rect, selected_tab: Sel
         create_tab_line(SelectedTab::Channels),
         create_tab_line(SelectedTab::Streams),
         create_tab_line(SelectedTab::Threads),
+        create_tab_line(SelectedTab::Runtime),
+        create_tab_line(SelectedTab::Tasks),
     ];
 
     let selected_index = (selected_tab.number() - 1) as usize;
@@ -0,0 +1,245 @@
+use super::common_styles;
+use crate::cmd::console::widgets::formatters::truncate_right;
+use hotpath::format_duration;
+use hotpath::json::{RuntimeJson, ThreadMetrics};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style},
+    symbols::border,
+    text::{Line, Span},
+    widgets::{Block, Cell, HighlightSpacing, Paragraph, Row, Table, TableState},
+    Frame,
+};
+
+/// Worker threads shown at most below the workers table
+const MAX_VISIBLE_THREADS: usize = 8;
+
+/// Workers are flagged as blocked when their threads use less CPU than this share of the
+/// time the workers were busy
+const BLOCKED_CPU_RATIO: f64 = 0.5;
+
+/// Ignore the busy vs CPU comparison for mostly idle runtimes
+const MIN_BUSY_PERCENT: f64 = 10.0;
+
+fn format_percent(percent: Option<f64>) -> String {
+    match percent {
+        Some(pct) => format!("{:.1}%", pct),
+        None => "-".to_string(),
+    }
+}
+
+fn format_optional(value: Option<usize>) -> String {
+    value.map_or_else(|| "-".to_string(), |v| v.to_string())
+}
+
+/// Renders the tokio runtime summary, the workers table, and the threads the workers run on
+#[hotpath::measure]
+pub(crate) fn render_runtime_panel(
+    runtime: &RuntimeJson,
+    area: Rect,
+    frame: &mut Frame,
+    table_state: &mut TableState,
+) {
+    // current_thread workers show their thread in the workers table
+    let show_threads = runtime.flavor != "current_thread" && !runtime.threads.is_empty();
+    let threads_height = if show_threads {
+        runtime.threads.len().min(MAX_VISIBLE_THREADS) as u16 + 3
+    } else {
+        0
+    };
+
+    let chunks = Layout::vertical([
+        Constraint::Length(2),
+        Constraint::Min(0),
+        Constraint::Length(threads_height),
+    ])
+    .split(area);
+
+    frame.render_widget(Paragraph::new(summary_lines(runtime)), chunks[0]);
+    render_workers_table(runtime, chunks[1], frame, table_state);
+    if show_threads {
+        render_threads_table(&runtime.threads, chunks[2], frame);
+    }
+}
+
+fn summary_lines(runtime: &RuntimeJson) -> Vec<Line<'static>> {
+    let mut spans = vec![
+        Span::raw(" Flavor: "),
+        Span::styled(runtime.flavor.clone(), Style::default().fg(Color::Yellow)),
+        Span::raw("  Workers: "),
+        Span::styled(
+            runtime.num_workers.to_string(),
+            Style::default().fg(Color::Cyan),
+        ),
+        Span::raw("  Alive tasks: "),
+        Span::styled(
+            runtime.alive_tasks.to_string(),
+            Style::default().fg(Color::Cyan),
+        ),
+        Span::raw("  Global queue: "),
+        Span::styled(
+            runtime.global_queue_depth.to_string(),
+            Style::default().fg(Color::Cyan),
+        ),
+    ];
+
+    match runtime.blocking_threads {
+        Some(blocking_threads) => {
+            spans.push(Span::raw("  Blocking pool: "));
+            spans.push(Span::styled(
+                format!(
+                    "{} threads, {} idle, {} queued",
+                    blocking_threads,
+                    format_optional(runtime.idle_blocking_threads),
+                    format_optional(runtime.blocking_queue_depth)
+                ),
+                Style::default().fg(Color::Cyan),
+            ));
+        }
+        None => {
+            spans.push(Span::raw("  "));
+            spans.push(Span::styled(
+                "Build with --cfg tokio_unstable for blocking pool and local queues",
+                common_styles::PLACEHOLDER_STYLE,
+            ));
+        }
+    }
+
+    let mut correlation = vec![
+        Span::raw(" Workers busy: "),
+        Span::styled(
+            format_percent(runtime.busy_percent),
+            Style::default().fg(Color::Green),
+        ),
+        Span::raw("  Worker threads CPU: "),
+        Span::styled(
+            format_percent(runtime.cpu_percent),
+            Style::default().fg(Color::Green),
+        ),
+    ];
+
+    if let (Some(busy), Some(cpu)) = (runtime.busy_percent, runtime.cpu_percent) {
+        if busy >= MIN_BUSY_PERCENT && cpu < busy * BLOCKED_CPU_RATIO {
+            correlation.push(Span::raw("  "));
+            correlation.push(Span::styled(
+                "⚠ Workers busy off-CPU, polls may block on I/O, sleeps or locks",
+                Style::default().fg(Color::Red),
+            ));
+        }
+    }
+
+    vec![Line::from(spans), Line::from(correlation)]
+}
+
+fn render_workers_table(
+    runtime: &RuntimeJson,
+    area: Rect,
+    frame: &mut Frame,
+    table_state: &mut TableState,
+) {
+    let header = Row::new(vec![
+        Cell::from("Worker"),
+        Cell::from("Busy %"),
+        Cell::from("Busy"),
+        Cell::from("State"),
+        Cell::from("Parks"),
+        Cell::from("Unparks"),
+        Cell::from("Local queue"),
+        Cell::from("TID"),
+        Cell::from("CPU %"),
+    ])
+    .style(common_styles::HEADER_STYLE)
+    .height(1);
+
+    let rows: Vec<Row> = runtime
+        .workers
+        .iter()
+        .map(|worker| {
+            let state = if worker.parked { "Parked" } else { "Active" };
+
+            Row::new(vec![
+                Cell::from(worker.index.to_string()),
+                Cell::from(format_percent(worker.busy_percent)),
+                Cell::from(format_duration(worker.busy_ns)),
+                Cell::from(state),
+                Cell::from(worker.park_count.to_string()),
+                Cell::from(worker.unpark_count.to_string()),
+                Cell::from(format_optional(worker.local_queue_depth)),
+                Cell::from(
+                    worker
+                        .os_tid
+                        .map_or_else(|| "-".to_string(), |tid| tid.to_string()),
+                ),
+                Cell::from(format_percent(worker.cpu_percent)),
+            ])
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Percentage(8),  // Worker
+        Constraint::Percentage(10), // Busy %
+        Constraint::Percentage(14), // Busy
+        Constraint::Percentage(10), // State
+        Constraint::Percentage(11), // Parks
+        Constraint::Percentage(11), // Unparks
+        Constraint::Percentage(12), // Local queue
+        Constraint::Percentage(12), // TID
+        Constraint::Percentage(10), // CPU %
+    ];
+
+    let selected = table_state.selected().unwrap_or(0) + 1;
+    let table_block = Block::bordered()
+        .title(format!(" [{}/{}] ", selected, runtime.workers.len()))
+        .border_set(border::THICK);
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(table_block)
+        .column_spacing(1)
+        .row_highlight_style(common_styles::SELECTED_ROW_STYLE)
+        .highlight_symbol(">> ")
+        .highlight_spacing(HighlightSpacing::Always);
+
+    frame.render_stateful_widget(table, area, table_state);
+}
+
+fn render_threads_table(threads: &[ThreadMetrics], area: Rect, frame: &mut Frame) {
+    let header = Row::new(vec!["Thread", "TID", "Status", "CPU %"])
+        .style(common_styles::HEADER_STYLE)
+        .height(1);
+
+    let rows: Vec<Row> = threads
+        .iter()
+        .take(MAX_VISIBLE_THREADS)
+        .map(|thread| {
+            Row::new(vec![
+                Cell::from(truncate_right(&thread.name, 24)),
+                Cell::from(thread.os_tid.to_string()),
+                Cell::from(format!("{} ({})", thread.status, thread.status_code)),
+                Cell::from(format_percent(thread.cpu_percent)),
+            ])
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Percentage(30), // Thread
+        Constraint::Percentage(15), // TID
+        Constraint::Percentage(25), // Status
+        Constraint::Percentage(15), // CPU %
+    ];
+
+    let block = Block::bordered()
+        .title(format!(
+            " Worker threads, blocking pool excluded: {} ",
+            threads.len()
+        ))
+        .border_set(border::PLAIN)
+        .border_style(common_styles::UNFOCUSED_BORDER_STYLE);
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(block)
+        .column_spacing(1);
+
+    frame.render_widget(table, area);
+}
@@ -3,8 +3,9 @@ pub(crate) mod logs;
 
 use super::common_styles;
 use crate::cmd::console::app::StreamsFocus;
-use crate::cmd::console::widgets::formatters::truncate_left;
-use hotpath::json::{ChannelState, SerializableStreamStats};
+use crate::cmd::console::widgets::formatters::{sparkline, truncate_left};
+use hotpath::format_bytes;
+use hotpath::json::{ChannelState, SerializableStreamStats, StatsWindow};
 use ratatui::{
     layout::{Constraint, Rect},
     style::{Color, Style},
@@ -25,14 +26,19 @@ pub(crate) fn render_streams_panel(
     focus: StreamsFocus,
     stream_position: usize,
     total_streams: usize,
+    stats_window: StatsWindow,
 ) {
     let available_width = area.width.saturating_sub(10);
-    let stream_width = ((available_width as f32 * 0.60) as usize).max(36);
+    let stream_width = ((available_width as f32 * 0.36) as usize).max(36);
+    let trend_width = (available_width as f32 * 0.22) as usize;
 
     let header = Row::new(vec![
         Cell::from("Stream"),
         Cell::from("State"),
         Cell::from("Yielded"),
+        Cell::from("Rate"),
+        Cell::from("Bytes/s"),
+        Cell::from("Trend"),
     ])
     .style(common_styles::HEADER_STYLE)
     .height(1);
@@ -52,16 +58,25 @@ pub(crate) fn render_streams_panel(
                 Cell::from(truncate_left(&stat.label, stream_width)),
                 Cell::from(state_text).style(state_style),
                 Cell::from(stat.items_yielded.to_string()),
+                Cell::from(format!("{:.1}/s", stat.yield_rate)),
+                Cell::from(format_bytes(stat.yield_bytes_rate as u64)),
+                Cell::from(sparkline(&stat.yield_history, trend_width))
+                    .style(Style::default().fg(Color::Cyan)),
             ])
         })
         .collect();
 
     let widths = [
-        Constraint::Percentage(60), // Stream
-        Constraint::Percentage(20), // State
-        Constraint::Percentage(20), // Yielded
+        Constraint::Percentage(36), // Stream
+        Constraint::Percentage(10), // State
+        Constraint::Percentage(10), // Yielded
+        Constraint::Percentage(10), // Rate
+        Constraint::Percentage(12), // Bytes/s
+        Constraint::Percentage(22), // Trend
     ];
 
+    let rate_title = format!(" Rate: {} ", stats_window.label());
+
     let table_block = if show_logs {
         let border_set = if focus == StreamsFocus::Streams {
             border::THICK
@@ -70,6 +85,7 @@ pub(crate) fn render_streams_panel(
         };
         Block::bordered()
             .title(format!(" [{}/{}] ", stream_position, total_streams))
+            .title(rate_title)
             .border_set(border_set)
             .border_style(if focus == StreamsFocus::Streams {
                 Style::default()
@@ -79,6 +95,7 @@ pub(crate) fn render_streams_panel(
     } else {
         Block::bordered()
             .title(format!(" [{}/{}] ", stream_position, total_streams))
+            .title(rate_title)
             .border_set(border::THICK)
     };
 
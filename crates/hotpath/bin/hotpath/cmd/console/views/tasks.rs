@@ -0,0 +1,159 @@
+use super::common_styles;
+use crate::cmd::console::widgets::formatters::{format_time_ago, truncate_left};
+use hotpath::format_duration;
+use hotpath::json::{TaskJson, TaskState, TasksJson};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style},
+    symbols::border,
+    text::{Line, Span},
+    widgets::{Block, Cell, HighlightSpacing, Paragraph, Row, Table, TableState},
+    Frame,
+};
+
+fn state_style(state: TaskState) -> Style {
+    match state {
+        TaskState::Running => Style::default().fg(Color::Green),
+        TaskState::Completed => Style::default().fg(Color::Gray),
+        TaskState::Cancelled => Style::default().fg(Color::Yellow),
+        TaskState::Panicked => Style::default().fg(Color::Red),
+    }
+}
+
+/// Running tasks are as old as their spawn, finished ones show how long they lived
+fn format_lifetime(task: &TaskJson, current_elapsed_ns: u64) -> String {
+    let end_ns = task.finished_at_ns.unwrap_or(current_elapsed_ns);
+    format_duration(end_ns.saturating_sub(task.spawned_at_ns))
+}
+
+/// Renders live tasks and the recently finished ones, with the panic message of the
+/// selected task
+#[hotpath::measure]
+pub(crate) fn render_tasks_panel(
+    tasks: &TasksJson,
+    area: Rect,
+    frame: &mut Frame,
+    table_state: &mut TableState,
+) {
+    let chunks = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(0),
+        Constraint::Length(1),
+    ])
+    .split(area);
+
+    let summary = Line::from(vec![
+        Span::raw(" Spawned: "),
+        Span::styled(
+            tasks.total_spawned.to_string(),
+            Style::default().fg(Color::Cyan),
+        ),
+        Span::raw("  Live: "),
+        Span::styled(tasks.live.to_string(), Style::default().fg(Color::Green)),
+    ]);
+    frame.render_widget(Paragraph::new(summary), chunks[0]);
+
+    render_tasks_table(tasks, chunks[1], frame, table_state);
+
+    let selected = table_state.selected().and_then(|idx| tasks.tasks.get(idx));
+    if let Some(task) = selected {
+        let details = match &task.panic_message {
+            Some(message) => Line::from(vec![
+                Span::raw(" Panicked: "),
+                Span::styled(message.clone(), Style::default().fg(Color::Red)),
+            ]),
+            None => Line::from(vec![
+                Span::raw(" Spawned at: "),
+                Span::styled(task.source.clone(), common_styles::PLACEHOLDER_STYLE),
+            ]),
+        };
+        frame.render_widget(Paragraph::new(details), chunks[2]);
+    }
+}
+
+fn render_tasks_table(
+    tasks: &TasksJson,
+    area: Rect,
+    frame: &mut Frame,
+    table_state: &mut TableState,
+) {
+    let header = Row::new(vec![
+        Cell::from("ID"),
+        Cell::from("Label"),
+        Cell::from("Kind"),
+        Cell::from("State"),
+        Cell::from("Parent"),
+        Cell::from("Polls"),
+        Cell::from("Busy"),
+        Cell::from("Lifetime"),
+        Cell::from("Finished"),
+        Cell::from("Threads"),
+    ])
+    .style(common_styles::HEADER_STYLE)
+    .height(1);
+
+    let label_width = (area.width.saturating_sub(90) as usize).max(16);
+
+    let rows: Vec<Row> = tasks
+        .tasks
+        .iter()
+        .map(|task| {
+            let finished = task.finished_at_ns.map_or_else(
+                || "-".to_string(),
+                |finished_at_ns| {
+                    format_time_ago(tasks.current_elapsed_ns.saturating_sub(finished_at_ns))
+                },
+            );
+            let threads = task
+                .threads
+                .iter()
+                .map(|tid| tid.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+
+            Row::new(vec![
+                Cell::from(task.id.to_string()),
+                Cell::from(truncate_left(&task.label, label_width)),
+                Cell::from(task.kind.to_string()),
+                Cell::from(task.state.to_string()).style(state_style(task.state)),
+                Cell::from(
+                    task.parent_id
+                        .map_or_else(|| "-".to_string(), |id| id.to_string()),
+                ),
+                Cell::from(task.poll_count.to_string()),
+                Cell::from(format_duration(task.busy_ns)),
+                Cell::from(format_lifetime(task, tasks.current_elapsed_ns)),
+                Cell::from(finished),
+                Cell::from(threads),
+            ])
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Length(6),  // ID
+        Constraint::Min(16),    // Label
+        Constraint::Length(9),  // Kind
+        Constraint::Length(10), // State
+        Constraint::Length(7),  // Parent
+        Constraint::Length(8),  // Polls
+        Constraint::Length(10), // Busy
+        Constraint::Length(10), // Lifetime
+        Constraint::Length(9),  // Finished
+        Constraint::Length(16), // Threads
+    ];
+
+    let selected = table_state.selected().unwrap_or(0) + 1;
+    let table_block = Block::bordered()
+        .title(format!(" [{}/{}] ", selected, tasks.tasks.len()))
+        .border_set(border::THICK);
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(table_block)
+        .column_spacing(1)
+        .row_highlight_style(common_styles::SELECTED_ROW_STYLE)
+        .highlight_symbol(">> ")
+        .highlight_spacing(HighlightSpacing::Always);
+
+    frame.render_stateful_widget(table, area, table_state);
+}
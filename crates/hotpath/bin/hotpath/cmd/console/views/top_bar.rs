@@ -6,7 +6,9 @@ use ratatui::{
     widgets::{Block, Paragraph},
     Frame,
 };
-use std::time::Instant;
+use std::time::{Duration, Instant};
+
+use crate::cmd::console::replay::ReplayState;
 
 /// Renders the top status bar showing connection status and refresh timer
 #[hotpath::measure]
@@ -17,8 +19,26 @@ pub(crate) fn render_status_bar(
     last_successful_fetch: Option<Instant>,
     has_error: bool,
     has_data: bool,
+    replay: Option<&ReplayState>,
 ) {
-    let status_text = if is_paused {
+    let status_text = if let Some(replay) = replay {
+        let position = format!(
+            " {} / {} (snapshot {}/{})",
+            format_playback_time(replay.elapsed()),
+            format_playback_time(replay.total()),
+            replay.position() + 1,
+            replay.len()
+        );
+        if is_paused {
+            Line::from(vec![
+                "⏸ ".yellow(),
+                "PAUSED".yellow().bold(),
+                position.into(),
+            ])
+        } else {
+            Line::from(vec!["▶ ".green(), "Replay".green().bold(), position.into()])
+        }
+    } else if is_paused {
         Line::from(vec!["⏸ ".yellow(), "PAUSED".yellow().bold()])
     } else if let Some(last_fetch) = last_successful_fetch {
         let elapsed = Instant::now().duration_since(last_fetch);
@@ -51,3 +71,13 @@ pub(crate) fn render_status_bar(
 
     frame.render_widget(paragraph, area);
 }
+
+fn format_playback_time(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    format!(
+        "{:02}:{:02}.{}",
+        secs / 60,
+        secs % 60,
+        duration.subsec_millis() / 100
+    )
+}
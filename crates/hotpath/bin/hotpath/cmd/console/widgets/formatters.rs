@@ -24,15 +24,22 @@ pub(crate) fn truncate_right(s: &str, max_len: usize) -> String {
     }
 }
 
+/// Fill level of bounded channels, and the high-water mark of unbounded ones. Queues past
+/// `warn_threshold` are flagged in red.
 pub(crate) fn queue_status(
     queued: u64,
+    max_queued: u64,
     channel_type: &ChannelType,
+    warn_threshold: Option<u64>,
     _width: usize,
 ) -> Cell<'static> {
+    let over_threshold = warn_threshold.is_some_and(|threshold| queued >= threshold);
+
     let capacity = match channel_type {
         ChannelType::Bounded(cap) => Some(*cap),
         ChannelType::Oneshot => Some(1),
-        ChannelType::Unbounded => None,
+        ChannelType::Broadcast(cap) => *cap,
+        ChannelType::Unbounded | ChannelType::Watch => None,
     };
 
     match capacity {
@@ -41,7 +48,7 @@ pub(crate) fn queue_status(
 
             let text = format!("[{}/{}]", queued, cap);
 
-            let color = if percentage >= 100.0 {
+            let color = if percentage >= 100.0 || over_threshold {
                 Color::Red
             } else if percentage >= 50.0 {
                 Color::Yellow
@@ -51,6 +58,14 @@ pub(crate) fn queue_status(
 
             Cell::from(text).style(Style::default().fg(color))
         }
+        _ if *channel_type == ChannelType::Unbounded => {
+            let text = format!("{} (max {})", queued, max_queued);
+            if over_threshold {
+                Cell::from(format!("⚠ {}", text)).style(Style::default().fg(Color::Red))
+            } else {
+                Cell::from(text)
+            }
+        }
         _ => Cell::from("N/A"),
     }
 }
@@ -116,3 +131,19 @@ pub(crate) fn format_time_ago(nanos_ago: u64) -> String {
         }
     }
 }
+
+/// Renders the last `width` values as a row of block characters scaled to their maximum.
+/// Empty seconds are left blank so idle periods stand out.
+pub(crate) fn sparkline(values: &[u64], width: usize) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    let values = &values[values.len().saturating_sub(width)..];
+    let max = values.iter().copied().max().unwrap_or(0);
+    values
+        .iter()
+        .map(|&value| match value {
+            0 => ' ',
+            _ => BARS[((value * BARS.len() as u64).div_ceil(max) as usize - 1).min(BARS.len() - 1)],
+        })
+        .collect()
+}
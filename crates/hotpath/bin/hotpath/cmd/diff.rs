@@ -0,0 +1,308 @@
+use clap::Parser;
+use eyre::Result;
+use hotpath::diff::{compare_metrics, FunctionMetricsDiff, MetricDiff, MetricsComparison};
+use hotpath::FunctionsJson;
+use prettytable::{color, format, Attr, Cell, Row, Table};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+/// Changes smaller than this are not highlighted when no `--threshold` is given.
+const DEFAULT_HIGHLIGHT_THRESHOLD: u32 = 20;
+
+#[derive(Debug, Parser)]
+pub struct DiffArgs {
+    #[arg(help = "Path to JSON metrics of the base run")]
+    pub base: PathBuf,
+
+    #[arg(help = "Path to JSON metrics of the head run")]
+    pub head: PathBuf,
+
+    #[arg(
+        long,
+        help = "Fail with a nonzero exit code if any function's Avg grows by more than this percentage"
+    )]
+    pub threshold: Option<u32>,
+}
+
+impl DiffArgs {
+    /// Prints the comparison. Fails with exit code 1 when `--threshold` is exceeded, so that
+    /// `main` still drops its profiling guard before exiting.
+    pub fn run(&self) -> Result<ExitCode> {
+        let base = read_metrics(&self.base)?;
+        let head = read_metrics(&self.head)?;
+
+        if base.hotpath_profiling_mode != head.hotpath_profiling_mode {
+            eyre::bail!(
+                "Cannot compare {} metrics with {} metrics",
+                base.hotpath_profiling_mode,
+                head.hotpath_profiling_mode
+            );
+        }
+
+        let comparison = compare_metrics(&base, &head);
+        let highlight_threshold = self.threshold.unwrap_or(DEFAULT_HIGHLIGHT_THRESHOLD);
+
+        println!(
+            "Performance Comparison {} → {}",
+            self.base.display(),
+            self.head.display()
+        );
+        println!(
+            "Total Elapsed Time: {}",
+            comparison.total_elapsed_diff.format_with_emoji(None)
+        );
+        println!(
+            "Profiling Mode: {} - {}",
+            head.hotpath_profiling_mode, head.description
+        );
+
+        if comparison.function_diffs.is_empty() {
+            println!("No functions to compare");
+        } else {
+            build_table(&comparison, &head.percentiles, highlight_threshold).printstd();
+        }
+
+        if let Some(threshold) = self.threshold {
+            let regressions = find_regressions(&comparison, threshold);
+            if !regressions.is_empty() {
+                println!();
+                for (function_name, diff) in &regressions {
+                    println!("Regression: {} Avg {}", function_name, diff);
+                }
+                eprintln!(
+                    "{} function(s) regressed by more than {}%",
+                    regressions.len(),
+                    threshold
+                );
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+fn read_metrics(path: &Path) -> Result<FunctionsJson> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| eyre::eyre!("Failed to read {}: {}", path.display(), e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| eyre::eyre!("Failed to deserialize {}: {}", path.display(), e))
+}
+
+fn build_table(comparison: &MetricsComparison, percentiles: &[u8], threshold: u32) -> Table {
+    let mut table = Table::new();
+    table.set_format(*format::consts::FORMAT_BOX_CHARS);
+
+    let mut header_cells = vec![Cell::new("Function"), Cell::new("Calls"), Cell::new("Avg")];
+    for &p in percentiles {
+        header_cells.push(Cell::new(&format!("P{}", p)));
+    }
+    header_cells.push(Cell::new("Total"));
+    header_cells.push(Cell::new("% Total"));
+    let has_self_column = comparison
+        .function_diffs
+        .iter()
+        .any(|diff| diff.metrics.len() > 4 + percentiles.len());
+    if has_self_column {
+        header_cells.push(Cell::new("Self"));
+    }
+    table.set_titles(Row::new(
+        header_cells
+            .into_iter()
+            .map(|cell| cell.with_style(Attr::Bold))
+            .collect(),
+    ));
+
+    for func_diff in &comparison.function_diffs {
+        let function_display = if func_diff.is_removed {
+            format!("- {}", func_diff.function_name)
+        } else if func_diff.is_new {
+            format!("+ {}", func_diff.function_name)
+        } else {
+            func_diff.function_name.clone()
+        };
+
+        let mut row_cells = vec![Cell::new(&function_display)];
+        for metric_diff in &func_diff.metrics {
+            row_cells.push(metric_cell(func_diff, metric_diff, threshold));
+        }
+        table.add_row(Row::new(row_cells));
+    }
+
+    table
+}
+
+/// Highlights growth above the threshold in red and shrinkage below it in green.
+/// Removed and new functions are left uncolored since every value changes to or from zero.
+fn metric_cell(func_diff: &FunctionMetricsDiff, metric_diff: &MetricDiff, threshold: u32) -> Cell {
+    let cell = Cell::new(&metric_diff.to_string());
+    if func_diff.is_removed || func_diff.is_new {
+        return cell;
+    }
+
+    let diff_percent = metric_diff.diff_percent();
+    let threshold = threshold as f64;
+    if diff_percent > threshold {
+        cell.with_style(Attr::ForegroundColor(color::RED))
+    } else if diff_percent < -threshold {
+        cell.with_style(Attr::ForegroundColor(color::GREEN))
+    } else {
+        cell
+    }
+}
+
+/// Functions present in both runs whose average time (or allocated bytes in alloc mode)
+/// per call grew by more than `threshold` percent.
+fn find_regressions(comparison: &MetricsComparison, threshold: u32) -> Vec<(&str, &MetricDiff)> {
+    comparison
+        .function_diffs
+        .iter()
+        .filter(|func_diff| !func_diff.is_removed && !func_diff.is_new)
+        .filter_map(|func_diff| {
+            let avg = func_diff.metrics.get(1)?;
+            match avg {
+                MetricDiff::DurationNs(..) | MetricDiff::Alloc(..)
+                    if avg.diff_percent() > threshold as f64 =>
+                {
+                    Some((func_diff.function_name.as_str(), avg))
+                }
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use hotpath::MetricType::{self, Alloc, CallsCount, DurationNs, Percentage};
+    use hotpath::ProfilingMode;
+
+    fn metrics(mode: ProfilingMode, data: Vec<(&str, Vec<MetricType>)>) -> FunctionsJson {
+        FunctionsJson {
+            hotpath_profiling_mode: mode,
+            total_elapsed: 100_000_000,
+            description: String::new(),
+            caller_name: "main".to_string(),
+            percentiles: vec![95],
+            data: data
+                .into_iter()
+                .map(|(name, row)| (name.to_string(), row))
+                .collect(),
+            call_tree: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_find_regressions_timing() {
+        let base = metrics(
+            ProfilingMode::Timing,
+            vec![
+                (
+                    "slow",
+                    vec![
+                        CallsCount(10),
+                        DurationNs(1000),
+                        DurationNs(1200),
+                        DurationNs(10000),
+                        Percentage(5000),
+                    ],
+                ),
+                (
+                    "stable",
+                    vec![
+                        CallsCount(10),
+                        DurationNs(1000),
+                        DurationNs(1200),
+                        DurationNs(10000),
+                        Percentage(5000),
+                    ],
+                ),
+            ],
+        );
+        let head = metrics(
+            ProfilingMode::Timing,
+            vec![
+                (
+                    "slow",
+                    vec![
+                        CallsCount(10),
+                        DurationNs(1500),
+                        DurationNs(1800),
+                        DurationNs(15000),
+                        Percentage(5500),
+                    ],
+                ),
+                (
+                    "stable",
+                    vec![
+                        CallsCount(20),
+                        DurationNs(1050),
+                        DurationNs(1200),
+                        DurationNs(21000),
+                        Percentage(4500),
+                    ],
+                ),
+                (
+                    "new",
+                    vec![
+                        CallsCount(1),
+                        DurationNs(99999),
+                        DurationNs(99999),
+                        DurationNs(99999),
+                        Percentage(100),
+                    ],
+                ),
+            ],
+        );
+
+        let comparison = compare_metrics(&base, &head);
+        let regressions = find_regressions(&comparison, 20);
+
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].0, "slow");
+        assert!(find_regressions(&comparison, 60).is_empty());
+    }
+
+    #[test]
+    fn test_find_regressions_alloc() {
+        let base = metrics(
+            ProfilingMode::Alloc,
+            vec![(
+                "allocating",
+                vec![
+                    CallsCount(10),
+                    Alloc(1024, 0),
+                    Alloc(2048, 0),
+                    Alloc(10240, 0),
+                    Percentage(10000),
+                    Alloc(10240, 0),
+                ],
+            )],
+        );
+        let head = metrics(
+            ProfilingMode::Alloc,
+            vec![(
+                "allocating",
+                vec![
+                    CallsCount(10),
+                    Alloc(4096, 0),
+                    Alloc(8192, 0),
+                    Alloc(40960, 0),
+                    Percentage(10000),
+                    Alloc(40960, 0),
+                ],
+            )],
+        );
+
+        let comparison = compare_metrics(&base, &head);
+        let regressions = find_regressions(&comparison, 20);
+
+        assert_eq!(regressions.len(), 1);
+        assert!(matches!(regressions[0].1, MetricDiff::Alloc(1024, 4096)));
+
+        let table = build_table(&comparison, &head.percentiles, 20).to_string();
+        assert!(table.contains("Self"));
+        assert!(table.contains("1.0 KB → 4.0 KB"));
+    }
+}
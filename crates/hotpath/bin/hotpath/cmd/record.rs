@@ -0,0 +1,339 @@
+use clap::Parser;
+use eyre::Result;
+use hotpath::json::{
+    ChannelsJson, FunctionsJson, FuturesJson, GraphFormat, Route, StatsWindow, StreamsJson,
+};
+use serde::de::DeserializeOwned;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use tokio::runtime::Runtime;
+
+use crate::cmd::console::http_worker::fetch_body;
+use crate::cmd::console::session::{SessionHeader, SessionWriter, Snapshot};
+use crate::cmd::console::{default_metrics_host, default_metrics_port, validate_metrics_host};
+
+const HTTP_TIMEOUT_MS: u64 = 2000;
+
+/// Routes polled on every snapshot, including each stats window the console can switch to.
+/// Per-item routes (logs, calls) are derived from their responses.
+fn list_routes() -> Vec<Route> {
+    let windows = std::iter::once(StatsWindow::Lifetime).chain(StatsWindow::SLIDING);
+
+    let mut routes = Vec::new();
+    for window in windows {
+        routes.push(Route::FunctionsTiming { window });
+        routes.push(Route::Channels { window });
+        routes.push(Route::Streams { window });
+    }
+    routes.extend([
+        Route::FunctionsAlloc,
+        Route::Futures,
+        Route::SlowPolls,
+        Route::Tasks,
+        Route::ChannelsGraph {
+            format: GraphFormat::Json,
+        },
+        Route::Threads,
+        Route::Runtime,
+    ]);
+    routes
+}
+
+#[derive(Debug, Parser)]
+pub struct RecordArgs {
+    #[arg(
+        long,
+        short,
+        value_name = "FILE",
+        help = "File to write the session to"
+    )]
+    pub output: PathBuf,
+
+    #[arg(
+        long,
+        default_value_t = default_metrics_port(),
+        help = "Port where the metrics HTTP server is running (env: HOTPATH_METRICS_PORT)"
+    )]
+    pub metrics_port: u16,
+
+    #[arg(
+        long,
+        default_value_t = default_metrics_host(),
+        value_parser = validate_metrics_host,
+        help = "Host URL where the metrics HTTP server is running (env: HOTPATH_METRICS_HOST)"
+    )]
+    pub metrics_host: String,
+
+    #[arg(
+        long,
+        default_value_t = 500,
+        help = "Snapshot interval in milliseconds"
+    )]
+    pub refresh_interval: u64,
+
+    #[arg(long, help = "Stop recording after this many seconds")]
+    pub duration: Option<u64>,
+}
+
+impl RecordArgs {
+    pub fn run(&self) -> Result<()> {
+        let base_url = format!(
+            "{}:{}",
+            self.metrics_host.trim_end_matches('/'),
+            self.metrics_port
+        );
+        let rt = Runtime::new()?;
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(HTTP_TIMEOUT_MS))
+            .build()?;
+
+        let mut writer = SessionWriter::create(
+            &self.output,
+            &SessionHeader::new(base_url.clone(), self.refresh_interval),
+        )?;
+
+        println!(
+            "Recording {} to {} (Ctrl-C to stop)",
+            base_url,
+            self.output.display()
+        );
+
+        let interval = Duration::from_millis(self.refresh_interval);
+        let max_duration = self.duration.map(Duration::from_secs);
+        let start = Instant::now();
+        let mut recorded = 0;
+
+        loop {
+            let tick = Instant::now();
+
+            match rt.block_on(capture_responses(&client, &base_url)) {
+                Ok(responses) => {
+                    writer.write_snapshot(&Snapshot {
+                        elapsed_ms: tick.duration_since(start).as_millis() as u64,
+                        responses,
+                    })?;
+                    recorded += 1;
+                }
+                Err(e) if recorded > 0 => {
+                    println!("Metrics server stopped responding ({}), stopping", e);
+                    break;
+                }
+                Err(e) => eyre::bail!("Failed to fetch metrics from {}: {}", base_url, e),
+            }
+
+            if max_duration.is_some_and(|max| start.elapsed() >= max) {
+                break;
+            }
+
+            std::thread::sleep(interval.saturating_sub(tick.elapsed()));
+        }
+
+        println!(
+            "Recorded {} snapshots to {}",
+            recorded,
+            self.output.display()
+        );
+
+        Ok(())
+    }
+}
+
+/// Fetches every list route and the per-item routes of everything they return.
+/// Fails if any list route fails, which usually means the program has exited.
+async fn capture_responses(
+    client: &reqwest::Client,
+    base_url: &str,
+) -> Result<BTreeMap<String, serde_json::Value>, String> {
+    let mut responses = BTreeMap::new();
+    let mut item_routes = Vec::new();
+
+    for (route, body) in fetch_all(client, base_url, list_routes()).await {
+        let body = body?;
+        if let Some(bytes) = &body {
+            item_routes.extend(item_routes_for(&route, bytes));
+        }
+        responses.insert(route.to_path(), to_value(body));
+    }
+
+    for (route, body) in fetch_all(client, base_url, item_routes).await {
+        // Items can disappear between the two requests, skip the ones that failed
+        if let Ok(body) = body {
+            responses.insert(route.to_path(), to_value(body));
+        }
+    }
+
+    Ok(responses)
+}
+
+/// Fetches routes one at a time, the metrics server handles requests sequentially anyway.
+async fn fetch_all(
+    client: &reqwest::Client,
+    base_url: &str,
+    routes: Vec<Route>,
+) -> Vec<(Route, Result<Option<Vec<u8>>, String>)> {
+    let mut results = Vec::with_capacity(routes.len());
+    for route in routes {
+        let body = fetch_body(&route, client, base_url).await;
+        results.push((route, body));
+    }
+    results
+}
+
+/// Item routes are derived from the lifetime responses only, sliding windows list a subset
+/// of the same items.
+fn item_routes_for(route: &Route, bytes: &[u8]) -> Vec<Route> {
+    match route {
+        Route::FunctionsTiming {
+            window: StatsWindow::Lifetime,
+        } => parse::<FunctionsJson>(bytes)
+            .map(|functions| {
+                functions
+                    .data
+                    .into_iter()
+                    .map(|(function_name, _)| Route::FunctionTimingLogs { function_name })
+                    .collect()
+            })
+            .unwrap_or_default(),
+        Route::FunctionsAlloc => parse::<FunctionsJson>(bytes)
+            .map(|functions| {
+                functions
+                    .data
+                    .into_iter()
+                    .map(|(function_name, _)| Route::FunctionAllocLogs { function_name })
+                    .collect()
+            })
+            .unwrap_or_default(),
+        Route::Futures => parse::<FuturesJson>(bytes)
+            .map(|futures| {
+                futures
+                    .futures
+                    .iter()
+                    .map(|future| Route::FutureCalls {
+                        future_id: future.id,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default(),
+        Route::Channels {
+            window: StatsWindow::Lifetime,
+        } => parse::<ChannelsJson>(bytes)
+            .map(|channels| {
+                channels
+                    .channels
+                    .iter()
+                    .map(|channel| Route::ChannelLogs {
+                        channel_id: channel.id,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default(),
+        Route::Streams {
+            window: StatsWindow::Lifetime,
+        } => parse::<StreamsJson>(bytes)
+            .map(|streams| {
+                streams
+                    .streams
+                    .iter()
+                    .map(|stream| Route::StreamLogs {
+                        stream_id: stream.id,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+fn parse<T: DeserializeOwned>(bytes: &[u8]) -> Option<T> {
+    serde_json::from_slice(bytes).ok()
+}
+
+/// 404 responses are stored as `null` so that replay can tell them apart from unrecorded routes.
+fn to_value(body: Option<Vec<u8>>) -> serde_json::Value {
+    body.and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .unwrap_or(serde_json::Value::Null)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_item_routes_for_functions() {
+        use hotpath::MetricType::{CallsCount, DurationNs, Percentage};
+
+        let row = vec![
+            CallsCount(1),
+            DurationNs(100),
+            DurationNs(100),
+            DurationNs(100),
+            Percentage(5000),
+        ];
+        let functions = FunctionsJson {
+            hotpath_profiling_mode: hotpath::ProfilingMode::Timing,
+            total_elapsed: 200,
+            description: String::new(),
+            caller_name: "main".to_string(),
+            percentiles: vec![95],
+            data: vec![
+                ("a::first".to_string(), row.clone()),
+                ("a::second".to_string(), row),
+            ],
+            call_tree: Vec::new(),
+        };
+        let bytes = serde_json::to_vec(&functions).unwrap();
+
+        assert_eq!(
+            item_routes_for(
+                &Route::FunctionsTiming {
+                    window: StatsWindow::Lifetime
+                },
+                &bytes
+            ),
+            vec![
+                Route::FunctionTimingLogs {
+                    function_name: "a::first".to_string()
+                },
+                Route::FunctionTimingLogs {
+                    function_name: "a::second".to_string()
+                },
+            ]
+        );
+        assert!(item_routes_for(
+            &Route::FunctionsTiming {
+                window: StatsWindow::Last1m
+            },
+            &bytes
+        )
+        .is_empty());
+        assert!(item_routes_for(&Route::Threads, &bytes).is_empty());
+        assert!(item_routes_for(
+            &Route::Channels {
+                window: StatsWindow::Lifetime
+            },
+            b"not json"
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_list_routes_cover_windows() {
+        let routes = list_routes();
+        for window in StatsWindow::SLIDING {
+            assert!(routes.contains(&Route::FunctionsTiming { window }));
+            assert!(routes.contains(&Route::Channels { window }));
+            assert!(routes.contains(&Route::Streams { window }));
+        }
+        assert!(!routes.contains(&Route::FunctionsTimingTree));
+    }
+
+    #[test]
+    fn test_not_found_is_stored_as_null() {
+        assert_eq!(to_value(None), serde_json::Value::Null);
+        assert_eq!(
+            to_value(Some(b"{\"a\":1}".to_vec())),
+            serde_json::json!({ "a": 1 })
+        );
+    }
+}
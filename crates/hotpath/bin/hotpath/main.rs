@@ -1,13 +1,20 @@
 mod cmd;
 use clap::{Parser, Subcommand};
 use cmd::console::ConsoleArgs;
+use cmd::diff::DiffArgs;
+use cmd::record::RecordArgs;
 use eyre::Result;
+use std::process::ExitCode;
 
 #[cfg(feature = "tui")]
 #[derive(Subcommand, Debug)]
 pub enum HPSubcommand {
     #[command(about = "Launch TUI console to monitor profiling metrics in real-time")]
     Console(ConsoleArgs),
+    #[command(about = "Compare two JSON profiling reports in the terminal")]
+    Diff(DiffArgs),
+    #[command(about = "Record metrics snapshots to a file for `hotpath console --replay`")]
+    Record(RecordArgs),
 }
 
 #[derive(Parser, Debug)]
@@ -24,14 +31,20 @@ pub struct HPArgs {
 }
 
 #[hotpath::main(limit = 20)]
-fn main() -> Result<()> {
+fn main() -> Result<ExitCode> {
     let root_args = HPArgs::parse();
 
     match root_args.cmd {
         HPSubcommand::Console(args) => {
             args.run()?;
         }
+        HPSubcommand::Diff(args) => {
+            return args.run();
+        }
+        HPSubcommand::Record(args) => {
+            args.run()?;
+        }
     }
 
-    Ok(())
+    Ok(ExitCode::SUCCESS)
 }
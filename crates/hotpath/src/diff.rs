@@ -0,0 +1,253 @@
+//! Comparison of two profiling runs, shared by `hotpath diff` and `hotpath-ci profile-pr`.
+
+use std::fmt;
+use std::time::Duration;
+
+use crate::{format_bytes, FunctionsJson};
+
+#[derive(Debug, Clone)]
+pub enum MetricDiff {
+    CallsCount(u64, u64), // (before, after)
+    DurationNs(u64, u64), // (before, after) - Duration in nanoseconds
+    Alloc(u64, u64),      // (before, after) - Bytes allocated
+    Percentage(u64, u64), // (before, after)
+}
+
+impl fmt::Display for MetricDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.format_with_emoji(None))
+    }
+}
+
+impl MetricDiff {
+    /// Relative change from before to after, in percent.
+    pub fn diff_percent(&self) -> f64 {
+        match self {
+            MetricDiff::CallsCount(before, after)
+            | MetricDiff::DurationNs(before, after)
+            | MetricDiff::Alloc(before, after)
+            | MetricDiff::Percentage(before, after) => calculate_percentage_diff(*before, *after),
+        }
+    }
+
+    /// Formats the change as `before → after (+x%)`, followed by a marker emoji
+    /// when the change exceeds the threshold.
+    pub fn format_with_emoji(&self, emoji_threshold: Option<u32>) -> String {
+        match self {
+            MetricDiff::CallsCount(before, after) => {
+                let diff_percent = calculate_percentage_diff(*before, *after);
+                let emoji = get_emoji_for_diff(diff_percent, emoji_threshold);
+                format!("{} → {} ({:+.1}%){}", before, after, diff_percent, emoji)
+            }
+            MetricDiff::DurationNs(before, after) => {
+                let diff_percent = calculate_percentage_diff(*before, *after);
+                let before_duration = Duration::from_nanos(*before);
+                let after_duration = Duration::from_nanos(*after);
+                let emoji = get_emoji_for_diff(diff_percent, emoji_threshold);
+                format!(
+                    "{:.2?} → {:.2?} ({:+.1}%){}",
+                    before_duration, after_duration, diff_percent, emoji
+                )
+            }
+            MetricDiff::Alloc(before, after) => {
+                let diff_percent = calculate_percentage_diff(*before, *after);
+                let emoji = get_emoji_for_diff(diff_percent, emoji_threshold);
+                format!(
+                    "{} → {} ({:+.1}%){}",
+                    format_bytes(*before),
+                    format_bytes(*after),
+                    diff_percent,
+                    emoji
+                )
+            }
+            MetricDiff::Percentage(before, after) => {
+                let diff_percent = calculate_percentage_diff(*before, *after);
+                let before_percent = *before as f64 / 100.0;
+                let after_percent = *after as f64 / 100.0;
+                let emoji = get_emoji_for_diff(diff_percent, emoji_threshold);
+                format!(
+                    "{:.2}% → {:.2}% ({:+.1}%){}",
+                    before_percent, after_percent, diff_percent, emoji
+                )
+            }
+        }
+    }
+}
+
+fn get_emoji_for_diff(diff_percent: f64, threshold: Option<u32>) -> &'static str {
+    if let Some(threshold_val) = threshold {
+        let threshold = threshold_val as f64;
+        if diff_percent > threshold {
+            " ⚠️ "
+        } else if diff_percent < -threshold {
+            " 🚀 "
+        } else {
+            "   "
+        }
+    } else {
+        ""
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MetricsComparison {
+    pub total_elapsed_diff: MetricDiff,
+    pub function_diffs: Vec<FunctionMetricsDiff>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FunctionMetricsDiff {
+    pub function_name: String,
+    pub metrics: Vec<MetricDiff>,
+    pub is_removed: bool, // True if function was removed (no longer measured)
+    pub is_new: bool,     // True if function is new (not in base)
+}
+
+fn calculate_percentage_diff(before: u64, after: u64) -> f64 {
+    if before == 0 {
+        if after == 0 {
+            0.0
+        } else {
+            100.0 // 100% increase from 0
+        }
+    } else {
+        ((after as f64 - before as f64) / before as f64) * 100.0
+    }
+}
+
+fn find_function<'a>(
+    data: &'a [(String, Vec<crate::MetricType>)],
+    name: &str,
+) -> Option<&'a Vec<crate::MetricType>> {
+    data.iter().find(|(n, _)| n == name).map(|(_, row)| row)
+}
+
+/// Compares per-function metrics of two profiling runs.
+///
+/// Functions are matched by name and sorted by `% Total` in `after_metrics`.
+pub fn compare_metrics(
+    before_metrics: &FunctionsJson,
+    after_metrics: &FunctionsJson,
+) -> MetricsComparison {
+    use crate::MetricType;
+
+    let total_elapsed_diff =
+        MetricDiff::DurationNs(before_metrics.total_elapsed, after_metrics.total_elapsed);
+
+    let mut function_diffs = Vec::new();
+    let mut new_functions = Vec::new();
+
+    for (function_name, after_row) in &after_metrics.data {
+        if let Some(before_row) = find_function(&before_metrics.data, function_name) {
+            let mut metrics = Vec::new();
+
+            for (metric_idx, after_metric) in after_row.iter().enumerate() {
+                if let Some(before_metric) = before_row.get(metric_idx) {
+                    let diff = match (before_metric, after_metric) {
+                        (MetricType::CallsCount(before_val), MetricType::CallsCount(after_val)) => {
+                            MetricDiff::CallsCount(*before_val, *after_val)
+                        }
+                        (MetricType::DurationNs(before_val), MetricType::DurationNs(after_val)) => {
+                            MetricDiff::DurationNs(*before_val, *after_val)
+                        }
+                        (MetricType::Alloc(before_val, _), MetricType::Alloc(after_val, _)) => {
+                            MetricDiff::Alloc(*before_val, *after_val)
+                        }
+                        (MetricType::Percentage(before_val), MetricType::Percentage(after_val)) => {
+                            MetricDiff::Percentage(*before_val, *after_val)
+                        }
+                        _ => continue,
+                    };
+                    metrics.push(diff);
+                }
+            }
+
+            function_diffs.push(FunctionMetricsDiff {
+                function_name: function_name.clone(),
+                metrics,
+                is_removed: false,
+                is_new: false,
+            });
+        } else {
+            let mut metrics = Vec::new();
+
+            for after_metric in after_row.iter() {
+                let diff = match after_metric {
+                    MetricType::CallsCount(after_val) => MetricDiff::CallsCount(0, *after_val),
+                    MetricType::DurationNs(after_val) => MetricDiff::DurationNs(0, *after_val),
+                    MetricType::Alloc(after_val, _) => MetricDiff::Alloc(0, *after_val),
+                    MetricType::Percentage(after_val) => MetricDiff::Percentage(0, *after_val),
+                    MetricType::Unsupported => continue,
+                };
+                metrics.push(diff);
+            }
+
+            new_functions.push(FunctionMetricsDiff {
+                function_name: function_name.clone(),
+                metrics,
+                is_removed: false,
+                is_new: true,
+            });
+        }
+    }
+
+    for (function_name, before_row) in &before_metrics.data {
+        if find_function(&after_metrics.data, function_name).is_none() {
+            let mut metrics = Vec::new();
+
+            for before_metric in before_row.iter() {
+                let diff = match before_metric {
+                    MetricType::CallsCount(before_val) => MetricDiff::CallsCount(*before_val, 0),
+                    MetricType::DurationNs(before_val) => MetricDiff::DurationNs(*before_val, 0),
+                    MetricType::Alloc(before_val, _) => MetricDiff::Alloc(*before_val, 0),
+                    MetricType::Percentage(before_val) => MetricDiff::Percentage(*before_val, 0),
+                    MetricType::Unsupported => continue,
+                };
+                metrics.push(diff);
+            }
+
+            function_diffs.push(FunctionMetricsDiff {
+                function_name: function_name.clone(),
+                metrics,
+                is_removed: true,
+                is_new: false,
+            });
+        }
+    }
+
+    function_diffs.extend(new_functions);
+
+    // Sort by percent_total in head branch (after value), descending order
+    function_diffs.sort_by(|a, b| {
+        let a_percent = a
+            .metrics
+            .iter()
+            .find_map(|m| {
+                if let MetricDiff::Percentage(_, after) = m {
+                    Some(*after)
+                } else {
+                    None
+                }
+            })
+            .unwrap_or(0);
+
+        let b_percent = b
+            .metrics
+            .iter()
+            .find_map(|m| {
+                if let MetricDiff::Percentage(_, after) = m {
+                    Some(*after)
+                } else {
+                    None
+                }
+            })
+            .unwrap_or(0);
+
+        b_percent.cmp(&a_percent)
+    });
+
+    MetricsComparison {
+        total_elapsed_diff,
+        function_diffs,
+    }
+}
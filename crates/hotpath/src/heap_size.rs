@@ -0,0 +1,279 @@
+//! Heap-aware size estimation for channel messages and stream items.
+
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque};
+use std::mem;
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// Bytes a value owns on the heap, on top of its inline `size_of`.
+///
+/// Channels created with `channel!(…, size = true)` and streams created with
+/// `stream!(…, size = true)` record `size_of::<T>() + heap_size()` for every message, so
+/// queued bytes and byte rates reflect the memory actually held by `Vec`, `String` or `Box`
+/// payloads.
+///
+/// Implementations are provided for primitives and std collections, and
+/// `#[derive(hotpath::HeapSize)]` sums the fields of structs and enums. The derive
+/// generates the same impl with profiling disabled.
+///
+/// Collections report their allocated capacity, hash tables ignore their control bytes, and
+/// `Rc` and `Arc` count the shared allocation in full for every handle.
+///
+/// # Examples
+///
+/// ```rust
+/// use hotpath::HeapSize;
+///
+/// struct Frame {
+///     id: u64,
+///     payload: Vec<u8>,
+/// }
+///
+/// impl HeapSize for Frame {
+///     fn heap_size(&self) -> usize {
+///         self.id.heap_size() + self.payload.heap_size()
+///     }
+/// }
+///
+/// let frame = Frame {
+///     id: 1,
+///     payload: Vec::with_capacity(1024),
+/// };
+/// assert_eq!(frame.heap_size(), 1024);
+/// ```
+pub trait HeapSize {
+    /// Heap bytes owned by this value, excluding its inline size.
+    fn heap_size(&self) -> usize;
+}
+
+/// Inline and heap size of a message.
+#[cfg(all(feature = "hotpath", not(feature = "hotpath-off")))]
+pub(crate) fn deep_size<T: HeapSize>(value: &T) -> u64 {
+    (mem::size_of::<T>() + value.heap_size()) as u64
+}
+
+macro_rules! impl_inline {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl HeapSize for $ty {
+                #[inline]
+                fn heap_size(&self) -> usize {
+                    0
+                }
+            }
+        )*
+    };
+}
+
+impl_inline!(
+    (),
+    bool,
+    char,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64,
+    std::time::Duration,
+    std::time::Instant,
+    std::time::SystemTime,
+);
+
+/// Borrowed data is owned by someone else.
+impl<T: ?Sized> HeapSize for &T {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl HeapSize for String {
+    fn heap_size(&self) -> usize {
+        self.capacity()
+    }
+}
+
+impl HeapSize for Box<str> {
+    fn heap_size(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T: HeapSize> HeapSize for Box<T> {
+    fn heap_size(&self) -> usize {
+        mem::size_of::<T>() + (**self).heap_size()
+    }
+}
+
+impl<T: HeapSize> HeapSize for Box<[T]> {
+    fn heap_size(&self) -> usize {
+        mem::size_of_val::<[T]>(self) + self.iter().map(HeapSize::heap_size).sum::<usize>()
+    }
+}
+
+impl<T: HeapSize> HeapSize for Rc<T> {
+    fn heap_size(&self) -> usize {
+        mem::size_of::<T>() + (**self).heap_size()
+    }
+}
+
+impl<T: HeapSize> HeapSize for Arc<T> {
+    fn heap_size(&self) -> usize {
+        mem::size_of::<T>() + (**self).heap_size()
+    }
+}
+
+impl<T: HeapSize + ToOwned + ?Sized> HeapSize for Cow<'_, T>
+where
+    T::Owned: HeapSize,
+{
+    fn heap_size(&self) -> usize {
+        match self {
+            Cow::Borrowed(_) => 0,
+            Cow::Owned(owned) => owned.heap_size(),
+        }
+    }
+}
+
+impl HeapSize for str {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl<T: HeapSize> HeapSize for [T] {
+    fn heap_size(&self) -> usize {
+        self.iter().map(HeapSize::heap_size).sum()
+    }
+}
+
+impl<T: HeapSize, const N: usize> HeapSize for [T; N] {
+    fn heap_size(&self) -> usize {
+        self.iter().map(HeapSize::heap_size).sum()
+    }
+}
+
+impl<T: HeapSize> HeapSize for Option<T> {
+    fn heap_size(&self) -> usize {
+        self.as_ref().map_or(0, HeapSize::heap_size)
+    }
+}
+
+impl<T: HeapSize, E: HeapSize> HeapSize for Result<T, E> {
+    fn heap_size(&self) -> usize {
+        match self {
+            Ok(value) => value.heap_size(),
+            Err(err) => err.heap_size(),
+        }
+    }
+}
+
+impl<T: HeapSize> HeapSize for Vec<T> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * mem::size_of::<T>() + self.iter().map(HeapSize::heap_size).sum::<usize>()
+    }
+}
+
+impl<T: HeapSize> HeapSize for VecDeque<T> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * mem::size_of::<T>() + self.iter().map(HeapSize::heap_size).sum::<usize>()
+    }
+}
+
+impl<T: HeapSize> HeapSize for BinaryHeap<T> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * mem::size_of::<T>() + self.iter().map(HeapSize::heap_size).sum::<usize>()
+    }
+}
+
+/// Every element is a separate node holding two pointers.
+impl<T: HeapSize> HeapSize for LinkedList<T> {
+    fn heap_size(&self) -> usize {
+        self.len() * (mem::size_of::<T>() + 2 * mem::size_of::<usize>())
+            + self.iter().map(HeapSize::heap_size).sum::<usize>()
+    }
+}
+
+impl<K: HeapSize, V: HeapSize, S> HeapSize for HashMap<K, V, S> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * mem::size_of::<(K, V)>()
+            + self
+                .iter()
+                .map(|(key, value)| key.heap_size() + value.heap_size())
+                .sum::<usize>()
+    }
+}
+
+impl<T: HeapSize, S> HeapSize for HashSet<T, S> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * mem::size_of::<T>() + self.iter().map(HeapSize::heap_size).sum::<usize>()
+    }
+}
+
+/// B-tree nodes are not exposed, so only the entries are counted.
+impl<K: HeapSize, V: HeapSize> HeapSize for BTreeMap<K, V> {
+    fn heap_size(&self) -> usize {
+        self.len() * mem::size_of::<(K, V)>()
+            + self
+                .iter()
+                .map(|(key, value)| key.heap_size() + value.heap_size())
+                .sum::<usize>()
+    }
+}
+
+impl<T: HeapSize> HeapSize for BTreeSet<T> {
+    fn heap_size(&self) -> usize {
+        self.len() * mem::size_of::<T>() + self.iter().map(HeapSize::heap_size).sum::<usize>()
+    }
+}
+
+macro_rules! impl_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: HeapSize),+> HeapSize for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn heap_size(&self) -> usize {
+                let ($($name,)+) = self;
+                0 $(+ $name.heap_size())+
+            }
+        }
+    };
+}
+
+impl_tuple!(A);
+impl_tuple!(A, B);
+impl_tuple!(A, B, C);
+impl_tuple!(A, B, C, D);
+impl_tuple!(A, B, C, D, E);
+impl_tuple!(A, B, C, D, E, F);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heap_size_of_std_types() {
+        assert_eq!(42u64.heap_size(), 0);
+        assert_eq!(String::with_capacity(100).heap_size(), 100);
+        assert_eq!(Vec::<u32>::with_capacity(10).heap_size(), 40);
+        assert_eq!(Box::new(7u64).heap_size(), 8);
+        assert_eq!(Some(String::with_capacity(5)).heap_size(), 5);
+
+        let nested: Vec<String> = vec![String::with_capacity(10), String::with_capacity(20)];
+        assert_eq!(
+            nested.heap_size(),
+            nested.capacity() * mem::size_of::<String>() + 30
+        );
+
+        let pair = (1u8, String::with_capacity(3));
+        assert_eq!(pair.heap_size(), 3);
+    }
+}
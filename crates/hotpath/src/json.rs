@@ -5,10 +5,12 @@
 
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::str::FromStr;
 use std::sync::LazyLock;
+use std::time::Duration;
 
-pub use crate::output::{FunctionLogsJson, FunctionsJson};
+pub use crate::output::{CallTreeNode, FunctionLogsJson, FunctionsJson, FunctionsTreeJson};
 
 /// State of a channel or stream.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
@@ -44,6 +46,9 @@ pub enum ChannelType {
     Bounded(usize),
     Unbounded,
     Oneshot,
+    /// Broadcast channel, with the capacity if passed to `channel!`
+    Broadcast(Option<usize>),
+    Watch,
 }
 
 impl std::fmt::Display for ChannelType {
@@ -52,6 +57,9 @@ impl std::fmt::Display for ChannelType {
             ChannelType::Bounded(size) => write!(f, "bounded[{}]", size),
             ChannelType::Unbounded => write!(f, "unbounded"),
             ChannelType::Oneshot => write!(f, "oneshot"),
+            ChannelType::Broadcast(Some(size)) => write!(f, "broadcast[{}]", size),
+            ChannelType::Broadcast(None) => write!(f, "broadcast"),
+            ChannelType::Watch => write!(f, "watch"),
         }
     }
 }
@@ -75,12 +83,22 @@ impl<'de> Deserialize<'de> for ChannelType {
         match s.as_str() {
             "unbounded" => Ok(ChannelType::Unbounded),
             "oneshot" => Ok(ChannelType::Oneshot),
+            "broadcast" => Ok(ChannelType::Broadcast(None)),
+            "watch" => Ok(ChannelType::Watch),
             _ => {
                 if let Some(inner) = s.strip_prefix("bounded[").and_then(|x| x.strip_suffix(']')) {
                     let size = inner
                         .parse()
                         .map_err(|_| serde::de::Error::custom("invalid bounded size"))?;
                     Ok(ChannelType::Bounded(size))
+                } else if let Some(inner) = s
+                    .strip_prefix("broadcast[")
+                    .and_then(|x| x.strip_suffix(']'))
+                {
+                    let size = inner
+                        .parse()
+                        .map_err(|_| serde::de::Error::custom("invalid broadcast size"))?;
+                    Ok(ChannelType::Broadcast(Some(size)))
                 } else {
                     Err(serde::de::Error::custom("invalid channel type"))
                 }
@@ -116,6 +134,9 @@ pub struct ChannelsJson {
     pub current_elapsed_ns: u64,
     /// Channel statistics
     pub channels: Vec<SerializableChannelStats>,
+    /// Value of `HOTPATH_CHANNEL_QUEUE_WARN`, `None` if queue warnings are disabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub queue_warn_threshold: Option<u64>,
 }
 
 /// Serializable version of channel statistics for JSON responses.
@@ -132,8 +153,110 @@ pub struct SerializableChannelStats {
     pub queued: u64,
     pub type_name: String,
     pub type_size: usize,
+    /// Bytes held by queued messages, their real size with `size = true` and
+    /// `queued * type_size` otherwise
     pub queued_bytes: u64,
     pub iter: u32,
+    /// Messages sent per second over the requested window
+    #[serde(default)]
+    pub sent_rate: f64,
+    /// Messages received per second over the requested window
+    #[serde(default)]
+    pub received_rate: f64,
+    /// Messages sent during the last complete second
+    #[serde(default)]
+    pub sent_rate_instant: u64,
+    /// Messages received during the last complete second
+    #[serde(default)]
+    pub received_rate_instant: u64,
+    /// Bytes sent per second over the requested window. Measured with `size = true`,
+    /// estimated as `sent_rate * type_size` otherwise.
+    #[serde(default)]
+    pub sent_bytes_rate: f64,
+    /// Estimated bytes received per second over the requested window
+    #[serde(default)]
+    pub received_bytes_rate: f64,
+    /// Messages sent in each of the last 30 complete seconds, oldest first
+    #[serde(default)]
+    pub sent_history: Vec<u64>,
+    /// Messages received in each of the last 30 complete seconds, oldest first
+    #[serde(default)]
+    pub received_history: Vec<u64>,
+    /// Live receivers of a broadcast or watch channel
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subscribers: Option<u64>,
+    /// Messages of a broadcast channel that the slowest receiver has not seen yet
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lag: Option<u64>,
+    /// Time between send and receive, `None` until a message has been received.
+    /// Never reported by channels that count a receive when the message reaches the proxy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub queue_wait: Option<DurationStats>,
+    /// Time producers spent blocked on the channel being full, `None` until it happens.
+    /// Never reported by unbounded channels and bounded futures channels.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub send_blocked: Option<SendBlockedStats>,
+    /// Total inline and heap bytes of sent messages, only measured with `size = true`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sent_bytes: Option<u64>,
+    /// Total inline and heap bytes of received messages, only measured with `size = true`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub received_bytes: Option<u64>,
+    /// Highest `queued` value seen so far
+    #[serde(default)]
+    pub max_queued: u64,
+    /// When `max_queued` was first reached, elapsed time since program start in nanoseconds
+    #[serde(default)]
+    pub max_queued_at_ns: u64,
+    /// Most recent times the queue reached `HOTPATH_CHANNEL_QUEUE_WARN`, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub queue_warnings: Vec<QueueWarning>,
+}
+
+/// Distribution of durations, in nanoseconds: channel queue wait, blocked sends and the
+/// busy, idle and total time of future calls.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DurationStats {
+    /// Recorded durations, e.g. messages matched from send to receive or finished calls
+    pub count: u64,
+    pub avg_ns: u64,
+    pub p50_ns: u64,
+    pub p95_ns: u64,
+    pub p99_ns: u64,
+    pub max_ns: u64,
+}
+
+/// Sends that found a bounded channel full, in nanoseconds.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SendBlockedStats {
+    /// Time spent waiting for capacity by sends that hit the full condition, including
+    /// rejected `try_send` calls
+    #[serde(flatten)]
+    pub durations: DurationStats,
+    pub total_ns: u64,
+    /// Breakdown by sending thread, most blocked first. Only `proxy = false` channels know
+    /// the sending thread
+    pub threads: Vec<ThreadSendBlocked>,
+}
+
+/// Blocked sends of a single producer thread.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThreadSendBlocked {
+    /// OS thread id the send was started on
+    pub tid: u64,
+    pub count: u64,
+    pub total_ns: u64,
+    pub max_ns: u64,
+}
+
+/// A channel queue reaching `HOTPATH_CHANNEL_QUEUE_WARN`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueueWarning {
+    /// Queued messages when the threshold was crossed
+    pub queued: u64,
+    pub threshold: u64,
+    /// Elapsed time since program start in nanoseconds
+    pub timestamp_ns: u64,
 }
 
 /// Serializable log response containing sent and received logs for channels.
@@ -144,6 +267,77 @@ pub struct ChannelLogs {
     pub received_logs: Vec<LogEntry>,
 }
 
+/// Response for the /channels/graph endpoint: producers, channels and consumers linked by the
+/// messages passing between them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChannelsGraphJson {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+}
+
+/// Kind of a node in the channels graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphNodeKind {
+    Channel,
+    /// Measured function that sends or receives
+    Function,
+    /// Thread that sends or receives outside of any measured function
+    Thread,
+}
+
+/// A channel, or a function or thread using one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphNode {
+    pub id: String,
+    pub kind: GraphNodeKind,
+    pub label: String,
+    /// OS thread ids the function or thread was seen on, empty for channels
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tids: Vec<u64>,
+}
+
+/// Messages sent from a producer to a channel, or received by a consumer from a channel.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphEdge {
+    pub from: String,
+    pub to: String,
+    pub messages: u64,
+}
+
+impl ChannelsGraphJson {
+    /// Renders the graph in Graphviz DOT format, channels as boxes and endpoints as ellipses.
+    pub fn to_dot(&self) -> String {
+        fn quote(s: &str) -> String {
+            format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+        }
+
+        let mut dot = String::from("digraph channels {\n    rankdir=LR;\n");
+        for node in &self.nodes {
+            let shape = match node.kind {
+                GraphNodeKind::Channel => "box",
+                GraphNodeKind::Function | GraphNodeKind::Thread => "ellipse",
+            };
+            dot.push_str(&format!(
+                "    {} [label={}, shape={}];\n",
+                quote(&node.id),
+                quote(&node.label),
+                shape
+            ));
+        }
+        for edge in &self.edges {
+            dot.push_str(&format!(
+                "    {} -> {} [label=\"{}\"];\n",
+                quote(&edge.from),
+                quote(&edge.to),
+                edge.messages
+            ));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
 /// Wrapper for streams-only JSON response.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StreamsJson {
@@ -165,6 +359,22 @@ pub struct SerializableStreamStats {
     pub type_name: String,
     pub type_size: usize,
     pub iter: u32,
+    /// Items yielded per second over the requested window
+    #[serde(default)]
+    pub yield_rate: f64,
+    /// Items yielded during the last complete second
+    #[serde(default)]
+    pub yield_rate_instant: u64,
+    /// Bytes yielded per second over the requested window. Measured with `size = true`,
+    /// estimated as `yield_rate * type_size` otherwise.
+    #[serde(default)]
+    pub yield_bytes_rate: f64,
+    /// Items yielded in each of the last 30 complete seconds, oldest first
+    #[serde(default)]
+    pub yield_history: Vec<u64>,
+    /// Total inline and heap bytes of yielded items, only measured with `size = true`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub yielded_bytes: Option<u64>,
 }
 
 /// Serializable log response containing yielded logs for streams.
@@ -212,6 +422,25 @@ pub struct FutureCall {
     pub state: FutureState,
     pub poll_count: u64,
     pub result: Option<String>,
+    /// Key/value pairs passed with `tags(...)`, e.g. a request id
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tags: BTreeMap<String, String>,
+    /// When the future was instrumented, elapsed time since program start in nanoseconds
+    #[serde(default)]
+    pub created_at_ns: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_poll_at_ns: Option<u64>,
+    /// When the future completed or was dropped, `None` while it is alive
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ended_at_ns: Option<u64>,
+    /// Time spent inside `poll`
+    #[serde(default)]
+    pub busy_ns: u64,
+    /// Longest single `poll`
+    #[serde(default)]
+    pub max_poll_ns: u64,
+    #[serde(default)]
+    pub wakes: WakeStats,
 }
 
 impl FutureCall {
@@ -222,8 +451,157 @@ impl FutureCall {
             state: FutureState::default(),
             poll_count: 0,
             result: None,
+            tags: BTreeMap::new(),
+            created_at_ns: 0,
+            first_poll_at_ns: None,
+            ended_at_ns: None,
+            busy_ns: 0,
+            max_poll_ns: 0,
+            wakes: WakeStats::default(),
         }
     }
+
+    /// Wall-clock time from creation until the future completed or was dropped.
+    pub fn lifetime_ns(&self) -> Option<u64> {
+        self.ended_at_ns
+            .map(|ended_at_ns| ended_at_ns.saturating_sub(self.created_at_ns))
+    }
+
+    /// Time the future was alive but not being polled, waiting to be woken or scheduled.
+    pub fn idle_ns(&self) -> Option<u64> {
+        self.lifetime_ns()
+            .map(|lifetime_ns| lifetime_ns.saturating_sub(self.busy_ns))
+    }
+}
+
+/// Polls that held the thread longer than `HOTPATH_SLOW_POLL_MS`, most recent first.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SlowPollsJson {
+    pub current_elapsed_ns: u64,
+    /// `None` if slow poll detection is disabled
+    pub threshold_ms: Option<u64>,
+    /// Slow polls recorded so far, including the ones no longer listed
+    pub total: u64,
+    pub slow_polls: Vec<SlowPoll>,
+}
+
+/// What was being polled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SlowPollKind {
+    /// A future instrumented with `future!` or `#[future_fn]`
+    Future,
+    /// An async function measured with `#[measure]`
+    Function,
+}
+
+impl std::fmt::Display for SlowPollKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SlowPollKind::Future => write!(f, "future"),
+            SlowPollKind::Function => write!(f, "function"),
+        }
+    }
+}
+
+/// A single poll that took longer than `HOTPATH_SLOW_POLL_MS`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SlowPoll {
+    pub kind: SlowPollKind,
+    /// Future label or measured function name
+    pub label: String,
+    /// Source location of the future, or the function name
+    pub source: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub future_id: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub call_id: Option<u64>,
+    /// OS thread id the poll ran on
+    pub tid: u64,
+    pub duration_ns: u64,
+    /// When the poll started, elapsed time since program start in nanoseconds
+    pub timestamp_ns: u64,
+}
+
+/// Tasks spawned with `spawn!` or `spawn_blocking!`: live ones in spawn order, then
+/// the recently finished ones, newest first.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TasksJson {
+    pub current_elapsed_ns: u64,
+    /// Tasks spawned so far, including finished ones no longer listed
+    pub total_spawned: u64,
+    pub live: usize,
+    pub tasks: Vec<TaskJson>,
+}
+
+/// How a task was spawned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskKind {
+    /// An async task, spawned with `spawn!`
+    Async,
+    /// A closure run on a blocking thread pool, spawned with `spawn_blocking!`
+    Blocking,
+}
+
+impl std::fmt::Display for TaskKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TaskKind::Async => write!(f, "async"),
+            TaskKind::Blocking => write!(f, "blocking"),
+        }
+    }
+}
+
+/// Task lifecycle state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskState {
+    /// Spawned and not finished yet
+    Running,
+    /// Ran to completion
+    Completed,
+    /// Dropped before completion, aborted or its runtime shut down
+    Cancelled,
+    /// Panicked while running
+    Panicked,
+}
+
+impl std::fmt::Display for TaskState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TaskState::Running => write!(f, "running"),
+            TaskState::Completed => write!(f, "completed"),
+            TaskState::Cancelled => write!(f, "cancelled"),
+            TaskState::Panicked => write!(f, "panicked"),
+        }
+    }
+}
+
+/// A single task spawned with `spawn!` or `spawn_blocking!`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskJson {
+    pub id: u64,
+    pub kind: TaskKind,
+    pub label: String,
+    /// Source location of the spawn
+    pub source: String,
+    /// Task that spawned this one, if it was spawned from inside a tracked task
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<u64>,
+    pub state: TaskState,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub panic_message: Option<String>,
+    /// Polls of an async task, 1 for a blocking task that started running
+    pub poll_count: u64,
+    /// Time spent running, in polls or in the blocking closure
+    pub busy_ns: u64,
+    /// OS thread ids the task ran on, in order of first use
+    pub threads: Vec<u64>,
+    /// When the task was spawned, elapsed time since program start in nanoseconds
+    pub spawned_at_ns: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finished_at_ns: Option<u64>,
 }
 
 /// Wrapper for futures-only JSON response.
@@ -242,6 +620,94 @@ pub struct SerializableFutureStats {
     pub has_custom_label: bool,
     pub call_count: u64,
     pub total_polls: u64,
+    /// Time each finished call spent inside `poll`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub busy: Option<DurationStats>,
+    /// Time each finished call was alive without being polled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idle: Option<DurationStats>,
+    /// Lifetime of each finished call, from creation until it completed or was dropped
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total: Option<DurationStats>,
+    /// The longest single `poll` across all calls
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub longest_poll: Option<LongestPoll>,
+    /// Wakes across all calls
+    #[serde(default)]
+    pub wakes: WakeStats,
+}
+
+/// How the waker passed to an instrumented future was used.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WakeStats {
+    pub wakes: u64,
+    /// Wakes while the future was being polled, e.g. by `yield_now`
+    pub self_wakes: u64,
+    /// Wakes of a future that had already completed
+    pub wakes_after_completion: u64,
+    /// Polls returning `Pending` without waking or keeping a clone of the waker, leaving
+    /// nothing to wake the future again
+    pub lost_wakeups: u64,
+}
+
+impl WakeStats {
+    pub fn record(&mut self, self_wake: bool, after_completion: bool) {
+        self.wakes += 1;
+        if self_wake {
+            self.self_wakes += 1;
+        }
+        if after_completion {
+            self.wakes_after_completion += 1;
+        }
+    }
+
+    /// Suspicious waker usage with how often it happened, most severe first
+    pub fn warnings(&self) -> Vec<(WakeWarning, u64)> {
+        [
+            (WakeWarning::LostWakeup, self.lost_wakeups),
+            (
+                WakeWarning::WokenAfterCompletion,
+                self.wakes_after_completion,
+            ),
+            (WakeWarning::SelfWake, self.self_wakes),
+        ]
+        .into_iter()
+        .filter(|(_, count)| *count > 0)
+        .collect()
+    }
+}
+
+/// Waker usage that points to a bug or a busy loop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WakeWarning {
+    LostWakeup,
+    WokenAfterCompletion,
+    SelfWake,
+}
+
+impl WakeWarning {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WakeWarning::LostWakeup => "pending without a retained waker (lost wakeup?)",
+            WakeWarning::WokenAfterCompletion => "woken after completion",
+            WakeWarning::SelfWake => "woken during its own poll",
+        }
+    }
+}
+
+impl std::fmt::Display for WakeWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A single `poll` that took longer than any other of the same future.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LongestPoll {
+    pub call_id: u64,
+    pub duration_ns: u64,
+    /// When the poll started, elapsed time since program start in nanoseconds
+    pub started_at_ns: u64,
 }
 
 /// Serializable response for future calls.
@@ -323,31 +789,231 @@ pub struct ThreadsJson {
     pub rss_bytes: Option<u64>,
 }
 
+/// Metrics of a single tokio runtime worker.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuntimeWorkerMetrics {
+    /// Worker index, stable for the lifetime of the runtime
+    pub index: usize,
+    /// Total time spent polling tasks and processing events in nanoseconds
+    pub busy_ns: u64,
+    /// Share of the last sample interval the worker was busy
+    /// None if this is the first sample
+    pub busy_percent: Option<f64>,
+    /// Times the worker parked waiting for work
+    pub park_count: u64,
+    /// Times the worker was unparked
+    pub unpark_count: u64,
+    /// Whether the worker is parked right now
+    pub parked: bool,
+    /// Tasks in the worker's local run queue (only with `--cfg tokio_unstable`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub local_queue_depth: Option<usize>,
+    /// OS thread the worker runs on. `multi_thread` workers are only matched with their
+    /// thread with `--cfg tokio_unstable`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub os_tid: Option<u64>,
+    /// CPU usage percentage of that thread
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_percent: Option<f64>,
+}
+
+/// JSON response structure for /runtime endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuntimeJson {
+    /// Current elapsed time since monitoring started in nanoseconds
+    pub current_elapsed_ns: u64,
+    /// Sample interval in milliseconds
+    pub sample_interval_ms: u64,
+    /// Runtime flavor, "current_thread" or "multi_thread"
+    pub flavor: String,
+    /// Number of worker threads
+    pub num_workers: usize,
+    /// Tasks spawned on the runtime that have not completed yet
+    pub alive_tasks: usize,
+    /// Tasks in the global run queue, scheduled from outside the runtime or overflowing
+    /// from the workers' local queues
+    pub global_queue_depth: usize,
+    /// Threads spawned by the blocking pool (only with `--cfg tokio_unstable`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blocking_threads: Option<usize>,
+    /// Blocking pool threads waiting for work (only with `--cfg tokio_unstable`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub idle_blocking_threads: Option<usize>,
+    /// Tasks waiting for a blocking pool thread (only with `--cfg tokio_unstable`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blocking_queue_depth: Option<usize>,
+    /// Per-worker metrics
+    pub workers: Vec<RuntimeWorkerMetrics>,
+    /// Sum of worker busy percentages over the last sample interval
+    pub busy_percent: Option<f64>,
+    /// Sum of CPU usage percentages of the threads the workers run on
+    pub cpu_percent: Option<f64>,
+    /// Threads the workers run on. For `multi_thread` runtimes these are the threads that ran
+    /// a task spawned on the runtime, blocking pool threads excluded
+    pub threads: Vec<ThreadMetrics>,
+}
+
+/// Time window that function timings and channel and stream rates are aggregated over.
+///
+/// Selected with the `window` query parameter, e.g. `/functions_timing?window=60s`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum StatsWindow {
+    /// Everything recorded since the program started
+    #[default]
+    Lifetime,
+    /// The last 10 seconds
+    Last10s,
+    /// The last minute
+    Last1m,
+    /// The last 5 minutes
+    Last5m,
+}
+
+impl StatsWindow {
+    /// Sliding windows, shortest first.
+    pub const SLIDING: [StatsWindow; 3] = [
+        StatsWindow::Last10s,
+        StatsWindow::Last1m,
+        StatsWindow::Last5m,
+    ];
+
+    /// Length of the window, `None` for [`StatsWindow::Lifetime`].
+    pub fn duration(&self) -> Option<Duration> {
+        match self {
+            StatsWindow::Lifetime => None,
+            StatsWindow::Last10s => Some(Duration::from_secs(10)),
+            StatsWindow::Last1m => Some(Duration::from_secs(60)),
+            StatsWindow::Last5m => Some(Duration::from_secs(300)),
+        }
+    }
+
+    /// Value of the `window` query parameter, `None` for [`StatsWindow::Lifetime`].
+    pub fn as_query(&self) -> Option<&'static str> {
+        match self {
+            StatsWindow::Lifetime => None,
+            StatsWindow::Last10s => Some("10s"),
+            StatsWindow::Last1m => Some("1m"),
+            StatsWindow::Last5m => Some("5m"),
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            StatsWindow::Lifetime => "lifetime",
+            StatsWindow::Last10s => "last 10s",
+            StatsWindow::Last1m => "last 1m",
+            StatsWindow::Last5m => "last 5m",
+        }
+    }
+
+    /// Cycles lifetime -> 10s -> 1m -> 5m -> lifetime.
+    pub fn next(&self) -> Self {
+        match self {
+            StatsWindow::Lifetime => StatsWindow::Last10s,
+            StatsWindow::Last10s => StatsWindow::Last1m,
+            StatsWindow::Last1m => StatsWindow::Last5m,
+            StatsWindow::Last5m => StatsWindow::Lifetime,
+        }
+    }
+
+    fn query_suffix(&self) -> String {
+        self.as_query()
+            .map(|window| format!("?window={}", window))
+            .unwrap_or_default()
+    }
+
+    fn from_query(query: Option<&str>) -> Result<Self, ()> {
+        let Some(query) = query else {
+            return Ok(StatsWindow::Lifetime);
+        };
+
+        match query
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("window="))
+        {
+            Some(value) => value.parse(),
+            None => Ok(StatsWindow::Lifetime),
+        }
+    }
+}
+
+impl FromStr for StatsWindow {
+    type Err = ();
+
+    /// Accepts `10s`, `1m`/`60s`, `5m`/`300s` and `lifetime`/`all`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "lifetime" | "all" => Ok(StatsWindow::Lifetime),
+            "10s" => Ok(StatsWindow::Last10s),
+            "1m" | "60s" => Ok(StatsWindow::Last1m),
+            "5m" | "300s" => Ok(StatsWindow::Last5m),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Output format of the channels graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GraphFormat {
+    #[default]
+    Json,
+    /// Graphviz DOT
+    Dot,
+}
+
+impl GraphFormat {
+    fn from_query(query: Option<&str>) -> Result<Self, ()> {
+        let value = query.and_then(|query| {
+            query
+                .split('&')
+                .find_map(|pair| pair.strip_prefix("format="))
+        });
+
+        match value {
+            None | Some("json") => Ok(GraphFormat::Json),
+            Some("dot") => Ok(GraphFormat::Dot),
+            Some(_) => Err(()),
+        }
+    }
+}
+
 /// HTTP routes for the hotpath metrics server.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Route {
-    /// GET /functions_timing - Returns timing metrics for all functions
-    FunctionsTiming,
+    /// GET /functions_timing[?window=60s] - Returns timing metrics for all functions
+    FunctionsTiming { window: StatsWindow },
+    /// GET /functions_timing/tree - Returns timing metrics aggregated by call path
+    FunctionsTimingTree,
     /// GET /functions_alloc - Returns allocation metrics for all functions
     FunctionsAlloc,
-    /// GET /channels - Returns all channel statistics
-    Channels,
-    /// GET /streams - Returns all stream statistics
-    Streams,
+    /// GET /channels[?window=60s] - Returns all channel statistics
+    Channels { window: StatsWindow },
+    /// GET /streams[?window=60s] - Returns all stream statistics
+    Streams { window: StatsWindow },
     /// GET /futures - Returns all future statistics
     Futures,
     /// GET /threads - Returns thread metrics
     Threads,
+    /// GET /runtime - Returns tokio runtime metrics
+    Runtime,
     /// GET /functions_timing/{base64_name}/logs - Returns timing logs for a function
     FunctionTimingLogs { function_name: String },
     /// GET /functions_alloc/{base64_name}/logs - Returns allocation logs for a function
     FunctionAllocLogs { function_name: String },
+    /// GET /channels/graph[?format=dot] - Returns the producer -> channel -> consumer graph
+    ChannelsGraph { format: GraphFormat },
     /// GET /channels/{id}/logs - Returns logs for a specific channel
     ChannelLogs { channel_id: u64 },
     /// GET /streams/{id}/logs - Returns logs for a specific stream
     StreamLogs { stream_id: u64 },
     /// GET /futures/{id}/calls - Returns calls for a specific future
     FutureCalls { future_id: u64 },
+    /// GET /slow_polls - Returns polls that took longer than `HOTPATH_SLOW_POLL_MS`
+    SlowPolls,
+    /// GET /tasks - Returns live and recently finished tasks spawned with `spawn!`
+    Tasks,
+    /// GET /metrics - Returns all metrics in OpenMetrics text format
+    Metrics,
 }
 
 impl Route {
@@ -355,12 +1021,16 @@ impl Route {
     pub fn to_path(&self) -> String {
         use base64::Engine;
         match self {
-            Route::FunctionsTiming => "/functions_timing".to_string(),
+            Route::FunctionsTiming { window } => {
+                format!("/functions_timing{}", window.query_suffix())
+            }
+            Route::FunctionsTimingTree => "/functions_timing/tree".to_string(),
             Route::FunctionsAlloc => "/functions_alloc".to_string(),
-            Route::Channels => "/channels".to_string(),
-            Route::Streams => "/streams".to_string(),
+            Route::Channels { window } => format!("/channels{}", window.query_suffix()),
+            Route::Streams { window } => format!("/streams{}", window.query_suffix()),
             Route::Futures => "/futures".to_string(),
             Route::Threads => "/threads".to_string(),
+            Route::Runtime => "/runtime".to_string(),
             Route::FunctionTimingLogs { function_name } => {
                 let encoded =
                     base64::engine::general_purpose::STANDARD.encode(function_name.as_bytes());
@@ -371,9 +1041,16 @@ impl Route {
                     base64::engine::general_purpose::STANDARD.encode(function_name.as_bytes());
                 format!("/functions_alloc/{}/logs", encoded)
             }
+            Route::ChannelsGraph { format } => match format {
+                GraphFormat::Json => "/channels/graph".to_string(),
+                GraphFormat::Dot => "/channels/graph?format=dot".to_string(),
+            },
             Route::ChannelLogs { channel_id } => format!("/channels/{}/logs", channel_id),
             Route::StreamLogs { stream_id } => format!("/streams/{}/logs", stream_id),
             Route::FutureCalls { future_id } => format!("/futures/{}/calls", future_id),
+            Route::SlowPolls => "/slow_polls".to_string(),
+            Route::Tasks => "/tasks".to_string(),
+            Route::Metrics => "/metrics".to_string(),
         }
     }
 
@@ -408,15 +1085,36 @@ impl FromStr for Route {
     /// Parses a URL path into a Route using regex patterns.
     /// Returns Err(()) if the path doesn't match any known route.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let path = s.split('?').next().unwrap_or(s);
+        let (path, query) = match s.split_once('?') {
+            Some((path, query)) => (path, Some(query)),
+            None => (s, None),
+        };
 
         match path {
-            "/functions_timing" => return Ok(Route::FunctionsTiming),
+            "/functions_timing" => {
+                let window = StatsWindow::from_query(query)?;
+                return Ok(Route::FunctionsTiming { window });
+            }
+            "/functions_timing/tree" => return Ok(Route::FunctionsTimingTree),
             "/functions_alloc" => return Ok(Route::FunctionsAlloc),
-            "/channels" => return Ok(Route::Channels),
-            "/streams" => return Ok(Route::Streams),
+            "/channels" => {
+                let window = StatsWindow::from_query(query)?;
+                return Ok(Route::Channels { window });
+            }
+            "/channels/graph" => {
+                let format = GraphFormat::from_query(query)?;
+                return Ok(Route::ChannelsGraph { format });
+            }
+            "/streams" => {
+                let window = StatsWindow::from_query(query)?;
+                return Ok(Route::Streams { window });
+            }
             "/futures" => return Ok(Route::Futures),
+            "/slow_polls" => return Ok(Route::SlowPolls),
+            "/tasks" => return Ok(Route::Tasks),
             "/threads" => return Ok(Route::Threads),
+            "/runtime" => return Ok(Route::Runtime),
+            "/metrics" => return Ok(Route::Metrics),
             _ => {}
         }
 
@@ -13,12 +13,15 @@
 /// * `Table` - Human-readable table format (default)
 /// * `Json` - JSON format
 /// * `JsonPretty` - Pretty-printed JSON format
+/// * `Folded` - Folded stack lines of measured functions for flamegraph tools.
+///   Channels, streams and futures guards print nothing.
 #[derive(Clone, Copy, Debug, Default)]
 pub enum Format {
     #[default]
     Table,
     Json,
     JsonPretty,
+    Folded,
 }
 
 #[cfg(all(feature = "hotpath", not(feature = "hotpath-off")))]
@@ -31,8 +34,16 @@ mod lib_on;
 pub use lib_on::channels;
 #[cfg(all(feature = "hotpath", not(feature = "hotpath-off")))]
 pub use lib_on::futures;
+#[cfg(all(
+    feature = "hotpath",
+    not(feature = "hotpath-off"),
+    feature = "tokio-runtime-metrics"
+))]
+pub use lib_on::runtime;
 #[cfg(all(feature = "hotpath", not(feature = "hotpath-off")))]
 pub use lib_on::streams;
+#[cfg(all(feature = "hotpath", not(feature = "hotpath-off")))]
+pub use lib_on::tasks;
 #[cfg(all(feature = "hotpath", not(feature = "hotpath-off"), feature = "threads"))]
 pub use lib_on::threads;
 
@@ -41,8 +52,9 @@ pub(crate) mod output;
 #[cfg(any(feature = "hotpath", feature = "ci", feature = "tui"))]
 pub use output::{
     ceil_char_boundary, floor_char_boundary, format_bytes, format_duration, shorten_function_name,
-    truncate_result, FunctionLogsJson, FunctionsDataJson, FunctionsJson, MetricType,
-    MetricsProvider, ProfilingMode, Reporter, MAX_RESULT_LEN,
+    truncate_result, CallTreeNode, DurationHistogram, FunctionLogsJson, FunctionsDataJson,
+    FunctionsJson, FunctionsTreeJson, MetricType, MetricsProvider, ProfilingMode, Reporter,
+    MAX_RESULT_LEN,
 };
 
 #[cfg(all(feature = "hotpath", not(feature = "hotpath-off")))]
@@ -50,18 +62,26 @@ pub(crate) mod output_on;
 
 #[cfg(all(feature = "hotpath", not(feature = "hotpath-off")))]
 pub(crate) mod metrics_server;
+#[cfg(all(feature = "hotpath", not(feature = "hotpath-off")))]
+pub(crate) mod openmetrics;
 
 #[cfg(all(feature = "hotpath-mcp", not(feature = "hotpath-off")))]
 pub(crate) mod mcp_server;
 
+#[cfg(any(feature = "ci", feature = "tui"))]
+pub mod diff;
+
 #[cfg(any(feature = "hotpath", feature = "ci", feature = "tui"))]
 pub mod json;
 #[cfg(any(feature = "hotpath", feature = "ci", feature = "tui"))]
-pub use json::Route;
+pub use json::{Route, StatsWindow};
 
 #[cfg(all(feature = "hotpath", not(feature = "hotpath-off")))]
 pub(crate) mod tid;
 
+mod heap_size;
+pub use heap_size::HeapSize;
+
 // When hotpath feature is not enabled or hotpath-off is enabled, use no-op stubs
 #[cfg(any(not(feature = "hotpath"), feature = "hotpath-off"))]
 #[doc(inline)]
@@ -1,4 +1,4 @@
-pub use hotpath_macros::{main, measure, measure_all, skip};
+pub use hotpath_macros::{main, measure, measure_all, skip, HeapSize};
 
 #[macro_export]
 macro_rules! measure_block {
@@ -57,6 +57,96 @@ macro_rules! channel {
     ($expr:expr, log = true, capacity = $capacity:expr, label = $label:expr) => {
         $expr
     };
+    ($expr:expr, proxy = false) => {
+        $expr
+    };
+    ($expr:expr, label = $label:expr, proxy = false) => {
+        $expr
+    };
+    ($expr:expr, proxy = false, label = $label:expr) => {
+        $expr
+    };
+    ($expr:expr, log = true, proxy = false) => {
+        $expr
+    };
+    ($expr:expr, proxy = false, log = true) => {
+        $expr
+    };
+    ($expr:expr, label = $label:expr, log = true, proxy = false) => {
+        $expr
+    };
+    ($expr:expr, label = $label:expr, proxy = false, log = true) => {
+        $expr
+    };
+    ($expr:expr, log = true, label = $label:expr, proxy = false) => {
+        $expr
+    };
+    ($expr:expr, log = true, proxy = false, label = $label:expr) => {
+        $expr
+    };
+    ($expr:expr, proxy = false, label = $label:expr, log = true) => {
+        $expr
+    };
+    ($expr:expr, proxy = false, log = true, label = $label:expr) => {
+        $expr
+    };
+    ($expr:expr, size = true) => {
+        $expr
+    };
+    ($expr:expr, size = true, label = $label:expr) => {
+        $expr
+    };
+    ($expr:expr, label = $label:expr, size = true) => {
+        $expr
+    };
+    ($expr:expr, size = true, capacity = $capacity:expr) => {
+        $expr
+    };
+    ($expr:expr, capacity = $capacity:expr, size = true) => {
+        $expr
+    };
+    ($expr:expr, size = true, label = $label:expr, capacity = $capacity:expr) => {
+        $expr
+    };
+    ($expr:expr, size = true, capacity = $capacity:expr, label = $label:expr) => {
+        $expr
+    };
+    ($expr:expr, label = $label:expr, size = true, capacity = $capacity:expr) => {
+        $expr
+    };
+    ($expr:expr, label = $label:expr, capacity = $capacity:expr, size = true) => {
+        $expr
+    };
+    ($expr:expr, capacity = $capacity:expr, size = true, label = $label:expr) => {
+        $expr
+    };
+    ($expr:expr, capacity = $capacity:expr, label = $label:expr, size = true) => {
+        $expr
+    };
+    ($expr:expr, size = true, proxy = false) => {
+        $expr
+    };
+    ($expr:expr, proxy = false, size = true) => {
+        $expr
+    };
+    ($expr:expr, size = true, proxy = false, label = $label:expr) => {
+        $expr
+    };
+    ($expr:expr, size = true, label = $label:expr, proxy = false) => {
+        $expr
+    };
+    ($expr:expr, proxy = false, size = true, label = $label:expr) => {
+        $expr
+    };
+    ($expr:expr, proxy = false, label = $label:expr, size = true) => {
+        $expr
+    };
+    ($expr:expr, label = $label:expr, size = true, proxy = false) => {
+        $expr
+    };
+    ($expr:expr, label = $label:expr, proxy = false, size = true) => {
+        $expr
+    };
 }
 
 #[macro_export]
@@ -76,6 +166,15 @@ macro_rules! stream {
     ($expr:expr, log = true, label = $label:expr) => {
         $expr
     };
+    ($expr:expr, size = true) => {
+        $expr
+    };
+    ($expr:expr, label = $label:expr, size = true) => {
+        $expr
+    };
+    ($expr:expr, size = true, label = $label:expr) => {
+        $expr
+    };
 }
 
 #[macro_export]
@@ -86,6 +185,50 @@ macro_rules! future {
     ($fut:expr, log = true) => {
         $fut
     };
+    ($fut:expr, label = $label:expr) => {
+        $fut
+    };
+    ($fut:expr, label = $label:expr, log = true) => {
+        $fut
+    };
+    ($fut:expr, log = true, label = $label:expr) => {
+        $fut
+    };
+    ($fut:expr, $(label = $label:expr,)? $(log = true,)? tags($($key:ident = $value:expr),* $(,)?)) => {{
+        // Keep variables only used in tags from being reported as unused
+        $(let _ = &$value;)*
+        $fut
+    }};
+}
+
+#[macro_export]
+macro_rules! spawn {
+    ($label:expr, $fut:expr, runtime = tokio) => {{
+        let _ = &$label;
+        ::tokio::spawn($fut)
+    }};
+    ($label:expr, $fut:expr, runtime = smol) => {{
+        let _ = &$label;
+        ::smol::spawn($fut)
+    }};
+    ($label:expr, $fut:expr) => {
+        $crate::spawn!($label, $fut, runtime = tokio)
+    };
+}
+
+#[macro_export]
+macro_rules! spawn_blocking {
+    ($label:expr, $f:expr, runtime = tokio) => {{
+        let _ = &$label;
+        ::tokio::task::spawn_blocking($f)
+    }};
+    ($label:expr, $f:expr, runtime = smol) => {{
+        let _ = &$label;
+        ::smol::unblock($f)
+    }};
+    ($label:expr, $f:expr) => {
+        $crate::spawn_blocking!($label, $f, runtime = tokio)
+    };
 }
 
 pub use crate::Format;
@@ -155,6 +298,14 @@ pub trait Reporter: Send + Sync {
         &self,
         metrics_provider: &dyn MetricsProvider<'_>,
     ) -> Result<(), Box<dyn std::error::Error>>;
+
+    fn report_periodic(
+        &self,
+        interval: &dyn MetricsProvider<'_>,
+        _cumulative: &dyn MetricsProvider<'_>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.report(interval)
+    }
 }
 
 pub trait MetricsProvider<'a> {}
@@ -178,6 +329,10 @@ impl FunctionsGuardBuilder {
         self
     }
 
+    pub fn report_every(self, _interval: std::time::Duration) -> Self {
+        self
+    }
+
     pub fn build(self) -> HotPath {
         HotPath
     }
@@ -194,6 +349,65 @@ impl FunctionsGuardBuilder {
 #[derive(Debug, Clone)]
 pub struct FunctionStats {}
 
+#[cfg(feature = "otlp")]
+pub struct OtlpReporterBuilder {}
+
+#[cfg(feature = "otlp")]
+impl Default for OtlpReporterBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "otlp")]
+impl OtlpReporterBuilder {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn endpoint(self, _endpoint: impl Into<String>) -> Self {
+        self
+    }
+
+    pub fn header(self, _key: impl Into<String>, _value: impl Into<String>) -> Self {
+        self
+    }
+
+    pub fn service_name(self, _service_name: impl Into<String>) -> Self {
+        self
+    }
+
+    pub fn interval(self, _interval: std::time::Duration) -> Self {
+        self
+    }
+
+    pub fn bounds(self, _bounds: &[u64]) -> Self {
+        self
+    }
+
+    pub fn build(self) -> OtlpReporter {
+        OtlpReporter {}
+    }
+}
+
+#[cfg(feature = "otlp")]
+pub struct OtlpReporter {}
+
+#[cfg(feature = "otlp")]
+impl OtlpReporter {
+    pub fn shutdown(&self) {}
+}
+
+#[cfg(feature = "otlp")]
+impl Reporter for OtlpReporter {
+    fn report(
+        &self,
+        _metrics_provider: &dyn MetricsProvider<'_>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        Ok(())
+    }
+}
+
 pub mod channels {
     use super::Format;
 
@@ -237,6 +451,17 @@ pub mod channels {
     impl Drop for ChannelsGuard {
         fn drop(&mut self) {}
     }
+
+    /// `channel!(expr, proxy = false)` returns the original channel ends when profiling is
+    /// disabled, so the wrapper types resolve to them.
+    #[cfg(feature = "tokio")]
+    pub mod direct {
+        pub mod tokio {
+            pub use tokio::sync::mpsc::{
+                Permit, Receiver, Sender, UnboundedReceiver, UnboundedSender,
+            };
+        }
+    }
 }
 
 pub mod streams {
@@ -1,24 +1,40 @@
 #[doc(hidden)]
 pub use cfg_if::cfg_if;
-pub use hotpath_macros::{future_fn, main, measure, measure_all, skip};
+pub use hotpath_macros::{future_fn, main, measure, measure_all, skip, HeapSize};
 
 pub mod channels;
 pub mod futures;
+#[cfg(feature = "tokio-runtime-metrics")]
+pub mod runtime;
 pub mod streams;
+pub mod tasks;
 #[cfg(feature = "threads")]
 pub mod threads;
 
 pub mod functions;
 
-pub use channels::{InstrumentChannel, InstrumentChannelLog};
+#[cfg(feature = "otlp")]
+pub mod otlp;
+
+pub(crate) mod durations;
+pub(crate) mod slow_polls;
+pub(crate) mod trace;
+pub(crate) mod window;
+
+pub use channels::{
+    InstrumentChannel, InstrumentChannelDirect, InstrumentChannelDirectLog,
+    InstrumentChannelDirectSize, InstrumentChannelLog, InstrumentChannelSize,
+};
 pub use futures::{InstrumentFuture, InstrumentFutureLog};
-pub use streams::{InstrumentStream, InstrumentStreamLog};
+pub use streams::{InstrumentStream, InstrumentStreamLog, InstrumentStreamSize};
 
 pub use functions::guard::{FunctionsGuard, FunctionsGuardBuilder};
 pub use functions::{
     measure_with_log, measure_with_log_async, FunctionStats, MeasurementGuard,
     MeasurementGuardWithLog,
 };
+#[cfg(feature = "otlp")]
+pub use otlp::{OtlpReporter, OtlpReporterBuilder};
 
 cfg_if::cfg_if! {
     if #[cfg(feature = "hotpath-alloc")] {
@@ -4,6 +4,7 @@ use crossbeam_channel::{unbounded, Sender as CbSender};
 use std::collections::{HashMap, VecDeque};
 use std::sync::atomic::AtomicU64;
 use std::sync::{Arc, OnceLock, RwLock};
+use std::time::Duration;
 
 #[cfg(target_os = "linux")]
 use quanta::Instant;
@@ -11,16 +12,34 @@ use quanta::Instant;
 #[cfg(not(target_os = "linux"))]
 use std::time::Instant;
 
+#[cfg(feature = "tokio")]
+pub mod direct;
 pub mod guard;
 pub use guard::{ChannelsGuard, ChannelsGuardBuilder};
 
+mod graph;
+mod high_water;
+mod message_size;
+mod queue_wait;
+mod send_blocked;
 mod wrapper;
 
+use crate::json::StatsWindow;
 pub use crate::json::{
-    ChannelLogs, ChannelState, ChannelType, ChannelsJson, LogEntry, SerializableChannelStats,
+    ChannelLogs, ChannelState, ChannelType, ChannelsGraphJson, ChannelsJson, DurationStats,
+    GraphEdge, GraphNode, GraphNodeKind, LogEntry, QueueWarning, SendBlockedStats,
+    SerializableChannelStats, ThreadSendBlocked,
 };
+use crate::lib_on::trace;
+use crate::lib_on::window::{lifetime_rate, RateHistory, WindowedCounter};
 use crate::metrics_server::METRICS_SERVER_PORT;
 use crate::output::truncate_result;
+use graph::{Endpoint, Endpoints};
+use high_water::HighWater;
+pub(crate) use high_water::QUEUE_WARN_THRESHOLD;
+use message_size::MessageSizes;
+use queue_wait::QueueWait;
+use send_blocked::SendBlocked;
 
 pub use crate::Format;
 
@@ -44,17 +63,51 @@ pub(crate) struct ChannelStats {
     pub(crate) sent_logs: VecDeque<LogEntry>,
     pub(crate) received_logs: VecDeque<LogEntry>,
     pub(crate) iter: u32,
+    sent_window: WindowedCounter,
+    received_window: WindowedCounter,
+    sent_history: RateHistory,
+    received_history: RateHistory,
+    pub(crate) subscribers: Option<u64>,
+    pub(crate) lag: Option<u64>,
+    /// Whether receives are counted when the forwarder hands a message over, rather than when
+    /// the consumer takes it
+    handoff: bool,
+    /// Messages the forwarder of a proxied channel last saw waiting in the original channel
+    waiting: u64,
+    queue_wait: QueueWait,
+    send_blocked: SendBlocked,
+    endpoints: Endpoints,
+    /// Deep message sizes, `None` unless instrumented with `size = true`
+    sizes: Option<MessageSizes>,
+    high_water: HighWater,
 }
 
 impl ChannelStats {
     pub fn queued(&self) -> u64 {
-        self.sent_count
-            .saturating_sub(self.received_count)
-            .saturating_sub(1)
+        if let ChannelType::Broadcast(_) = self.channel_type {
+            return self.lag.unwrap_or(0);
+        }
+
+        self.sent_count.saturating_sub(self.received_count) + self.waiting
     }
 
     pub fn queued_bytes(&self) -> u64 {
-        self.queued() * self.type_size as u64
+        match &self.sizes {
+            Some(sizes) => sizes.queued_bytes(self.queued()),
+            None => self.queued() * self.type_size as u64,
+        }
+    }
+
+    /// Sent and received messages per second over `window`, `now` being the elapsed time.
+    fn rates(&self, window: StatsWindow, now: Duration) -> (f64, f64) {
+        (
+            self.sent_window
+                .rate(window, now)
+                .unwrap_or_else(|| lifetime_rate(self.sent_count, now)),
+            self.received_window
+                .rate(window, now)
+                .unwrap_or_else(|| lifetime_rate(self.received_count, now)),
+        )
     }
 }
 
@@ -80,11 +133,32 @@ impl From<&ChannelStats> for SerializableChannelStats {
             type_size: channel_stats.type_size,
             queued_bytes: channel_stats.queued_bytes(),
             iter: channel_stats.iter,
+            sent_rate: 0.0,
+            received_rate: 0.0,
+            sent_rate_instant: 0,
+            received_rate_instant: 0,
+            sent_bytes_rate: 0.0,
+            received_bytes_rate: 0.0,
+            sent_history: Vec::new(),
+            received_history: Vec::new(),
+            subscribers: channel_stats.subscribers,
+            lag: channel_stats.lag,
+            queue_wait: channel_stats.queue_wait.stats(),
+            send_blocked: channel_stats.send_blocked.stats(),
+            sent_bytes: channel_stats.sizes.as_ref().map(MessageSizes::sent_bytes),
+            received_bytes: channel_stats
+                .sizes
+                .as_ref()
+                .map(MessageSizes::received_bytes),
+            max_queued: channel_stats.high_water.max_queued(),
+            max_queued_at_ns: channel_stats.high_water.max_queued_at_ns(),
+            queue_warnings: channel_stats.high_water.warnings(),
         }
     }
 }
 
 impl ChannelStats {
+    #[allow(clippy::too_many_arguments)]
     fn new(
         id: u64,
         source: &'static str,
@@ -93,6 +167,7 @@ impl ChannelStats {
         type_name: &'static str,
         type_size: usize,
         iter: u32,
+        handoff: bool,
     ) -> Self {
         Self {
             id,
@@ -107,6 +182,19 @@ impl ChannelStats {
             sent_logs: VecDeque::new(),
             received_logs: VecDeque::new(),
             iter,
+            sent_window: WindowedCounter::new(),
+            received_window: WindowedCounter::new(),
+            sent_history: RateHistory::default(),
+            received_history: RateHistory::default(),
+            subscribers: None,
+            lag: None,
+            handoff,
+            waiting: 0,
+            queue_wait: QueueWait::new(),
+            send_blocked: SendBlocked::new(),
+            endpoints: Endpoints::default(),
+            sizes: None,
+            high_water: HighWater::default(),
         }
     }
 
@@ -119,7 +207,8 @@ impl ChannelStats {
         let is_full = match self.channel_type {
             ChannelType::Bounded(cap) => queued >= cap as u64,
             ChannelType::Oneshot => queued >= 1,
-            ChannelType::Unbounded => false,
+            ChannelType::Broadcast(Some(cap)) => queued >= cap as u64,
+            ChannelType::Unbounded | ChannelType::Broadcast(None) | ChannelType::Watch => false,
         };
 
         if is_full {
@@ -128,6 +217,23 @@ impl ChannelStats {
             self.state = ChannelState::Active;
         }
     }
+
+    /// Tracks the high-water mark and reports the channel on stderr if its queue just reached
+    /// `HOTPATH_CHANNEL_QUEUE_WARN`.
+    fn record_queued(&mut self, timestamp_ns: u64) {
+        let warning = self
+            .high_water
+            .record(self.queued(), timestamp_ns, *QUEUE_WARN_THRESHOLD);
+        if let Some(warning) = warning {
+            eprintln!(
+                "[hotpath] Channel '{}' at {} has {} queued messages (HOTPATH_CHANNEL_QUEUE_WARN={})",
+                resolve_label(self.source, self.label.as_deref(), Some(self.iter)),
+                self.source,
+                warning.queued,
+                warning.threshold,
+            );
+        }
+    }
 }
 
 /// Events sent to the background channel statistics collection thread.
@@ -140,15 +246,57 @@ pub(crate) enum ChannelEvent {
         channel_type: ChannelType,
         type_name: &'static str,
         type_size: usize,
+        handoff: bool,
+        /// Whether sends report the deep size of their message
+        sized: bool,
     },
+    /// `endpoint` is only known to `proxy = false` wrappers, which see the caller
     MessageSent {
         id: u64,
         log: Option<String>,
+        size: Option<u64>,
         timestamp: Instant,
+        endpoint: Option<Endpoint>,
     },
     MessageReceived {
         id: u64,
         timestamp: Instant,
+        endpoint: Option<Endpoint>,
+    },
+    /// Messages waiting in the original channel of a proxied channel, sampled by its forwarder
+    #[cfg_attr(
+        not(any(
+            feature = "crossbeam",
+            feature = "flume",
+            feature = "async-channel",
+            feature = "tokio"
+        )),
+        allow(dead_code)
+    )]
+    Waiting {
+        id: u64,
+        waiting: u64,
+        timestamp: Instant,
+    },
+    /// Periodic sample of a broadcast channel's sender
+    #[cfg_attr(not(feature = "tokio"), allow(dead_code))]
+    BroadcastState {
+        id: u64,
+        subscribers: u64,
+        lag: u64,
+    },
+    /// Periodic sample of a watch channel, reported by its forwarder
+    #[cfg_attr(not(feature = "tokio"), allow(dead_code))]
+    WatchState {
+        id: u64,
+        subscribers: u64,
+    },
+    /// A send found the channel full. `tid` is only known to `proxy = false` wrappers, which
+    /// see the caller
+    SendBlocked {
+        id: u64,
+        tid: Option<u64>,
+        blocked_ns: u64,
     },
     Closed {
         id: u64,
@@ -201,27 +349,64 @@ pub(crate) fn init_channels_state() -> &'static ChannelStatsState {
                             channel_type,
                             type_name,
                             type_size,
+                            handoff,
+                            sized,
                         } => {
                             // Count existing items with the same source location
                             let iter = stats.values().filter(|s| s.source == source).count() as u32;
 
-                            stats.insert(
-                                id,
-                                ChannelStats::new(
+                            if trace::is_enabled() {
+                                trace::record_channel_created(
                                     id,
-                                    source,
-                                    display_label,
-                                    channel_type,
-                                    type_name,
-                                    type_size,
-                                    iter,
-                                ),
+                                    resolve_label(source, display_label.as_deref(), Some(iter)),
+                                );
+                            }
+
+                            let mut channel_stats = ChannelStats::new(
+                                id,
+                                source,
+                                display_label,
+                                channel_type,
+                                type_name,
+                                type_size,
+                                iter,
+                                handoff,
                             );
+                            if sized {
+                                channel_stats.sizes = Some(MessageSizes::new());
+                            }
+                            stats.insert(id, channel_stats);
                         }
-                        ChannelEvent::MessageSent { id, log, timestamp } => {
+                        ChannelEvent::MessageSent {
+                            id,
+                            log,
+                            size,
+                            timestamp,
+                            endpoint,
+                        } => {
+                            trace::record_channel_event(id, "send", timestamp);
                             if let Some(channel_stats) = stats.get_mut(&id) {
+                                let timestamp_ns = timestamp_nanos(timestamp);
                                 channel_stats.sent_count += 1;
+                                // Pulled by the forwarder, so no longer waiting behind the proxy
+                                channel_stats.waiting = channel_stats.waiting.saturating_sub(1);
+                                if let Some(endpoint) = endpoint {
+                                    channel_stats.endpoints.sent(endpoint);
+                                }
                                 channel_stats.update_state();
+                                channel_stats.record_queued(timestamp_ns);
+                                let elapsed = Duration::from_nanos(timestamp_ns);
+                                channel_stats.sent_window.record(elapsed);
+                                channel_stats.sent_history.record(elapsed);
+                                if let (Some(sizes), Some(size)) = (&mut channel_stats.sizes, size)
+                                {
+                                    sizes.sent(size, elapsed);
+                                }
+                                // Handoff channels count receives before the consumer takes the
+                                // message, which would only time the forwarder
+                                if !channel_stats.handoff {
+                                    channel_stats.queue_wait.sent(timestamp_ns);
+                                }
 
                                 let limit = get_log_limit();
                                 if channel_stats.sent_logs.len() >= limit {
@@ -229,16 +414,35 @@ pub(crate) fn init_channels_state() -> &'static ChannelStatsState {
                                 }
                                 channel_stats.sent_logs.push_back(LogEntry::new(
                                     channel_stats.sent_count,
-                                    timestamp_nanos(timestamp),
+                                    timestamp_ns,
                                     log.map(truncate_result),
                                     None,
                                 ));
                             }
                         }
-                        ChannelEvent::MessageReceived { id, timestamp } => {
+                        ChannelEvent::MessageReceived {
+                            id,
+                            timestamp,
+                            endpoint,
+                        } => {
+                            trace::record_channel_event(id, "recv", timestamp);
                             if let Some(channel_stats) = stats.get_mut(&id) {
+                                let timestamp_ns = timestamp_nanos(timestamp);
                                 channel_stats.received_count += 1;
+                                if let Some(endpoint) = endpoint {
+                                    channel_stats.endpoints.received(endpoint);
+                                }
                                 channel_stats.update_state();
+                                channel_stats.record_queued(timestamp_ns);
+                                let elapsed = Duration::from_nanos(timestamp_ns);
+                                channel_stats.received_window.record(elapsed);
+                                channel_stats.received_history.record(elapsed);
+                                if let Some(sizes) = &mut channel_stats.sizes {
+                                    sizes.received(elapsed);
+                                }
+                                if !channel_stats.handoff {
+                                    channel_stats.queue_wait.received(timestamp_ns);
+                                }
 
                                 let limit = get_log_limit();
                                 if channel_stats.received_logs.len() >= limit {
@@ -246,12 +450,49 @@ pub(crate) fn init_channels_state() -> &'static ChannelStatsState {
                                 }
                                 channel_stats.received_logs.push_back(LogEntry::new(
                                     channel_stats.received_count,
-                                    timestamp_nanos(timestamp),
+                                    timestamp_ns,
                                     None,
                                     None,
                                 ));
                             }
                         }
+                        ChannelEvent::Waiting {
+                            id,
+                            waiting,
+                            timestamp,
+                        } => {
+                            if let Some(channel_stats) = stats.get_mut(&id) {
+                                channel_stats.waiting = waiting;
+                                channel_stats.update_state();
+                                channel_stats.record_queued(timestamp_nanos(timestamp));
+                            }
+                        }
+                        ChannelEvent::BroadcastState {
+                            id,
+                            subscribers,
+                            lag,
+                        } => {
+                            if let Some(channel_stats) = stats.get_mut(&id) {
+                                channel_stats.subscribers = Some(subscribers);
+                                channel_stats.lag = Some(lag);
+                                channel_stats.update_state();
+                                channel_stats.record_queued(timestamp_nanos(Instant::now()));
+                            }
+                        }
+                        ChannelEvent::WatchState { id, subscribers } => {
+                            if let Some(channel_stats) = stats.get_mut(&id) {
+                                channel_stats.subscribers = Some(subscribers);
+                            }
+                        }
+                        ChannelEvent::SendBlocked {
+                            id,
+                            tid,
+                            blocked_ns,
+                        } => {
+                            if let Some(channel_stats) = stats.get_mut(&id) {
+                                channel_stats.send_blocked.record(tid, blocked_ns);
+                            }
+                        }
                         ChannelEvent::Closed { id } => {
                             if let Some(channel_stats) = stats.get_mut(&id) {
                                 channel_stats.state = ChannelState::Closed;
@@ -331,6 +572,49 @@ pub trait InstrumentChannelLog {
     ) -> Self::Output;
 }
 
+/// Trait for instrumenting channels with deep message sizes.
+///
+/// This trait is not intended for direct use. Use the `channel!` macro with `size = true` instead.
+#[doc(hidden)]
+pub trait InstrumentChannelSize {
+    type Output;
+    fn instrument_size(
+        self,
+        source: &'static str,
+        label: Option<String>,
+        capacity: Option<usize>,
+    ) -> Self::Output;
+}
+
+/// Trait for instrumenting channels without a forwarding proxy.
+///
+/// This trait is not intended for direct use. Use the `channel!` macro with `proxy = false` instead.
+#[doc(hidden)]
+pub trait InstrumentChannelDirect {
+    type Output;
+    fn instrument_direct(self, source: &'static str, label: Option<String>) -> Self::Output;
+}
+
+/// Trait for instrumenting channels without a forwarding proxy, with message logging.
+///
+/// This trait is not intended for direct use. Use the `channel!` macro with `proxy = false`
+/// and `log = true` instead.
+#[doc(hidden)]
+pub trait InstrumentChannelDirectLog {
+    type Output;
+    fn instrument_direct_log(self, source: &'static str, label: Option<String>) -> Self::Output;
+}
+
+/// Trait for instrumenting channels without a forwarding proxy, with deep message sizes.
+///
+/// This trait is not intended for direct use. Use the `channel!` macro with `proxy = false`
+/// and `size = true` instead.
+#[doc(hidden)]
+pub trait InstrumentChannelDirectSize {
+    type Output;
+    fn instrument_direct_size(self, source: &'static str, label: Option<String>) -> Self::Output;
+}
+
 cfg_if::cfg_if! {
     if #[cfg(any(feature = "tokio", feature = "futures"))] {
         use std::sync::LazyLock;
@@ -510,6 +794,263 @@ macro_rules! channel {
             Some($capacity),
         )
     }};
+
+    // Variants with proxy = false
+    ($expr:expr, proxy = false) => {{
+        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+        $crate::InstrumentChannelDirect::instrument_direct($expr, CHANNEL_ID, None)
+    }};
+
+    ($expr:expr, label = $label:expr, proxy = false) => {{
+        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+        $crate::InstrumentChannelDirect::instrument_direct(
+            $expr,
+            CHANNEL_ID,
+            Some($label.to_string()),
+        )
+    }};
+
+    ($expr:expr, proxy = false, label = $label:expr) => {{
+        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+        $crate::InstrumentChannelDirect::instrument_direct(
+            $expr,
+            CHANNEL_ID,
+            Some($label.to_string()),
+        )
+    }};
+
+    ($expr:expr, log = true, proxy = false) => {{
+        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+        $crate::InstrumentChannelDirectLog::instrument_direct_log($expr, CHANNEL_ID, None)
+    }};
+
+    ($expr:expr, proxy = false, log = true) => {{
+        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+        $crate::InstrumentChannelDirectLog::instrument_direct_log($expr, CHANNEL_ID, None)
+    }};
+
+    ($expr:expr, label = $label:expr, log = true, proxy = false) => {{
+        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+        $crate::InstrumentChannelDirectLog::instrument_direct_log(
+            $expr,
+            CHANNEL_ID,
+            Some($label.to_string()),
+        )
+    }};
+
+    ($expr:expr, label = $label:expr, proxy = false, log = true) => {{
+        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+        $crate::InstrumentChannelDirectLog::instrument_direct_log(
+            $expr,
+            CHANNEL_ID,
+            Some($label.to_string()),
+        )
+    }};
+
+    ($expr:expr, log = true, label = $label:expr, proxy = false) => {{
+        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+        $crate::InstrumentChannelDirectLog::instrument_direct_log(
+            $expr,
+            CHANNEL_ID,
+            Some($label.to_string()),
+        )
+    }};
+
+    ($expr:expr, log = true, proxy = false, label = $label:expr) => {{
+        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+        $crate::InstrumentChannelDirectLog::instrument_direct_log(
+            $expr,
+            CHANNEL_ID,
+            Some($label.to_string()),
+        )
+    }};
+
+    ($expr:expr, proxy = false, label = $label:expr, log = true) => {{
+        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+        $crate::InstrumentChannelDirectLog::instrument_direct_log(
+            $expr,
+            CHANNEL_ID,
+            Some($label.to_string()),
+        )
+    }};
+
+    ($expr:expr, proxy = false, log = true, label = $label:expr) => {{
+        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+        $crate::InstrumentChannelDirectLog::instrument_direct_log(
+            $expr,
+            CHANNEL_ID,
+            Some($label.to_string()),
+        )
+    }};
+
+    // Variants with size = true
+    ($expr:expr, size = true) => {{
+        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+        $crate::InstrumentChannelSize::instrument_size($expr, CHANNEL_ID, None, None)
+    }};
+
+    ($expr:expr, size = true, label = $label:expr) => {{
+        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+        $crate::InstrumentChannelSize::instrument_size(
+            $expr,
+            CHANNEL_ID,
+            Some($label.to_string()),
+            None,
+        )
+    }};
+
+    ($expr:expr, label = $label:expr, size = true) => {{
+        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+        $crate::InstrumentChannelSize::instrument_size(
+            $expr,
+            CHANNEL_ID,
+            Some($label.to_string()),
+            None,
+        )
+    }};
+
+    ($expr:expr, size = true, capacity = $capacity:expr) => {{
+        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+        const _: usize = $capacity;
+        $crate::InstrumentChannelSize::instrument_size($expr, CHANNEL_ID, None, Some($capacity))
+    }};
+
+    ($expr:expr, capacity = $capacity:expr, size = true) => {{
+        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+        const _: usize = $capacity;
+        $crate::InstrumentChannelSize::instrument_size($expr, CHANNEL_ID, None, Some($capacity))
+    }};
+
+    ($expr:expr, size = true, label = $label:expr, capacity = $capacity:expr) => {{
+        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+        const _: usize = $capacity;
+        $crate::InstrumentChannelSize::instrument_size(
+            $expr,
+            CHANNEL_ID,
+            Some($label.to_string()),
+            Some($capacity),
+        )
+    }};
+
+    ($expr:expr, size = true, capacity = $capacity:expr, label = $label:expr) => {{
+        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+        const _: usize = $capacity;
+        $crate::InstrumentChannelSize::instrument_size(
+            $expr,
+            CHANNEL_ID,
+            Some($label.to_string()),
+            Some($capacity),
+        )
+    }};
+
+    ($expr:expr, label = $label:expr, size = true, capacity = $capacity:expr) => {{
+        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+        const _: usize = $capacity;
+        $crate::InstrumentChannelSize::instrument_size(
+            $expr,
+            CHANNEL_ID,
+            Some($label.to_string()),
+            Some($capacity),
+        )
+    }};
+
+    ($expr:expr, label = $label:expr, capacity = $capacity:expr, size = true) => {{
+        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+        const _: usize = $capacity;
+        $crate::InstrumentChannelSize::instrument_size(
+            $expr,
+            CHANNEL_ID,
+            Some($label.to_string()),
+            Some($capacity),
+        )
+    }};
+
+    ($expr:expr, capacity = $capacity:expr, size = true, label = $label:expr) => {{
+        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+        const _: usize = $capacity;
+        $crate::InstrumentChannelSize::instrument_size(
+            $expr,
+            CHANNEL_ID,
+            Some($label.to_string()),
+            Some($capacity),
+        )
+    }};
+
+    ($expr:expr, capacity = $capacity:expr, label = $label:expr, size = true) => {{
+        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+        const _: usize = $capacity;
+        $crate::InstrumentChannelSize::instrument_size(
+            $expr,
+            CHANNEL_ID,
+            Some($label.to_string()),
+            Some($capacity),
+        )
+    }};
+
+    // Variants with proxy = false and size = true
+    ($expr:expr, size = true, proxy = false) => {{
+        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+        $crate::InstrumentChannelDirectSize::instrument_direct_size($expr, CHANNEL_ID, None)
+    }};
+
+    ($expr:expr, proxy = false, size = true) => {{
+        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+        $crate::InstrumentChannelDirectSize::instrument_direct_size($expr, CHANNEL_ID, None)
+    }};
+
+    ($expr:expr, size = true, proxy = false, label = $label:expr) => {{
+        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+        $crate::InstrumentChannelDirectSize::instrument_direct_size(
+            $expr,
+            CHANNEL_ID,
+            Some($label.to_string()),
+        )
+    }};
+
+    ($expr:expr, size = true, label = $label:expr, proxy = false) => {{
+        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+        $crate::InstrumentChannelDirectSize::instrument_direct_size(
+            $expr,
+            CHANNEL_ID,
+            Some($label.to_string()),
+        )
+    }};
+
+    ($expr:expr, proxy = false, size = true, label = $label:expr) => {{
+        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+        $crate::InstrumentChannelDirectSize::instrument_direct_size(
+            $expr,
+            CHANNEL_ID,
+            Some($label.to_string()),
+        )
+    }};
+
+    ($expr:expr, proxy = false, label = $label:expr, size = true) => {{
+        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+        $crate::InstrumentChannelDirectSize::instrument_direct_size(
+            $expr,
+            CHANNEL_ID,
+            Some($label.to_string()),
+        )
+    }};
+
+    ($expr:expr, label = $label:expr, size = true, proxy = false) => {{
+        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+        $crate::InstrumentChannelDirectSize::instrument_direct_size(
+            $expr,
+            CHANNEL_ID,
+            Some($label.to_string()),
+        )
+    }};
+
+    ($expr:expr, label = $label:expr, proxy = false, size = true) => {{
+        const CHANNEL_ID: &'static str = concat!(file!(), ":", line!());
+        $crate::InstrumentChannelDirectSize::instrument_direct_size(
+            $expr,
+            CHANNEL_ID,
+            Some($label.to_string()),
+        )
+    }};
 }
 
 fn get_all_channel_stats() -> HashMap<u64, ChannelStats> {
@@ -546,23 +1087,55 @@ pub(crate) fn get_sorted_channel_stats() -> Vec<ChannelStats> {
 }
 
 pub fn get_channels_json() -> ChannelsJson {
-    let channels = get_sorted_channel_stats()
-        .iter()
-        .map(SerializableChannelStats::from)
-        .collect();
+    get_channels_json_for(StatsWindow::Lifetime)
+}
 
-    let current_elapsed_ns = START_TIME
+/// Channel statistics with throughput rates computed over `window`. Instantaneous rates and
+/// histories always cover the last complete seconds.
+pub(crate) fn get_channels_json_for(window: StatsWindow) -> ChannelsJson {
+    let current_elapsed = START_TIME
         .get()
         .expect("START_TIME must be initialized")
-        .elapsed()
-        .as_nanos() as u64;
+        .elapsed();
+    let current_elapsed_ns = current_elapsed.as_nanos() as u64;
+
+    let channels = get_sorted_channel_stats()
+        .iter()
+        .map(|channel_stats| {
+            let (sent_rate, received_rate) = channel_stats.rates(window, current_elapsed);
+            let sent_history = channel_stats.sent_history.counts(current_elapsed);
+            let received_history = channel_stats.received_history.counts(current_elapsed);
+            let type_size = channel_stats.type_size as f64;
+            let (sent_bytes_rate, received_bytes_rate) = match &channel_stats.sizes {
+                Some(sizes) => sizes.rates(window, current_elapsed),
+                None => (sent_rate * type_size, received_rate * type_size),
+            };
+            SerializableChannelStats {
+                sent_rate,
+                received_rate,
+                sent_rate_instant: sent_history.last().copied().unwrap_or(0),
+                received_rate_instant: received_history.last().copied().unwrap_or(0),
+                sent_bytes_rate,
+                received_bytes_rate,
+                sent_history,
+                received_history,
+                ..SerializableChannelStats::from(channel_stats)
+            }
+        })
+        .collect();
 
     ChannelsJson {
         current_elapsed_ns,
         channels,
+        queue_warn_threshold: *QUEUE_WARN_THRESHOLD,
     }
 }
 
+/// Producers and consumers of every channel, as reported by `proxy = false` wrappers.
+pub fn get_channels_graph() -> ChannelsGraphJson {
+    graph::build_graph(&get_sorted_channel_stats())
+}
+
 pub fn get_channel_logs(channel_id: &str) -> Option<ChannelLogs> {
     let id = channel_id.parse::<u64>().ok()?;
     let stats = get_all_channel_stats();
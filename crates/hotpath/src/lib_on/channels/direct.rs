@@ -0,0 +1,97 @@
+//! Channel wrappers that count messages at their send and recv call sites.
+//!
+//! Created with `channel!(expr, proxy = false)`. Unlike the default instrumentation there is
+//! no forwarding thread or task and no proxy channel: the wrappers call straight into the
+//! original channel, so capacity and backpressure are unchanged. The trade-off is that the
+//! returned ends are wrapper types instead of the original ones.
+
+pub mod tokio;
+
+use crossbeam_channel::Sender as CbSender;
+
+use crate::channels::graph::Endpoint;
+use crate::channels::wrapper::common::{Instant, RegisteredChannel};
+use crate::channels::ChannelEvent;
+use crate::tid::current_tid;
+
+/// Reports events for one side of a direct channel, and closes it on drop.
+#[derive(Debug)]
+struct ChannelHandle {
+    id: u64,
+    stats_tx: CbSender<ChannelEvent>,
+}
+
+impl ChannelHandle {
+    /// Handles for the send and receive sides of a registered channel.
+    fn pair(registered: RegisteredChannel) -> (Self, Self) {
+        let RegisteredChannel { id, stats_tx } = registered;
+        (
+            Self {
+                id,
+                stats_tx: stats_tx.clone(),
+            },
+            Self { id, stats_tx },
+        )
+    }
+
+    fn sent(&self, log: Option<String>, size: Option<u64>) {
+        let _ = self.stats_tx.send(ChannelEvent::MessageSent {
+            id: self.id,
+            log,
+            size,
+            timestamp: Instant::now(),
+            endpoint: Some(Endpoint::current()),
+        });
+    }
+
+    /// Reports a send that found the channel full. Rejected non-blocking sends were blocked
+    /// for zero nanoseconds.
+    fn send_blocked(&self, tid: u64, blocked_ns: u64) {
+        let _ = self.stats_tx.send(ChannelEvent::SendBlocked {
+            id: self.id,
+            tid: Some(tid),
+            blocked_ns,
+        });
+    }
+
+    fn received(&self) {
+        let _ = self.stats_tx.send(ChannelEvent::MessageReceived {
+            id: self.id,
+            timestamp: Instant::now(),
+            endpoint: Some(Endpoint::current()),
+        });
+    }
+}
+
+impl Drop for ChannelHandle {
+    fn drop(&mut self) {
+        let _ = self.stats_tx.send(ChannelEvent::Closed { id: self.id });
+    }
+}
+
+/// Start of a send that found the channel full and has to wait for capacity.
+struct FullSince {
+    tid: u64,
+    started: Instant,
+}
+
+impl FullSince {
+    fn now() -> Self {
+        Self {
+            tid: current_tid(),
+            started: Instant::now(),
+        }
+    }
+
+    fn report(self, handle: &ChannelHandle) {
+        let blocked = Instant::now().duration_since(self.started);
+        handle.send_blocked(self.tid, blocked.as_nanos() as u64);
+    }
+}
+
+/// Formats a message for the channel logs, set when instrumented with `log = true`.
+type LogFn<T> = Option<fn(&T) -> String>;
+
+fn debug_log<T: std::fmt::Debug>(msg: &T) -> String {
+    format!("{:?}", msg)
+}
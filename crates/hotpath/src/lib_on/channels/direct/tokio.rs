@@ -0,0 +1,507 @@
+//! Direct wrappers for `tokio::sync::mpsc` channels.
+//!
+//! The methods mirror the Tokio API, so code using them compiles unchanged when `hotpath` is
+//! disabled and these types become aliases of the Tokio ones.
+
+use std::fmt;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::{SendError, SendTimeoutError, TryRecvError, TrySendError};
+
+use super::{debug_log, ChannelHandle, FullSince, LogFn};
+use crate::channels::wrapper::common::{register_channel, SizeFn};
+use crate::channels::{
+    ChannelType, InstrumentChannelDirect, InstrumentChannelDirectLog, InstrumentChannelDirectSize,
+};
+use crate::heap_size::{deep_size, HeapSize};
+use crate::tid::current_tid;
+
+/// Instrumented [`mpsc::Sender`].
+pub struct Sender<T> {
+    inner: mpsc::Sender<T>,
+    handle: Arc<ChannelHandle>,
+    log: LogFn<T>,
+    size: SizeFn<T>,
+}
+
+/// Instrumented [`mpsc::Receiver`].
+pub struct Receiver<T> {
+    inner: mpsc::Receiver<T>,
+    handle: ChannelHandle,
+}
+
+/// Instrumented [`mpsc::Permit`], counts the message when it is sent.
+pub struct Permit<'a, T> {
+    inner: mpsc::Permit<'a, T>,
+    sender: &'a Sender<T>,
+}
+
+/// Instrumented [`mpsc::UnboundedSender`].
+pub struct UnboundedSender<T> {
+    inner: mpsc::UnboundedSender<T>,
+    handle: Arc<ChannelHandle>,
+    log: LogFn<T>,
+    size: SizeFn<T>,
+}
+
+/// Instrumented [`mpsc::UnboundedReceiver`].
+pub struct UnboundedReceiver<T> {
+    inner: mpsc::UnboundedReceiver<T>,
+    handle: ChannelHandle,
+}
+
+fn wrap_channel<T>(
+    inner: (mpsc::Sender<T>, mpsc::Receiver<T>),
+    source: &'static str,
+    label: Option<String>,
+    log: LogFn<T>,
+    size: SizeFn<T>,
+) -> (Sender<T>, Receiver<T>) {
+    let (inner_tx, inner_rx) = inner;
+    let channel_type = ChannelType::Bounded(inner_tx.max_capacity());
+    let (tx_handle, rx_handle) = ChannelHandle::pair(register_channel::<T>(
+        source,
+        label,
+        channel_type,
+        size.is_some(),
+    ));
+
+    (
+        Sender {
+            inner: inner_tx,
+            handle: Arc::new(tx_handle),
+            log,
+            size,
+        },
+        Receiver {
+            inner: inner_rx,
+            handle: rx_handle,
+        },
+    )
+}
+
+fn wrap_unbounded<T>(
+    inner: (mpsc::UnboundedSender<T>, mpsc::UnboundedReceiver<T>),
+    source: &'static str,
+    label: Option<String>,
+    log: LogFn<T>,
+    size: SizeFn<T>,
+) -> (UnboundedSender<T>, UnboundedReceiver<T>) {
+    let (inner_tx, inner_rx) = inner;
+    let (tx_handle, rx_handle) = ChannelHandle::pair(register_channel::<T>(
+        source,
+        label,
+        ChannelType::Unbounded,
+        size.is_some(),
+    ));
+
+    (
+        UnboundedSender {
+            inner: inner_tx,
+            handle: Arc::new(tx_handle),
+            log,
+            size,
+        },
+        UnboundedReceiver {
+            inner: inner_rx,
+            handle: rx_handle,
+        },
+    )
+}
+
+impl<T> Sender<T> {
+    fn log_message(&self, value: &T) -> Option<String> {
+        self.log.map(|log| log(value))
+    }
+
+    fn message_size(&self, value: &T) -> Option<u64> {
+        self.size.map(|size| size(value))
+    }
+
+    /// Starts timing a send that will have to wait for capacity.
+    fn full_since(&self) -> Option<FullSince> {
+        (self.inner.capacity() == 0).then(FullSince::now)
+    }
+
+    fn report_blocked(&self, full_since: Option<FullSince>) {
+        if let Some(full_since) = full_since {
+            full_since.report(&self.handle);
+        }
+    }
+
+    fn report_rejected<E>(&self, err: &TrySendError<E>) {
+        if let TrySendError::Full(_) = err {
+            self.handle.send_blocked(current_tid(), 0);
+        }
+    }
+
+    /// See [`mpsc::Sender::send`].
+    pub async fn send(&self, value: T) -> Result<(), SendError<T>> {
+        let log = self.log_message(&value);
+        let size = self.message_size(&value);
+        let full_since = self.full_since();
+        let result = self.inner.send(value).await;
+        self.report_blocked(full_since);
+        result?;
+        self.handle.sent(log, size);
+        Ok(())
+    }
+
+    /// See [`mpsc::Sender::try_send`].
+    pub fn try_send(&self, message: T) -> Result<(), TrySendError<T>> {
+        let log = self.log_message(&message);
+        let size = self.message_size(&message);
+        if let Err(err) = self.inner.try_send(message) {
+            self.report_rejected(&err);
+            return Err(err);
+        }
+        self.handle.sent(log, size);
+        Ok(())
+    }
+
+    /// See [`mpsc::Sender::send_timeout`].
+    pub async fn send_timeout(
+        &self,
+        value: T,
+        timeout: Duration,
+    ) -> Result<(), SendTimeoutError<T>> {
+        let log = self.log_message(&value);
+        let size = self.message_size(&value);
+        let full_since = self.full_since();
+        let result = self.inner.send_timeout(value, timeout).await;
+        self.report_blocked(full_since);
+        result?;
+        self.handle.sent(log, size);
+        Ok(())
+    }
+
+    /// See [`mpsc::Sender::blocking_send`].
+    pub fn blocking_send(&self, value: T) -> Result<(), SendError<T>> {
+        let log = self.log_message(&value);
+        let size = self.message_size(&value);
+        let full_since = self.full_since();
+        let result = self.inner.blocking_send(value);
+        self.report_blocked(full_since);
+        result?;
+        self.handle.sent(log, size);
+        Ok(())
+    }
+
+    /// See [`mpsc::Sender::reserve`].
+    pub async fn reserve(&self) -> Result<Permit<'_, T>, SendError<()>> {
+        let full_since = self.full_since();
+        let result = self.inner.reserve().await;
+        self.report_blocked(full_since);
+        let inner = result?;
+        Ok(Permit {
+            inner,
+            sender: self,
+        })
+    }
+
+    /// See [`mpsc::Sender::try_reserve`].
+    pub fn try_reserve(&self) -> Result<Permit<'_, T>, TrySendError<()>> {
+        let inner = self
+            .inner
+            .try_reserve()
+            .inspect_err(|err| self.report_rejected(err))?;
+        Ok(Permit {
+            inner,
+            sender: self,
+        })
+    }
+
+    /// See [`mpsc::Sender::closed`].
+    pub async fn closed(&self) {
+        self.inner.closed().await
+    }
+
+    /// See [`mpsc::Sender::is_closed`].
+    pub fn is_closed(&self) -> bool {
+        self.inner.is_closed()
+    }
+
+    /// See [`mpsc::Sender::same_channel`].
+    pub fn same_channel(&self, other: &Self) -> bool {
+        self.inner.same_channel(&other.inner)
+    }
+
+    /// See [`mpsc::Sender::capacity`].
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity()
+    }
+
+    /// See [`mpsc::Sender::max_capacity`].
+    pub fn max_capacity(&self) -> usize {
+        self.inner.max_capacity()
+    }
+}
+
+impl<T> Permit<'_, T> {
+    /// See [`mpsc::Permit::send`].
+    pub fn send(self, value: T) {
+        let log = self.sender.log_message(&value);
+        let size = self.sender.message_size(&value);
+        self.inner.send(value);
+        self.sender.handle.sent(log, size);
+    }
+}
+
+impl<T> Receiver<T> {
+    /// See [`mpsc::Receiver::recv`].
+    pub async fn recv(&mut self) -> Option<T> {
+        let msg = self.inner.recv().await?;
+        self.handle.received();
+        Some(msg)
+    }
+
+    /// See [`mpsc::Receiver::recv_many`].
+    pub async fn recv_many(&mut self, buffer: &mut Vec<T>, limit: usize) -> usize {
+        let count = self.inner.recv_many(buffer, limit).await;
+        for _ in 0..count {
+            self.handle.received();
+        }
+        count
+    }
+
+    /// See [`mpsc::Receiver::try_recv`].
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        let msg = self.inner.try_recv()?;
+        self.handle.received();
+        Ok(msg)
+    }
+
+    /// See [`mpsc::Receiver::blocking_recv`].
+    pub fn blocking_recv(&mut self) -> Option<T> {
+        let msg = self.inner.blocking_recv()?;
+        self.handle.received();
+        Some(msg)
+    }
+
+    /// See [`mpsc::Receiver::poll_recv`].
+    pub fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let poll = self.inner.poll_recv(cx);
+        if let Poll::Ready(Some(_)) = poll {
+            self.handle.received();
+        }
+        poll
+    }
+
+    /// See [`mpsc::Receiver::close`].
+    pub fn close(&mut self) {
+        self.inner.close()
+    }
+
+    /// See [`mpsc::Receiver::is_closed`].
+    pub fn is_closed(&self) -> bool {
+        self.inner.is_closed()
+    }
+
+    /// See [`mpsc::Receiver::is_empty`].
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// See [`mpsc::Receiver::len`].
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// See [`mpsc::Receiver::capacity`].
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity()
+    }
+
+    /// See [`mpsc::Receiver::max_capacity`].
+    pub fn max_capacity(&self) -> usize {
+        self.inner.max_capacity()
+    }
+}
+
+impl<T> UnboundedSender<T> {
+    /// See [`mpsc::UnboundedSender::send`].
+    pub fn send(&self, message: T) -> Result<(), SendError<T>> {
+        let log = self.log.map(|log| log(&message));
+        let size = self.size.map(|size| size(&message));
+        self.inner.send(message)?;
+        self.handle.sent(log, size);
+        Ok(())
+    }
+
+    /// See [`mpsc::UnboundedSender::closed`].
+    pub async fn closed(&self) {
+        self.inner.closed().await
+    }
+
+    /// See [`mpsc::UnboundedSender::is_closed`].
+    pub fn is_closed(&self) -> bool {
+        self.inner.is_closed()
+    }
+
+    /// See [`mpsc::UnboundedSender::same_channel`].
+    pub fn same_channel(&self, other: &Self) -> bool {
+        self.inner.same_channel(&other.inner)
+    }
+}
+
+impl<T> UnboundedReceiver<T> {
+    /// See [`mpsc::UnboundedReceiver::recv`].
+    pub async fn recv(&mut self) -> Option<T> {
+        let msg = self.inner.recv().await?;
+        self.handle.received();
+        Some(msg)
+    }
+
+    /// See [`mpsc::UnboundedReceiver::recv_many`].
+    pub async fn recv_many(&mut self, buffer: &mut Vec<T>, limit: usize) -> usize {
+        let count = self.inner.recv_many(buffer, limit).await;
+        for _ in 0..count {
+            self.handle.received();
+        }
+        count
+    }
+
+    /// See [`mpsc::UnboundedReceiver::try_recv`].
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        let msg = self.inner.try_recv()?;
+        self.handle.received();
+        Ok(msg)
+    }
+
+    /// See [`mpsc::UnboundedReceiver::blocking_recv`].
+    pub fn blocking_recv(&mut self) -> Option<T> {
+        let msg = self.inner.blocking_recv()?;
+        self.handle.received();
+        Some(msg)
+    }
+
+    /// See [`mpsc::UnboundedReceiver::poll_recv`].
+    pub fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let poll = self.inner.poll_recv(cx);
+        if let Poll::Ready(Some(_)) = poll {
+            self.handle.received();
+        }
+        poll
+    }
+
+    /// See [`mpsc::UnboundedReceiver::close`].
+    pub fn close(&mut self) {
+        self.inner.close()
+    }
+
+    /// See [`mpsc::UnboundedReceiver::is_closed`].
+    pub fn is_closed(&self) -> bool {
+        self.inner.is_closed()
+    }
+
+    /// See [`mpsc::UnboundedReceiver::is_empty`].
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// See [`mpsc::UnboundedReceiver::len`].
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            handle: self.handle.clone(),
+            log: self.log,
+            size: self.size,
+        }
+    }
+}
+
+impl<T> Clone for UnboundedSender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            handle: self.handle.clone(),
+            log: self.log,
+            size: self.size,
+        }
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl<T> fmt::Debug for Permit<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl<T> fmt::Debug for UnboundedSender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl<T> fmt::Debug for UnboundedReceiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl<T> InstrumentChannelDirect for (mpsc::Sender<T>, mpsc::Receiver<T>) {
+    type Output = (Sender<T>, Receiver<T>);
+    fn instrument_direct(self, source: &'static str, label: Option<String>) -> Self::Output {
+        wrap_channel(self, source, label, None, None)
+    }
+}
+
+impl<T> InstrumentChannelDirect for (mpsc::UnboundedSender<T>, mpsc::UnboundedReceiver<T>) {
+    type Output = (UnboundedSender<T>, UnboundedReceiver<T>);
+    fn instrument_direct(self, source: &'static str, label: Option<String>) -> Self::Output {
+        wrap_unbounded(self, source, label, None, None)
+    }
+}
+
+impl<T: fmt::Debug> InstrumentChannelDirectLog for (mpsc::Sender<T>, mpsc::Receiver<T>) {
+    type Output = (Sender<T>, Receiver<T>);
+    fn instrument_direct_log(self, source: &'static str, label: Option<String>) -> Self::Output {
+        wrap_channel(self, source, label, Some(debug_log::<T>), None)
+    }
+}
+
+impl<T: fmt::Debug> InstrumentChannelDirectLog
+    for (mpsc::UnboundedSender<T>, mpsc::UnboundedReceiver<T>)
+{
+    type Output = (UnboundedSender<T>, UnboundedReceiver<T>);
+    fn instrument_direct_log(self, source: &'static str, label: Option<String>) -> Self::Output {
+        wrap_unbounded(self, source, label, Some(debug_log::<T>), None)
+    }
+}
+
+impl<T: HeapSize> InstrumentChannelDirectSize for (mpsc::Sender<T>, mpsc::Receiver<T>) {
+    type Output = (Sender<T>, Receiver<T>);
+    fn instrument_direct_size(self, source: &'static str, label: Option<String>) -> Self::Output {
+        wrap_channel(self, source, label, None, Some(deep_size::<T>))
+    }
+}
+
+impl<T: HeapSize> InstrumentChannelDirectSize
+    for (mpsc::UnboundedSender<T>, mpsc::UnboundedReceiver<T>)
+{
+    type Output = (UnboundedSender<T>, UnboundedReceiver<T>);
+    fn instrument_direct_size(self, source: &'static str, label: Option<String>) -> Self::Output {
+        wrap_unbounded(self, source, label, None, Some(deep_size::<T>))
+    }
+}
@@ -0,0 +1,208 @@
+//! Producer -> channel -> consumer topology.
+//!
+//! `proxy = false` channels report the thread and the innermost measured function of every
+//! send and receive call. Proxied channels are only touched by their forwarders, so they show
+//! up in the graph without producers or consumers.
+
+use std::collections::{BTreeMap, HashMap};
+
+use super::{resolve_label, ChannelStats};
+use crate::json::{ChannelsGraphJson, GraphEdge, GraphNode, GraphNodeKind};
+use crate::lib_on::functions::call_tree::current_scope;
+use crate::tid::current_tid;
+
+/// Distinct endpoints kept per channel side, so a stream of short-lived threads can't grow
+/// the map without bound.
+const MAX_ENDPOINTS: usize = 256;
+
+/// Thread and enclosing measured function of a send or receive call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct Endpoint {
+    tid: u64,
+    scope: Option<&'static str>,
+}
+
+impl Endpoint {
+    #[cfg_attr(not(feature = "tokio"), allow(dead_code))]
+    pub(crate) fn current() -> Self {
+        Self {
+            tid: current_tid(),
+            scope: current_scope(),
+        }
+    }
+
+    /// Sends and receives within a measured function are grouped by function, so a worker
+    /// pool shows up as a single node.
+    fn node(&self) -> GraphNode {
+        let (id, kind, label) = match self.scope {
+            Some(name) => (
+                format!("fn:{}", name),
+                GraphNodeKind::Function,
+                name.to_string(),
+            ),
+            None => (
+                format!("thread:{}", self.tid),
+                GraphNodeKind::Thread,
+                format!("thread {}", self.tid),
+            ),
+        };
+        GraphNode {
+            id,
+            kind,
+            label,
+            tids: Vec::new(),
+        }
+    }
+}
+
+/// Message counts by endpoint for both sides of a channel.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Endpoints {
+    senders: HashMap<Endpoint, u64>,
+    receivers: HashMap<Endpoint, u64>,
+}
+
+impl Endpoints {
+    pub fn sent(&mut self, endpoint: Endpoint) {
+        record(&mut self.senders, endpoint);
+    }
+
+    pub fn received(&mut self, endpoint: Endpoint) {
+        record(&mut self.receivers, endpoint);
+    }
+}
+
+fn record(counts: &mut HashMap<Endpoint, u64>, endpoint: Endpoint) {
+    let full = counts.len() >= MAX_ENDPOINTS;
+    match counts.get_mut(&endpoint) {
+        Some(count) => *count += 1,
+        None if !full => {
+            counts.insert(endpoint, 1);
+        }
+        None => {}
+    }
+}
+
+/// Builds the graph of `channels`, in the given order, followed by their endpoints.
+pub(crate) fn build_graph<'a>(
+    channels: impl IntoIterator<Item = &'a ChannelStats>,
+) -> ChannelsGraphJson {
+    let mut nodes = Vec::new();
+    let mut endpoint_nodes: BTreeMap<String, GraphNode> = BTreeMap::new();
+    let mut edges: BTreeMap<(String, String), u64> = BTreeMap::new();
+
+    for channel in channels {
+        let channel_id = format!("channel:{}", channel.id);
+        nodes.push(GraphNode {
+            id: channel_id.clone(),
+            kind: GraphNodeKind::Channel,
+            label: resolve_label(channel.source, channel.label.as_deref(), Some(channel.iter)),
+            tids: Vec::new(),
+        });
+
+        for (endpoint, count) in &channel.endpoints.senders {
+            let from = add_endpoint(&mut endpoint_nodes, endpoint);
+            *edges.entry((from, channel_id.clone())).or_default() += count;
+        }
+        for (endpoint, count) in &channel.endpoints.receivers {
+            let to = add_endpoint(&mut endpoint_nodes, endpoint);
+            *edges.entry((channel_id.clone(), to)).or_default() += count;
+        }
+    }
+
+    nodes.extend(endpoint_nodes.into_values());
+    ChannelsGraphJson {
+        nodes,
+        edges: edges
+            .into_iter()
+            .map(|((from, to), messages)| GraphEdge { from, to, messages })
+            .collect(),
+    }
+}
+
+fn add_endpoint(nodes: &mut BTreeMap<String, GraphNode>, endpoint: &Endpoint) -> String {
+    let node = endpoint.node();
+    let node = nodes.entry(node.id.clone()).or_insert(node);
+    if let Err(pos) = node.tids.binary_search(&endpoint.tid) {
+        node.tids.insert(pos, endpoint.tid);
+    }
+    node.id.clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn channel(id: u64, label: &str) -> ChannelStats {
+        ChannelStats::new(
+            id,
+            "src/main.rs:1",
+            Some(label.to_string()),
+            crate::json::ChannelType::Bounded(8),
+            "u32",
+            4,
+            0,
+            false,
+        )
+    }
+
+    #[test]
+    fn test_graph_links_producers_and_consumers() {
+        let source = Endpoint {
+            tid: 1,
+            scope: Some("app::source"),
+        };
+        let worker_a = Endpoint {
+            tid: 2,
+            scope: Some("app::parse"),
+        };
+        let worker_b = Endpoint {
+            tid: 3,
+            scope: Some("app::parse"),
+        };
+        let sink = Endpoint {
+            tid: 4,
+            scope: None,
+        };
+
+        let mut raw = channel(0, "raw");
+        let mut parsed = channel(1, "parsed");
+        for _ in 0..3 {
+            raw.endpoints.sent(source);
+        }
+        raw.endpoints.received(worker_a);
+        raw.endpoints.received(worker_a);
+        raw.endpoints.received(worker_b);
+        parsed.endpoints.sent(worker_a);
+        parsed.endpoints.sent(worker_b);
+        parsed.endpoints.received(sink);
+
+        let graph = build_graph([&raw, &parsed]);
+
+        let labels: Vec<&str> = graph.nodes.iter().map(|n| n.label.as_str()).collect();
+        assert_eq!(
+            labels,
+            ["raw", "parsed", "app::parse", "app::source", "thread 4"]
+        );
+        assert_eq!(graph.nodes[2].tids, [2, 3]);
+        assert_eq!(graph.nodes[4].kind, GraphNodeKind::Thread);
+
+        let edge = |from: &str, to: &str| {
+            graph
+                .edges
+                .iter()
+                .find(|e| e.from == from && e.to == to)
+                .map(|e| e.messages)
+        };
+        assert_eq!(graph.edges.len(), 4);
+        assert_eq!(edge("fn:app::source", "channel:0"), Some(3));
+        assert_eq!(edge("channel:0", "fn:app::parse"), Some(3));
+        assert_eq!(edge("fn:app::parse", "channel:1"), Some(2));
+        assert_eq!(edge("channel:1", "thread:4"), Some(1));
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph channels {"));
+        assert!(dot.contains("\"channel:0\" [label=\"raw\", shape=box];"));
+        assert!(dot.contains("\"fn:app::parse\" -> \"channel:1\" [label=\"2\"];"));
+    }
+}
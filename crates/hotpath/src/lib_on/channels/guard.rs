@@ -6,7 +6,7 @@ use std::time::Instant;
 
 use prettytable::{Cell, Row, Table};
 
-use crate::channels::{get_sorted_channel_stats, resolve_label};
+use crate::channels::{get_sorted_channel_stats, resolve_label, QUEUE_WARN_THRESHOLD};
 use crate::output::format_bytes;
 use crate::Format;
 
@@ -123,6 +123,12 @@ impl Default for ChannelsGuard {
 
 impl Drop for ChannelsGuard {
     fn drop(&mut self) {
+        // Folded stacks only cover measured functions, and any other line on stdout would
+        // corrupt them
+        if matches!(self.format, Format::Folded) {
+            return;
+        }
+
         let elapsed = self.start_time.elapsed();
         let channels = get_sorted_channel_stats();
 
@@ -147,6 +153,7 @@ impl Drop for ChannelsGuard {
                     Cell::new("Sent"),
                     Cell::new("Received"),
                     Cell::new("Queued"),
+                    Cell::new("Max Queued"),
                     Cell::new("Mem"),
                 ]));
 
@@ -163,6 +170,7 @@ impl Drop for ChannelsGuard {
                         Cell::new(&channel_stats.sent_count.to_string()),
                         Cell::new(&channel_stats.received_count.to_string()),
                         Cell::new(&channel_stats.queued().to_string()),
+                        Cell::new(&channel_stats.high_water.max_queued().to_string()),
                         Cell::new(&format_bytes(channel_stats.queued_bytes())),
                     ]));
                 }
@@ -177,6 +185,7 @@ impl Drop for ChannelsGuard {
                         .iter()
                         .map(crate::channels::SerializableChannelStats::from)
                         .collect(),
+                    queue_warn_threshold: *QUEUE_WARN_THRESHOLD,
                 };
                 match serde_json::to_string(&channels_json) {
                     Ok(json) => println!("{}", json),
@@ -190,12 +199,14 @@ impl Drop for ChannelsGuard {
                         .iter()
                         .map(crate::channels::SerializableChannelStats::from)
                         .collect(),
+                    queue_warn_threshold: *QUEUE_WARN_THRESHOLD,
                 };
                 match serde_json::to_string_pretty(&channels_json) {
                     Ok(json) => println!("{}", json),
                     Err(e) => eprintln!("Failed to serialize statistics to pretty JSON: {}", e),
                 }
             }
+            Format::Folded => {}
         }
     }
 }
@@ -0,0 +1,117 @@
+//! Peak queue depth of a channel, and warnings once it reaches `HOTPATH_CHANNEL_QUEUE_WARN`.
+
+use std::collections::VecDeque;
+use std::sync::LazyLock;
+
+use crate::json::QueueWarning;
+
+/// Queued messages at which a channel is reported as growing, disabled if unset or zero.
+pub(crate) static QUEUE_WARN_THRESHOLD: LazyLock<Option<u64>> = LazyLock::new(|| {
+    std::env::var("HOTPATH_CHANNEL_QUEUE_WARN")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .filter(|threshold| *threshold > 0)
+});
+
+/// Most recent warnings kept per channel.
+const MAX_WARNINGS: usize = 16;
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct HighWater {
+    max_queued: u64,
+    max_queued_at_ns: u64,
+    /// Set once the threshold is crossed, cleared when the queue drains to half of it, so
+    /// a queue hovering around the threshold doesn't warn on every message
+    above: bool,
+    warnings: VecDeque<QueueWarning>,
+}
+
+impl HighWater {
+    /// Records the current queue depth and returns a warning if it just crossed `threshold`.
+    pub fn record(
+        &mut self,
+        queued: u64,
+        timestamp_ns: u64,
+        threshold: Option<u64>,
+    ) -> Option<QueueWarning> {
+        if queued > self.max_queued {
+            self.max_queued = queued;
+            self.max_queued_at_ns = timestamp_ns;
+        }
+
+        let threshold = threshold?;
+        if self.above {
+            if queued <= threshold / 2 {
+                self.above = false;
+            }
+            return None;
+        }
+        if queued < threshold {
+            return None;
+        }
+
+        self.above = true;
+        let warning = QueueWarning {
+            queued,
+            threshold,
+            timestamp_ns,
+        };
+        if self.warnings.len() >= MAX_WARNINGS {
+            self.warnings.pop_front();
+        }
+        self.warnings.push_back(warning);
+        Some(warning)
+    }
+
+    pub fn max_queued(&self) -> u64 {
+        self.max_queued
+    }
+
+    pub fn max_queued_at_ns(&self) -> u64 {
+        self.max_queued_at_ns
+    }
+
+    pub fn warnings(&self) -> Vec<QueueWarning> {
+        self.warnings.iter().copied().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_high_water_warns_once_per_crossing() {
+        let mut high_water = HighWater::default();
+        let threshold = Some(10);
+
+        for queued in 1..10 {
+            assert!(high_water.record(queued, queued, threshold).is_none());
+        }
+        let warning = high_water
+            .record(10, 100, threshold)
+            .expect("Expected a warning at the threshold");
+        assert_eq!(warning.queued, 10);
+        assert_eq!(warning.timestamp_ns, 100);
+
+        // Still above, or drained but not below half of the threshold
+        assert!(high_water.record(25, 200, threshold).is_none());
+        assert!(high_water.record(6, 300, threshold).is_none());
+        assert!(high_water.record(12, 400, threshold).is_none());
+
+        assert!(high_water.record(5, 500, threshold).is_none());
+        assert!(high_water.record(11, 600, threshold).is_some());
+
+        assert_eq!(high_water.max_queued(), 25);
+        assert_eq!(high_water.max_queued_at_ns(), 200);
+        assert_eq!(high_water.warnings().len(), 2);
+    }
+
+    #[test]
+    fn test_high_water_without_threshold() {
+        let mut high_water = HighWater::default();
+        assert!(high_water.record(1_000, 1, None).is_none());
+        assert_eq!(high_water.max_queued(), 1_000);
+        assert!(high_water.warnings().is_empty());
+    }
+}
@@ -0,0 +1,130 @@
+//! Deep sizes of messages sent over `size = true` channels.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crate::json::StatsWindow;
+use crate::lib_on::window::{lifetime_rate, WindowedCounter};
+
+/// Sizes kept for messages that were not received yet. Older ones are dropped, so a channel
+/// without a consumer doesn't grow without bound.
+const MAX_PENDING: usize = 100_000;
+
+/// Matches the n-th received message with the n-th sent one, channels being FIFO, so the
+/// receive side doesn't have to measure messages again.
+#[derive(Debug, Clone)]
+pub(crate) struct MessageSizes {
+    /// Sizes of messages in flight, oldest first
+    pending: VecDeque<u64>,
+    /// Receives whose send event has not been processed yet
+    early: u64,
+    /// Oldest in-flight messages dropped from `pending`, skipped when received
+    dropped: u64,
+    sent_bytes: u64,
+    received_bytes: u64,
+    sent_window: WindowedCounter,
+    received_window: WindowedCounter,
+}
+
+impl MessageSizes {
+    pub fn new() -> Self {
+        Self {
+            pending: VecDeque::new(),
+            early: 0,
+            dropped: 0,
+            sent_bytes: 0,
+            received_bytes: 0,
+            sent_window: WindowedCounter::new(),
+            received_window: WindowedCounter::new(),
+        }
+    }
+
+    pub fn sent(&mut self, size: u64, elapsed: Duration) {
+        self.sent_bytes += size;
+        self.sent_window.add(elapsed, size);
+
+        if self.early > 0 {
+            self.early -= 1;
+            self.record_received(size, elapsed);
+            return;
+        }
+
+        if self.pending.len() >= MAX_PENDING {
+            self.pending.pop_front();
+            self.dropped += 1;
+        }
+        self.pending.push_back(size);
+    }
+
+    pub fn received(&mut self, elapsed: Duration) {
+        if self.dropped > 0 {
+            self.dropped -= 1;
+            return;
+        }
+
+        match self.pending.pop_front() {
+            Some(size) => self.record_received(size, elapsed),
+            // Send and receive events come from different threads and can be reordered
+            None => self.early += 1,
+        }
+    }
+
+    fn record_received(&mut self, size: u64, elapsed: Duration) {
+        self.received_bytes += size;
+        self.received_window.add(elapsed, size);
+    }
+
+    pub fn sent_bytes(&self) -> u64 {
+        self.sent_bytes
+    }
+
+    pub fn received_bytes(&self) -> u64 {
+        self.received_bytes
+    }
+
+    /// Bytes held by the newest `queued` messages in flight. Proxied channels count one
+    /// message less than they have in flight, the one waiting in the proxy.
+    pub fn queued_bytes(&self, queued: u64) -> u64 {
+        self.pending.iter().rev().take(queued as usize).sum()
+    }
+
+    /// Sent and received bytes per second over `window`, `now` being the elapsed time.
+    pub fn rates(&self, window: StatsWindow, now: Duration) -> (f64, f64) {
+        (
+            self.sent_window
+                .rate(window, now)
+                .unwrap_or_else(|| lifetime_rate(self.sent_bytes, now)),
+            self.received_window
+                .rate(window, now)
+                .unwrap_or_else(|| lifetime_rate(self.received_bytes, now)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_sizes_track_queued_bytes() {
+        let mut sizes = MessageSizes::new();
+        sizes.sent(100, Duration::from_millis(1));
+        sizes.sent(2_000, Duration::from_millis(2));
+        sizes.sent(30, Duration::from_millis(3));
+        assert_eq!(sizes.queued_bytes(3), 2_130);
+        assert_eq!(sizes.queued_bytes(2), 2_030);
+
+        sizes.received(Duration::from_millis(4));
+        assert_eq!(sizes.received_bytes(), 100);
+        assert_eq!(sizes.queued_bytes(2), 2_030);
+
+        // A receive reported before its send is matched once the send arrives
+        sizes.received(Duration::from_millis(5));
+        sizes.received(Duration::from_millis(6));
+        sizes.received(Duration::from_millis(7));
+        sizes.sent(7, Duration::from_millis(8));
+        assert_eq!(sizes.sent_bytes(), 2_137);
+        assert_eq!(sizes.received_bytes(), 2_137);
+        assert_eq!(sizes.queued_bytes(0), 0);
+    }
+}
@@ -0,0 +1,96 @@
+//! Correlates channel sends with receives to measure how long messages wait in the queue.
+
+use std::collections::VecDeque;
+
+use crate::json::DurationStats;
+use crate::lib_on::durations::Durations;
+
+/// Send timestamps kept for messages that were not received yet. Older ones are dropped,
+/// so a channel without a consumer doesn't grow without bound.
+const MAX_PENDING: usize = 100_000;
+
+/// Matches the n-th received message with the n-th sent one, channels being FIFO.
+#[derive(Debug, Clone)]
+pub(crate) struct QueueWait {
+    /// Send timestamps of messages in flight, oldest first
+    pending: VecDeque<u64>,
+    /// Receive timestamps whose send event has not been processed yet
+    early: VecDeque<u64>,
+    /// Oldest in-flight messages dropped from `pending`, skipped when received
+    dropped: u64,
+    waits: Durations,
+}
+
+impl QueueWait {
+    pub fn new() -> Self {
+        Self {
+            pending: VecDeque::new(),
+            early: VecDeque::new(),
+            dropped: 0,
+            waits: Durations::new(),
+        }
+    }
+
+    pub fn sent(&mut self, timestamp_ns: u64) {
+        if let Some(received_ns) = self.early.pop_front() {
+            self.waits.record(received_ns.saturating_sub(timestamp_ns));
+            return;
+        }
+
+        if self.pending.len() >= MAX_PENDING {
+            self.pending.pop_front();
+            self.dropped += 1;
+        }
+        self.pending.push_back(timestamp_ns);
+    }
+
+    pub fn received(&mut self, timestamp_ns: u64) {
+        if self.dropped > 0 {
+            self.dropped -= 1;
+            return;
+        }
+
+        match self.pending.pop_front() {
+            Some(sent_ns) => self.waits.record(timestamp_ns.saturating_sub(sent_ns)),
+            // Send and receive events come from different threads and can be reordered
+            None => self.early.push_back(timestamp_ns),
+        }
+    }
+
+    pub fn stats(&self) -> Option<DurationStats> {
+        self.waits.stats()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queue_wait_matches_fifo_order() {
+        let mut wait = QueueWait::new();
+        assert!(wait.stats().is_none());
+
+        wait.sent(100);
+        wait.sent(200);
+        wait.received(1_100);
+        // Receive processed before its send
+        wait.received(1_200);
+        wait.received(1_500);
+        wait.sent(600);
+        wait.sent(1_400);
+        wait.received(1_500);
+        wait.sent(2_000);
+        wait.received(2_000_002_000);
+
+        let stats = wait.stats().unwrap();
+        assert_eq!(stats.count, 5);
+        assert_eq!(stats.max_ns, 2_000_000_000);
+        assert_eq!(
+            stats.avg_ns,
+            (1_000 + 1_000 + 900 + 100 + 2_000_000_000) / 5
+        );
+        assert!(stats.p99_ns > 1_900_000_000);
+        assert!(stats.p50_ns <= stats.p95_ns && stats.p95_ns <= stats.p99_ns);
+    }
+}
@@ -0,0 +1,95 @@
+//! Time producers spend blocked on a full bounded channel, per call and per sending thread.
+
+use std::collections::HashMap;
+
+use crate::json::{SendBlockedStats, ThreadSendBlocked};
+use crate::lib_on::durations::Durations;
+
+#[derive(Debug, Clone, Default)]
+struct ThreadTotals {
+    count: u64,
+    total_ns: u64,
+    max_ns: u64,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct SendBlocked {
+    blocked: Durations,
+    threads: HashMap<u64, ThreadTotals>,
+}
+
+impl ThreadTotals {
+    fn record(&mut self, blocked_ns: u64) {
+        self.count += 1;
+        self.total_ns += blocked_ns;
+        self.max_ns = self.max_ns.max(blocked_ns);
+    }
+}
+
+impl SendBlocked {
+    pub fn new() -> Self {
+        Self {
+            blocked: Durations::new(),
+            threads: HashMap::new(),
+        }
+    }
+
+    /// Records a send that found the channel full, `blocked_ns` being zero for rejected
+    /// non-blocking sends. Sends from an unknown thread only count towards the totals.
+    pub fn record(&mut self, tid: Option<u64>, blocked_ns: u64) {
+        self.blocked.record(blocked_ns);
+        if let Some(tid) = tid {
+            self.threads.entry(tid).or_default().record(blocked_ns);
+        }
+    }
+
+    pub fn stats(&self) -> Option<SendBlockedStats> {
+        let durations = self.blocked.stats()?;
+        let mut threads: Vec<ThreadSendBlocked> = self
+            .threads
+            .iter()
+            .map(|(tid, totals)| ThreadSendBlocked {
+                tid: *tid,
+                count: totals.count,
+                total_ns: totals.total_ns,
+                max_ns: totals.max_ns,
+            })
+            .collect();
+        threads.sort_by(|a, b| b.total_ns.cmp(&a.total_ns).then(a.tid.cmp(&b.tid)));
+
+        Some(SendBlockedStats {
+            durations,
+            total_ns: self.blocked.total_ns(),
+            threads,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_send_blocked_by_thread() {
+        let mut blocked = SendBlocked::new();
+        assert!(blocked.stats().is_none());
+
+        blocked.record(Some(7), 100);
+        blocked.record(Some(7), 300);
+        blocked.record(Some(9), 1_000);
+        blocked.record(Some(9), 0);
+        blocked.record(None, 2_600);
+
+        let stats = blocked.stats().unwrap();
+        assert_eq!(stats.durations.count, 5);
+        assert_eq!(stats.total_ns, 4_000);
+        assert_eq!(stats.durations.avg_ns, 800);
+        assert_eq!(stats.durations.max_ns, 2_600);
+
+        assert_eq!(stats.threads.len(), 2);
+        assert_eq!(stats.threads[0].tid, 9);
+        assert_eq!(stats.threads[0].count, 2);
+        assert_eq!(stats.threads[1].total_ns, 400);
+        assert_eq!(stats.threads[1].max_ns, 300);
+    }
+}
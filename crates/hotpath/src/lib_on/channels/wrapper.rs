@@ -1,6 +1,10 @@
+#[cfg(feature = "async-channel")]
+pub(crate) mod async_channel;
 pub(crate) mod common;
 #[cfg(feature = "crossbeam")]
 pub(crate) mod crossbeam;
+#[cfg(feature = "flume")]
+pub(crate) mod flume;
 #[cfg(feature = "futures")]
 pub(crate) mod ftc;
 pub(crate) mod std;
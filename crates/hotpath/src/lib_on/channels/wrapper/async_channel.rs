@@ -0,0 +1,211 @@
+use std::future::Future;
+use std::pin::pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+
+use async_channel::{Receiver, Sender};
+
+use crate::channels::wrapper::common::{
+    register_channel, Forwarder, SizeFn, FIRST_SAMPLE, SAMPLE_INTERVAL,
+};
+use crate::channels::ChannelType;
+use crate::heap_size::{deep_size, HeapSize};
+
+/// Unparks the forwarder thread when a future it blocks on can make progress.
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Polls `fut` to completion on the current thread. While it is pending `sample` runs at a
+/// backing-off interval for as long as it returns `true`, then the thread parks until woken.
+fn block_on<F: Future>(waker: &Waker, fut: F, mut sample: impl FnMut() -> bool) -> F::Output {
+    let mut fut = pin!(fut);
+    let mut cx = Context::from_waker(waker);
+    let mut interval = FIRST_SAMPLE;
+    let mut sampling = true;
+
+    loop {
+        if let Poll::Ready(output) = fut.as_mut().poll(&mut cx) {
+            return output;
+        }
+        if sampling {
+            sampling = sample();
+        }
+        if sampling {
+            thread::park_timeout(interval);
+            interval = (interval * 2).min(SAMPLE_INTERVAL);
+        } else {
+            thread::park();
+        }
+    }
+}
+
+/// Reports the messages the consumer took from the proxy since the last call.
+fn report_taken<T>(forwarder: &Forwarder, proxy_tx: &Sender<T>, pushed: usize, taken: &mut usize) {
+    let now_taken = pushed.saturating_sub(proxy_tx.len());
+    while *taken < now_taken {
+        forwarder.received();
+        *taken += 1;
+    }
+}
+
+/// Internal implementation for wrapping async-channel channels with optional logging.
+/// Uses single proxy design: User -> [Original] -> Thread -> [Proxy] -> User
+///
+/// The forwarder blocks on a dedicated thread, so it works with any async runtime. Both
+/// ends stay `async_channel` types, so receivers can be cloned and consumed from sync or
+/// async code. async-channel has no zero-capacity channels, so messages taken by the
+/// consumer are counted from the length of the proxy.
+fn wrap_channel_impl<T, F>(
+    inner: (Sender<T>, Receiver<T>),
+    source: &'static str,
+    label: Option<String>,
+    mut log_on_send: F,
+    size_of: SizeFn<T>,
+) -> (Sender<T>, Receiver<T>)
+where
+    T: Send + 'static,
+    F: FnMut(&T) -> Option<String> + Send + 'static,
+{
+    let (inner_tx, inner_rx) = inner;
+    let (proxy_tx, proxy_rx, channel_type) = match inner_tx.capacity() {
+        Some(capacity) => {
+            let (proxy_tx, proxy_rx) = async_channel::bounded::<T>(1);
+            (proxy_tx, proxy_rx, ChannelType::Bounded(capacity))
+        }
+        None => {
+            let (proxy_tx, proxy_rx) = async_channel::unbounded::<T>();
+            (proxy_tx, proxy_rx, ChannelType::Unbounded)
+        }
+    };
+
+    let registered = register_channel::<T>(source, label, channel_type, size_of.is_some());
+    let mut forwarder = match inner_tx.capacity() {
+        Some(capacity) => Forwarder::bounded(registered, capacity),
+        None => Forwarder::new(registered),
+    };
+
+    // Single forwarder: inner_rx -> proxy_tx
+    thread::spawn(move || {
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        // Messages handed to the proxy, and taken from it by the consumer
+        let (mut pushed, mut taken) = (0, 0);
+
+        loop {
+            report_taken(&forwarder, &proxy_tx, pushed, &mut taken);
+            forwarder.waiting(inner_rx.len());
+            let msg = block_on(&waker, inner_rx.recv(), || {
+                report_taken(&forwarder, &proxy_tx, pushed, &mut taken);
+                taken < pushed
+            });
+            let Ok(msg) = msg else {
+                break; // all inner senders gone
+            };
+
+            forwarder.sent(log_on_send(&msg), size_of.map(|size_of| size_of(&msg)));
+            let sent = block_on(&waker, proxy_tx.send(msg), || {
+                report_taken(&forwarder, &proxy_tx, pushed, &mut taken);
+                forwarder.waiting(inner_rx.len());
+                true
+            });
+            if sent.is_err() {
+                break; // all proxy receivers dropped
+            }
+            pushed += 1;
+        }
+
+        // Keep reporting the messages left in the proxy until the consumer takes them
+        let mut interval = FIRST_SAMPLE;
+        loop {
+            report_taken(&forwarder, &proxy_tx, pushed, &mut taken);
+            if taken == pushed || proxy_tx.is_closed() {
+                break;
+            }
+            thread::sleep(interval);
+            interval = (interval * 2).min(SAMPLE_INTERVAL);
+        }
+        forwarder.closed();
+    });
+
+    (inner_tx, proxy_rx)
+}
+
+/// Wrap an async-channel channel with proxy ends. Returns (outer_tx, outer_rx).
+pub(crate) fn wrap_channel<T: Send + 'static>(
+    inner: (Sender<T>, Receiver<T>),
+    source: &'static str,
+    label: Option<String>,
+) -> (Sender<T>, Receiver<T>) {
+    wrap_channel_impl(inner, source, label, |_| None, None)
+}
+
+/// Wrap an async-channel channel with logging enabled. Returns (outer_tx, outer_rx).
+pub(crate) fn wrap_channel_log<T: Send + std::fmt::Debug + 'static>(
+    inner: (Sender<T>, Receiver<T>),
+    source: &'static str,
+    label: Option<String>,
+) -> (Sender<T>, Receiver<T>) {
+    wrap_channel_impl(inner, source, label, |msg| Some(format!("{:?}", msg)), None)
+}
+
+/// Wrap an async-channel channel recording the deep size of every message.
+pub(crate) fn wrap_channel_size<T: Send + HeapSize + 'static>(
+    inner: (Sender<T>, Receiver<T>),
+    source: &'static str,
+    label: Option<String>,
+) -> (Sender<T>, Receiver<T>) {
+    wrap_channel_impl(inner, source, label, |_| None, Some(deep_size::<T>))
+}
+
+use crate::channels::InstrumentChannel;
+
+impl<T: Send + 'static> InstrumentChannel
+    for (async_channel::Sender<T>, async_channel::Receiver<T>)
+{
+    type Output = (async_channel::Sender<T>, async_channel::Receiver<T>);
+    fn instrument(
+        self,
+        source: &'static str,
+        label: Option<String>,
+        _capacity: Option<usize>,
+    ) -> Self::Output {
+        wrap_channel(self, source, label)
+    }
+}
+
+use crate::channels::InstrumentChannelLog;
+
+impl<T: Send + std::fmt::Debug + 'static> InstrumentChannelLog
+    for (async_channel::Sender<T>, async_channel::Receiver<T>)
+{
+    type Output = (async_channel::Sender<T>, async_channel::Receiver<T>);
+    fn instrument_log(
+        self,
+        source: &'static str,
+        label: Option<String>,
+        _capacity: Option<usize>,
+    ) -> Self::Output {
+        wrap_channel_log(self, source, label)
+    }
+}
+
+use crate::channels::InstrumentChannelSize;
+
+impl<T: Send + HeapSize + 'static> InstrumentChannelSize
+    for (async_channel::Sender<T>, async_channel::Receiver<T>)
+{
+    type Output = (async_channel::Sender<T>, async_channel::Receiver<T>);
+    fn instrument_size(
+        self,
+        source: &'static str,
+        label: Option<String>,
+        _capacity: Option<usize>,
+    ) -> Self::Output {
+        wrap_channel_size(self, source, label)
+    }
+}
@@ -1,7 +1,9 @@
 //! Common utilities for channel wrappers.
 
+use std::collections::VecDeque;
 use std::mem;
 use std::sync::atomic::Ordering;
+use std::time::Duration;
 
 use crossbeam_channel::Sender as CbSender;
 
@@ -12,6 +14,29 @@ pub use quanta::Instant;
 #[cfg(not(target_os = "linux"))]
 pub use std::time::Instant;
 
+/// Measures the inline and heap size of a message, set when instrumented with `size = true`.
+pub(crate) type SizeFn<T> = Option<fn(&T) -> u64>;
+
+/// How often a forwarder waiting for its consumer samples the original channel.
+#[cfg_attr(
+    not(any(
+        feature = "crossbeam",
+        feature = "flume",
+        feature = "async-channel",
+        feature = "tokio"
+    )),
+    allow(dead_code)
+)]
+pub(crate) const SAMPLE_INTERVAL: Duration = Duration::from_millis(10);
+
+/// First interval at which a forwarder samples a proxy that only exposes its `len`, doubled
+/// on every sample up to [`SAMPLE_INTERVAL`]. Bounds how late a take is reported.
+#[cfg_attr(
+    not(any(feature = "async-channel", feature = "futures")),
+    allow(dead_code)
+)]
+pub(crate) const FIRST_SAMPLE: Duration = Duration::from_micros(100);
+
 pub struct RegisteredChannel {
     pub id: u64,
     pub stats_tx: CbSender<ChannelEvent>,
@@ -21,6 +46,29 @@ pub fn register_channel<T>(
     source: &'static str,
     label: Option<String>,
     channel_type: ChannelType,
+    sized: bool,
+) -> RegisteredChannel {
+    register::<T>(source, label, channel_type, false, sized)
+}
+
+/// Registers a channel whose receives are counted when its forwarder hands a message over,
+/// rather than when the consumer takes it.
+#[cfg(any(feature = "tokio", feature = "futures"))]
+pub fn register_handoff_channel<T>(
+    source: &'static str,
+    label: Option<String>,
+    channel_type: ChannelType,
+    sized: bool,
+) -> RegisteredChannel {
+    register::<T>(source, label, channel_type, true, sized)
+}
+
+fn register<T>(
+    source: &'static str,
+    label: Option<String>,
+    channel_type: ChannelType,
+    handoff: bool,
+    sized: bool,
 ) -> RegisteredChannel {
     let type_name = std::any::type_name::<T>();
     let (stats_tx, _) = init_channels_state();
@@ -33,6 +81,8 @@ pub fn register_channel<T>(
         channel_type,
         type_name,
         type_size: mem::size_of::<T>(),
+        handoff,
+        sized,
     });
 
     RegisteredChannel {
@@ -40,3 +90,130 @@ pub fn register_channel<T>(
         stats_tx: stats_tx.clone(),
     }
 }
+
+/// Reports what the forwarder of a proxied channel observes.
+///
+/// Messages are counted as sent when the forwarder pulls them from the original channel and
+/// as received when the consumer takes them from the proxy. Messages still waiting in the
+/// original channel are sampled with [`Forwarder::waiting`], so they count as queued too, and
+/// their sends are timestamped with the sample that first saw them. Producers never reach
+/// the forwarder, so a bounded channel sampled full counts as one blocked send until a
+/// sample sees room in it again.
+#[derive(Clone)]
+pub(crate) struct Forwarder {
+    id: u64,
+    stats_tx: CbSender<ChannelEvent>,
+    /// When each message waiting in the original channel was first seen, oldest first
+    arrivals: VecDeque<Instant>,
+    /// Capacity of the original channel, `None` for unbounded channels
+    capacity: Option<usize>,
+    /// When the original channel was first sampled full
+    full_since: Option<Instant>,
+}
+
+impl Forwarder {
+    pub fn new(registered: RegisteredChannel) -> Self {
+        let RegisteredChannel { id, stats_tx } = registered;
+        Self {
+            id,
+            stats_tx,
+            arrivals: VecDeque::new(),
+            capacity: None,
+            full_since: None,
+        }
+    }
+
+    /// Creates a forwarder that reports blocked sends while the original channel, holding up
+    /// to `capacity` messages, is sampled full. Zero-capacity channels never count as full.
+    #[cfg_attr(
+        not(any(
+            feature = "crossbeam",
+            feature = "flume",
+            feature = "async-channel",
+            feature = "tokio"
+        )),
+        allow(dead_code)
+    )]
+    pub fn bounded(registered: RegisteredChannel, capacity: usize) -> Self {
+        Self {
+            capacity: (capacity > 0).then_some(capacity),
+            ..Self::new(registered)
+        }
+    }
+
+    /// Records the number of messages waiting in the original channel, sampled while the
+    /// forwarder is not pulling from it.
+    #[cfg_attr(
+        not(any(
+            feature = "crossbeam",
+            feature = "flume",
+            feature = "async-channel",
+            feature = "tokio"
+        )),
+        allow(dead_code)
+    )]
+    pub fn waiting(&mut self, waiting: usize) {
+        if let Some(capacity) = self.capacity {
+            if waiting >= capacity {
+                self.full_since.get_or_insert_with(Instant::now);
+            } else {
+                self.report_full();
+            }
+        }
+        if waiting == self.arrivals.len() {
+            return;
+        }
+
+        let now = Instant::now();
+        // Channels are FIFO, so new messages are the newest ones
+        self.arrivals.resize(waiting, now);
+        let _ = self.stats_tx.send(ChannelEvent::Waiting {
+            id: self.id,
+            waiting: waiting as u64,
+            timestamp: now,
+        });
+    }
+
+    /// Reports a message pulled from the original channel, timestamped with when it was first
+    /// seen waiting there.
+    pub fn sent(&mut self, log: Option<String>, size: Option<u64>) {
+        let timestamp = self.arrivals.pop_front().unwrap_or_else(Instant::now);
+        let _ = self.stats_tx.send(ChannelEvent::MessageSent {
+            id: self.id,
+            log,
+            size,
+            timestamp,
+            endpoint: None,
+        });
+    }
+
+    /// Reports a message taken from the proxy by the consumer.
+    pub fn received(&self) {
+        let _ = self.stats_tx.send(ChannelEvent::MessageReceived {
+            id: self.id,
+            timestamp: Instant::now(),
+            endpoint: None,
+        });
+    }
+
+    /// Reports a send that waited `blocked` for room in the channel, on an unknown thread.
+    pub fn send_blocked(&self, blocked: Duration) {
+        let _ = self.stats_tx.send(ChannelEvent::SendBlocked {
+            id: self.id,
+            tid: None,
+            blocked_ns: blocked.as_nanos() as u64,
+        });
+    }
+
+    /// Ends the period in which the original channel was sampled full, if any.
+    fn report_full(&mut self) {
+        if let Some(full_since) = self.full_since.take() {
+            self.send_blocked(Instant::now().duration_since(full_since));
+        }
+    }
+
+    pub fn closed(mut self) {
+        self.report_full();
+        let _ = self.stats_tx.send(ChannelEvent::Closed { id: self.id });
+    }
+}
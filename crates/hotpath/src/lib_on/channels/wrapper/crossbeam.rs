@@ -1,7 +1,45 @@
-use crossbeam_channel::{self, Receiver, Sender};
-
-use crate::channels::wrapper::common::{register_channel, Instant, RegisteredChannel};
-use crate::channels::{ChannelEvent, ChannelType};
+use crossbeam_channel::{self, Receiver, SendTimeoutError, Sender};
+
+use crate::channels::wrapper::common::{register_channel, Forwarder, SizeFn, SAMPLE_INTERVAL};
+use crate::channels::ChannelType;
+use crate::heap_size::{deep_size, HeapSize};
+
+/// Forwards `inner_rx` to a zero-capacity proxy, so each send to the proxy completes when
+/// the consumer takes the message. While waiting for the consumer the forwarder samples how
+/// many messages are waiting in the original channel.
+fn spawn_forwarder<T, F>(
+    inner_rx: Receiver<T>,
+    proxy_tx: Sender<T>,
+    mut forwarder: Forwarder,
+    mut log_on_send: F,
+    size_of: SizeFn<T>,
+) where
+    T: Send + 'static,
+    F: FnMut(&T) -> Option<String> + Send + 'static,
+{
+    std::thread::spawn(move || {
+        'forward: loop {
+            forwarder.waiting(inner_rx.len());
+            let Ok(mut msg) = inner_rx.recv() else {
+                break;
+            };
+            forwarder.sent(log_on_send(&msg), size_of.map(|size_of| size_of(&msg)));
+            loop {
+                match proxy_tx.send_timeout(msg, SAMPLE_INTERVAL) {
+                    Ok(()) => break,
+                    Err(SendTimeoutError::Timeout(unsent)) => {
+                        msg = unsent;
+                        forwarder.waiting(inner_rx.len());
+                    }
+                    // proxy_rx dropped
+                    Err(SendTimeoutError::Disconnected(_)) => break 'forward,
+                }
+            }
+            forwarder.received();
+        }
+        forwarder.closed();
+    });
+}
 
 /// Internal implementation for wrapping bounded crossbeam channels with optional logging.
 fn wrap_bounded_impl<T, F>(
@@ -9,39 +47,26 @@ fn wrap_bounded_impl<T, F>(
     source: &'static str,
     label: Option<String>,
     capacity: usize,
-    mut log_on_send: F,
+    log_on_send: F,
+    size_of: SizeFn<T>,
 ) -> (Sender<T>, Receiver<T>)
 where
     T: Send + 'static,
     F: FnMut(&T) -> Option<String> + Send + 'static,
 {
     let (inner_tx, inner_rx) = inner;
-    let (proxy_tx, proxy_rx) = crossbeam_channel::bounded::<T>(1);
-
-    let RegisteredChannel { id, stats_tx } =
-        register_channel::<T>(source, label, ChannelType::Bounded(capacity));
-
-    // Single forwarder: inner_rx -> proxy_tx
-    std::thread::spawn(move || {
-        while let Ok(msg) = inner_rx.recv() {
-            let log = log_on_send(&msg);
-            let _ = stats_tx.send(ChannelEvent::MessageSent {
-                id,
-                log,
-                timestamp: Instant::now(),
-            });
-            if proxy_tx.send(msg).is_ok() {
-                let _ = stats_tx.send(ChannelEvent::MessageReceived {
-                    id,
-                    timestamp: Instant::now(),
-                });
-            } else {
-                // proxy_rx dropped
-                break;
-            }
-        }
-        let _ = stats_tx.send(ChannelEvent::Closed { id });
-    });
+    let (proxy_tx, proxy_rx) = crossbeam_channel::bounded::<T>(0);
+
+    let forwarder = Forwarder::bounded(
+        register_channel::<T>(
+            source,
+            label,
+            ChannelType::Bounded(capacity),
+            size_of.is_some(),
+        ),
+        capacity,
+    );
+    spawn_forwarder(inner_rx, proxy_tx, forwarder, log_on_send, size_of);
 
     (inner_tx, proxy_rx)
 }
@@ -54,7 +79,7 @@ pub(crate) fn wrap_bounded<T: Send + 'static>(
     label: Option<String>,
     capacity: usize,
 ) -> (Sender<T>, Receiver<T>) {
-    wrap_bounded_impl(inner, source, label, capacity, |_| None)
+    wrap_bounded_impl(inner, source, label, capacity, |_| None, None)
 }
 
 /// Wrap a bounded crossbeam channel with logging enabled. Returns (outer_tx, outer_rx).
@@ -64,51 +89,56 @@ pub(crate) fn wrap_bounded_log<T: Send + std::fmt::Debug + 'static>(
     label: Option<String>,
     capacity: usize,
 ) -> (Sender<T>, Receiver<T>) {
-    wrap_bounded_impl(inner, source, label, capacity, |msg| {
-        Some(format!("{:?}", msg))
-    })
+    wrap_bounded_impl(
+        inner,
+        source,
+        label,
+        capacity,
+        |msg| Some(format!("{:?}", msg)),
+        None,
+    )
+}
+
+/// Wrap a bounded crossbeam channel recording the deep size of every message.
+pub(crate) fn wrap_bounded_size<T: Send + HeapSize + 'static>(
+    inner: (Sender<T>, Receiver<T>),
+    source: &'static str,
+    label: Option<String>,
+    capacity: usize,
+) -> (Sender<T>, Receiver<T>) {
+    wrap_bounded_impl(
+        inner,
+        source,
+        label,
+        capacity,
+        |_| None,
+        Some(deep_size::<T>),
+    )
 }
 
 /// Internal implementation for wrapping unbounded crossbeam channels with optional logging.
-/// Uses single proxy design: User -> [Original] -> Thread -> [Proxy unbounded] -> User
+/// Uses single proxy design: User -> [Original] -> Thread -> [Proxy rendezvous] -> User
 fn wrap_unbounded_impl<T, F>(
     inner: (Sender<T>, Receiver<T>),
     source: &'static str,
     label: Option<String>,
-    mut log_on_send: F,
+    log_on_send: F,
+    size_of: SizeFn<T>,
 ) -> (Sender<T>, Receiver<T>)
 where
     T: Send + 'static,
     F: FnMut(&T) -> Option<String> + Send + 'static,
 {
     let (inner_tx, inner_rx) = inner;
-    let (proxy_tx, proxy_rx) = crossbeam_channel::unbounded::<T>();
+    let (proxy_tx, proxy_rx) = crossbeam_channel::bounded::<T>(0);
 
-    let RegisteredChannel { id, stats_tx } =
-        register_channel::<T>(source, label, ChannelType::Unbounded);
-
-    // Single forwarder: inner_rx -> proxy_tx
-    std::thread::spawn(move || {
-        while let Ok(msg) = inner_rx.recv() {
-            let log = log_on_send(&msg);
-            let _ = stats_tx.send(ChannelEvent::MessageSent {
-                id,
-                log,
-                timestamp: Instant::now(),
-            });
-            // MessageReceived logged before user receives
-            if proxy_tx.send(msg).is_ok() {
-                let _ = stats_tx.send(ChannelEvent::MessageReceived {
-                    id,
-                    timestamp: Instant::now(),
-                });
-            } else {
-                // proxy_rx dropped
-                break;
-            }
-        }
-        let _ = stats_tx.send(ChannelEvent::Closed { id });
-    });
+    let forwarder = Forwarder::new(register_channel::<T>(
+        source,
+        label,
+        ChannelType::Unbounded,
+        size_of.is_some(),
+    ));
+    spawn_forwarder(inner_rx, proxy_tx, forwarder, log_on_send, size_of);
 
     (inner_tx, proxy_rx)
 }
@@ -119,7 +149,7 @@ pub(crate) fn wrap_unbounded<T: Send + 'static>(
     source: &'static str,
     label: Option<String>,
 ) -> (Sender<T>, Receiver<T>) {
-    wrap_unbounded_impl(inner, source, label, |_| None)
+    wrap_unbounded_impl(inner, source, label, |_| None, None)
 }
 
 /// Wrap an unbounded crossbeam channel with logging enabled. Returns (outer_tx, outer_rx).
@@ -128,7 +158,16 @@ pub(crate) fn wrap_unbounded_log<T: Send + std::fmt::Debug + 'static>(
     source: &'static str,
     label: Option<String>,
 ) -> (Sender<T>, Receiver<T>) {
-    wrap_unbounded_impl(inner, source, label, |msg| Some(format!("{:?}", msg)))
+    wrap_unbounded_impl(inner, source, label, |msg| Some(format!("{:?}", msg)), None)
+}
+
+/// Wrap an unbounded crossbeam channel recording the deep size of every message.
+pub(crate) fn wrap_unbounded_size<T: Send + HeapSize + 'static>(
+    inner: (Sender<T>, Receiver<T>),
+    source: &'static str,
+    label: Option<String>,
+) -> (Sender<T>, Receiver<T>) {
+    wrap_unbounded_impl(inner, source, label, |_| None, Some(deep_size::<T>))
 }
 
 use crate::channels::InstrumentChannel;
@@ -172,3 +211,24 @@ impl<T: Send + std::fmt::Debug + 'static> InstrumentChannelLog
         }
     }
 }
+
+use crate::channels::InstrumentChannelSize;
+
+impl<T: Send + HeapSize + 'static> InstrumentChannelSize
+    for (crossbeam_channel::Sender<T>, crossbeam_channel::Receiver<T>)
+{
+    type Output = (crossbeam_channel::Sender<T>, crossbeam_channel::Receiver<T>);
+    fn instrument_size(
+        self,
+        source: &'static str,
+        label: Option<String>,
+        _capacity: Option<usize>,
+    ) -> Self::Output {
+        // Crossbeam uses the same Sender/Receiver types for both bounded and unbounded
+        // We check the capacity to determine which type it is
+        match self.0.capacity() {
+            Some(capacity) => wrap_bounded_size(self, source, label, capacity),
+            None => wrap_unbounded_size(self, source, label),
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use flume::{Receiver, SendTimeoutError, Sender};
+
+use crate::channels::wrapper::common::{register_channel, Forwarder, SizeFn, SAMPLE_INTERVAL};
+use crate::channels::ChannelType;
+use crate::heap_size::{deep_size, HeapSize};
+
+/// Internal implementation for wrapping flume channels with optional logging.
+/// Uses single proxy design: User -> [Original] -> Thread -> [Proxy rendezvous] -> User
+///
+/// The proxy receiver is a regular `flume::Receiver`, so clones of it compete for messages
+/// and both its blocking and `_async` methods keep working.
+fn wrap_channel_impl<T, F>(
+    inner: (Sender<T>, Receiver<T>),
+    source: &'static str,
+    label: Option<String>,
+    mut log_on_send: F,
+    size_of: SizeFn<T>,
+) -> (Sender<T>, Receiver<T>)
+where
+    T: Send + 'static,
+    F: FnMut(&T) -> Option<String> + Send + 'static,
+{
+    let (inner_tx, inner_rx) = inner;
+    // Zero capacity, so a send to the proxy completes when the consumer takes the message
+    let (proxy_tx, proxy_rx) = flume::bounded::<T>(0);
+    let mut forwarder = match inner_tx.capacity() {
+        Some(capacity) => Forwarder::bounded(
+            register_channel::<T>(
+                source,
+                label,
+                ChannelType::Bounded(capacity),
+                size_of.is_some(),
+            ),
+            capacity,
+        ),
+        None => Forwarder::new(register_channel::<T>(
+            source,
+            label,
+            ChannelType::Unbounded,
+            size_of.is_some(),
+        )),
+    };
+
+    // Single forwarder: inner_rx -> proxy_tx
+    std::thread::spawn(move || {
+        'forward: loop {
+            forwarder.waiting(inner_rx.len());
+            let Ok(mut msg) = inner_rx.recv() else {
+                break;
+            };
+            forwarder.sent(log_on_send(&msg), size_of.map(|size_of| size_of(&msg)));
+            loop {
+                match proxy_tx.send_timeout(msg, SAMPLE_INTERVAL) {
+                    Ok(()) => break,
+                    Err(SendTimeoutError::Timeout(unsent)) => {
+                        msg = unsent;
+                        forwarder.waiting(inner_rx.len());
+                    }
+                    // all proxy receivers dropped
+                    Err(SendTimeoutError::Disconnected(_)) => break 'forward,
+                }
+            }
+            forwarder.received();
+        }
+        forwarder.closed();
+    });
+
+    (inner_tx, proxy_rx)
+}
+
+/// Wrap a flume channel with proxy ends. Returns (outer_tx, outer_rx).
+pub(crate) fn wrap_channel<T: Send + 'static>(
+    inner: (Sender<T>, Receiver<T>),
+    source: &'static str,
+    label: Option<String>,
+) -> (Sender<T>, Receiver<T>) {
+    wrap_channel_impl(inner, source, label, |_| None, None)
+}
+
+/// Wrap a flume channel with logging enabled. Returns (outer_tx, outer_rx).
+pub(crate) fn wrap_channel_log<T: Send + std::fmt::Debug + 'static>(
+    inner: (Sender<T>, Receiver<T>),
+    source: &'static str,
+    label: Option<String>,
+) -> (Sender<T>, Receiver<T>) {
+    wrap_channel_impl(inner, source, label, |msg| Some(format!("{:?}", msg)), None)
+}
+
+/// Wrap a flume channel recording the deep size of every message.
+pub(crate) fn wrap_channel_size<T: Send + HeapSize + 'static>(
+    inner: (Sender<T>, Receiver<T>),
+    source: &'static str,
+    label: Option<String>,
+) -> (Sender<T>, Receiver<T>) {
+    wrap_channel_impl(inner, source, label, |_| None, Some(deep_size::<T>))
+}
+
+use crate::channels::InstrumentChannel;
+
+impl<T: Send + 'static> InstrumentChannel for (flume::Sender<T>, flume::Receiver<T>) {
+    type Output = (flume::Sender<T>, flume::Receiver<T>);
+    fn instrument(
+        self,
+        source: &'static str,
+        label: Option<String>,
+        _capacity: Option<usize>,
+    ) -> Self::Output {
+        wrap_channel(self, source, label)
+    }
+}
+
+use crate::channels::InstrumentChannelLog;
+
+impl<T: Send + std::fmt::Debug + 'static> InstrumentChannelLog
+    for (flume::Sender<T>, flume::Receiver<T>)
+{
+    type Output = (flume::Sender<T>, flume::Receiver<T>);
+    fn instrument_log(
+        self,
+        source: &'static str,
+        label: Option<String>,
+        _capacity: Option<usize>,
+    ) -> Self::Output {
+        wrap_channel_log(self, source, label)
+    }
+}
+
+use crate::channels::InstrumentChannelSize;
+
+impl<T: Send + HeapSize + 'static> InstrumentChannelSize
+    for (flume::Sender<T>, flume::Receiver<T>)
+{
+    type Output = (flume::Sender<T>, flume::Receiver<T>);
+    fn instrument_size(
+        self,
+        source: &'static str,
+        label: Option<String>,
+        _capacity: Option<usize>,
+    ) -> Self::Output {
+        wrap_channel_size(self, source, label)
+    }
+}
@@ -3,8 +3,12 @@ use futures_channel::mpsc::{Receiver, Sender, UnboundedReceiver, UnboundedSender
 use futures_channel::oneshot;
 use futures_util::sink::SinkExt;
 
-use crate::channels::wrapper::common::{register_channel, Instant, RegisteredChannel};
+use crate::channels::wrapper::common::{
+    register_channel, register_handoff_channel, Forwarder, Instant, RegisteredChannel, SizeFn,
+    FIRST_SAMPLE, SAMPLE_INTERVAL,
+};
 use crate::channels::{ChannelEvent, ChannelType, RT};
+use crate::heap_size::{deep_size, HeapSize};
 
 /// Internal implementation for wrapping bounded futures channels with optional logging.
 fn wrap_channel_impl<T, F>(
@@ -13,38 +17,36 @@ fn wrap_channel_impl<T, F>(
     label: Option<String>,
     capacity: usize,
     mut get_msg_log: F,
+    size_of: SizeFn<T>,
 ) -> (Sender<T>, Receiver<T>)
 where
     T: Send + 'static,
     F: FnMut(&T) -> Option<String> + Send + 'static + Clone,
 {
     let (inner_tx, mut inner_rx) = inner;
-    let (mut proxy_tx, proxy_rx) = mpsc::channel::<T>(1);
+    // No buffer, so flushing a send to the proxy waits until the consumer takes the message
+    let (mut proxy_tx, proxy_rx) = mpsc::channel::<T>(0);
 
-    let RegisteredChannel { id, stats_tx } =
-        register_channel::<T>(source, label, ChannelType::Bounded(capacity));
+    let mut forwarder = Forwarder::new(register_channel::<T>(
+        source,
+        label,
+        ChannelType::Bounded(capacity),
+        size_of.is_some(),
+    ));
 
     // Single forwarder: inner_rx -> proxy_tx
     RT.spawn(async move {
         use futures_util::stream::StreamExt;
         while let Some(msg) = inner_rx.next().await {
-            let log = get_msg_log(&msg);
-            let _ = stats_tx.send(ChannelEvent::MessageSent {
-                id,
-                log,
-                timestamp: Instant::now(),
-            });
-            if proxy_tx.send(msg).await.is_ok() {
-                let _ = stats_tx.send(ChannelEvent::MessageReceived {
-                    id,
-                    timestamp: Instant::now(),
-                });
-            } else {
+            forwarder.sent(get_msg_log(&msg), size_of.map(|size_of| size_of(&msg)));
+            // A dropped receiver also counts as flushed
+            if proxy_tx.send(msg).await.is_err() || proxy_tx.is_closed() {
                 // proxy_rx dropped
                 break;
             }
+            forwarder.received();
         }
-        let _ = stats_tx.send(ChannelEvent::Closed { id });
+        forwarder.closed();
     });
 
     // User sends to inner_tx directly, receives from proxy_rx
@@ -59,7 +61,7 @@ pub(crate) fn wrap_channel<T: Send + 'static>(
     label: Option<String>,
     capacity: usize,
 ) -> (Sender<T>, Receiver<T>) {
-    wrap_channel_impl(inner, source, label, capacity, |_| None)
+    wrap_channel_impl(inner, source, label, capacity, |_| None, None)
 }
 
 /// Wrap a bounded futures channel with logging enabled. Returns (outer_tx, outer_rx).
@@ -69,18 +71,58 @@ pub(crate) fn wrap_channel_log<T: Send + std::fmt::Debug + 'static>(
     label: Option<String>,
     capacity: usize,
 ) -> (Sender<T>, Receiver<T>) {
-    wrap_channel_impl(inner, source, label, capacity, |msg| {
-        Some(format!("{:?}", msg))
-    })
+    wrap_channel_impl(
+        inner,
+        source,
+        label,
+        capacity,
+        |msg| Some(format!("{:?}", msg)),
+        None,
+    )
+}
+
+/// Wrap a bounded futures channel recording the deep size of every message.
+pub(crate) fn wrap_channel_size<T: Send + HeapSize + 'static>(
+    inner: (Sender<T>, Receiver<T>),
+    source: &'static str,
+    label: Option<String>,
+    capacity: usize,
+) -> (Sender<T>, Receiver<T>) {
+    wrap_channel_impl(
+        inner,
+        source,
+        label,
+        capacity,
+        |_| None,
+        Some(deep_size::<T>),
+    )
+}
+
+/// Reports the messages the consumer took from the proxy since the last call.
+fn report_taken<T>(
+    forwarder: &Forwarder,
+    proxy_tx: &UnboundedSender<T>,
+    pushed: usize,
+    taken: &mut usize,
+) {
+    let now_taken = pushed.saturating_sub(proxy_tx.len());
+    while *taken < now_taken {
+        forwarder.received();
+        *taken += 1;
+    }
 }
 
 /// Internal implementation for wrapping unbounded futures channels with optional logging.
 /// Uses single proxy design: User -> [Original] -> Thread -> [Proxy unbounded] -> User
+///
+/// Messages taken by the consumer are counted from the length of the proxy, sampled while it
+/// still holds any.
 fn wrap_unbounded_impl<T, F>(
     inner: (UnboundedSender<T>, UnboundedReceiver<T>),
     source: &'static str,
     label: Option<String>,
     mut get_msg_log: F,
+    size_of: SizeFn<T>,
 ) -> (UnboundedSender<T>, UnboundedReceiver<T>)
 where
     T: Send + 'static,
@@ -89,30 +131,56 @@ where
     let (inner_tx, mut inner_rx) = inner;
     let (proxy_tx, proxy_rx) = mpsc::unbounded::<T>();
 
-    let RegisteredChannel { id, stats_tx } =
-        register_channel::<T>(source, label, ChannelType::Unbounded);
+    let mut forwarder = Forwarder::new(register_channel::<T>(
+        source,
+        label,
+        ChannelType::Unbounded,
+        size_of.is_some(),
+    ));
 
     // Single forwarder: inner_rx -> proxy_tx
     RT.spawn(async move {
         use futures_util::stream::StreamExt;
-        while let Some(msg) = inner_rx.next().await {
-            let log = get_msg_log(&msg);
-            let _ = stats_tx.send(ChannelEvent::MessageSent {
-                id,
-                log,
-                timestamp: Instant::now(),
-            });
-            if proxy_tx.unbounded_send(msg).is_ok() {
-                let _ = stats_tx.send(ChannelEvent::MessageReceived {
-                    id,
-                    timestamp: Instant::now(),
-                });
+        // Messages handed to the proxy, and taken from it by the consumer
+        let (mut pushed, mut taken) = (0, 0);
+        let mut interval = FIRST_SAMPLE;
+
+        loop {
+            report_taken(&forwarder, &proxy_tx, pushed, &mut taken);
+            let msg = if taken < pushed {
+                tokio::select! {
+                    msg = inner_rx.next() => msg,
+                    _ = tokio::time::sleep(interval) => {
+                        interval = (interval * 2).min(SAMPLE_INTERVAL);
+                        continue;
+                    }
+                }
             } else {
+                inner_rx.next().await
+            };
+            let Some(msg) = msg else {
+                break; // all inner senders gone
+            };
+
+            forwarder.sent(get_msg_log(&msg), size_of.map(|size_of| size_of(&msg)));
+            if proxy_tx.unbounded_send(msg).is_err() {
                 // proxy_rx dropped
                 break;
             }
+            pushed += 1;
+            interval = FIRST_SAMPLE;
+        }
+
+        // Keep reporting the messages left in the proxy until the consumer takes them
+        loop {
+            report_taken(&forwarder, &proxy_tx, pushed, &mut taken);
+            if taken == pushed || proxy_tx.is_closed() {
+                break;
+            }
+            tokio::time::sleep(interval).await;
+            interval = (interval * 2).min(SAMPLE_INTERVAL);
         }
-        let _ = stats_tx.send(ChannelEvent::Closed { id });
+        forwarder.closed();
     });
 
     // User sends to inner_tx directly, receives from proxy_rx
@@ -125,7 +193,7 @@ pub(crate) fn wrap_unbounded<T: Send + 'static>(
     source: &'static str,
     label: Option<String>,
 ) -> (UnboundedSender<T>, UnboundedReceiver<T>) {
-    wrap_unbounded_impl(inner, source, label, |_| None)
+    wrap_unbounded_impl(inner, source, label, |_| None, None)
 }
 
 /// Wrap an unbounded futures channel with logging enabled. Returns (outer_tx, outer_rx).
@@ -134,7 +202,16 @@ pub(crate) fn wrap_unbounded_log<T: Send + std::fmt::Debug + 'static>(
     source: &'static str,
     label: Option<String>,
 ) -> (UnboundedSender<T>, UnboundedReceiver<T>) {
-    wrap_unbounded_impl(inner, source, label, |msg| Some(format!("{:?}", msg)))
+    wrap_unbounded_impl(inner, source, label, |msg| Some(format!("{:?}", msg)), None)
+}
+
+/// Wrap an unbounded futures channel recording the deep size of every message.
+pub(crate) fn wrap_unbounded_size<T: Send + HeapSize + 'static>(
+    inner: (UnboundedSender<T>, UnboundedReceiver<T>),
+    source: &'static str,
+    label: Option<String>,
+) -> (UnboundedSender<T>, UnboundedReceiver<T>) {
+    wrap_unbounded_impl(inner, source, label, |_| None, Some(deep_size::<T>))
 }
 
 /// Internal implementation for wrapping oneshot futures channels with optional logging.
@@ -143,6 +220,7 @@ fn wrap_oneshot_impl<T, F>(
     source: &'static str,
     label: Option<String>,
     mut get_msg_log: F,
+    size_of: SizeFn<T>,
 ) -> (oneshot::Sender<T>, oneshot::Receiver<T>)
 where
     T: Send + 'static,
@@ -152,7 +230,7 @@ where
     let (proxy_tx, proxy_rx) = oneshot::channel::<T>();
 
     let RegisteredChannel { id, stats_tx } =
-        register_channel::<T>(source, label, ChannelType::Oneshot);
+        register_handoff_channel::<T>(source, label, ChannelType::Oneshot, size_of.is_some());
 
     // Single forwarder: inner_rx -> proxy_tx
     RT.spawn(async move {
@@ -165,16 +243,20 @@ where
                 match msg {
                     Ok(msg) => {
                         let log = get_msg_log(&msg);
+                        let size = size_of.map(|size_of| size_of(&msg));
                         let _ = stats_tx.send(ChannelEvent::MessageSent {
                             id,
                             log,
+                            size,
                             timestamp: Instant::now(),
+                            endpoint: None,
                         });
                         let _ = stats_tx.send(ChannelEvent::Notified { id });
                         if proxy_tx.take().unwrap().send(msg).is_ok() {
                             let _ = stats_tx.send(ChannelEvent::MessageReceived {
                                 id,
                                 timestamp: Instant::now(),
+                                endpoint: None,
                             });
                             message_completed = true;
                         }
@@ -205,7 +287,7 @@ pub(crate) fn wrap_oneshot<T: Send + 'static>(
     source: &'static str,
     label: Option<String>,
 ) -> (oneshot::Sender<T>, oneshot::Receiver<T>) {
-    wrap_oneshot_impl(inner, source, label, |_| None)
+    wrap_oneshot_impl(inner, source, label, |_| None, None)
 }
 
 /// Wrap a oneshot futures channel with logging enabled. Returns (outer_tx, outer_rx).
@@ -214,7 +296,16 @@ pub(crate) fn wrap_oneshot_log<T: Send + std::fmt::Debug + 'static>(
     source: &'static str,
     label: Option<String>,
 ) -> (oneshot::Sender<T>, oneshot::Receiver<T>) {
-    wrap_oneshot_impl(inner, source, label, |msg| Some(format!("{:?}", msg)))
+    wrap_oneshot_impl(inner, source, label, |msg| Some(format!("{:?}", msg)), None)
+}
+
+/// Wrap a oneshot futures channel recording the deep size of every message.
+pub(crate) fn wrap_oneshot_size<T: Send + HeapSize + 'static>(
+    inner: (oneshot::Sender<T>, oneshot::Receiver<T>),
+    source: &'static str,
+    label: Option<String>,
+) -> (oneshot::Sender<T>, oneshot::Receiver<T>) {
+    wrap_oneshot_impl(inner, source, label, |_| None, Some(deep_size::<T>))
 }
 
 use crate::channels::InstrumentChannel;
@@ -346,3 +437,68 @@ impl<T: Send + std::fmt::Debug + 'static> InstrumentChannelLog
         wrap_oneshot_log(self, source, label)
     }
 }
+
+use crate::channels::InstrumentChannelSize;
+
+impl<T: Send + HeapSize + 'static> InstrumentChannelSize
+    for (
+        futures_channel::mpsc::Sender<T>,
+        futures_channel::mpsc::Receiver<T>,
+    )
+{
+    type Output = (
+        futures_channel::mpsc::Sender<T>,
+        futures_channel::mpsc::Receiver<T>,
+    );
+    fn instrument_size(
+        self,
+        source: &'static str,
+        label: Option<String>,
+        capacity: Option<usize>,
+    ) -> Self::Output {
+        if capacity.is_none() {
+            panic!("Capacity is required for bounded futures channels, because they don't expose their capacity in a public API");
+        }
+        wrap_channel_size(self, source, label, capacity.unwrap())
+    }
+}
+
+impl<T: Send + HeapSize + 'static> InstrumentChannelSize
+    for (
+        futures_channel::mpsc::UnboundedSender<T>,
+        futures_channel::mpsc::UnboundedReceiver<T>,
+    )
+{
+    type Output = (
+        futures_channel::mpsc::UnboundedSender<T>,
+        futures_channel::mpsc::UnboundedReceiver<T>,
+    );
+    fn instrument_size(
+        self,
+        source: &'static str,
+        label: Option<String>,
+        _capacity: Option<usize>,
+    ) -> Self::Output {
+        wrap_unbounded_size(self, source, label)
+    }
+}
+
+impl<T: Send + HeapSize + 'static> InstrumentChannelSize
+    for (
+        futures_channel::oneshot::Sender<T>,
+        futures_channel::oneshot::Receiver<T>,
+    )
+{
+    type Output = (
+        futures_channel::oneshot::Sender<T>,
+        futures_channel::oneshot::Receiver<T>,
+    );
+    fn instrument_size(
+        self,
+        source: &'static str,
+        label: Option<String>,
+        _capacity: Option<usize>,
+    ) -> Self::Output {
+        wrap_oneshot_size(self, source, label)
+    }
+}
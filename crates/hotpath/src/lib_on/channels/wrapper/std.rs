@@ -1,49 +1,96 @@
 use std::sync::mpsc::{self, Receiver, Sender, SyncSender};
 
-use crate::channels::wrapper::common::{register_channel, Instant, RegisteredChannel};
-use crate::channels::{ChannelEvent, ChannelType};
+use crossbeam_channel::{Receiver as CbReceiver, Sender as CbSender, TrySendError};
 
-/// Internal implementation for wrapping bounded std channels with optional logging.
-fn wrap_sync_channel_impl<T, F>(
-    inner: (SyncSender<T>, Receiver<T>),
-    source: &'static str,
-    label: Option<String>,
-    capacity: usize,
+use crate::channels::wrapper::common::{register_channel, Forwarder, Instant, SizeFn};
+use crate::channels::ChannelType;
+use crate::heap_size::{deep_size, HeapSize};
+
+/// Forwards `inner_rx` to the consumer through two threads. The first pulls every message as
+/// soon as it is sent into `staging`, std receivers having no `len` to sample the original
+/// channel with. The second hands them to a zero-capacity proxy, so each send to the proxy
+/// completes when the consumer takes the message. Returns the proxy receiver.
+fn spawn_forwarders<T, F>(
+    inner_rx: Receiver<T>,
+    staging: (CbSender<T>, CbReceiver<T>),
+    forwarder: Forwarder,
     mut log_on_send: F,
-) -> (SyncSender<T>, Receiver<T>)
+    size_of: SizeFn<T>,
+) -> Receiver<T>
 where
     T: Send + 'static,
     F: FnMut(&T) -> Option<String> + Send + 'static,
 {
-    let (inner_tx, inner_rx) = inner;
-    let (proxy_tx, proxy_rx) = mpsc::sync_channel::<T>(1);
+    let (staging_tx, staging_rx) = staging;
+    let (proxy_tx, proxy_rx) = mpsc::sync_channel::<T>(0);
 
-    let RegisteredChannel { id, stats_tx } =
-        register_channel::<T>(source, label, ChannelType::Bounded(capacity));
-
-    // Single forwarder: inner_rx -> proxy_tx
+    let mut puller = forwarder.clone();
     std::thread::spawn(move || {
         while let Ok(msg) = inner_rx.recv() {
-            let log = log_on_send(&msg);
-            let _ = stats_tx.send(ChannelEvent::MessageSent {
-                id,
-                log,
-                timestamp: Instant::now(),
-            });
-            if proxy_tx.send(msg).is_ok() {
-                let _ = stats_tx.send(ChannelEvent::MessageReceived {
-                    id,
-                    timestamp: Instant::now(),
-                });
-            } else {
+            puller.sent(log_on_send(&msg), size_of.map(|size_of| size_of(&msg)));
+            let sent = match staging_tx.try_send(msg) {
+                Ok(()) => Ok(()),
+                // Staging is full, so producers block on the front channel behind this send
+                Err(TrySendError::Full(msg)) => {
+                    let started = Instant::now();
+                    let sent = staging_tx.send(msg);
+                    puller.send_blocked(Instant::now().duration_since(started));
+                    sent.map_err(|_| ())
+                }
+                Err(TrySendError::Disconnected(_)) => Err(()),
+            };
+            if sent.is_err() {
                 // proxy_rx dropped
                 break;
             }
         }
-        let _ = stats_tx.send(ChannelEvent::Closed { id });
     });
 
-    (inner_tx, proxy_rx)
+    std::thread::spawn(move || {
+        while let Ok(msg) = staging_rx.recv() {
+            if proxy_tx.send(msg).is_err() {
+                // proxy_rx dropped
+                break;
+            }
+            forwarder.received();
+        }
+        forwarder.closed();
+    });
+
+    proxy_rx
+}
+
+/// Internal implementation for wrapping bounded std channels with optional logging.
+/// User -> [Front] -> Thread -> [Staging] -> Thread -> [Proxy rendezvous] -> User
+///
+/// The original channel is replaced with a one-slot front channel, so the backlog builds up in
+/// `staging` where the forwarder sees it. Together with the messages held by the two threads,
+/// it takes `capacity` plus two messages before a send blocks.
+fn wrap_sync_channel_impl<T, F>(
+    inner: (SyncSender<T>, Receiver<T>),
+    source: &'static str,
+    label: Option<String>,
+    capacity: usize,
+    log_on_send: F,
+    size_of: SizeFn<T>,
+) -> (SyncSender<T>, Receiver<T>)
+where
+    T: Send + 'static,
+    F: FnMut(&T) -> Option<String> + Send + 'static,
+{
+    drop(inner);
+    let (front_tx, front_rx) = mpsc::sync_channel::<T>(1);
+
+    let forwarder = Forwarder::new(register_channel::<T>(
+        source,
+        label,
+        ChannelType::Bounded(capacity),
+        size_of.is_some(),
+    ));
+    let staging = crossbeam_channel::bounded(capacity.saturating_sub(1));
+    let proxy_rx = spawn_forwarders(front_rx, staging, forwarder, log_on_send, size_of);
+
+    (front_tx, proxy_rx)
 }
 
 /// Wrap a bounded std channel with proxy ends. Returns (outer_tx, outer_rx).
@@ -54,7 +101,7 @@ pub(crate) fn wrap_sync_channel<T: Send + 'static>(
     label: Option<String>,
     capacity: usize,
 ) -> (SyncSender<T>, Receiver<T>) {
-    wrap_sync_channel_impl(inner, source, label, capacity, |_| None)
+    wrap_sync_channel_impl(inner, source, label, capacity, |_| None, None)
 }
 
 /// Wrap a bounded std channel with logging enabled. Returns (outer_tx, outer_rx).
@@ -64,50 +111,56 @@ pub(crate) fn wrap_sync_channel_log<T: Send + std::fmt::Debug + 'static>(
     label: Option<String>,
     capacity: usize,
 ) -> (SyncSender<T>, Receiver<T>) {
-    wrap_sync_channel_impl(inner, source, label, capacity, |msg| {
-        Some(format!("{:?}", msg))
-    })
+    wrap_sync_channel_impl(
+        inner,
+        source,
+        label,
+        capacity,
+        |msg| Some(format!("{:?}", msg)),
+        None,
+    )
+}
+
+/// Wrap a bounded std channel recording the deep size of every message.
+pub(crate) fn wrap_sync_channel_size<T: Send + HeapSize + 'static>(
+    inner: (SyncSender<T>, Receiver<T>),
+    source: &'static str,
+    label: Option<String>,
+    capacity: usize,
+) -> (SyncSender<T>, Receiver<T>) {
+    wrap_sync_channel_impl(
+        inner,
+        source,
+        label,
+        capacity,
+        |_| None,
+        Some(deep_size::<T>),
+    )
 }
 
 /// Internal implementation for wrapping unbounded std channels with optional logging.
-/// Uses single proxy design: User -> [Original] -> Thread -> [Proxy unbounded] -> User
+/// User -> [Original] -> Thread -> [Staging] -> Thread -> [Proxy rendezvous] -> User
 fn wrap_channel_impl<T, F>(
     inner: (Sender<T>, Receiver<T>),
     source: &'static str,
     label: Option<String>,
-    mut log_on_send: F,
+    log_on_send: F,
+    size_of: SizeFn<T>,
 ) -> (Sender<T>, Receiver<T>)
 where
     T: Send + 'static,
     F: FnMut(&T) -> Option<String> + Send + 'static,
 {
     let (inner_tx, inner_rx) = inner;
-    let (proxy_tx, proxy_rx) = mpsc::channel::<T>();
 
-    let RegisteredChannel { id, stats_tx } =
-        register_channel::<T>(source, label, ChannelType::Unbounded);
-
-    // Single forwarder: inner_rx -> proxy_tx
-    std::thread::spawn(move || {
-        while let Ok(msg) = inner_rx.recv() {
-            let log = log_on_send(&msg);
-            let _ = stats_tx.send(ChannelEvent::MessageSent {
-                id,
-                log,
-                timestamp: Instant::now(),
-            });
-            if proxy_tx.send(msg).is_ok() {
-                let _ = stats_tx.send(ChannelEvent::MessageReceived {
-                    id,
-                    timestamp: Instant::now(),
-                });
-            } else {
-                // proxy_rx dropped
-                break;
-            }
-        }
-        let _ = stats_tx.send(ChannelEvent::Closed { id });
-    });
+    let forwarder = Forwarder::new(register_channel::<T>(
+        source,
+        label,
+        ChannelType::Unbounded,
+        size_of.is_some(),
+    ));
+    let staging = crossbeam_channel::unbounded();
+    let proxy_rx = spawn_forwarders(inner_rx, staging, forwarder, log_on_send, size_of);
 
     (inner_tx, proxy_rx)
 }
@@ -118,7 +171,7 @@ pub(crate) fn wrap_channel<T: Send + 'static>(
     source: &'static str,
     label: Option<String>,
 ) -> (Sender<T>, Receiver<T>) {
-    wrap_channel_impl(inner, source, label, |_| None)
+    wrap_channel_impl(inner, source, label, |_| None, None)
 }
 
 /// Wrap an unbounded std channel with logging enabled. Returns (outer_tx, outer_rx).
@@ -127,7 +180,16 @@ pub(crate) fn wrap_channel_log<T: Send + std::fmt::Debug + 'static>(
     source: &'static str,
     label: Option<String>,
 ) -> (Sender<T>, Receiver<T>) {
-    wrap_channel_impl(inner, source, label, |msg| Some(format!("{:?}", msg)))
+    wrap_channel_impl(inner, source, label, |msg| Some(format!("{:?}", msg)), None)
+}
+
+/// Wrap an unbounded std channel recording the deep size of every message.
+pub(crate) fn wrap_channel_size<T: Send + HeapSize + 'static>(
+    inner: (Sender<T>, Receiver<T>),
+    source: &'static str,
+    label: Option<String>,
+) -> (Sender<T>, Receiver<T>) {
+    wrap_channel_impl(inner, source, label, |_| None, Some(deep_size::<T>))
 }
 
 use crate::channels::InstrumentChannel;
@@ -195,3 +257,36 @@ impl<T: Send + std::fmt::Debug + 'static> InstrumentChannelLog
         wrap_sync_channel_log(self, source, label, capacity.unwrap())
     }
 }
+
+use crate::channels::InstrumentChannelSize;
+
+impl<T: Send + HeapSize + 'static> InstrumentChannelSize
+    for (std::sync::mpsc::Sender<T>, std::sync::mpsc::Receiver<T>)
+{
+    type Output = (std::sync::mpsc::Sender<T>, std::sync::mpsc::Receiver<T>);
+    fn instrument_size(
+        self,
+        source: &'static str,
+        label: Option<String>,
+        _capacity: Option<usize>,
+    ) -> Self::Output {
+        wrap_channel_size(self, source, label)
+    }
+}
+
+impl<T: Send + HeapSize + 'static> InstrumentChannelSize
+    for (std::sync::mpsc::SyncSender<T>, std::sync::mpsc::Receiver<T>)
+{
+    type Output = (std::sync::mpsc::SyncSender<T>, std::sync::mpsc::Receiver<T>);
+    fn instrument_size(
+        self,
+        source: &'static str,
+        label: Option<String>,
+        capacity: Option<usize>,
+    ) -> Self::Output {
+        if capacity.is_none() {
+            panic!("Capacity is required for bounded std channels, because they don't expose their capacity in a public API");
+        }
+        wrap_sync_channel_size(self, source, label, capacity.unwrap())
+    }
+}
@@ -1,9 +1,13 @@
 use tokio::sync::mpsc;
 use tokio::sync::mpsc::{Receiver, Sender, UnboundedReceiver, UnboundedSender};
-use tokio::sync::oneshot;
+use tokio::sync::{broadcast, oneshot, watch};
 
-use crate::channels::wrapper::common::{register_channel, Instant, RegisteredChannel};
+use crate::channels::wrapper::common::{
+    register_channel, register_handoff_channel, Forwarder, Instant, RegisteredChannel, SizeFn,
+    SAMPLE_INTERVAL,
+};
 use crate::channels::{ChannelEvent, ChannelType, RT};
+use crate::heap_size::{deep_size, HeapSize};
 
 /// Internal implementation for wrapping bounded Tokio channels with optional logging.
 fn wrap_channel_impl<T, F>(
@@ -11,6 +15,7 @@ fn wrap_channel_impl<T, F>(
     source: &'static str,
     label: Option<String>,
     mut log_on_send: F,
+    size_of: SizeFn<T>,
 ) -> (Sender<T>, Receiver<T>)
 where
     T: Send + 'static,
@@ -20,42 +25,53 @@ where
     let capacity = inner_tx.capacity();
     let (proxy_tx, proxy_rx) = mpsc::channel::<T>(1);
 
-    let RegisteredChannel { id, stats_tx } =
-        register_channel::<T>(source, label, ChannelType::Bounded(capacity));
+    let mut forwarder = Forwarder::bounded(
+        register_channel::<T>(
+            source,
+            label,
+            ChannelType::Bounded(capacity),
+            size_of.is_some(),
+        ),
+        capacity,
+    );
 
-    // Single forwarder: inner_rx -> proxy_tx
+    // Single forwarder: inner_rx -> proxy_tx. The proxy holds a single message, so reserving
+    // its slot completes when the consumer took the previous one.
     RT.spawn(async move {
+        let mut in_proxy = false;
+
         loop {
-            tokio::select! {
-                msg = inner_rx.recv() => {
-                    match msg {
-                        Some(msg) => {
-                            let log = log_on_send(&msg);
-                            let _ = stats_tx.send(ChannelEvent::MessageSent {
-                                id,
-                                log,
-                                timestamp: Instant::now(),
-                            });
-                            if proxy_tx.send(msg).await.is_ok() {
-                                let _ = stats_tx.send(ChannelEvent::MessageReceived {
-                                    id,
-                                    timestamp: Instant::now(),
-                                });
-                            } else {
-                                // proxy_rx dropped
-                                break;
-                            }
-                        }
-                        None => break, // inner_tx dropped (all senders gone)
+            let permit = loop {
+                tokio::select! {
+                    permit = proxy_tx.reserve() => break permit,
+                    _ = tokio::time::sleep(SAMPLE_INTERVAL) => {
+                        forwarder.waiting(inner_rx.len());
                     }
                 }
+            };
+            let Ok(permit) = permit else {
+                break; // proxy_rx dropped
+            };
+            if in_proxy {
+                forwarder.received();
+            }
+
+            forwarder.waiting(inner_rx.len());
+            let msg = tokio::select! {
+                msg = inner_rx.recv() => msg,
                 _ = proxy_tx.closed() => {
                     // proxy_rx was dropped, close the channel
                     break;
                 }
-            }
+            };
+            let Some(msg) = msg else {
+                break; // inner_tx dropped (all senders gone)
+            };
+            forwarder.sent(log_on_send(&msg), size_of.map(|size_of| size_of(&msg)));
+            permit.send(msg);
+            in_proxy = true;
         }
-        let _ = stats_tx.send(ChannelEvent::Closed { id });
+        forwarder.closed();
     });
 
     (inner_tx, proxy_rx)
@@ -68,7 +84,7 @@ pub(crate) fn wrap_channel<T: Send + 'static>(
     source: &'static str,
     label: Option<String>,
 ) -> (Sender<T>, Receiver<T>) {
-    wrap_channel_impl(inner, source, label, |_| None)
+    wrap_channel_impl(inner, source, label, |_| None, None)
 }
 
 /// Wrap a bounded Tokio channel with logging enabled. Returns (outer_tx, outer_rx).
@@ -77,7 +93,16 @@ pub(crate) fn wrap_channel_log<T: Send + std::fmt::Debug + 'static>(
     source: &'static str,
     label: Option<String>,
 ) -> (Sender<T>, Receiver<T>) {
-    wrap_channel_impl(inner, source, label, |msg| Some(format!("{:?}", msg)))
+    wrap_channel_impl(inner, source, label, |msg| Some(format!("{:?}", msg)), None)
+}
+
+/// Wrap a bounded Tokio channel recording the deep size of every message.
+pub(crate) fn wrap_channel_size<T: Send + HeapSize + 'static>(
+    inner: (Sender<T>, Receiver<T>),
+    source: &'static str,
+    label: Option<String>,
+) -> (Sender<T>, Receiver<T>) {
+    wrap_channel_impl(inner, source, label, |_| None, Some(deep_size::<T>))
 }
 
 /// Internal implementation for wrapping unbounded Tokio channels with optional logging.
@@ -87,6 +112,7 @@ fn wrap_unbounded_impl<T, F>(
     source: &'static str,
     label: Option<String>,
     mut log_on_send: F,
+    size_of: SizeFn<T>,
 ) -> (UnboundedSender<T>, UnboundedReceiver<T>)
 where
     T: Send + 'static,
@@ -96,7 +122,7 @@ where
     let (proxy_tx, proxy_rx) = mpsc::unbounded_channel::<T>();
 
     let RegisteredChannel { id, stats_tx } =
-        register_channel::<T>(source, label, ChannelType::Unbounded);
+        register_handoff_channel::<T>(source, label, ChannelType::Unbounded, size_of.is_some());
 
     // Single forwarder: inner_rx -> proxy_tx
     RT.spawn(async move {
@@ -106,15 +132,19 @@ where
                     match msg {
                         Some(msg) => {
                             let log = log_on_send(&msg);
+                            let size = size_of.map(|size_of| size_of(&msg));
                             let _ = stats_tx.send(ChannelEvent::MessageSent {
                                 id,
                                 log,
+                                size,
                                 timestamp: Instant::now(),
+                                endpoint: None,
                             });
                             if proxy_tx.send(msg).is_ok() {
                                 let _ = stats_tx.send(ChannelEvent::MessageReceived {
                                     id,
                                     timestamp: Instant::now(),
+                                    endpoint: None,
                                 });
                             } else {
                                 // proxy_rx dropped
@@ -142,7 +172,7 @@ pub(crate) fn wrap_unbounded<T: Send + 'static>(
     source: &'static str,
     label: Option<String>,
 ) -> (UnboundedSender<T>, UnboundedReceiver<T>) {
-    wrap_unbounded_impl(inner, source, label, |_| None)
+    wrap_unbounded_impl(inner, source, label, |_| None, None)
 }
 
 /// Wrap an unbounded Tokio channel with logging enabled. Returns (outer_tx, outer_rx).
@@ -151,7 +181,16 @@ pub(crate) fn wrap_unbounded_log<T: Send + std::fmt::Debug + 'static>(
     source: &'static str,
     label: Option<String>,
 ) -> (UnboundedSender<T>, UnboundedReceiver<T>) {
-    wrap_unbounded_impl(inner, source, label, |msg| Some(format!("{:?}", msg)))
+    wrap_unbounded_impl(inner, source, label, |msg| Some(format!("{:?}", msg)), None)
+}
+
+/// Wrap an unbounded Tokio channel recording the deep size of every message.
+pub(crate) fn wrap_unbounded_size<T: Send + HeapSize + 'static>(
+    inner: (UnboundedSender<T>, UnboundedReceiver<T>),
+    source: &'static str,
+    label: Option<String>,
+) -> (UnboundedSender<T>, UnboundedReceiver<T>) {
+    wrap_unbounded_impl(inner, source, label, |_| None, Some(deep_size::<T>))
 }
 
 /// Internal implementation for wrapping oneshot Tokio channels with optional logging.
@@ -160,6 +199,7 @@ fn wrap_oneshot_impl<T, F>(
     source: &'static str,
     label: Option<String>,
     mut log_on_send: F,
+    size_of: SizeFn<T>,
 ) -> (oneshot::Sender<T>, oneshot::Receiver<T>)
 where
     T: Send + 'static,
@@ -169,7 +209,7 @@ where
     let (proxy_tx, proxy_rx) = oneshot::channel::<T>();
 
     let RegisteredChannel { id, stats_tx } =
-        register_channel::<T>(source, label, ChannelType::Oneshot);
+        register_handoff_channel::<T>(source, label, ChannelType::Oneshot, size_of.is_some());
 
     // Single forwarder: inner_rx -> proxy_tx
     RT.spawn(async move {
@@ -182,16 +222,20 @@ where
                 match msg {
                     Ok(msg) => {
                         let log = log_on_send(&msg);
+                        let size = size_of.map(|size_of| size_of(&msg));
                         let _ = stats_tx.send(ChannelEvent::MessageSent {
                             id,
                             log,
+                            size,
                             timestamp: Instant::now(),
+                            endpoint: None,
                         });
                         let _ = stats_tx.send(ChannelEvent::Notified { id });
                         if proxy_tx.take().unwrap().send(msg).is_ok() {
                             let _ = stats_tx.send(ChannelEvent::MessageReceived {
                                 id,
                                 timestamp: Instant::now(),
+                                endpoint: None,
                             });
                             message_completed = true;
                         }
@@ -221,7 +265,7 @@ pub(crate) fn wrap_oneshot<T: Send + 'static>(
     source: &'static str,
     label: Option<String>,
 ) -> (oneshot::Sender<T>, oneshot::Receiver<T>) {
-    wrap_oneshot_impl(inner, source, label, |_| None)
+    wrap_oneshot_impl(inner, source, label, |_| None, None)
 }
 
 /// Wrap a oneshot Tokio channel with logging enabled. Returns (outer_tx, outer_rx).
@@ -230,7 +274,152 @@ pub(crate) fn wrap_oneshot_log<T: Send + std::fmt::Debug + 'static>(
     source: &'static str,
     label: Option<String>,
 ) -> (oneshot::Sender<T>, oneshot::Receiver<T>) {
-    wrap_oneshot_impl(inner, source, label, |msg| Some(format!("{:?}", msg)))
+    wrap_oneshot_impl(inner, source, label, |msg| Some(format!("{:?}", msg)), None)
+}
+
+/// Wrap a oneshot Tokio channel recording the deep size of every message.
+pub(crate) fn wrap_oneshot_size<T: Send + HeapSize + 'static>(
+    inner: (oneshot::Sender<T>, oneshot::Receiver<T>),
+    source: &'static str,
+    label: Option<String>,
+) -> (oneshot::Sender<T>, oneshot::Receiver<T>) {
+    wrap_oneshot_impl(inner, source, label, |_| None, Some(deep_size::<T>))
+}
+
+/// How often broadcast and watch monitors sample receiver state.
+const STATE_SAMPLE_INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// Instrument a broadcast channel. Returns the original ends.
+///
+/// Receivers are created with `Sender::subscribe`, so they can't be proxied, and a hidden
+/// receiver would change what `send` and `receiver_count` return. Instead the sender is
+/// sampled through a weak handle: `receiver_count()` gives the subscriber count and `len()`
+/// the messages the slowest receiver has not seen yet. Messages themselves are not observed.
+pub(crate) fn wrap_broadcast<T: Clone + Send + 'static>(
+    inner: (broadcast::Sender<T>, broadcast::Receiver<T>),
+    source: &'static str,
+    label: Option<String>,
+    capacity: Option<usize>,
+) -> (broadcast::Sender<T>, broadcast::Receiver<T>) {
+    let (inner_tx, inner_rx) = inner;
+    let weak_tx = inner_tx.downgrade();
+
+    let RegisteredChannel { id, stats_tx } =
+        register_handoff_channel::<T>(source, label, ChannelType::Broadcast(capacity), false);
+
+    RT.spawn(async move {
+        let mut ticker = tokio::time::interval(STATE_SAMPLE_INTERVAL);
+
+        loop {
+            ticker.tick().await;
+            // The upgraded sender is dropped before the next tick, so it never keeps the
+            // channel open on its own for longer than a sample
+            let Some(tx) = weak_tx.upgrade() else {
+                break; // all senders gone
+            };
+            let _ = stats_tx.send(ChannelEvent::BroadcastState {
+                id,
+                subscribers: tx.receiver_count() as u64,
+                lag: tx.len() as u64,
+            });
+        }
+        let _ = stats_tx.send(ChannelEvent::Closed { id });
+    });
+
+    (inner_tx, inner_rx)
+}
+
+/// Instrument a broadcast Tokio channel. Messages are not observed, so nothing is logged.
+pub(crate) fn wrap_broadcast_log<T: Clone + Send + std::fmt::Debug + 'static>(
+    inner: (broadcast::Sender<T>, broadcast::Receiver<T>),
+    source: &'static str,
+    label: Option<String>,
+    capacity: Option<usize>,
+) -> (broadcast::Sender<T>, broadcast::Receiver<T>) {
+    wrap_broadcast(inner, source, label, capacity)
+}
+
+/// Internal implementation for wrapping watch Tokio channels with optional logging.
+/// Every version published on the inner channel is forwarded to a proxy watch channel,
+/// so `MessageSent` counts version changes. Receivers cloned from the returned end
+/// are reported as subscribers.
+fn wrap_watch_impl<T, F>(
+    inner: (watch::Sender<T>, watch::Receiver<T>),
+    source: &'static str,
+    label: Option<String>,
+    mut log_on_send: F,
+) -> (watch::Sender<T>, watch::Receiver<T>)
+where
+    T: Clone + Send + Sync + 'static,
+    F: FnMut(&T) -> Option<String> + Send + 'static,
+{
+    let (inner_tx, mut inner_rx) = inner;
+    let initial = inner_rx.borrow_and_update().clone();
+    let (proxy_tx, proxy_rx) = watch::channel(initial);
+
+    let RegisteredChannel { id, stats_tx } =
+        register_handoff_channel::<T>(source, label, ChannelType::Watch, false);
+
+    // Single forwarder: inner_rx -> proxy_tx
+    RT.spawn(async move {
+        let mut ticker = tokio::time::interval(STATE_SAMPLE_INTERVAL);
+
+        loop {
+            tokio::select! {
+                changed = inner_rx.changed() => {
+                    if changed.is_err() {
+                        break; // inner_tx dropped
+                    }
+                    let value = inner_rx.borrow_and_update().clone();
+                    let log = log_on_send(&value);
+                    let _ = stats_tx.send(ChannelEvent::MessageSent {
+                        id,
+                        log,
+                        size: None,
+                        timestamp: Instant::now(),
+                        endpoint: None,
+                    });
+                    proxy_tx.send_replace(value);
+                    let _ = stats_tx.send(ChannelEvent::MessageReceived {
+                        id,
+                        timestamp: Instant::now(),
+                        endpoint: None,
+                    });
+                }
+                _ = proxy_tx.closed() => {
+                    // all proxy receivers were dropped, close the channel
+                    break;
+                }
+                _ = ticker.tick() => {
+                    let _ = stats_tx.send(ChannelEvent::WatchState {
+                        id,
+                        subscribers: proxy_tx.receiver_count() as u64,
+                    });
+                }
+            }
+        }
+        let _ = stats_tx.send(ChannelEvent::Closed { id });
+    });
+
+    (inner_tx, proxy_rx)
+}
+
+/// Wrap a watch channel with a proxy receiver. Returns (outer_tx, outer_rx).
+pub(crate) fn wrap_watch<T: Clone + Send + Sync + 'static>(
+    inner: (watch::Sender<T>, watch::Receiver<T>),
+    source: &'static str,
+    label: Option<String>,
+) -> (watch::Sender<T>, watch::Receiver<T>) {
+    wrap_watch_impl(inner, source, label, |_| None)
+}
+
+/// Wrap a watch Tokio channel with logging enabled. Returns (outer_tx, outer_rx).
+pub(crate) fn wrap_watch_log<T: Clone + Send + Sync + std::fmt::Debug + 'static>(
+    inner: (watch::Sender<T>, watch::Receiver<T>),
+    source: &'static str,
+    label: Option<String>,
+) -> (watch::Sender<T>, watch::Receiver<T>) {
+    wrap_watch_impl(inner, source, label, |msg| Some(format!("{:?}", msg)))
 }
 
 use crate::channels::InstrumentChannel;
@@ -271,6 +460,34 @@ impl<T: Send + 'static> InstrumentChannel for (oneshot::Sender<T>, oneshot::Rece
     }
 }
 
+impl<T: Clone + Send + 'static> InstrumentChannel
+    for (broadcast::Sender<T>, broadcast::Receiver<T>)
+{
+    type Output = (broadcast::Sender<T>, broadcast::Receiver<T>);
+    fn instrument(
+        self,
+        source: &'static str,
+        label: Option<String>,
+        capacity: Option<usize>,
+    ) -> Self::Output {
+        wrap_broadcast(self, source, label, capacity)
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> InstrumentChannel
+    for (watch::Sender<T>, watch::Receiver<T>)
+{
+    type Output = (watch::Sender<T>, watch::Receiver<T>);
+    fn instrument(
+        self,
+        source: &'static str,
+        label: Option<String>,
+        _capacity: Option<usize>,
+    ) -> Self::Output {
+        wrap_watch(self, source, label)
+    }
+}
+
 use crate::channels::InstrumentChannelLog;
 
 impl<T: Send + std::fmt::Debug + 'static> InstrumentChannelLog for (Sender<T>, Receiver<T>) {
@@ -312,3 +529,73 @@ impl<T: Send + std::fmt::Debug + 'static> InstrumentChannelLog
         wrap_oneshot_log(self, source, label)
     }
 }
+
+impl<T: Clone + Send + std::fmt::Debug + 'static> InstrumentChannelLog
+    for (broadcast::Sender<T>, broadcast::Receiver<T>)
+{
+    type Output = (broadcast::Sender<T>, broadcast::Receiver<T>);
+    fn instrument_log(
+        self,
+        source: &'static str,
+        label: Option<String>,
+        capacity: Option<usize>,
+    ) -> Self::Output {
+        wrap_broadcast_log(self, source, label, capacity)
+    }
+}
+
+impl<T: Clone + Send + Sync + std::fmt::Debug + 'static> InstrumentChannelLog
+    for (watch::Sender<T>, watch::Receiver<T>)
+{
+    type Output = (watch::Sender<T>, watch::Receiver<T>);
+    fn instrument_log(
+        self,
+        source: &'static str,
+        label: Option<String>,
+        _capacity: Option<usize>,
+    ) -> Self::Output {
+        wrap_watch_log(self, source, label)
+    }
+}
+
+use crate::channels::InstrumentChannelSize;
+
+impl<T: Send + HeapSize + 'static> InstrumentChannelSize for (Sender<T>, Receiver<T>) {
+    type Output = (Sender<T>, Receiver<T>);
+    fn instrument_size(
+        self,
+        source: &'static str,
+        label: Option<String>,
+        _capacity: Option<usize>,
+    ) -> Self::Output {
+        wrap_channel_size(self, source, label)
+    }
+}
+
+impl<T: Send + HeapSize + 'static> InstrumentChannelSize
+    for (UnboundedSender<T>, UnboundedReceiver<T>)
+{
+    type Output = (UnboundedSender<T>, UnboundedReceiver<T>);
+    fn instrument_size(
+        self,
+        source: &'static str,
+        label: Option<String>,
+        _capacity: Option<usize>,
+    ) -> Self::Output {
+        wrap_unbounded_size(self, source, label)
+    }
+}
+
+impl<T: Send + HeapSize + 'static> InstrumentChannelSize
+    for (oneshot::Sender<T>, oneshot::Receiver<T>)
+{
+    type Output = (oneshot::Sender<T>, oneshot::Receiver<T>);
+    fn instrument_size(
+        self,
+        source: &'static str,
+        label: Option<String>,
+        _capacity: Option<usize>,
+    ) -> Self::Output {
+        wrap_oneshot_size(self, source, label)
+    }
+}
@@ -0,0 +1,87 @@
+//! Duration histograms shared by channel queue wait, blocked sends, future call timings and
+//! the sliding windows.
+
+use hdrhistogram::Histogram;
+
+use crate::json::DurationStats;
+
+// Histograms auto-resize, so a coarser precision keeps rarely used ones small
+const SIGFIGS: u8 = 2;
+const HIGH_NS: u64 = 3_600_000_000_000; // 1 hour in nanoseconds
+
+/// Empty auto-resizing duration histogram.
+pub(crate) fn new_histogram() -> Histogram<u64> {
+    Histogram::new(SIGFIGS).expect("hdrhistogram init")
+}
+
+/// Records `ns` into `hist`, clamped to one hour.
+pub(crate) fn record_ns(hist: &mut Histogram<u64>, ns: u64) {
+    // saturating_record would clamp to the current bounds instead of resizing
+    hist.record(ns.min(HIGH_NS))
+        .expect("auto-resizing histogram");
+}
+
+/// Distribution of recorded durations, with the exact total and maximum.
+#[derive(Debug, Clone)]
+pub(crate) struct Durations {
+    total_ns: u64,
+    max_ns: u64,
+    hist: Histogram<u64>,
+}
+
+impl Durations {
+    pub fn new() -> Self {
+        Self {
+            total_ns: 0,
+            max_ns: 0,
+            hist: new_histogram(),
+        }
+    }
+
+    pub fn record(&mut self, ns: u64) {
+        self.total_ns += ns;
+        self.max_ns = self.max_ns.max(ns);
+        record_ns(&mut self.hist, ns);
+    }
+
+    pub fn total_ns(&self) -> u64 {
+        self.total_ns
+    }
+
+    /// `None` until a duration was recorded.
+    pub fn stats(&self) -> Option<DurationStats> {
+        let count = self.hist.len();
+        if count == 0 {
+            return None;
+        }
+
+        Some(DurationStats {
+            count,
+            avg_ns: self.total_ns / count,
+            p50_ns: self.hist.value_at_quantile(0.50),
+            p95_ns: self.hist.value_at_quantile(0.95),
+            p99_ns: self.hist.value_at_quantile(0.99),
+            max_ns: self.max_ns,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_durations_clamp_histogram_only() {
+        let mut durations = Durations::new();
+        assert!(durations.stats().is_none());
+
+        durations.record(100);
+        durations.record(2 * HIGH_NS);
+
+        let stats = durations.stats().unwrap();
+        assert_eq!(stats.count, 2);
+        assert_eq!(durations.total_ns(), 2 * HIGH_NS + 100);
+        assert_eq!(stats.max_ns, 2 * HIGH_NS);
+        assert!(stats.p99_ns <= HIGH_NS + HIGH_NS / 100);
+    }
+}
@@ -5,7 +5,13 @@ use std::{sync::OnceLock, sync::RwLock, time::Duration};
 use arc_swap::ArcSwapOption;
 use crossbeam_channel::{bounded, Sender};
 
-use crate::{metrics_server::RECV_TIMEOUT_MS, FunctionLogsJson, FunctionsJson};
+use crate::json::StatsWindow;
+use crate::lib_on::window::{enable_durations, WindowSnapshot};
+use crate::output::MetricType;
+use crate::{
+    metrics_server::RECV_TIMEOUT_MS, DurationHistogram, FunctionLogsJson, FunctionsJson,
+    FunctionsTreeJson,
+};
 
 cfg_if::cfg_if! {
     if #[cfg(feature = "hotpath-alloc")] {
@@ -22,10 +28,12 @@ cfg_if::cfg_if! {
     }
 }
 
+#[doc(hidden)]
+pub use crate::lib_on::slow_polls::{time_polls, TimedPolls};
 pub(crate) use crate::output::truncate_result;
 
 impl MeasurementGuard {
-    pub fn build(measurement_name: &'static str, wrapper: bool, _is_async: bool) -> Self {
+    pub fn build(measurement_name: &'static str, wrapper: bool, is_async: bool) -> Self {
         #[allow(clippy::needless_bool)]
         let unsupported_async = if wrapper {
             // Top wrapper functions are not inside a runtime
@@ -35,7 +43,7 @@ impl MeasurementGuard {
                 if #[cfg(feature = "hotpath-alloc")] {
                     // For allocation profiling: mark async as unsupported unless
                     // running on Tokio CurrentThread. Non-Tokio runtimes are unsupported.
-                    if _is_async {
+                    if is_async {
                         match Handle::try_current() {
                             Ok(h) => h.runtime_flavor() != RuntimeFlavor::CurrentThread,
                             Err(_) => true,
@@ -49,19 +57,23 @@ impl MeasurementGuard {
             }
         };
 
-        MeasurementGuard::new(measurement_name, wrapper, unsupported_async)
+        if is_async {
+            MeasurementGuard::new_async(measurement_name, wrapper, unsupported_async)
+        } else {
+            MeasurementGuard::new(measurement_name, wrapper, unsupported_async)
+        }
     }
 }
 
 impl MeasurementGuardWithLog {
-    pub fn build(measurement_name: &'static str, wrapper: bool, _is_async: bool) -> Self {
+    pub fn build(measurement_name: &'static str, wrapper: bool, is_async: bool) -> Self {
         #[allow(clippy::needless_bool)]
         let unsupported_async = if wrapper {
             false
         } else {
             cfg_if::cfg_if! {
                 if #[cfg(feature = "hotpath-alloc")] {
-                    if _is_async {
+                    if is_async {
                         match Handle::try_current() {
                             Ok(h) => h.runtime_flavor() != RuntimeFlavor::CurrentThread,
                             Err(_) => true,
@@ -75,7 +87,11 @@ impl MeasurementGuardWithLog {
             }
         };
 
-        MeasurementGuardWithLog::new(measurement_name, wrapper, unsupported_async)
+        if is_async {
+            MeasurementGuardWithLog::new_async(measurement_name, wrapper, unsupported_async)
+        } else {
+            MeasurementGuardWithLog::new(measurement_name, wrapper, unsupported_async)
+        }
     }
 }
 
@@ -101,23 +117,37 @@ where
     F: FnOnce() -> Fut,
     Fut: std::future::Future<Output = T>,
 {
-    let guard = MeasurementGuardWithLog::build(name, false, true);
-    let result = f().await;
-    guard.finish_with_result(&result);
-    result
+    time_polls(
+        name,
+        call_tree::track_frame(name, async move {
+            let guard = MeasurementGuardWithLog::build(name, false, true);
+            let result = f().await;
+            guard.finish_with_result(&result);
+            result
+        }),
+    )
+    .await
 }
 
 pub(crate) static FUNCTIONS_STATE: OnceLock<ArcSwapOption<RwLock<FunctionsState>>> =
     OnceLock::new();
 
+pub mod call_tree;
 pub mod guard;
 
 /// Query request sent from TUI HTTP server to profiler worker thread
 pub(crate) enum FunctionsQuery {
-    /// Request timing metrics snapshot
-    Timing(Sender<FunctionsJson>),
+    /// Request timing metrics snapshot over a time window
+    Timing {
+        window: StatsWindow,
+        response_tx: Sender<FunctionsJson>,
+    },
+    /// Request timing metrics aggregated by call path
+    TimingTree(Sender<FunctionsTreeJson>),
     /// Request full metrics snapshot (allocation metrics) - returns None if hotpath-alloc not enabled
     Alloc(Sender<Option<FunctionsJson>>),
+    /// Request timing and allocation metrics of every function, ignoring `limit`
+    Unlimited(Sender<(FunctionsJson, Option<FunctionsJson>)>),
     /// Request timing function logs for a specific function (returns None if function not found)
     LogsTiming {
         function_name: String,
@@ -128,6 +158,79 @@ pub(crate) enum FunctionsQuery {
         function_name: String,
         response_tx: Sender<Option<FunctionLogsJson>>,
     },
+    /// Request duration histograms of all functions bucketed over the given bounds
+    #[cfg_attr(not(feature = "otlp"), allow(dead_code))]
+    DurationHistograms {
+        bounds: Vec<u64>,
+        response_tx: Sender<Vec<(String, DurationHistogram)>>,
+    },
+}
+
+/// Buckets recorded durations over explicit upper bounds, see [`DurationHistogram`].
+pub(crate) fn bucket_durations(
+    hist: &hdrhistogram::Histogram<u64>,
+    sum_ns: u64,
+    bounds: &[u64],
+) -> DurationHistogram {
+    let mut bucket_counts = vec![0; bounds.len() + 1];
+    for value in hist.iter_recorded() {
+        let bucket = bounds.partition_point(|bound| *bound < value.value_iterated_to());
+        bucket_counts[bucket] += value.count_at_value();
+    }
+
+    DurationHistogram {
+        count: hist.len(),
+        sum_ns,
+        min_ns: hist.min(),
+        max_ns: hist.max(),
+        bucket_counts,
+    }
+}
+
+/// Builds timing report rows from per-function window snapshots. Columns match the lifetime
+/// report, except self time which is not tracked per window.
+pub(crate) fn windowed_timing_rows(
+    mut snapshots: Vec<(&'static str, bool, WindowSnapshot)>,
+    percentiles: &[u8],
+    limit: usize,
+) -> Vec<(String, Vec<MetricType>)> {
+    snapshots.retain(|(_, _, snapshot)| snapshot.count > 0);
+
+    let reference_total = snapshots
+        .iter()
+        .find(|(_, wrapper, _)| *wrapper)
+        .map(|(_, _, snapshot)| snapshot.total_ns)
+        .or_else(|| snapshots.first().map(|(_, _, s)| s.span.as_nanos() as u64))
+        .unwrap_or(0);
+
+    snapshots.sort_by(|a, b| b.2.total_ns.cmp(&a.2.total_ns).then_with(|| a.0.cmp(b.0)));
+    if limit > 0 {
+        snapshots.truncate(limit);
+    }
+
+    snapshots
+        .into_iter()
+        .map(|(function_name, _, snapshot)| {
+            let percentage = if reference_total > 0 {
+                (snapshot.total_ns as f64 / reference_total as f64) * 100.0
+            } else {
+                0.0
+            };
+
+            let mut metrics = vec![
+                MetricType::CallsCount(snapshot.count),
+                MetricType::DurationNs(snapshot.avg_ns()),
+            ];
+            for p in percentiles {
+                metrics.push(MetricType::DurationNs(snapshot.percentile(*p as f64)));
+            }
+            metrics.push(MetricType::DurationNs(snapshot.total_ns));
+            metrics.push(MetricType::Percentage((percentage * 100.0) as u64));
+            metrics.push(MetricType::Unsupported);
+
+            (function_name.to_string(), metrics)
+        })
+        .collect()
 }
 
 /// Helper to send a query to the functions worker and receive the response.
@@ -154,12 +257,17 @@ where
 }
 
 // Get instrumented functions profiling information
-pub(crate) fn get_functions_timing_json() -> FunctionsJson {
-    if let Some(metrics) = try_get_functions_timing_from_worker() {
+pub(crate) fn get_functions_timing_json(window: StatsWindow) -> FunctionsJson {
+    enable_durations(window);
+    if let Some(metrics) = try_get_functions_timing_from_worker(window) {
         return metrics;
     }
 
     // Fallback if query fails: return empty functions data
+    empty_functions_timing_json()
+}
+
+fn empty_functions_timing_json() -> FunctionsJson {
     FunctionsJson {
         hotpath_profiling_mode: crate::output::ProfilingMode::Timing,
         total_elapsed: 0,
@@ -167,9 +275,25 @@ pub(crate) fn get_functions_timing_json() -> FunctionsJson {
         caller_name: "hotpath".to_string(),
         percentiles: vec![95],
         data: Vec::new(),
+        call_tree: Vec::new(),
     }
 }
 
+// Get timing and allocation metrics of every function, without the display `limit`
+pub(crate) fn get_all_functions_json() -> (FunctionsJson, Option<FunctionsJson>) {
+    query_functions_state(FunctionsQuery::Unlimited)
+        .unwrap_or_else(|| (empty_functions_timing_json(), None))
+}
+
+// Get instrumented functions timing aggregated by call path
+pub(crate) fn get_functions_timing_tree_json() -> FunctionsTreeJson {
+    query_functions_state(FunctionsQuery::TimingTree).unwrap_or_else(|| FunctionsTreeJson {
+        total_elapsed: 0,
+        caller_name: "hotpath".to_string(),
+        tree: Vec::new(),
+    })
+}
+
 // Get instrumented functions calls information
 pub(crate) fn get_function_logs_timing(function_name: &str) -> Option<FunctionLogsJson> {
     let name = function_name.to_string();
@@ -180,8 +304,11 @@ pub(crate) fn get_function_logs_timing(function_name: &str) -> Option<FunctionLo
     .flatten()
 }
 
-fn try_get_functions_timing_from_worker() -> Option<FunctionsJson> {
-    query_functions_state(FunctionsQuery::Timing)
+fn try_get_functions_timing_from_worker(window: StatsWindow) -> Option<FunctionsJson> {
+    query_functions_state(|response_tx| FunctionsQuery::Timing {
+        window,
+        response_tx,
+    })
 }
 
 // Get a JSON representation of all functions and their allocations
@@ -200,3 +327,15 @@ pub(crate) fn get_function_logs_alloc(function_name: &str) -> Option<FunctionLog
     })
     .flatten()
 }
+
+// Get duration histograms of all measured functions
+#[cfg(feature = "otlp")]
+pub(crate) fn get_functions_duration_histograms(
+    bounds: &[u64],
+) -> Option<Vec<(String, DurationHistogram)>> {
+    let bounds = bounds.to_vec();
+    query_functions_state(|response_tx| FunctionsQuery::DurationHistograms {
+        bounds,
+        response_tx,
+    })
+}
@@ -92,6 +92,9 @@ fn get_or_create_slot(tid: u64) -> Option<&'static ThreadAllocStats> {
 pub struct AllocationInfo {
     pub bytes_total: Cell<u64>,
     pub count_total: Cell<u64>,
+    /// Part of the totals reported by nested measured scopes
+    pub bytes_children: Cell<u64>,
+    pub count_children: Cell<u64>,
     pub unsupported_async: Cell<bool>,
 }
 
@@ -101,6 +104,10 @@ impl std::ops::AddAssign for AllocationInfo {
             .set(self.bytes_total.get() + other.bytes_total.get());
         self.count_total
             .set(self.count_total.get() + other.count_total.get());
+        self.bytes_children
+            .set(self.bytes_children.get() + other.bytes_children.get());
+        self.count_children
+            .set(self.count_children.get() + other.count_children.get());
         self.unsupported_async
             .set(self.unsupported_async.get() | other.unsupported_async.get());
     }
@@ -118,6 +125,8 @@ thread_local! {
         elements: [const { AllocationInfo {
             bytes_total: Cell::new(0),
             count_total: Cell::new(0),
+            bytes_children: Cell::new(0),
+            count_children: Cell::new(0),
             unsupported_async: Cell::new(false)
         } }; MAX_DEPTH],
         tracking_enabled: Cell::new(true),
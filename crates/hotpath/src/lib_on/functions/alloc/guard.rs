@@ -4,6 +4,7 @@ use quanta::Instant;
 #[cfg(not(target_os = "linux"))]
 use std::time::Instant;
 
+use super::super::call_tree;
 use super::super::truncate_result;
 
 #[must_use = "guard is dropped immediately without measuring anything"]
@@ -12,12 +13,29 @@ pub struct MeasurementGuard {
     wrapper: bool,
     unsupported_async: bool,
     tid: u64,
+    frame_id: u64,
     start: Instant,
 }
 
 impl MeasurementGuard {
     #[inline]
     pub fn new(name: &'static str, wrapper: bool, unsupported_async: bool) -> Self {
+        Self::with_frame(name, wrapper, unsupported_async, enter_frame(name))
+    }
+
+    /// Guard of a measured async function, which pushes its frame on each poll instead
+    #[inline]
+    pub(crate) fn new_async(name: &'static str, wrapper: bool, unsupported_async: bool) -> Self {
+        Self::with_frame(name, wrapper, unsupported_async, call_tree::adopt(name))
+    }
+
+    #[inline]
+    fn with_frame(
+        name: &'static str,
+        wrapper: bool,
+        unsupported_async: bool,
+        frame_id: u64,
+    ) -> Self {
         if !unsupported_async {
             super::core::ALLOCATIONS.with(|stack| {
                 let current_depth = stack.depth.get();
@@ -26,6 +44,8 @@ impl MeasurementGuard {
                 let depth = stack.depth.get() as usize;
                 stack.elements[depth].bytes_total.set(0);
                 stack.elements[depth].count_total.set(0);
+                stack.elements[depth].bytes_children.set(0);
+                stack.elements[depth].count_children.set(0);
                 stack.elements[depth].unsupported_async.set(false);
             });
         }
@@ -35,6 +55,7 @@ impl MeasurementGuard {
             wrapper,
             unsupported_async,
             tid: crate::tid::current_tid(),
+            frame_id,
             start: Instant::now(),
         }
     }
@@ -46,49 +67,30 @@ impl Drop for MeasurementGuard {
         let duration = self.start.elapsed();
         let cross_thread = crate::tid::current_tid() != self.tid;
 
-        let (bytes_total, count_total, unsupported_async) =
-            if self.unsupported_async || cross_thread {
-                (0, 0, self.unsupported_async)
-            } else {
-                super::core::ALLOCATIONS.with(|stack| {
-                    let depth = stack.depth.get() as usize;
-                    let bytes = stack.elements[depth].bytes_total.get();
-                    let count = stack.elements[depth].count_total.get();
-                    let unsup_async = stack.elements[depth].unsupported_async.get();
-
-                    stack.depth.set(stack.depth.get() - 1);
-
-                    if !super::shared::is_alloc_self_enabled() {
-                        let parent = stack.depth.get() as usize;
-                        stack.elements[parent]
-                            .bytes_total
-                            .set(stack.elements[parent].bytes_total.get() + bytes);
-                        stack.elements[parent]
-                            .count_total
-                            .set(stack.elements[parent].count_total.get() + count);
-                        stack.elements[parent]
-                            .unsupported_async
-                            .set(stack.elements[parent].unsupported_async.get() | unsup_async);
-                    }
-
-                    (bytes, count, unsup_async)
-                })
-            };
+        let allocs = if self.unsupported_async || cross_thread {
+            FrameAllocs::unsupported(self.unsupported_async)
+        } else {
+            exit_alloc_frame()
+        };
 
         super::core::ALLOCATIONS.with(|stack| {
             stack.tracking_enabled.set(false);
         });
 
-        let tid = if cross_thread { None } else { Some(self.tid) };
+        let path = call_tree::exit(self.frame_id, duration.as_nanos() as u64);
+        let tid = (!cross_thread).then_some(self.tid);
         super::state::send_alloc_measurement(
             self.name,
-            bytes_total,
-            count_total,
+            allocs.bytes_total,
+            allocs.count_total,
+            allocs.self_bytes,
+            allocs.self_count,
             duration,
-            unsupported_async,
+            allocs.unsupported_async,
             self.wrapper,
             cross_thread,
             tid,
+            path,
         );
 
         super::core::ALLOCATIONS.with(|stack| {
@@ -103,6 +105,7 @@ pub struct MeasurementGuardWithLog {
     wrapper: bool,
     unsupported_async: bool,
     tid: u64,
+    frame_id: u64,
     start: Instant,
     finished: bool,
 }
@@ -110,6 +113,22 @@ pub struct MeasurementGuardWithLog {
 impl MeasurementGuardWithLog {
     #[inline]
     pub fn new(name: &'static str, wrapper: bool, unsupported_async: bool) -> Self {
+        Self::with_frame(name, wrapper, unsupported_async, enter_frame(name))
+    }
+
+    /// Guard of a measured async function, which pushes its frame on each poll instead
+    #[inline]
+    pub(crate) fn new_async(name: &'static str, wrapper: bool, unsupported_async: bool) -> Self {
+        Self::with_frame(name, wrapper, unsupported_async, call_tree::adopt(name))
+    }
+
+    #[inline]
+    fn with_frame(
+        name: &'static str,
+        wrapper: bool,
+        unsupported_async: bool,
+        frame_id: u64,
+    ) -> Self {
         if !unsupported_async {
             super::core::ALLOCATIONS.with(|stack| {
                 let current_depth = stack.depth.get();
@@ -118,6 +137,8 @@ impl MeasurementGuardWithLog {
                 let depth = stack.depth.get() as usize;
                 stack.elements[depth].bytes_total.set(0);
                 stack.elements[depth].count_total.set(0);
+                stack.elements[depth].bytes_children.set(0);
+                stack.elements[depth].count_children.set(0);
                 stack.elements[depth].unsupported_async.set(false);
             });
         }
@@ -127,6 +148,7 @@ impl MeasurementGuardWithLog {
             wrapper,
             unsupported_async,
             tid: crate::tid::current_tid(),
+            frame_id,
             start: Instant::now(),
             finished: false,
         }
@@ -140,49 +162,30 @@ impl MeasurementGuardWithLog {
         let duration = self.start.elapsed();
         let cross_thread = crate::tid::current_tid() != self.tid;
 
-        let (bytes_total, count_total, unsupported_async) =
-            if self.unsupported_async || cross_thread {
-                (0, 0, self.unsupported_async)
-            } else {
-                super::core::ALLOCATIONS.with(|stack| {
-                    let depth = stack.depth.get() as usize;
-                    let bytes = stack.elements[depth].bytes_total.get();
-                    let count = stack.elements[depth].count_total.get();
-                    let unsup_async = stack.elements[depth].unsupported_async.get();
-
-                    stack.depth.set(stack.depth.get() - 1);
-
-                    if !super::shared::is_alloc_self_enabled() {
-                        let parent = stack.depth.get() as usize;
-                        stack.elements[parent]
-                            .bytes_total
-                            .set(stack.elements[parent].bytes_total.get() + bytes);
-                        stack.elements[parent]
-                            .count_total
-                            .set(stack.elements[parent].count_total.get() + count);
-                        stack.elements[parent]
-                            .unsupported_async
-                            .set(stack.elements[parent].unsupported_async.get() | unsup_async);
-                    }
-
-                    (bytes, count, unsup_async)
-                })
-            };
+        let allocs = if self.unsupported_async || cross_thread {
+            FrameAllocs::unsupported(self.unsupported_async)
+        } else {
+            exit_alloc_frame()
+        };
 
         super::core::ALLOCATIONS.with(|stack| {
             stack.tracking_enabled.set(false);
         });
 
-        let tid = if cross_thread { None } else { Some(self.tid) };
+        let path = call_tree::exit(self.frame_id, duration.as_nanos() as u64);
+        let tid = (!cross_thread).then_some(self.tid);
         super::state::send_alloc_measurement_with_log(
             self.name,
-            bytes_total,
-            count_total,
+            allocs.bytes_total,
+            allocs.count_total,
+            allocs.self_bytes,
+            allocs.self_count,
             duration,
-            unsupported_async,
+            allocs.unsupported_async,
             self.wrapper,
             cross_thread,
             tid,
+            path,
             Some(result_str),
         );
 
@@ -199,49 +202,30 @@ impl Drop for MeasurementGuardWithLog {
             let duration = self.start.elapsed();
             let cross_thread = crate::tid::current_tid() != self.tid;
 
-            let (bytes_total, count_total, unsupported_async) =
-                if self.unsupported_async || cross_thread {
-                    (0, 0, self.unsupported_async)
-                } else {
-                    super::core::ALLOCATIONS.with(|stack| {
-                        let depth = stack.depth.get() as usize;
-                        let bytes = stack.elements[depth].bytes_total.get();
-                        let count = stack.elements[depth].count_total.get();
-                        let unsup_async = stack.elements[depth].unsupported_async.get();
-
-                        stack.depth.set(stack.depth.get() - 1);
-
-                        if !super::shared::is_alloc_self_enabled() {
-                            let parent = stack.depth.get() as usize;
-                            stack.elements[parent]
-                                .bytes_total
-                                .set(stack.elements[parent].bytes_total.get() + bytes);
-                            stack.elements[parent]
-                                .count_total
-                                .set(stack.elements[parent].count_total.get() + count);
-                            stack.elements[parent]
-                                .unsupported_async
-                                .set(stack.elements[parent].unsupported_async.get() | unsup_async);
-                        }
-
-                        (bytes, count, unsup_async)
-                    })
-                };
+            let allocs = if self.unsupported_async || cross_thread {
+                FrameAllocs::unsupported(self.unsupported_async)
+            } else {
+                exit_alloc_frame()
+            };
 
             super::core::ALLOCATIONS.with(|stack| {
                 stack.tracking_enabled.set(false);
             });
 
-            let tid = if cross_thread { None } else { Some(self.tid) };
+            let path = call_tree::exit(self.frame_id, duration.as_nanos() as u64);
+            let tid = (!cross_thread).then_some(self.tid);
             super::state::send_alloc_measurement_with_log(
                 self.name,
-                bytes_total,
-                count_total,
+                allocs.bytes_total,
+                allocs.count_total,
+                allocs.self_bytes,
+                allocs.self_count,
                 duration,
-                unsupported_async,
+                allocs.unsupported_async,
                 self.wrapper,
                 cross_thread,
                 tid,
+                path,
                 None,
             );
 
@@ -251,3 +235,74 @@ impl Drop for MeasurementGuardWithLog {
         }
     }
 }
+
+/// Pushes a call stack frame without counting its bookkeeping as an allocation.
+#[inline]
+fn enter_frame(name: &'static str) -> u64 {
+    super::core::ALLOCATIONS.with(|stack| {
+        let tracking_enabled = stack.tracking_enabled.replace(false);
+        let frame_id = call_tree::enter(name);
+        stack.tracking_enabled.set(tracking_enabled);
+        frame_id
+    })
+}
+
+/// Allocations recorded by a finished measured scope.
+struct FrameAllocs {
+    bytes_total: u64,
+    count_total: u64,
+    self_bytes: u64,
+    self_count: u64,
+    unsupported_async: bool,
+}
+
+impl FrameAllocs {
+    fn unsupported(unsupported_async: bool) -> Self {
+        Self {
+            bytes_total: 0,
+            count_total: 0,
+            self_bytes: 0,
+            self_count: 0,
+            unsupported_async,
+        }
+    }
+}
+
+/// Pops the current allocation frame and, unless `HOTPATH_ALLOC_SELF` is set, adds its
+/// totals to the enclosing frame. Self allocations exclude the totals of nested frames.
+#[inline]
+fn exit_alloc_frame() -> FrameAllocs {
+    super::core::ALLOCATIONS.with(|stack| {
+        let depth = stack.depth.get() as usize;
+        let info = &stack.elements[depth];
+        let bytes = info.bytes_total.get();
+        let count = info.count_total.get();
+        let unsup_async = info.unsupported_async.get();
+        let allocs = FrameAllocs {
+            bytes_total: bytes,
+            count_total: count,
+            self_bytes: bytes.saturating_sub(info.bytes_children.get()),
+            self_count: count.saturating_sub(info.count_children.get()),
+            unsupported_async: unsup_async,
+        };
+
+        stack.depth.set(stack.depth.get() - 1);
+
+        if !super::shared::is_alloc_self_enabled() {
+            let parent = &stack.elements[stack.depth.get() as usize];
+            parent.bytes_total.set(parent.bytes_total.get() + bytes);
+            parent.count_total.set(parent.count_total.get() + count);
+            parent
+                .bytes_children
+                .set(parent.bytes_children.get() + bytes);
+            parent
+                .count_children
+                .set(parent.count_children.get() + count);
+            parent
+                .unsupported_async
+                .set(parent.unsupported_async.get() | unsup_async);
+        }
+
+        allocs
+    })
+}
@@ -2,8 +2,12 @@ use crate::ProfilingMode;
 use std::collections::HashMap;
 use std::time::Duration;
 
+use super::super::call_tree::{build_call_tree, build_folded_stacks};
+use super::super::windowed_timing_rows;
 use super::state::FunctionStats;
-use crate::output::{MetricType, MetricsProvider};
+use crate::json::StatsWindow;
+use crate::lib_on::window::WindowSnapshot;
+use crate::output::{CallTreeNode, DurationHistogram, MetricType, MetricsProvider};
 
 pub struct StatsData<'a> {
     pub stats: &'a HashMap<&'static str, FunctionStats>,
@@ -19,6 +23,35 @@ pub struct TimingStatsData<'a> {
     pub percentiles: Vec<u8>,
     pub caller_name: &'static str,
     pub limit: usize,
+    pub window: StatsWindow,
+}
+
+impl TimingStatsData<'_> {
+    /// Restricts `metric_data` to measurements taken within `window`.
+    pub(crate) fn with_window(mut self, window: StatsWindow) -> Self {
+        self.window = window;
+        self
+    }
+
+    fn window_snapshots(&self) -> Option<Vec<(&'static str, bool, WindowSnapshot)>> {
+        if self.window == StatsWindow::Lifetime {
+            return None;
+        }
+
+        Some(
+            self.stats
+                .iter()
+                .filter(|(_, s)| s.has_data)
+                .filter_map(|(name, s)| {
+                    Some((
+                        *name,
+                        s.wrapper,
+                        s.windowed(self.window, self.total_elapsed)?,
+                    ))
+                })
+                .collect(),
+        )
+    }
 }
 
 impl<'a> MetricsProvider<'a> for StatsData<'a> {
@@ -143,9 +176,11 @@ impl<'a> MetricsProvider<'a> for StatsData<'a> {
                 if stats.has_unsupported_async || stats.cross_thread {
                     metrics.push(MetricType::Unsupported);
                     metrics.push(MetricType::Unsupported);
+                    metrics.push(MetricType::Unsupported);
                 } else {
                     metrics.push(MetricType::Alloc(stats.total_bytes(), stats.total_count()));
                     metrics.push(MetricType::Percentage((percentage * 100.0) as u64));
+                    metrics.push(MetricType::Alloc(stats.self_bytes, stats.self_count));
                 }
 
                 (function_name.to_string(), metrics)
@@ -176,6 +211,20 @@ impl<'a> MetricsProvider<'a> for StatsData<'a> {
 
         (displayed_count, total_count)
     }
+
+    fn folded_stacks(&self) -> Vec<(String, u64)> {
+        build_folded_stacks(
+            self.stats
+                .iter()
+                .filter(|(_, s)| !s.has_unsupported_async)
+                .map(|(name, s)| (*name, &s.call_paths)),
+            |stats| stats.self_bytes,
+        )
+    }
+
+    fn duration_histograms(&self, bounds: &[u64]) -> Vec<(String, DurationHistogram)> {
+        duration_histograms(self.stats, bounds)
+    }
 }
 
 impl<'a> MetricsProvider<'a> for TimingStatsData<'a> {
@@ -192,6 +241,7 @@ impl<'a> MetricsProvider<'a> for TimingStatsData<'a> {
             percentiles,
             caller_name,
             limit,
+            window: StatsWindow::Lifetime,
         }
     }
 
@@ -200,7 +250,13 @@ impl<'a> MetricsProvider<'a> for TimingStatsData<'a> {
     }
 
     fn description(&self) -> String {
-        "Function execution time metrics.".to_string()
+        match self.window {
+            StatsWindow::Lifetime => "Function execution time metrics.".to_string(),
+            window => format!(
+                "Function execution time metrics over the {}.",
+                window.label()
+            ),
+        }
     }
 
     fn percentiles(&self) -> Vec<u8> {
@@ -212,6 +268,10 @@ impl<'a> MetricsProvider<'a> for TimingStatsData<'a> {
     }
 
     fn metric_data(&self) -> Vec<(String, Vec<MetricType>)> {
+        if let Some(snapshots) = self.window_snapshots() {
+            return windowed_timing_rows(snapshots, &self.percentiles, self.limit);
+        }
+
         let mut filtered_stats: Vec<_> = self.stats.iter().filter(|(_, s)| s.has_data).collect();
 
         filtered_stats.sort_by(|a, b| {
@@ -258,6 +318,7 @@ impl<'a> MetricsProvider<'a> for TimingStatsData<'a> {
 
                 metrics.push(MetricType::DurationNs(stats.total_duration_ns));
                 metrics.push(MetricType::Percentage((percentage * 100.0) as u64));
+                metrics.push(MetricType::DurationNs(stats.self_duration_ns()));
 
                 (function_name.to_string(), metrics)
             })
@@ -273,7 +334,10 @@ impl<'a> MetricsProvider<'a> for TimingStatsData<'a> {
     }
 
     fn entry_counts(&self) -> (usize, usize) {
-        let total_count = self.stats.iter().filter(|(_, s)| s.has_data).count();
+        let total_count = match self.window_snapshots() {
+            Some(snapshots) => snapshots.iter().filter(|(_, _, s)| s.count > 0).count(),
+            None => self.stats.iter().filter(|(_, s)| s.has_data).count(),
+        };
 
         let displayed_count = if self.limit > 0 && self.limit < total_count {
             self.limit
@@ -283,4 +347,32 @@ impl<'a> MetricsProvider<'a> for TimingStatsData<'a> {
 
         (displayed_count, total_count)
     }
+
+    fn call_tree(&self) -> Vec<CallTreeNode> {
+        build_call_tree(self.stats.iter().map(|(name, s)| (*name, &s.call_paths)))
+    }
+
+    fn folded_stacks(&self) -> Vec<(String, u64)> {
+        build_folded_stacks(
+            self.stats.iter().map(|(name, s)| (*name, &s.call_paths)),
+            |stats| stats.self_ns,
+        )
+    }
+
+    fn duration_histograms(&self, bounds: &[u64]) -> Vec<(String, DurationHistogram)> {
+        duration_histograms(self.stats, bounds)
+    }
+}
+
+fn duration_histograms(
+    stats: &HashMap<&'static str, FunctionStats>,
+    bounds: &[u64],
+) -> Vec<(String, DurationHistogram)> {
+    let mut histograms: Vec<_> = stats
+        .iter()
+        .filter(|(_, s)| s.has_data)
+        .filter_map(|(name, s)| Some((name.to_string(), s.duration_histogram(bounds)?)))
+        .collect();
+    histograms.sort_by(|a, b| a.0.cmp(&b.0));
+    histograms
 }
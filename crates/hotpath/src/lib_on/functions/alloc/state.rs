@@ -1,10 +1,17 @@
 use crossbeam_channel::{Receiver, Sender};
 use hdrhistogram::Histogram;
 use std::cell::RefCell;
-use std::collections::{HashMap, VecDeque};
+use std::collections::{hash_map::Entry, HashMap, VecDeque};
 use std::sync::Mutex;
 use std::time::{Duration, Instant};
 
+use super::super::bucket_durations;
+use super::super::call_tree::{record_call_path, CallPaths, ScopePath};
+use crate::json::StatsWindow;
+use crate::lib_on::trace;
+use crate::lib_on::window::{WindowSnapshot, WindowedDurations};
+use crate::output::DurationHistogram;
+
 const BATCH_SIZE: usize = 64;
 const FLUSH_INTERVAL_MS: u64 = 50;
 
@@ -17,6 +24,7 @@ struct MeasurementBatch {
 
 impl MeasurementBatch {
     fn new() -> Self {
+        crate::lib_on::trace::register_current_thread();
         Self {
             measurements: Vec::with_capacity(BATCH_SIZE),
             last_flush: Instant::now(),
@@ -31,11 +39,14 @@ impl MeasurementBatch {
         name: &'static str,
         bytes_total: u64,
         count_total: u64,
+        self_bytes: u64,
+        self_count: u64,
         duration: Duration,
         unsupported_async: bool,
         wrapper: bool,
         cross_thread: bool,
         tid: Option<u64>,
+        path: Option<ScopePath>,
         result_log: Option<String>,
     ) {
         if self.sender.is_none() {
@@ -57,12 +68,16 @@ impl MeasurementBatch {
             name,
             bytes_total,
             count_total,
+            self_bytes,
+            self_count,
             duration,
             measurement_time: Instant::now(),
             unsupported_async,
             wrapper,
             cross_thread,
             tid,
+            end_tid: crate::tid::current_tid(),
+            path,
             result_log,
         };
 
@@ -106,16 +121,22 @@ pub(crate) fn flush_batch() {
     });
 }
 
+#[derive(Clone)]
 pub struct Measurement {
     pub name: &'static str,
     pub bytes_total: u64,
     pub count_total: u64,
+    pub self_bytes: u64,
+    pub self_count: u64,
     pub duration: Duration,
     pub measurement_time: Instant,
     pub unsupported_async: bool,
     pub wrapper: bool,
     pub cross_thread: bool,
     pub tid: Option<u64>,
+    /// Thread the scope finished on, the thread of the last poll for async functions
+    pub end_tid: u64,
+    pub path: Option<ScopePath>,
     pub result_log: Option<String>,
 }
 
@@ -135,11 +156,15 @@ pub struct FunctionStats {
     count_total_hist: Option<Histogram<u64>>,
     duration_hist: Option<Histogram<u64>>,
     pub total_duration_ns: u64,
+    pub self_bytes: u64,
+    pub self_count: u64,
     pub has_data: bool,
     pub has_unsupported_async: bool,
     pub wrapper: bool,
     pub cross_thread: bool,
     pub recent_logs: VecDeque<LogEntry>,
+    pub call_paths: CallPaths,
+    windows: WindowedDurations,
 }
 
 impl FunctionStats {
@@ -194,14 +219,19 @@ impl FunctionStats {
             count_total_hist: Some(count_total_hist),
             duration_hist: Some(duration_hist),
             total_duration_ns: duration_ns,
+            self_bytes: 0,
+            self_count: 0,
             has_data: true,
             has_unsupported_async: unsupported_async,
             wrapper,
             cross_thread,
             recent_logs,
+            call_paths: CallPaths::new(),
+            windows: WindowedDurations::new(),
         };
         s.record_alloc(bytes_total, count_total);
         s.record_duration(duration_ns);
+        s.windows.record(elapsed, duration_ns);
         s
     }
 
@@ -252,6 +282,7 @@ impl FunctionStats {
         let duration_ns = duration.as_nanos() as u64;
         self.total_duration_ns += duration_ns;
         self.record_duration(duration_ns);
+        self.windows.record(elapsed, duration_ns);
 
         if self.recent_logs.len() == self.recent_logs.capacity() && self.recent_logs.capacity() > 0
         {
@@ -326,6 +357,11 @@ impl FunctionStats {
         self.count_total_hist.as_ref().unwrap().mean() as u64
     }
 
+    /// Total execution time minus the time spent in nested measured scopes.
+    pub fn self_duration_ns(&self) -> u64 {
+        self.call_paths.values().map(|stats| stats.self_ns).sum()
+    }
+
     #[inline]
     pub fn duration_percentile(&self, p: f64) -> u64 {
         if self.count == 0 || self.duration_hist.is_none() {
@@ -342,6 +378,16 @@ impl FunctionStats {
         }
         self.duration_hist.as_ref().unwrap().mean() as u64
     }
+
+    pub fn duration_histogram(&self, bounds: &[u64]) -> Option<DurationHistogram> {
+        let hist = self.duration_hist.as_ref().filter(|_| self.count > 0)?;
+        Some(bucket_durations(hist, self.total_duration_ns, bounds))
+    }
+
+    /// Durations recorded within `window` of `now` (elapsed since the profiler started).
+    pub(crate) fn windowed(&self, window: StatsWindow, now: Duration) -> Option<WindowSnapshot> {
+        self.windows.snapshot(window, now)
+    }
 }
 
 pub(crate) struct FunctionsState {
@@ -361,35 +407,61 @@ pub(crate) fn process_measurement(
     recent_logs_limit: usize,
     start_time: Instant,
 ) {
-    let elapsed = m.measurement_time.duration_since(start_time);
-    if let Some(s) = stats.get_mut(m.name) {
-        s.update_alloc(
-            m.bytes_total,
-            m.count_total,
+    if trace::is_enabled() {
+        let args = (!m.unsupported_async && !m.cross_thread)
+            .then(|| serde_json::json!({ "bytes": m.bytes_total, "allocations": m.count_total }));
+        trace::record_span(
+            m.name,
+            trace::Category::Function,
+            m.measurement_time,
             m.duration,
-            elapsed,
-            m.unsupported_async,
-            m.cross_thread,
-            m.tid,
-            m.result_log,
+            m.end_tid,
+            args,
         );
-    } else {
-        stats.insert(
-            m.name,
-            FunctionStats::new_alloc(
+    }
+    record_measurement(stats, m, recent_logs_limit, start_time);
+}
+
+/// Aggregates a measurement into `stats` without recording it in the trace.
+pub(crate) fn record_measurement(
+    stats: &mut HashMap<&'static str, FunctionStats>,
+    m: Measurement,
+    recent_logs_limit: usize,
+    start_time: Instant,
+) {
+    let elapsed = m.measurement_time.duration_since(start_time);
+    let duration_ns = m.duration.as_nanos() as u64;
+    let s = match stats.entry(m.name) {
+        Entry::Occupied(entry) => {
+            let s = entry.into_mut();
+            s.update_alloc(
                 m.bytes_total,
                 m.count_total,
                 m.duration,
                 elapsed,
                 m.unsupported_async,
-                m.wrapper,
                 m.cross_thread,
-                recent_logs_limit,
                 m.tid,
                 m.result_log,
-            ),
-        );
-    }
+            );
+            s
+        }
+        Entry::Vacant(entry) => entry.insert(FunctionStats::new_alloc(
+            m.bytes_total,
+            m.count_total,
+            m.duration,
+            elapsed,
+            m.unsupported_async,
+            m.wrapper,
+            m.cross_thread,
+            recent_logs_limit,
+            m.tid,
+            m.result_log,
+        )),
+    };
+    s.self_bytes += m.self_bytes;
+    s.self_count += m.self_count;
+    record_call_path(&mut s.call_paths, m.path, duration_ns).self_bytes += m.self_bytes;
 }
 
 use super::super::FUNCTIONS_STATE;
@@ -399,21 +471,27 @@ pub fn send_alloc_measurement(
     name: &'static str,
     bytes_total: u64,
     count_total: u64,
+    self_bytes: u64,
+    self_count: u64,
     duration: Duration,
     unsupported_async: bool,
     wrapper: bool,
     cross_thread: bool,
     tid: Option<u64>,
+    path: Option<ScopePath>,
 ) {
     send_alloc_measurement_with_log(
         name,
         bytes_total,
         count_total,
+        self_bytes,
+        self_count,
         duration,
         unsupported_async,
         wrapper,
         cross_thread,
         tid,
+        path,
         None,
     );
 }
@@ -423,11 +501,14 @@ pub fn send_alloc_measurement_with_log(
     name: &'static str,
     bytes_total: u64,
     count_total: u64,
+    self_bytes: u64,
+    self_count: u64,
     duration: Duration,
     unsupported_async: bool,
     wrapper: bool,
     cross_thread: bool,
     tid: Option<u64>,
+    path: Option<ScopePath>,
     result_log: Option<String>,
 ) {
     if FUNCTIONS_STATE.get().is_none() {
@@ -441,11 +522,14 @@ pub fn send_alloc_measurement_with_log(
             name,
             bytes_total,
             count_total,
+            self_bytes,
+            self_count,
             duration,
             unsupported_async,
             wrapper,
             cross_thread,
             tid,
+            path,
             result_log,
         );
     });
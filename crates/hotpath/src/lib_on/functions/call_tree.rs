@@ -0,0 +1,408 @@
+//! Call path attribution for measured functions.
+//!
+//! Every measurement guard pushes a frame onto a per-thread stack when it is created and
+//! pops it when it is dropped. The frames below the popped one form its call path, and the
+//! time reported by nested frames is subtracted from the parent to get its self time.
+//!
+//! Measured async functions keep their frame between polls and push it only while they are
+//! being polled, so tasks polled on the same thread in between are not attributed to them.
+//! Their call path is the stack of the poll they finish in, and the time of the nested
+//! scopes is summed over all their polls.
+
+use pin_project_lite::pin_project;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::task::{Context, Poll};
+
+use crate::output::CallTreeNode;
+
+static FRAME_ID_COUNTER: AtomicU64 = AtomicU64::new(1);
+
+struct Frame {
+    id: u64,
+    name: &'static str,
+    children_ns: u64,
+}
+
+thread_local! {
+    static CALL_STACK: RefCell<Vec<Frame>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Call path of a finished measured scope.
+#[derive(Debug, Clone)]
+pub struct ScopePath {
+    /// Names of the enclosing measured scopes, outermost first
+    pub parents: Vec<&'static str>,
+    /// Duration minus the time spent in nested measured scopes on the same thread
+    pub self_ns: u64,
+}
+
+/// Pushes a new frame onto the current thread's call stack and returns its id.
+#[inline]
+pub(crate) fn enter(name: &'static str) -> u64 {
+    let id = FRAME_ID_COUNTER.fetch_add(1, Ordering::Relaxed);
+    CALL_STACK.with(|stack| {
+        stack.borrow_mut().push(Frame {
+            id,
+            name,
+            children_ns: 0,
+        });
+    });
+    id
+}
+
+/// Frame of a measured async function, kept between its polls.
+#[derive(Debug, Default)]
+struct AsyncFrame {
+    id: u64,
+    children_ns: u64,
+}
+
+/// Pushes the frame of a measured async function for the duration of a single poll.
+#[inline]
+fn resume(name: &'static str, frame: &mut AsyncFrame) {
+    if frame.id == 0 {
+        frame.id = FRAME_ID_COUNTER.fetch_add(1, Ordering::Relaxed);
+    }
+    CALL_STACK.with(|stack| {
+        stack.borrow_mut().push(Frame {
+            id: frame.id,
+            name,
+            children_ns: frame.children_ns,
+        });
+    });
+}
+
+/// Pops the frame pushed by [`resume`] at the end of a poll, keeping the time of the
+/// nested scopes that finished during it. Does nothing if the function finished, its
+/// guard having popped the frame already.
+#[inline]
+fn suspend(frame: &mut AsyncFrame) {
+    CALL_STACK.with(|stack| {
+        let mut stack = stack.borrow_mut();
+        if let Some(pos) = stack.iter().rposition(|f| f.id == frame.id) {
+            frame.children_ns = stack[pos].children_ns;
+            stack.truncate(pos);
+        }
+    });
+}
+
+/// Keeps the call stack frame of an async function measured with `#[hotpath::measure]` on
+/// the polling thread while it is being polled.
+///
+/// This function is not intended for direct use.
+#[doc(hidden)]
+pub fn track_frame<F: Future>(name: &'static str, inner: F) -> FramePolls<F> {
+    FramePolls {
+        inner,
+        name,
+        frame: AsyncFrame::default(),
+    }
+}
+
+pin_project! {
+    /// Pushes the frame of the inner future before each of its polls and pops it after.
+    #[doc(hidden)]
+    pub struct FramePolls<F> {
+        #[pin]
+        inner: F,
+        name: &'static str,
+        frame: AsyncFrame,
+    }
+}
+
+impl<F: Future> Future for FramePolls<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        resume(this.name, this.frame);
+        let result = this.inner.poll(cx);
+        suspend(this.frame);
+        result
+    }
+}
+
+/// Id of the frame a measured async function pushed for the current poll, to be popped by
+/// its guard with [`exit`]. Returns 0, which matches no frame, if `name` is not on top.
+#[inline]
+pub(crate) fn adopt(name: &'static str) -> u64 {
+    CALL_STACK.with(|stack| {
+        stack
+            .borrow()
+            .last()
+            .filter(|frame| frame.name == name)
+            .map_or(0, |frame| frame.id)
+    })
+}
+
+/// Pops the frame with the given id from the current thread's call stack.
+///
+/// Frames above it belong to scopes that never finished on this thread (e.g. futures that
+/// were moved to another worker), so they are discarded. Returns `None` if the frame is
+/// not on this thread's stack.
+#[inline]
+pub(crate) fn exit(id: u64, duration_ns: u64) -> Option<ScopePath> {
+    CALL_STACK.with(|stack| {
+        let mut stack = stack.borrow_mut();
+        let pos = stack.iter().rposition(|frame| frame.id == id)?;
+        let frame = &stack[pos];
+        let self_ns = duration_ns.saturating_sub(frame.children_ns);
+        stack.truncate(pos);
+
+        if let Some(parent) = stack.last_mut() {
+            parent.children_ns += duration_ns;
+        }
+
+        Some(ScopePath {
+            parents: stack.iter().map(|frame| frame.name).collect(),
+            self_ns,
+        })
+    })
+}
+
+/// Name of the innermost measured scope on the current thread.
+#[inline]
+pub(crate) fn current_scope() -> Option<&'static str> {
+    CALL_STACK.with(|stack| stack.borrow().last().map(|frame| frame.name))
+}
+
+/// Aggregated timing of a function for a single call path.
+#[derive(Debug, Clone, Default)]
+pub struct CallPathStats {
+    pub count: u64,
+    pub total_ns: u64,
+    pub self_ns: u64,
+    /// Allocated bytes excluding nested measured scopes (alloc mode only)
+    pub self_bytes: u64,
+}
+
+impl CallPathStats {
+    pub(crate) fn record(&mut self, duration_ns: u64, self_ns: u64) {
+        self.count += 1;
+        self.total_ns += duration_ns;
+        self.self_ns += self_ns;
+    }
+}
+
+/// Per-function call path statistics, keyed by the names of the enclosing scopes.
+pub type CallPaths = HashMap<Vec<&'static str>, CallPathStats>;
+
+/// Records a measurement under its call path. Measurements without a known path
+/// are recorded as roots with their full duration as self time.
+pub(crate) fn record_call_path(
+    call_paths: &mut CallPaths,
+    path: Option<ScopePath>,
+    duration_ns: u64,
+) -> &mut CallPathStats {
+    let (parents, self_ns) = match path {
+        Some(path) => (path.parents, path.self_ns),
+        None => (Vec::new(), duration_ns),
+    };
+    let stats = call_paths.entry(parents).or_default();
+    stats.record(duration_ns, self_ns);
+    stats
+}
+
+#[derive(Default)]
+struct TreeBuilder {
+    name: &'static str,
+    stats: CallPathStats,
+    children: HashMap<&'static str, TreeBuilder>,
+}
+
+impl TreeBuilder {
+    fn node_mut(&mut self, path: &[&'static str]) -> &mut TreeBuilder {
+        path.iter().fold(self, |node, &name| {
+            node.children.entry(name).or_insert_with(|| TreeBuilder {
+                name,
+                ..Default::default()
+            })
+        })
+    }
+
+    fn into_nodes(self) -> Vec<CallTreeNode> {
+        let mut nodes: Vec<CallTreeNode> = self
+            .children
+            .into_values()
+            .map(|child| CallTreeNode {
+                name: child.name.to_string(),
+                calls: child.stats.count,
+                total_ns: child.stats.total_ns,
+                self_ns: child.stats.self_ns,
+                children: child.into_nodes(),
+            })
+            .collect();
+
+        nodes.sort_by(|a, b| {
+            b.total_ns
+                .cmp(&a.total_ns)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        nodes
+    }
+}
+
+/// Builds a call tree from per-function call path statistics.
+pub(crate) fn build_call_tree<'a>(
+    functions: impl Iterator<Item = (&'static str, &'a CallPaths)>,
+) -> Vec<CallTreeNode> {
+    let mut root = TreeBuilder::default();
+
+    for (name, call_paths) in functions {
+        for (parents, stats) in call_paths {
+            let node = root.node_mut(parents).node_mut(&[name]);
+            node.stats.count += stats.count;
+            node.stats.total_ns += stats.total_ns;
+            node.stats.self_ns += stats.self_ns;
+        }
+    }
+
+    root.into_nodes()
+}
+
+/// Builds Brendan Gregg folded stack lines (`outer;inner weight`) from per-function
+/// call path statistics. Paths with zero weight are skipped.
+pub(crate) fn build_folded_stacks<'a>(
+    functions: impl Iterator<Item = (&'static str, &'a CallPaths)>,
+    weight: impl Fn(&CallPathStats) -> u64,
+) -> Vec<(String, u64)> {
+    let mut stacks: Vec<(String, u64)> = functions
+        .flat_map(|(name, call_paths)| {
+            call_paths
+                .iter()
+                .map(move |(parents, stats)| (name, parents, stats))
+        })
+        .filter_map(|(name, parents, stats)| {
+            let weight = weight(stats);
+            if weight == 0 {
+                return None;
+            }
+
+            let stack = parents
+                .iter()
+                .chain(std::iter::once(&name))
+                .map(|frame| frame.replace(';', ":"))
+                .collect::<Vec<_>>()
+                .join(";");
+            Some((stack, weight))
+        })
+        .collect();
+
+    stacks.sort();
+    stacks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nested_scopes_build_call_tree() {
+        let outer = enter("outer");
+        let inner = enter("inner");
+        let inner_path = exit(inner, 40).unwrap();
+        let outer_path = exit(outer, 100).unwrap();
+
+        assert_eq!(inner_path.parents, vec!["outer"]);
+        assert_eq!(inner_path.self_ns, 40);
+        assert!(outer_path.parents.is_empty());
+        assert_eq!(outer_path.self_ns, 60);
+
+        let mut outer_paths = CallPaths::new();
+        record_call_path(&mut outer_paths, Some(outer_path), 100);
+        let mut inner_paths = CallPaths::new();
+        record_call_path(&mut inner_paths, Some(inner_path), 40);
+        record_call_path(&mut inner_paths, None, 10);
+
+        let tree = build_call_tree([("outer", &outer_paths), ("inner", &inner_paths)].into_iter());
+
+        assert_eq!(tree.len(), 2);
+        assert_eq!(tree[0].name, "outer");
+        assert_eq!(tree[0].self_ns, 60);
+        assert_eq!(tree[0].children[0].name, "inner");
+        assert_eq!(tree[0].children[0].total_ns, 40);
+        assert_eq!(tree[1].name, "inner");
+        assert_eq!(tree[1].self_ns, 10);
+    }
+
+    #[test]
+    fn test_folded_stacks_use_self_weight() {
+        let mut outer_paths = CallPaths::new();
+        record_call_path(
+            &mut outer_paths,
+            Some(ScopePath {
+                parents: Vec::new(),
+                self_ns: 60,
+            }),
+            100,
+        );
+        let mut inner_paths = CallPaths::new();
+        record_call_path(
+            &mut inner_paths,
+            Some(ScopePath {
+                parents: vec!["outer"],
+                self_ns: 40,
+            }),
+            40,
+        )
+        .self_bytes = 512;
+
+        let functions = [("outer", &outer_paths), ("inner", &inner_paths)];
+
+        let timing = build_folded_stacks(functions.into_iter(), |stats| stats.self_ns);
+        assert_eq!(
+            timing,
+            vec![("outer".to_string(), 60), ("outer;inner".to_string(), 40)]
+        );
+
+        let alloc = build_folded_stacks(functions.into_iter(), |stats| stats.self_bytes);
+        assert_eq!(alloc, vec![("outer;inner".to_string(), 512)]);
+    }
+
+    #[test]
+    fn test_exit_discards_unfinished_frames() {
+        let outer = enter("outer");
+        let _moved = enter("moved_future");
+        let outer_path = exit(outer, 100).unwrap();
+
+        assert!(outer_path.parents.is_empty());
+        assert_eq!(outer_path.self_ns, 100);
+        CALL_STACK.with(|stack| assert!(stack.borrow().is_empty()));
+    }
+
+    #[test]
+    fn test_interleaved_async_frames() {
+        let mut first = AsyncFrame::default();
+        let mut second = AsyncFrame::default();
+
+        // Both functions are polled once and return Pending, the first one after a
+        // nested sync scope finished
+        resume("first", &mut first);
+        let nested = enter("nested");
+        exit(nested, 30).unwrap();
+        suspend(&mut first);
+        resume("second", &mut second);
+        assert_eq!(current_scope(), Some("second"));
+        suspend(&mut second);
+        CALL_STACK.with(|stack| assert!(stack.borrow().is_empty()));
+
+        // Each one finishes in its own poll, its guard popping the frame
+        resume("second", &mut second);
+        let second_path = exit(adopt("second"), 50).unwrap();
+        suspend(&mut second);
+        resume("first", &mut first);
+        let first_path = exit(adopt("first"), 100).unwrap();
+        suspend(&mut first);
+
+        assert!(second_path.parents.is_empty());
+        assert_eq!(second_path.self_ns, 50);
+        assert!(first_path.parents.is_empty());
+        assert_eq!(first_path.self_ns, 70);
+        CALL_STACK.with(|stack| assert!(stack.borrow().is_empty()));
+        assert_eq!(adopt("first"), 0);
+    }
+}
@@ -1,13 +1,15 @@
 use arc_swap::ArcSwapOption;
-use crossbeam_channel::{bounded, select, unbounded};
+use crossbeam_channel::{bounded, never, select, tick, unbounded};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex, RwLock};
 use std::thread;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use crate::metrics_server::METRICS_SERVER_PORT;
-use crate::output::{FunctionLogEntry, FunctionLogsJson, FunctionsJson, MetricsProvider};
-use crate::output_on::{JsonPrettyReporter, JsonReporter, TableReporter};
+use crate::output::{
+    FunctionLogEntry, FunctionLogsJson, FunctionsJson, FunctionsTreeJson, MetricsProvider,
+};
+use crate::output_on::{FoldedReporter, JsonPrettyReporter, JsonReporter, TableReporter};
 use crate::Reporter;
 
 use super::{FunctionsQuery, FUNCTIONS_STATE};
@@ -16,12 +18,12 @@ cfg_if::cfg_if! {
     if #[cfg(feature = "hotpath-alloc")] {
         use super::alloc::{
             report::{StatsData, TimingStatsData},
-            state::{FunctionStats, FunctionsState, Measurement, process_measurement, flush_batch},
+            state::{FunctionStats, FunctionsState, Measurement, process_measurement, record_measurement, flush_batch},
         };
     } else {
         use super::timing::{
             report::StatsData,
-            state::{FunctionStats, FunctionsState, Measurement, process_measurement, flush_batch},
+            state::{FunctionStats, FunctionsState, Measurement, process_measurement, record_measurement, flush_batch},
         };
     }
 }
@@ -107,6 +109,7 @@ pub struct FunctionsGuardBuilder {
     percentiles: Vec<u8>,
     reporter: ReporterConfig,
     limit: usize,
+    report_every: Option<Duration>,
 }
 
 impl FunctionsGuardBuilder {
@@ -134,6 +137,7 @@ impl FunctionsGuardBuilder {
             percentiles: vec![95],
             reporter: ReporterConfig::None,
             limit: 15,
+            report_every: None,
         }
     }
 
@@ -194,6 +198,35 @@ impl FunctionsGuardBuilder {
         self
     }
 
+    /// Invokes the reporter periodically while the guard is alive.
+    ///
+    /// Every `interval` the reporter's [`Reporter::report_periodic`] receives a snapshot
+    /// of the measurements since the previous periodic report, and a cumulative snapshot
+    /// since the guard was built. Intervals without any measurements are skipped. The
+    /// final report is still generated when the guard is dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - Time between periodic reports
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "hotpath")]
+    /// # {
+    /// use std::time::Duration;
+    /// use hotpath::FunctionsGuardBuilder;
+    ///
+    /// let _guard = FunctionsGuardBuilder::new("server")
+    ///     .report_every(Duration::from_secs(60))
+    ///     .build();
+    /// # }
+    /// ```
+    pub fn report_every(mut self, interval: Duration) -> Self {
+        self.report_every = Some(interval);
+        self
+    }
+
     /// Sets the output format for the profiling report.
     ///
     /// # Arguments
@@ -288,6 +321,7 @@ impl FunctionsGuardBuilder {
                 Format::Table => Box::new(TableReporter),
                 Format::Json => Box::new(JsonReporter),
                 Format::JsonPretty => Box::new(JsonPrettyReporter),
+                Format::Folded => Box::new(FoldedReporter),
             },
             ReporterConfig::Custom(reporter) => reporter,
             ReporterConfig::None => Box::new(TableReporter),
@@ -304,6 +338,7 @@ impl FunctionsGuardBuilder {
             self.limit,
             reporter,
             recent_logs_limit,
+            self.report_every,
         )
     }
 
@@ -349,7 +384,7 @@ impl FunctionsGuardBuilder {
 #[must_use = "guard is dropped immediately without generating a report"]
 pub struct FunctionsGuard {
     state: Arc<RwLock<FunctionsState>>,
-    reporter: Box<dyn Reporter>,
+    reporter: Arc<dyn Reporter>,
     wrapper_guard: Option<MeasurementGuard>,
 }
 
@@ -358,8 +393,9 @@ impl FunctionsGuard {
         caller_name: &'static str,
         percentiles: &[u8],
         limit: usize,
-        _reporter: Box<dyn Reporter>,
+        reporter: Box<dyn Reporter>,
         recent_logs_limit: usize,
+        report_every: Option<Duration>,
     ) -> Self {
         // Disable allocation tracking during infrastructure initialization
         // to prevent profiling overhead from being included in measurements
@@ -372,6 +408,17 @@ impl FunctionsGuard {
         }
 
         let percentiles = percentiles.to_vec();
+        crate::lib_on::trace::init();
+
+        // Override reporter with JsonReporter when HOTPATH_JSON env var is enabled
+        let reporter: Arc<dyn Reporter> = if std::env::var("HOTPATH_JSON")
+            .map(|v| v.eq_ignore_ascii_case("true") || v == "1")
+            .unwrap_or(false)
+        {
+            Arc::new(JsonReporter)
+        } else {
+            Arc::from(reporter)
+        };
 
         let arc_swap = FUNCTIONS_STATE.get_or_init(|| ArcSwapOption::from(None));
 
@@ -401,17 +448,26 @@ impl FunctionsGuard {
         let worker_caller_name = caller_name;
         let worker_limit = limit;
         let worker_recent_logs_limit = recent_logs_limit;
+        let worker_reporter = Arc::clone(&reporter);
+        let report_every = report_every.filter(|interval| !interval.is_zero());
+        let report_tick = report_every.map(tick).unwrap_or_else(never);
 
         thread::Builder::new()
             .name("hp-functions".into())
             .spawn(move || {
                 let mut local_stats = HashMap::<&'static str, FunctionStats>::new();
+                // Measurements since the last periodic report, only tracked with report_every
+                let mut interval_stats = report_every.map(|_| HashMap::<&'static str, FunctionStats>::new());
+                let mut interval_start = worker_start_time;
 
                 loop {
                     select! {
                         recv(rx) -> result => {
                             match result {
                                 Ok(measurement) => {
+                                    if let Some(interval_stats) = interval_stats.as_mut() {
+                                        record_measurement(interval_stats, measurement.clone(), worker_recent_logs_limit, worker_start_time);
+                                    }
                                     process_measurement(&mut local_stats, measurement, worker_recent_logs_limit, worker_start_time);
                                 }
                                 Err(_) => break, // Channel disconnected
@@ -424,6 +480,33 @@ impl FunctionsGuard {
                             }
                             break;
                         }
+                        recv(report_tick) -> _ => {
+                            let Some(interval_stats) = interval_stats.as_mut() else {
+                                continue;
+                            };
+                            let now = Instant::now();
+                            if !interval_stats.is_empty() {
+                                let interval_provider = StatsData::new(
+                                    interval_stats,
+                                    now.duration_since(interval_start),
+                                    worker_percentiles.clone(),
+                                    worker_caller_name,
+                                    worker_limit,
+                                );
+                                let cumulative_provider = StatsData::new(
+                                    &local_stats,
+                                    now.duration_since(worker_start_time),
+                                    worker_percentiles.clone(),
+                                    worker_caller_name,
+                                    worker_limit,
+                                );
+                                if let Err(e) = worker_reporter.report_periodic(&interval_provider, &cumulative_provider) {
+                                    eprintln!("Failed to report hotpath metrics: {}", e);
+                                }
+                                interval_stats.clear();
+                            }
+                            interval_start = now;
+                        }
                         recv(query_rx) -> result => {
                             if let Ok(query_request) = result {
                                 match query_request {
@@ -448,7 +531,41 @@ impl FunctionsGuard {
                                             }
                                         }
                                     }
-                                    FunctionsQuery::Timing(response_tx) => {
+                                    FunctionsQuery::Unlimited(response_tx) => {
+                                        use crate::output::MetricsProvider;
+                                        let total_elapsed = worker_start_time.elapsed();
+                                        cfg_if::cfg_if! {
+                                            if #[cfg(feature = "hotpath-alloc")] {
+                                                let timing_provider = TimingStatsData::new(
+                                                    &local_stats,
+                                                    total_elapsed,
+                                                    worker_percentiles.clone(),
+                                                    worker_caller_name,
+                                                    0,
+                                                );
+                                                let alloc_provider = StatsData::new(
+                                                    &local_stats,
+                                                    total_elapsed,
+                                                    worker_percentiles.clone(),
+                                                    worker_caller_name,
+                                                    0,
+                                                );
+                                                let alloc_json = Some(FunctionsJson::from(&alloc_provider as &dyn MetricsProvider));
+                                            } else {
+                                                let timing_provider = StatsData::new(
+                                                    &local_stats,
+                                                    total_elapsed,
+                                                    worker_percentiles.clone(),
+                                                    worker_caller_name,
+                                                    0,
+                                                );
+                                                let alloc_json = None;
+                                            }
+                                        }
+                                        let timing_json = FunctionsJson::from(&timing_provider as &dyn MetricsProvider);
+                                        let _ = response_tx.send((timing_json, alloc_json));
+                                    }
+                                    FunctionsQuery::Timing { window, response_tx } => {
                                         cfg_if::cfg_if! {
                                             if #[cfg(feature = "hotpath-alloc")] {
                                                 // Create timing metrics snapshot
@@ -460,7 +577,8 @@ impl FunctionsGuard {
                                                     worker_percentiles.clone(),
                                                     worker_caller_name,
                                                     worker_limit,
-                                                );
+                                                )
+                                                .with_window(window);
                                                 let metrics_json = FunctionsJson::from(&metrics_provider as &dyn MetricsProvider);
                                                 let _ = response_tx.send(metrics_json);
                                             } else {
@@ -472,12 +590,41 @@ impl FunctionsGuard {
                                                     worker_percentiles.clone(),
                                                     worker_caller_name,
                                                     worker_limit,
-                                                );
+                                                )
+                                                .with_window(window);
                                                 let metrics_json = FunctionsJson::from(&metrics_provider as &dyn MetricsProvider);
                                                 let _ = response_tx.send(metrics_json);
                                             }
                                         }
                                     }
+                                    FunctionsQuery::TimingTree(response_tx) => {
+                                        cfg_if::cfg_if! {
+                                            if #[cfg(feature = "hotpath-alloc")] {
+                                                let total_elapsed = worker_start_time.elapsed();
+                                                let metrics_provider = TimingStatsData::new(
+                                                    &local_stats,
+                                                    total_elapsed,
+                                                    worker_percentiles.clone(),
+                                                    worker_caller_name,
+                                                    worker_limit,
+                                                );
+                                            } else {
+                                                let total_elapsed = worker_start_time.elapsed();
+                                                let metrics_provider = StatsData::new(
+                                                    &local_stats,
+                                                    total_elapsed,
+                                                    worker_percentiles.clone(),
+                                                    worker_caller_name,
+                                                    worker_limit,
+                                                );
+                                            }
+                                        }
+                                        let _ = response_tx.send(FunctionsTreeJson {
+                                            total_elapsed: metrics_provider.total_elapsed(),
+                                            caller_name: worker_caller_name.to_string(),
+                                            tree: metrics_provider.call_tree(),
+                                        });
+                                    }
                                     FunctionsQuery::LogsTiming { function_name, response_tx } => {
                                         let response = if let Some(stats) = local_stats.get(function_name.as_str()) {
                                             cfg_if::cfg_if! {
@@ -549,6 +696,16 @@ impl FunctionsGuard {
                                             }
                                         }
                                     }
+                                    FunctionsQuery::DurationHistograms { bounds, response_tx } => {
+                                        let metrics_provider = StatsData::new(
+                                            &local_stats,
+                                            worker_start_time.elapsed(),
+                                            worker_percentiles.clone(),
+                                            worker_caller_name,
+                                            worker_limit,
+                                        );
+                                        let _ = response_tx.send(metrics_provider.duration_histograms(&bounds));
+                                    }
                                 }
                             }
                         }
@@ -573,15 +730,9 @@ impl FunctionsGuard {
         #[cfg(feature = "hotpath-mcp")]
         crate::mcp_server::start_mcp_server_once();
 
-        // Override reporter with JsonReporter when HOTPATH_JSON env var is enabled
-        let reporter: Box<dyn Reporter> = if std::env::var("HOTPATH_JSON")
-            .map(|v| v.eq_ignore_ascii_case("true") || v == "1")
-            .unwrap_or(false)
-        {
-            Box::new(JsonReporter)
-        } else {
-            _reporter
-        };
+        // Picks up the runtime if the guard is built inside one
+        #[cfg(feature = "tokio-runtime-metrics")]
+        crate::runtime::init_runtime_monitoring();
 
         let wrapper_guard = MeasurementGuard::build(caller_name, true, false);
 
@@ -648,6 +799,8 @@ impl Drop for FunctionsGuard {
             }
         }
 
+        crate::lib_on::trace::write_trace_file();
+
         if let Some(arc_swap) = FUNCTIONS_STATE.get() {
             arc_swap.store(None);
         }
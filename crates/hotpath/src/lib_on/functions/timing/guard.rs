@@ -4,6 +4,7 @@ use quanta::Instant;
 #[cfg(not(target_os = "linux"))]
 use std::time::Instant;
 
+use super::super::call_tree;
 use super::super::truncate_result;
 
 #[doc(hidden)]
@@ -13,6 +14,7 @@ pub struct MeasurementGuard {
     start: Instant,
     wrapper: bool,
     tid: u64,
+    frame_id: u64,
 }
 
 impl MeasurementGuard {
@@ -23,6 +25,19 @@ impl MeasurementGuard {
             start: Instant::now(),
             wrapper,
             tid: crate::tid::current_tid(),
+            frame_id: call_tree::enter(name),
+        }
+    }
+
+    /// Guard of a measured async function, which pushes its frame on each poll instead
+    #[inline]
+    pub(crate) fn new_async(name: &'static str, wrapper: bool, _unsupported_sync: bool) -> Self {
+        Self {
+            name,
+            start: Instant::now(),
+            wrapper,
+            tid: crate::tid::current_tid(),
+            frame_id: call_tree::adopt(name),
         }
     }
 }
@@ -32,8 +47,9 @@ impl Drop for MeasurementGuard {
     fn drop(&mut self) {
         let dur = self.start.elapsed();
         let cross_thread = crate::tid::current_tid() != self.tid;
-        let tid = if cross_thread { None } else { Some(self.tid) };
-        super::state::send_duration_measurement(self.name, dur, self.wrapper, tid);
+        let path = call_tree::exit(self.frame_id, dur.as_nanos() as u64);
+        let tid = (!cross_thread).then_some(self.tid);
+        super::state::send_duration_measurement(self.name, dur, self.wrapper, tid, path);
     }
 }
 
@@ -44,6 +60,7 @@ pub struct MeasurementGuardWithLog {
     start: Instant,
     wrapper: bool,
     tid: u64,
+    frame_id: u64,
     finished: bool,
 }
 
@@ -55,6 +72,20 @@ impl MeasurementGuardWithLog {
             start: Instant::now(),
             wrapper,
             tid: crate::tid::current_tid(),
+            frame_id: call_tree::enter(name),
+            finished: false,
+        }
+    }
+
+    /// Guard of a measured async function, which pushes its frame on each poll instead
+    #[inline]
+    pub(crate) fn new_async(name: &'static str, wrapper: bool, _unsupported_sync: bool) -> Self {
+        Self {
+            name,
+            start: Instant::now(),
+            wrapper,
+            tid: crate::tid::current_tid(),
+            frame_id: call_tree::adopt(name),
             finished: false,
         }
     }
@@ -64,13 +95,15 @@ impl MeasurementGuardWithLog {
         self.finished = true;
         let dur = self.start.elapsed();
         let cross_thread = crate::tid::current_tid() != self.tid;
-        let tid = if cross_thread { None } else { Some(self.tid) };
+        let path = call_tree::exit(self.frame_id, dur.as_nanos() as u64);
+        let tid = (!cross_thread).then_some(self.tid);
         let result_str = truncate_result(format!("{:?}", result));
         super::state::send_duration_measurement_with_log(
             self.name,
             dur,
             self.wrapper,
             tid,
+            path,
             Some(result_str),
         );
     }
@@ -82,12 +115,14 @@ impl Drop for MeasurementGuardWithLog {
         if !self.finished {
             let dur = self.start.elapsed();
             let cross_thread = crate::tid::current_tid() != self.tid;
-            let tid = if cross_thread { None } else { Some(self.tid) };
+            let path = call_tree::exit(self.frame_id, dur.as_nanos() as u64);
+            let tid = (!cross_thread).then_some(self.tid);
             super::state::send_duration_measurement_with_log(
                 self.name,
                 dur,
                 self.wrapper,
                 tid,
+                path,
                 None,
             );
         }
@@ -3,8 +3,12 @@ use std::time::Duration;
 
 use crate::ProfilingMode;
 
+use super::super::call_tree::{build_call_tree, build_folded_stacks};
+use super::super::windowed_timing_rows;
 use super::state::FunctionStats;
-use crate::output::{MetricType, MetricsProvider};
+use crate::json::StatsWindow;
+use crate::lib_on::window::WindowSnapshot;
+use crate::output::{CallTreeNode, DurationHistogram, MetricType, MetricsProvider};
 
 pub struct StatsData<'a> {
     pub stats: &'a HashMap<&'static str, FunctionStats>,
@@ -12,6 +16,35 @@ pub struct StatsData<'a> {
     pub percentiles: Vec<u8>,
     pub caller_name: &'static str,
     pub limit: usize,
+    pub window: StatsWindow,
+}
+
+impl StatsData<'_> {
+    /// Restricts `metric_data` to measurements taken within `window`.
+    pub(crate) fn with_window(mut self, window: StatsWindow) -> Self {
+        self.window = window;
+        self
+    }
+
+    fn window_snapshots(&self) -> Option<Vec<(&'static str, bool, WindowSnapshot)>> {
+        if self.window == StatsWindow::Lifetime {
+            return None;
+        }
+
+        Some(
+            self.stats
+                .iter()
+                .filter(|(_, s)| s.has_data)
+                .filter_map(|(name, s)| {
+                    Some((
+                        *name,
+                        s.wrapper,
+                        s.windowed(self.window, self.total_elapsed)?,
+                    ))
+                })
+                .collect(),
+        )
+    }
 }
 
 impl<'a> MetricsProvider<'a> for StatsData<'a> {
@@ -28,6 +61,7 @@ impl<'a> MetricsProvider<'a> for StatsData<'a> {
             percentiles,
             caller_name,
             limit,
+            window: StatsWindow::Lifetime,
         }
     }
 
@@ -36,7 +70,13 @@ impl<'a> MetricsProvider<'a> for StatsData<'a> {
     }
 
     fn description(&self) -> String {
-        "Execution duration of functions.".to_string()
+        match self.window {
+            StatsWindow::Lifetime => "Execution duration of functions.".to_string(),
+            window => format!(
+                "Execution duration of functions over the {}.",
+                window.label()
+            ),
+        }
     }
 
     fn profiling_mode(&self) -> ProfilingMode {
@@ -44,6 +84,10 @@ impl<'a> MetricsProvider<'a> for StatsData<'a> {
     }
 
     fn metric_data(&self) -> Vec<(String, Vec<MetricType>)> {
+        if let Some(snapshots) = self.window_snapshots() {
+            return windowed_timing_rows(snapshots, &self.percentiles, self.limit);
+        }
+
         let wrapper_total = self
             .stats
             .iter()
@@ -87,6 +131,7 @@ impl<'a> MetricsProvider<'a> for StatsData<'a> {
 
                 metrics.push(MetricType::DurationNs(stats.total_duration_ns));
                 metrics.push(MetricType::Percentage((percentage * 100.0) as u64));
+                metrics.push(MetricType::DurationNs(stats.self_duration_ns()));
 
                 (function_name.to_string(), metrics)
             })
@@ -102,7 +147,10 @@ impl<'a> MetricsProvider<'a> for StatsData<'a> {
     }
 
     fn entry_counts(&self) -> (usize, usize) {
-        let total_count = self.stats.iter().filter(|(_, s)| s.has_data).count();
+        let total_count = match self.window_snapshots() {
+            Some(snapshots) => snapshots.iter().filter(|(_, _, s)| s.count > 0).count(),
+            None => self.stats.iter().filter(|(_, s)| s.has_data).count(),
+        };
 
         let displayed_count = if self.limit > 0 && self.limit < total_count {
             self.limit
@@ -112,4 +160,26 @@ impl<'a> MetricsProvider<'a> for StatsData<'a> {
 
         (displayed_count, total_count)
     }
+
+    fn call_tree(&self) -> Vec<CallTreeNode> {
+        build_call_tree(self.stats.iter().map(|(name, s)| (*name, &s.call_paths)))
+    }
+
+    fn folded_stacks(&self) -> Vec<(String, u64)> {
+        build_folded_stacks(
+            self.stats.iter().map(|(name, s)| (*name, &s.call_paths)),
+            |stats| stats.self_ns,
+        )
+    }
+
+    fn duration_histograms(&self, bounds: &[u64]) -> Vec<(String, DurationHistogram)> {
+        let mut histograms: Vec<_> = self
+            .stats
+            .iter()
+            .filter(|(_, s)| s.has_data)
+            .filter_map(|(name, s)| Some((name.to_string(), s.duration_histogram(bounds)?)))
+            .collect();
+        histograms.sort_by(|a, b| a.0.cmp(&b.0));
+        histograms
+    }
 }
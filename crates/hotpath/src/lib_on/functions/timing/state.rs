@@ -1,10 +1,17 @@
 use crossbeam_channel::{Receiver, Sender};
 use hdrhistogram::Histogram;
 use std::cell::RefCell;
-use std::collections::{HashMap, VecDeque};
+use std::collections::{hash_map::Entry, HashMap, VecDeque};
 use std::sync::Mutex;
 use std::time::{Duration, Instant};
 
+use super::super::bucket_durations;
+use super::super::call_tree::{record_call_path, CallPaths, ScopePath};
+use crate::json::StatsWindow;
+use crate::lib_on::trace;
+use crate::lib_on::window::{WindowSnapshot, WindowedDurations};
+use crate::output::DurationHistogram;
+
 const BATCH_SIZE: usize = 64;
 const FLUSH_INTERVAL_MS: u64 = 50;
 
@@ -17,6 +24,7 @@ struct MeasurementBatch {
 
 impl MeasurementBatch {
     fn new() -> Self {
+        crate::lib_on::trace::register_current_thread();
         Self {
             measurements: Vec::with_capacity(BATCH_SIZE),
             last_flush: Instant::now(),
@@ -31,6 +39,7 @@ impl MeasurementBatch {
         duration: Duration,
         wrapper: bool,
         tid: Option<u64>,
+        path: Option<ScopePath>,
         result_log: Option<String>,
     ) {
         if self.sender.is_none() {
@@ -54,6 +63,8 @@ impl MeasurementBatch {
             name,
             wrapper,
             tid,
+            end_tid: crate::tid::current_tid(),
+            path,
             result_log,
         };
 
@@ -97,12 +108,16 @@ pub(crate) fn flush_batch() {
     });
 }
 
+#[derive(Clone)]
 pub struct Measurement {
     pub duration_ns: u64,
     pub measurement_time: Instant,
     pub name: &'static str,
     pub wrapper: bool,
     pub tid: Option<u64>,
+    /// Thread the scope finished on, the thread of the last poll for async functions
+    pub end_tid: u64,
+    pub path: Option<ScopePath>,
     pub result_log: Option<String>,
 }
 
@@ -114,6 +129,8 @@ pub struct FunctionStats {
     pub has_data: bool,
     pub wrapper: bool,
     pub recent_logs: VecDeque<(u64, Duration, Option<u64>, Option<String>)>, // (duration_ns, elapsed, tid, result_log)
+    pub call_paths: CallPaths,
+    windows: WindowedDurations,
 }
 
 impl FunctionStats {
@@ -142,8 +159,11 @@ impl FunctionStats {
             has_data: true,
             wrapper,
             recent_logs,
+            call_paths: CallPaths::new(),
+            windows: WindowedDurations::new(),
         };
         s.record_time(first_ns);
+        s.windows.record(elapsed, first_ns);
         s
     }
 
@@ -165,6 +185,7 @@ impl FunctionStats {
         self.total_duration_ns += duration_ns;
         self.count += 1;
         self.record_time(duration_ns);
+        self.windows.record(elapsed, duration_ns);
 
         if self.recent_logs.len() == self.recent_logs.capacity() && self.recent_logs.capacity() > 0
         {
@@ -175,11 +196,12 @@ impl FunctionStats {
     }
 
     pub fn avg_duration_ns(&self) -> u64 {
-        if self.count == 0 {
-            0
-        } else {
-            self.total_duration_ns / self.count
-        }
+        self.total_duration_ns.checked_div(self.count).unwrap_or(0)
+    }
+
+    /// Total duration minus the time spent in nested measured scopes.
+    pub fn self_duration_ns(&self) -> u64 {
+        self.call_paths.values().map(|stats| stats.self_ns).sum()
     }
 
     #[inline]
@@ -191,6 +213,16 @@ impl FunctionStats {
         let v = self.hist.as_ref().unwrap().value_at_percentile(p);
         Duration::from_nanos(v)
     }
+
+    pub fn duration_histogram(&self, bounds: &[u64]) -> Option<DurationHistogram> {
+        let hist = self.hist.as_ref().filter(|_| self.count > 0)?;
+        Some(bucket_durations(hist, self.total_duration_ns, bounds))
+    }
+
+    /// Durations recorded within `window` of `now` (elapsed since the profiler started).
+    pub(crate) fn windowed(&self, window: StatsWindow, now: Duration) -> Option<WindowSnapshot> {
+        self.windows.snapshot(window, now)
+    }
 }
 
 pub(crate) struct FunctionsState {
@@ -209,23 +241,42 @@ pub(crate) fn process_measurement(
     m: Measurement,
     recent_logs_limit: usize,
     start_time: Instant,
+) {
+    trace::record_span(
+        m.name,
+        trace::Category::Function,
+        m.measurement_time,
+        Duration::from_nanos(m.duration_ns),
+        m.end_tid,
+        None,
+    );
+    record_measurement(stats, m, recent_logs_limit, start_time);
+}
+
+/// Aggregates a measurement into `stats` without recording it in the trace.
+pub(crate) fn record_measurement(
+    stats: &mut HashMap<&'static str, FunctionStats>,
+    m: Measurement,
+    recent_logs_limit: usize,
+    start_time: Instant,
 ) {
     let elapsed = m.measurement_time.duration_since(start_time);
-    if let Some(s) = stats.get_mut(m.name) {
-        s.update_duration(m.duration_ns, elapsed, m.tid, m.result_log);
-    } else {
-        stats.insert(
-            m.name,
-            FunctionStats::new_duration(
-                m.duration_ns,
-                elapsed,
-                m.wrapper,
-                recent_logs_limit,
-                m.tid,
-                m.result_log,
-            ),
-        );
-    }
+    let s = match stats.entry(m.name) {
+        Entry::Occupied(entry) => {
+            let s = entry.into_mut();
+            s.update_duration(m.duration_ns, elapsed, m.tid, m.result_log);
+            s
+        }
+        Entry::Vacant(entry) => entry.insert(FunctionStats::new_duration(
+            m.duration_ns,
+            elapsed,
+            m.wrapper,
+            recent_logs_limit,
+            m.tid,
+            m.result_log,
+        )),
+    };
+    record_call_path(&mut s.call_paths, m.path, m.duration_ns);
 }
 
 use super::super::FunctionsQuery;
@@ -236,8 +287,9 @@ pub fn send_duration_measurement(
     duration: Duration,
     wrapper: bool,
     tid: Option<u64>,
+    path: Option<ScopePath>,
 ) {
-    send_duration_measurement_with_log(name, duration, wrapper, tid, None);
+    send_duration_measurement_with_log(name, duration, wrapper, tid, path, None);
 }
 
 pub fn send_duration_measurement_with_log(
@@ -245,6 +297,7 @@ pub fn send_duration_measurement_with_log(
     duration: Duration,
     wrapper: bool,
     tid: Option<u64>,
+    path: Option<ScopePath>,
     result_log: Option<String>,
 ) {
     if FUNCTIONS_STATE.get().is_none() {
@@ -256,6 +309,6 @@ pub fn send_duration_measurement_with_log(
     MEASUREMENT_BATCH.with(|batch| {
         batch
             .borrow_mut()
-            .add(name, duration, wrapper, tid, result_log);
+            .add(name, duration, wrapper, tid, path, result_log);
     });
 }
@@ -1,6 +1,6 @@
 //! Futures instrumentation module - tracks async Future lifecycle and poll statistics.
 
-use crate::channels::{get_log_limit, resolve_label, START_TIME};
+use crate::channels::{get_log_limit, resolve_label, timestamp_nanos, START_TIME};
 use crate::metrics_server::METRICS_SERVER_PORT;
 use crossbeam_channel::{unbounded, Sender as CbSender};
 use std::collections::{HashMap, VecDeque};
@@ -14,13 +14,23 @@ use quanta::Instant;
 use std::time::Instant;
 
 pub mod guard;
+mod timing;
 pub(crate) mod wrapper;
 
 pub use guard::{FuturesGuard, FuturesGuardBuilder};
 pub use wrapper::{InstrumentedFuture, InstrumentedFutureLog};
 
-pub use crate::json::{FutureCall, FutureCalls, FutureState, FuturesJson, SerializableFutureStats};
+pub use crate::json::{
+    DurationStats, FutureCall, FutureCalls, FutureState, FuturesJson, LongestPoll,
+    SerializableFutureStats, WakeStats, WakeWarning,
+};
+
 pub use crate::Format;
+use timing::FutureTiming;
+
+/// Key/value pairs attached to a single call with `tags(...)`.
+#[doc(hidden)]
+pub type FutureTags = Vec<(&'static str, String)>;
 
 pub(crate) static FUTURE_ID_COUNTER: AtomicU64 = AtomicU64::new(0);
 pub(crate) static FUTURE_CALL_ID_COUNTER: AtomicU64 = AtomicU64::new(0);
@@ -62,6 +72,8 @@ pub struct FutureStats {
     pub label: Option<String>,
     pub calls: VecDeque<FutureCall>,
     pub call_count: u64,
+    pub wakes: WakeStats,
+    timing: FutureTiming,
 }
 
 impl FutureStats {
@@ -72,6 +84,8 @@ impl FutureStats {
             label,
             calls: VecDeque::new(),
             call_count: 0,
+            wakes: WakeStats::default(),
+            timing: FutureTiming::new(),
         }
     }
 
@@ -97,6 +111,11 @@ impl From<&FutureStats> for SerializableFutureStats {
             has_custom_label: future_stats.label.is_some(),
             call_count: future_stats.call_count,
             total_polls: future_stats.total_polls(),
+            busy: future_stats.timing.busy(),
+            idle: future_stats.timing.idle(),
+            total: future_stats.timing.total(),
+            longest_poll: future_stats.timing.longest_poll(),
+            wakes: future_stats.wakes,
         }
     }
 }
@@ -119,20 +138,39 @@ pub(crate) enum FutureEvent {
     CallCreated {
         future_id: u64,
         call_id: u64,
+        tags: FutureTags,
+        timestamp: Instant,
     },
     Polled {
         future_id: u64,
         call_id: u64,
         result: PollResult,
         log_message: Option<String>,
+        started: Instant,
+        busy_ns: u64,
+        /// Returned `Pending` without waking or keeping a clone of the waker
+        lost_wakeup: bool,
+    },
+    Woken {
+        future_id: u64,
+        call_id: u64,
+        /// Woken while being polled
+        self_wake: bool,
+        after_completion: bool,
     },
     Completed {
         future_id: u64,
         call_id: u64,
+        timestamp: Instant,
+        lifetime_ns: u64,
+        busy_ns: u64,
     },
     Cancelled {
         future_id: u64,
         call_id: u64,
+        timestamp: Instant,
+        lifetime_ns: u64,
+        busy_ns: u64,
     },
 }
 
@@ -183,16 +221,25 @@ fn process_future_event(stats_map: &mut HashMap<u64, FutureStats>, event: Future
                 FutureStats::new(future_id, source, display_label),
             );
         }
-        FutureEvent::CallCreated { future_id, call_id } => {
+        FutureEvent::CallCreated {
+            future_id,
+            call_id,
+            tags,
+            timestamp,
+        } => {
             if let Some(future_stats) = stats_map.get_mut(&future_id) {
                 future_stats.call_count += 1;
                 let limit = get_log_limit();
                 if future_stats.calls.len() >= limit {
                     future_stats.calls.pop_front();
                 }
-                future_stats
-                    .calls
-                    .push_back(FutureCall::new(call_id, future_id));
+                let mut call = FutureCall::new(call_id, future_id);
+                call.tags = tags
+                    .into_iter()
+                    .map(|(key, value)| (key.to_string(), value))
+                    .collect();
+                call.created_at_ns = timestamp_nanos(timestamp);
+                future_stats.calls.push_back(call);
             }
         }
         FutureEvent::Polled {
@@ -200,10 +247,24 @@ fn process_future_event(stats_map: &mut HashMap<u64, FutureStats>, event: Future
             call_id,
             result,
             log_message,
+            started,
+            busy_ns,
+            lost_wakeup,
         } => {
             if let Some(future_stats) = stats_map.get_mut(&future_id) {
+                let started_at_ns = timestamp_nanos(started);
+                future_stats.timing.polled(call_id, started_at_ns, busy_ns);
+                if lost_wakeup {
+                    future_stats.wakes.lost_wakeups += 1;
+                }
                 if let Some(call) = future_stats.find_call_mut(call_id) {
                     call.poll_count += 1;
+                    if lost_wakeup {
+                        call.wakes.lost_wakeups += 1;
+                    }
+                    call.first_poll_at_ns.get_or_insert(started_at_ns);
+                    call.busy_ns += busy_ns;
+                    call.max_poll_ns = call.max_poll_ns.max(busy_ns);
                     match result {
                         PollResult::Pending => {
                             call.state = FutureState::Suspended;
@@ -218,19 +279,48 @@ fn process_future_event(stats_map: &mut HashMap<u64, FutureStats>, event: Future
                 }
             }
         }
-        FutureEvent::Completed { future_id, call_id } => {
+        FutureEvent::Woken {
+            future_id,
+            call_id,
+            self_wake,
+            after_completion,
+        } => {
+            if let Some(future_stats) = stats_map.get_mut(&future_id) {
+                future_stats.wakes.record(self_wake, after_completion);
+                if let Some(call) = future_stats.find_call_mut(call_id) {
+                    call.wakes.record(self_wake, after_completion);
+                }
+            }
+        }
+        FutureEvent::Completed {
+            future_id,
+            call_id,
+            timestamp,
+            lifetime_ns,
+            busy_ns,
+        } => {
             if let Some(future_stats) = stats_map.get_mut(&future_id) {
+                future_stats.timing.ended(lifetime_ns, busy_ns);
                 if let Some(call) = future_stats.find_call_mut(call_id) {
                     call.state = FutureState::Ready;
+                    call.ended_at_ns = Some(timestamp_nanos(timestamp));
                 }
             }
         }
-        FutureEvent::Cancelled { future_id, call_id } => {
+        FutureEvent::Cancelled {
+            future_id,
+            call_id,
+            timestamp,
+            lifetime_ns,
+            busy_ns,
+        } => {
             if let Some(future_stats) = stats_map.get_mut(&future_id) {
+                future_stats.timing.ended(lifetime_ns, busy_ns);
                 if let Some(call) = future_stats.find_call_mut(call_id) {
                     if call.state != FutureState::Ready {
                         call.state = FutureState::Cancelled;
                     }
+                    call.ended_at_ns = Some(timestamp_nanos(timestamp));
                 }
             }
         }
@@ -250,7 +340,12 @@ pub(crate) fn send_future_event(event: FutureEvent) {
 #[doc(hidden)]
 pub trait InstrumentFuture {
     type Output;
-    fn instrument_future(self, source: &'static str) -> Self::Output;
+    fn instrument_future(
+        self,
+        source: &'static str,
+        label: Option<String>,
+        tags: FutureTags,
+    ) -> Self::Output;
 }
 
 /// Trait for instrumenting futures with output logging (requires Debug).
@@ -259,14 +354,24 @@ pub trait InstrumentFuture {
 #[doc(hidden)]
 pub trait InstrumentFutureLog {
     type Output;
-    fn instrument_future_log(self, source: &'static str) -> Self::Output;
+    fn instrument_future_log(
+        self,
+        source: &'static str,
+        label: Option<String>,
+        tags: FutureTags,
+    ) -> Self::Output;
 }
 
 impl<F: std::future::Future> InstrumentFuture for F {
     type Output = InstrumentedFuture<F>;
 
-    fn instrument_future(self, source: &'static str) -> Self::Output {
-        InstrumentedFuture::new(self, source)
+    fn instrument_future(
+        self,
+        source: &'static str,
+        label: Option<String>,
+        tags: FutureTags,
+    ) -> Self::Output {
+        InstrumentedFuture::with_label_and_tags(self, source, label, tags)
     }
 }
 
@@ -276,8 +381,13 @@ where
 {
     type Output = InstrumentedFutureLog<F>;
 
-    fn instrument_future_log(self, source: &'static str) -> Self::Output {
-        InstrumentedFutureLog::new(self, source)
+    fn instrument_future_log(
+        self,
+        source: &'static str,
+        label: Option<String>,
+        tags: FutureTags,
+    ) -> Self::Output {
+        InstrumentedFutureLog::with_label_and_tags(self, source, label, tags)
     }
 }
 
@@ -326,6 +436,24 @@ pub fn get_futures_json() -> FuturesJson {
     }
 }
 
+/// Labels of all futures as shown in the futures list, by future id.
+pub(crate) fn future_labels() -> HashMap<u64, String> {
+    let Some((_, stats_map)) = FUTURES_STATE.get() else {
+        return HashMap::new();
+    };
+    stats_map
+        .read()
+        .unwrap()
+        .values()
+        .map(|future_stats| {
+            (
+                future_stats.id,
+                resolve_label(future_stats.source, future_stats.label.as_deref(), None),
+            )
+        })
+        .collect()
+}
+
 pub fn get_future_calls(future_id: u64) -> Option<FutureCalls> {
     let stats = get_all_future_stats();
     stats.get(&future_id).map(|s| FutureCalls {
@@ -340,6 +468,9 @@ pub fn get_future_calls(future_id: u64) -> Option<FutureCalls> {
 ///
 /// - `future!(expr)` - No Debug requirement, prints `Ready` without the value
 /// - `future!(expr, log = true)` - Requires Debug, prints `Ready(value)`
+/// - `future!(expr, label = "name")` - Custom label, can be combined with `log = true`
+/// - `future!(expr, tags(key = value, ...))` - Per-call tags, formatted with `Display`.
+///   They go last, after `label` and `log`.
 ///
 /// # Examples
 ///
@@ -351,20 +482,73 @@ pub fn get_future_calls(future_id: u64) -> Option<FutureCalls> {
 ///
 /// // With logging (requires Debug on output type)
 /// let result = future!(async { 42 }, log = true).await;
+///
+/// // With a label, and the request id attached to this call
+/// let result = future!(handle(req), label = "handle", tags(request_id = req_id)).await;
 /// ```
 #[macro_export]
 macro_rules! future {
-    // Basic: no Debug requirement
-    ($fut:expr) => {{
+    (@instrument instrument_future, $fut:expr, $label:expr, [$($key:ident = $value:expr),*]) => {{
         const FUTURE_LOC: &'static str = concat!(file!(), ":", line!());
         $crate::futures::init_futures_state();
-        $crate::InstrumentFuture::instrument_future($fut, FUTURE_LOC)
+        let tags: $crate::futures::FutureTags =
+            ::std::vec![$((stringify!($key), ($value).to_string())),*];
+        $crate::InstrumentFuture::instrument_future($fut, FUTURE_LOC, $label, tags)
     }};
 
-    // With logging: requires Debug
-    ($fut:expr, log = true) => {{
+    (@instrument instrument_future_log, $fut:expr, $label:expr, [$($key:ident = $value:expr),*]) => {{
         const FUTURE_LOC: &'static str = concat!(file!(), ":", line!());
         $crate::futures::init_futures_state();
-        $crate::InstrumentFutureLog::instrument_future_log($fut, FUTURE_LOC)
+        let tags: $crate::futures::FutureTags =
+            ::std::vec![$((stringify!($key), ($value).to_string())),*];
+        $crate::InstrumentFutureLog::instrument_future_log($fut, FUTURE_LOC, $label, tags)
     }};
+
+    // Basic: no Debug requirement
+    ($fut:expr) => {
+        $crate::future!(@instrument instrument_future, $fut, None, [])
+    };
+
+    // With logging: requires Debug
+    ($fut:expr, log = true) => {
+        $crate::future!(@instrument instrument_future_log, $fut, None, [])
+    };
+
+    ($fut:expr, label = $label:expr) => {
+        $crate::future!(@instrument instrument_future, $fut, Some($label.to_string()), [])
+    };
+
+    ($fut:expr, label = $label:expr, log = true) => {
+        $crate::future!(@instrument instrument_future_log, $fut, Some($label.to_string()), [])
+    };
+
+    ($fut:expr, log = true, label = $label:expr) => {
+        $crate::future!(@instrument instrument_future_log, $fut, Some($label.to_string()), [])
+    };
+
+    ($fut:expr, tags($($key:ident = $value:expr),* $(,)?)) => {
+        $crate::future!(@instrument instrument_future, $fut, None, [$($key = $value),*])
+    };
+
+    ($fut:expr, label = $label:expr, tags($($key:ident = $value:expr),* $(,)?)) => {
+        $crate::future!(
+            @instrument instrument_future,
+            $fut,
+            Some($label.to_string()),
+            [$($key = $value),*]
+        )
+    };
+
+    ($fut:expr, log = true, tags($($key:ident = $value:expr),* $(,)?)) => {
+        $crate::future!(@instrument instrument_future_log, $fut, None, [$($key = $value),*])
+    };
+
+    ($fut:expr, label = $label:expr, log = true, tags($($key:ident = $value:expr),* $(,)?)) => {
+        $crate::future!(
+            @instrument instrument_future_log,
+            $fut,
+            Some($label.to_string()),
+            [$($key = $value),*]
+        )
+    };
 }
@@ -6,8 +6,12 @@ use std::time::Instant;
 
 use prettytable::{Cell, Row, Table};
 
-use crate::futures::{get_futures_json, init_futures_state, FuturesJson};
-use crate::Format;
+use crate::futures::{get_futures_json, init_futures_state, DurationStats, FuturesJson};
+use crate::{format_duration, Format};
+
+fn format_avg(stats: Option<DurationStats>) -> String {
+    stats.map_or_else(|| "-".to_string(), |stats| format_duration(stats.avg_ns))
+}
 
 /// Builder for creating a FuturesGuard with custom configuration.
 ///
@@ -124,6 +128,12 @@ impl Default for FuturesGuard {
 
 impl Drop for FuturesGuard {
     fn drop(&mut self) {
+        // Folded stacks only cover measured functions, and any other line on stdout would
+        // corrupt them
+        if matches!(self.format, Format::Folded) {
+            return;
+        }
+
         let elapsed = self.start_time.elapsed();
         let futures_json = get_futures_json();
 
@@ -145,6 +155,11 @@ impl Drop for FuturesGuard {
                     Cell::new("Future"),
                     Cell::new("Calls"),
                     Cell::new("Polls"),
+                    Cell::new("Busy avg"),
+                    Cell::new("Idle avg"),
+                    Cell::new("Total p95"),
+                    Cell::new("Longest poll"),
+                    Cell::new("Wakes"),
                 ]));
 
                 for future_stats in &futures_json.futures {
@@ -152,11 +167,34 @@ impl Drop for FuturesGuard {
                         Cell::new(&future_stats.label),
                         Cell::new(&future_stats.call_count.to_string()),
                         Cell::new(&future_stats.total_polls.to_string()),
+                        Cell::new(&format_avg(future_stats.busy)),
+                        Cell::new(&format_avg(future_stats.idle)),
+                        Cell::new(&future_stats.total.map_or_else(
+                            || "-".to_string(),
+                            |total| format_duration(total.p95_ns),
+                        )),
+                        Cell::new(&future_stats.longest_poll.map_or_else(
+                            || "-".to_string(),
+                            |longest| {
+                                format!(
+                                    "{} (call {})",
+                                    format_duration(longest.duration_ns),
+                                    longest.call_id
+                                )
+                            },
+                        )),
+                        Cell::new(&future_stats.wakes.wakes.to_string()),
                     ]));
                 }
 
                 println!("\nFutures:");
                 table.printstd();
+
+                for future_stats in &futures_json.futures {
+                    for (warning, count) in future_stats.wakes.warnings() {
+                        println!("⚠ {}: {} ({}x)", future_stats.label, warning, count);
+                    }
+                }
             }
             Format::Json => {
                 // Update elapsed time to use actual guard elapsed time
@@ -179,6 +217,7 @@ impl Drop for FuturesGuard {
                     Err(e) => eprintln!("Failed to serialize statistics to pretty JSON: {}", e),
                 }
             }
+            Format::Folded => {}
         }
     }
 }
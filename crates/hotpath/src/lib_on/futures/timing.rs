@@ -0,0 +1,93 @@
+//! Busy, idle and total time of finished future calls, and the longest single poll.
+
+use crate::json::{DurationStats, LongestPoll};
+use crate::lib_on::durations::Durations;
+
+/// Timings aggregated over all calls of a future, including the ones no longer kept in its
+/// call log.
+#[derive(Debug, Clone)]
+pub(crate) struct FutureTiming {
+    busy: Durations,
+    idle: Durations,
+    total: Durations,
+    longest_poll: Option<LongestPoll>,
+}
+
+impl FutureTiming {
+    pub fn new() -> Self {
+        Self {
+            busy: Durations::new(),
+            idle: Durations::new(),
+            total: Durations::new(),
+            longest_poll: None,
+        }
+    }
+
+    pub fn polled(&mut self, call_id: u64, started_at_ns: u64, duration_ns: u64) {
+        if self
+            .longest_poll
+            .is_none_or(|longest| duration_ns > longest.duration_ns)
+        {
+            self.longest_poll = Some(LongestPoll {
+                call_id,
+                duration_ns,
+                started_at_ns,
+            });
+        }
+    }
+
+    /// Records a call that completed or was dropped after `lifetime_ns`, `busy_ns` of which
+    /// were spent polling.
+    pub fn ended(&mut self, lifetime_ns: u64, busy_ns: u64) {
+        self.busy.record(busy_ns);
+        self.idle.record(lifetime_ns.saturating_sub(busy_ns));
+        self.total.record(lifetime_ns);
+    }
+
+    pub fn busy(&self) -> Option<DurationStats> {
+        self.busy.stats()
+    }
+
+    pub fn idle(&self) -> Option<DurationStats> {
+        self.idle.stats()
+    }
+
+    pub fn total(&self) -> Option<DurationStats> {
+        self.total.stats()
+    }
+
+    pub fn longest_poll(&self) -> Option<LongestPoll> {
+        self.longest_poll
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_future_timing() {
+        let mut timing = FutureTiming::new();
+        assert!(timing.total().is_none());
+
+        timing.polled(1, 100, 50);
+        timing.polled(1, 1_000, 300);
+        timing.polled(2, 2_000, 300);
+        timing.ended(2_000, 350);
+        timing.ended(1_000, 1_500);
+
+        let longest = timing.longest_poll().unwrap();
+        assert_eq!(longest.call_id, 1);
+        assert_eq!(longest.duration_ns, 300);
+        assert_eq!(longest.started_at_ns, 1_000);
+
+        let total = timing.total().unwrap();
+        assert_eq!(total.count, 2);
+        assert_eq!(total.avg_ns, 1_500);
+        assert_eq!(total.max_ns, 2_000);
+        assert_eq!(timing.busy().unwrap().max_ns, 1_500);
+        // Busy time measured on the polling thread can exceed the lifetime, idle stays at 0
+        assert_eq!(timing.idle().unwrap().max_ns, 1_650);
+        assert_eq!(timing.idle().unwrap().avg_ns, 1_650 / 2);
+    }
+}
@@ -1,20 +1,50 @@
 //! Instrumented Future wrapper that tracks lifecycle events.
 
 use crate::functions::truncate_result;
+use crate::lib_on::slow_polls::{check_poll, start_poll, SlowPollTarget};
+use crate::lib_on::trace;
+use crate::tid::current_tid;
 
 use super::{
-    get_or_create_future_id, send_future_event, FutureEvent, PollResult, FUTURE_CALL_ID_COUNTER,
+    get_or_create_future_id, send_future_event, FutureEvent, FutureTags, Instant, PollResult,
+    FUTURE_CALL_ID_COUNTER,
 };
 use pin_project_lite::pin_project;
 use std::future::Future;
 use std::mem::ManuallyDrop;
 use std::pin::Pin;
-use std::sync::atomic::Ordering;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
 
+/// Shared between a call and every waker handed out while polling it.
+#[derive(Default)]
+struct WakeState {
+    polling: AtomicBool,
+    woken_in_poll: AtomicBool,
+    completed: AtomicBool,
+}
+
 struct WakerData {
     inner: Waker,
+    future_id: u64,
+    call_id: u64,
+    state: Arc<WakeState>,
+}
+
+impl WakerData {
+    fn record_wake(&self) {
+        let self_wake = self.state.polling.load(Ordering::Relaxed);
+        if self_wake {
+            self.state.woken_in_poll.store(true, Ordering::Relaxed);
+        }
+        send_future_event(FutureEvent::Woken {
+            future_id: self.future_id,
+            call_id: self.call_id,
+            self_wake,
+            after_completion: self.state.completed.load(Ordering::Relaxed),
+        });
+    }
 }
 
 fn waker_clone(data: *const ()) -> RawWaker {
@@ -24,13 +54,15 @@ fn waker_clone(data: *const ()) -> RawWaker {
 }
 
 fn waker_wake(data: *const ()) {
-    unsafe { Arc::increment_strong_count(data as *const WakerData) };
+    // Takes over the reference of the consumed waker
     let arc = unsafe { Arc::from_raw(data as *const WakerData) };
+    arc.record_wake();
     arc.inner.wake_by_ref();
 }
 
 fn waker_wake_by_ref(data: *const ()) {
     let arc = ManuallyDrop::new(unsafe { Arc::from_raw(data as *const WakerData) });
+    arc.record_wake();
     arc.inner.wake_by_ref();
 }
 
@@ -43,12 +75,74 @@ fn waker_drop(data: *const ()) {
 static VTABLE: RawWakerVTable =
     RawWakerVTable::new(waker_clone, waker_wake, waker_wake_by_ref, waker_drop);
 
-fn create_instrumented_waker(waker: &Waker) -> Waker {
+fn create_instrumented_waker(data: &Arc<WakerData>) -> Waker {
+    let raw = RawWaker::new(Arc::into_raw(Arc::clone(data)) as *const (), &VTABLE);
+    unsafe { Waker::from_raw(raw) }
+}
+
+/// Polls with a waker that reports wakes of this call. Also returns whether the poll
+/// returned `Pending` without keeping a clone of the waker or waking it, in which case
+/// nothing is left to wake the future again.
+fn poll_with_instrumented_waker<T>(
+    cx: &mut Context<'_>,
+    future_id: u64,
+    call_id: u64,
+    wake_state: &Arc<WakeState>,
+    poll: impl FnOnce(&mut Context<'_>) -> Poll<T>,
+) -> (Poll<T>, bool) {
+    #[cfg(feature = "tokio-runtime-metrics")]
+    crate::runtime::init_runtime_monitoring();
+
     let data = Arc::new(WakerData {
-        inner: waker.clone(),
+        inner: cx.waker().clone(),
+        future_id,
+        call_id,
+        state: Arc::clone(wake_state),
     });
-    let raw = RawWaker::new(Arc::into_raw(data) as *const (), &VTABLE);
-    unsafe { Waker::from_raw(raw) }
+    let instrumented_waker = create_instrumented_waker(&data);
+    let mut instrumented_cx = Context::from_waker(&instrumented_waker);
+
+    wake_state.woken_in_poll.store(false, Ordering::Relaxed);
+    wake_state.polling.store(true, Ordering::Relaxed);
+    let result = poll(&mut instrumented_cx);
+    wake_state.polling.store(false, Ordering::Relaxed);
+
+    drop(instrumented_waker);
+    // Any reference besides `data` is a waker clone kept to wake the future later
+    let lost_wakeup = result.is_pending()
+        && Arc::strong_count(&data) == 1
+        && !wake_state.woken_in_poll.load(Ordering::Relaxed);
+
+    (result, lost_wakeup)
+}
+
+/// Registers a new call of the future at `location`, and the future itself on its first call.
+/// The label of the first call names the future.
+fn register_call(
+    location: &'static str,
+    label: Option<String>,
+    tags: FutureTags,
+    created: Instant,
+) -> (u64, u64) {
+    let (future_id, is_new) = get_or_create_future_id(location);
+    let call_id = FUTURE_CALL_ID_COUNTER.fetch_add(1, Ordering::Relaxed);
+
+    if is_new {
+        send_future_event(FutureEvent::Created {
+            future_id,
+            source: location,
+            display_label: label,
+        });
+    }
+
+    send_future_event(FutureEvent::CallCreated {
+        future_id,
+        call_id,
+        tags,
+        timestamp: created,
+    });
+
+    (future_id, call_id)
 }
 
 pin_project! {
@@ -57,6 +151,7 @@ pin_project! {
     /// Created via the `future!` macro, this wrapper tracks:
     /// - Creation
     /// - Each poll call with result (Pending/Ready) and thread ID
+    /// - Wakes, including self-wakes and `Pending` returned without retaining the waker
     /// - Drop (cancellation if not completed)
     ///
     /// This variant does NOT require `Debug` on the output type.
@@ -64,15 +159,27 @@ pin_project! {
     pub struct InstrumentedFuture<F: Future> {
         #[pin]
         inner: F,
+        source: &'static str,
         future_id: u64,
         call_id: u64,
         completed: bool,
+        created: Instant,
+        // Time spent inside `poll` so far
+        busy_ns: u64,
+        wake_state: Arc<WakeState>,
     }
 
     impl<F: Future> PinnedDrop for InstrumentedFuture<F> {
         fn drop(this: Pin<&mut Self>) {
             if !this.completed {
-                send_future_event(FutureEvent::Cancelled { future_id: this.future_id, call_id: this.call_id });
+                let timestamp = Instant::now();
+                send_future_event(FutureEvent::Cancelled {
+                    future_id: this.future_id,
+                    call_id: this.call_id,
+                    timestamp,
+                    lifetime_ns: timestamp.duration_since(this.created).as_nanos() as u64,
+                    busy_ns: this.busy_ns,
+                });
             }
         }
     }
@@ -81,24 +188,28 @@ pin_project! {
 impl<F: Future> InstrumentedFuture<F> {
     /// Create a new instrumented future.
     pub fn new(inner: F, location: &'static str) -> Self {
-        let (future_id, is_new) = get_or_create_future_id(location);
-        let call_id = FUTURE_CALL_ID_COUNTER.fetch_add(1, Ordering::Relaxed);
-
-        if is_new {
-            send_future_event(FutureEvent::Created {
-                future_id,
-                source: location,
-                display_label: None,
-            });
-        }
+        Self::with_label_and_tags(inner, location, None, Vec::new())
+    }
 
-        send_future_event(FutureEvent::CallCreated { future_id, call_id });
+    /// Create a new instrumented future, reported under `label` and tagged with `tags`.
+    pub fn with_label_and_tags(
+        inner: F,
+        location: &'static str,
+        label: Option<String>,
+        tags: FutureTags,
+    ) -> Self {
+        let created = Instant::now();
+        let (future_id, call_id) = register_call(location, label, tags, created);
 
         Self {
             inner,
+            source: location,
             future_id,
             call_id,
             completed: false,
+            created,
+            busy_ns: 0,
+            wake_state: Arc::default(),
         }
     }
 }
@@ -111,15 +222,42 @@ impl<F: Future> Future for InstrumentedFuture<F> {
         let future_id = *this.future_id;
         let call_id = *this.call_id;
 
-        let instrumented_waker = create_instrumented_waker(cx.waker());
-        let mut instrumented_cx = Context::from_waker(&instrumented_waker);
-
-        let result = this.inner.poll(&mut instrumented_cx);
+        let scope = start_poll();
+        let started = Instant::now();
+        let (result, lost_wakeup) =
+            poll_with_instrumented_waker(cx, future_id, call_id, this.wake_state, |cx| {
+                this.inner.poll(cx)
+            });
+        let ended = Instant::now();
+        let busy = ended.duration_since(started);
+        let busy_ns = busy.as_nanos() as u64;
+        *this.busy_ns += busy_ns;
+        check_poll(
+            scope,
+            SlowPollTarget::Future {
+                future_id,
+                call_id,
+                source: this.source,
+            },
+            started,
+            busy,
+        );
+        if trace::is_enabled() {
+            trace::record_span(
+                this.source,
+                trace::Category::Future,
+                std::time::Instant::now(),
+                busy,
+                current_tid(),
+                None,
+            );
+        }
 
         let poll_result = match &result {
             Poll::Pending => PollResult::Pending,
             Poll::Ready(_) => {
                 *this.completed = true;
+                this.wake_state.completed.store(true, Ordering::Relaxed);
                 PollResult::Ready
             }
         };
@@ -129,10 +267,19 @@ impl<F: Future> Future for InstrumentedFuture<F> {
             call_id,
             result: poll_result,
             log_message: None,
+            started,
+            busy_ns,
+            lost_wakeup,
         });
 
         if *this.completed {
-            send_future_event(FutureEvent::Completed { future_id, call_id });
+            send_future_event(FutureEvent::Completed {
+                future_id,
+                call_id,
+                timestamp: ended,
+                lifetime_ns: ended.duration_since(*this.created).as_nanos() as u64,
+                busy_ns: *this.busy_ns,
+            });
         }
 
         result
@@ -145,21 +292,34 @@ pin_project! {
     /// Created via the `future!(expr, log = true)` macro, this wrapper tracks:
     /// - Creation
     /// - Each poll call with result (Pending/Ready with Debug output) and thread ID
+    /// - Wakes, including self-wakes and `Pending` returned without retaining the waker
     /// - Drop (cancellation if not completed)
     ///
     /// This variant requires `Debug` on the output type to log the value.
     pub struct InstrumentedFutureLog<F: Future> {
         #[pin]
         inner: F,
+        source: &'static str,
         future_id: u64,
         call_id: u64,
         completed: bool,
+        created: Instant,
+        // Time spent inside `poll` so far
+        busy_ns: u64,
+        wake_state: Arc<WakeState>,
     }
 
     impl<F: Future> PinnedDrop for InstrumentedFutureLog<F> {
         fn drop(this: Pin<&mut Self>) {
             if !this.completed {
-                send_future_event(FutureEvent::Cancelled { future_id: this.future_id, call_id: this.call_id });
+                let timestamp = Instant::now();
+                send_future_event(FutureEvent::Cancelled {
+                    future_id: this.future_id,
+                    call_id: this.call_id,
+                    timestamp,
+                    lifetime_ns: timestamp.duration_since(this.created).as_nanos() as u64,
+                    busy_ns: this.busy_ns,
+                });
             }
         }
     }
@@ -168,24 +328,29 @@ pin_project! {
 impl<F: Future> InstrumentedFutureLog<F> {
     /// Create a new instrumented future with logging.
     pub fn new(inner: F, location: &'static str) -> Self {
-        let (future_id, is_new) = get_or_create_future_id(location);
-        let call_id = FUTURE_CALL_ID_COUNTER.fetch_add(1, Ordering::Relaxed);
-
-        if is_new {
-            send_future_event(FutureEvent::Created {
-                future_id,
-                source: location,
-                display_label: None,
-            });
-        }
+        Self::with_label_and_tags(inner, location, None, Vec::new())
+    }
 
-        send_future_event(FutureEvent::CallCreated { future_id, call_id });
+    /// Create a new instrumented future with logging, reported under `label` and tagged
+    /// with `tags`.
+    pub fn with_label_and_tags(
+        inner: F,
+        location: &'static str,
+        label: Option<String>,
+        tags: FutureTags,
+    ) -> Self {
+        let created = Instant::now();
+        let (future_id, call_id) = register_call(location, label, tags, created);
 
         Self {
             inner,
+            source: location,
             future_id,
             call_id,
             completed: false,
+            created,
+            busy_ns: 0,
+            wake_state: Arc::default(),
         }
     }
 }
@@ -201,15 +366,42 @@ where
         let future_id = *this.future_id;
         let call_id = *this.call_id;
 
-        let instrumented_waker = create_instrumented_waker(cx.waker());
-        let mut instrumented_cx = Context::from_waker(&instrumented_waker);
-
-        let result = this.inner.poll(&mut instrumented_cx);
+        let scope = start_poll();
+        let started = Instant::now();
+        let (result, lost_wakeup) =
+            poll_with_instrumented_waker(cx, future_id, call_id, this.wake_state, |cx| {
+                this.inner.poll(cx)
+            });
+        let ended = Instant::now();
+        let busy = ended.duration_since(started);
+        let busy_ns = busy.as_nanos() as u64;
+        *this.busy_ns += busy_ns;
+        check_poll(
+            scope,
+            SlowPollTarget::Future {
+                future_id,
+                call_id,
+                source: this.source,
+            },
+            started,
+            busy,
+        );
+        if trace::is_enabled() {
+            trace::record_span(
+                this.source,
+                trace::Category::Future,
+                std::time::Instant::now(),
+                busy,
+                current_tid(),
+                None,
+            );
+        }
 
         let (poll_result, log_message) = match &result {
             Poll::Pending => (PollResult::Pending, None),
             Poll::Ready(value) => {
                 *this.completed = true;
+                this.wake_state.completed.store(true, Ordering::Relaxed);
                 (
                     PollResult::Ready,
                     Some(truncate_result(format!("{:?}", value))),
@@ -222,10 +414,19 @@ where
             call_id,
             result: poll_result,
             log_message,
+            started,
+            busy_ns,
+            lost_wakeup,
         });
 
         if *this.completed {
-            send_future_event(FutureEvent::Completed { future_id, call_id });
+            send_future_event(FutureEvent::Completed {
+                future_id,
+                call_id,
+                timestamp: ended,
+                lifetime_ns: ended.duration_since(*this.created).as_nanos() as u64,
+                busy_ns: *this.busy_ns,
+            });
         }
 
         result
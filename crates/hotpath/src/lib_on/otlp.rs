@@ -0,0 +1,756 @@
+//! OTLP/HTTP metrics exporter.
+//!
+//! [`OtlpReporter`] pushes function duration histograms, channel queue depths and per-thread
+//! CPU usage to an OpenTelemetry collector using the OTLP/HTTP JSON encoding. Metrics are
+//! pushed periodically from a background thread while the program runs, and once more with
+//! the final function statistics when the [`FunctionsGuard`](crate::FunctionsGuard) is dropped.
+
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crossbeam_channel::{bounded, RecvTimeoutError, Sender};
+use serde_json::{json, Value};
+
+use crate::json::{ChannelsJson, ThreadsJson};
+use crate::{DurationHistogram, MetricsProvider, Reporter};
+
+const DEFAULT_ENDPOINT: &str = "http://localhost:4318";
+const METRICS_PATH: &str = "/v1/metrics";
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(10);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+const AGGREGATION_TEMPORALITY_CUMULATIVE: u8 = 2;
+
+/// Default duration histogram bucket bounds in nanoseconds, from 1µs to 10s.
+pub const DEFAULT_BOUNDS_NS: [u64; 15] = [
+    1_000,
+    5_000,
+    10_000,
+    50_000,
+    100_000,
+    500_000,
+    1_000_000,
+    5_000_000,
+    10_000_000,
+    50_000_000,
+    100_000_000,
+    500_000_000,
+    1_000_000_000,
+    5_000_000_000,
+    10_000_000_000,
+];
+
+/// Builder for [`OtlpReporter`].
+///
+/// Defaults follow the standard OpenTelemetry environment variables:
+///
+/// * `OTEL_EXPORTER_OTLP_METRICS_ENDPOINT` - full URL of the metrics endpoint
+/// * `OTEL_EXPORTER_OTLP_ENDPOINT` - base URL, `/v1/metrics` is appended (default `http://localhost:4318`)
+/// * `OTEL_EXPORTER_OTLP_HEADERS` - comma separated `key=value` pairs sent with every request
+/// * `OTEL_SERVICE_NAME` - `service.name` resource attribute (default: executable name)
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # #[cfg(feature = "hotpath")]
+/// # {
+/// use std::time::Duration;
+/// use hotpath::{FunctionsGuardBuilder, OtlpReporterBuilder};
+///
+/// let reporter = OtlpReporterBuilder::new()
+///     .endpoint("http://collector:4318/v1/metrics")
+///     .interval(Duration::from_secs(5))
+///     .build();
+///
+/// let _guard = FunctionsGuardBuilder::new("main")
+///     .reporter(Box::new(reporter))
+///     .build();
+/// # }
+/// ```
+pub struct OtlpReporterBuilder {
+    endpoint: String,
+    headers: Vec<(String, String)>,
+    service_name: String,
+    interval: Duration,
+    bounds: Vec<u64>,
+}
+
+impl Default for OtlpReporterBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OtlpReporterBuilder {
+    pub fn new() -> Self {
+        let endpoint = std::env::var("OTEL_EXPORTER_OTLP_METRICS_ENDPOINT")
+            .ok()
+            .filter(|endpoint| !endpoint.is_empty())
+            .unwrap_or_else(|| {
+                let base = std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT")
+                    .ok()
+                    .filter(|endpoint| !endpoint.is_empty())
+                    .unwrap_or_else(|| DEFAULT_ENDPOINT.to_string());
+                format!("{}{}", base.trim_end_matches('/'), METRICS_PATH)
+            });
+
+        let headers = std::env::var("OTEL_EXPORTER_OTLP_HEADERS")
+            .map(|headers| parse_headers(&headers))
+            .unwrap_or_default();
+
+        let service_name = std::env::var("OTEL_SERVICE_NAME")
+            .ok()
+            .filter(|name| !name.is_empty())
+            .or_else(|| {
+                std::env::current_exe()
+                    .ok()?
+                    .file_stem()
+                    .map(|name| name.to_string_lossy().into_owned())
+            })
+            .unwrap_or_else(|| "unknown_service".to_string());
+
+        Self {
+            endpoint,
+            headers,
+            service_name,
+            interval: DEFAULT_INTERVAL,
+            bounds: DEFAULT_BOUNDS_NS.to_vec(),
+        }
+    }
+
+    /// Sets the full URL metrics are posted to, e.g. `http://localhost:4318/v1/metrics`.
+    pub fn endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = endpoint.into();
+        self
+    }
+
+    /// Adds an HTTP header sent with every export request.
+    pub fn header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((key.into(), value.into()));
+        self
+    }
+
+    /// Sets the `service.name` resource attribute.
+    pub fn service_name(mut self, service_name: impl Into<String>) -> Self {
+        self.service_name = service_name.into();
+        self
+    }
+
+    /// Sets how often metrics are pushed while the program runs (default 10s).
+    /// A zero interval disables periodic pushes, leaving only the final flush.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Sets the upper bounds of duration histogram buckets in nanoseconds.
+    pub fn bounds(mut self, bounds: &[u64]) -> Self {
+        let mut bounds = bounds.to_vec();
+        bounds.sort_unstable();
+        bounds.dedup();
+        self.bounds = bounds;
+        self
+    }
+
+    /// Builds the reporter and starts the periodic push thread.
+    pub fn build(self) -> OtlpReporter {
+        let agent: ureq::Agent = ureq::Agent::config_builder()
+            .timeout_global(Some(REQUEST_TIMEOUT))
+            .build()
+            .into();
+
+        let exporter = Arc::new(Exporter {
+            agent,
+            endpoint: self.endpoint,
+            headers: self.headers,
+            service_name: self.service_name,
+            bounds: self.bounds,
+            start_time_unix_nano: unix_nanos(),
+        });
+
+        let mut reporter = OtlpReporter {
+            exporter: Arc::clone(&exporter),
+            stop_tx: Mutex::new(None),
+            worker: Mutex::new(None),
+        };
+
+        if !self.interval.is_zero() {
+            let (stop_tx, stop_rx) = bounded::<()>(1);
+            let interval = self.interval;
+            let worker = thread::Builder::new()
+                .name("hp-otlp".into())
+                .spawn(move || {
+                    let mut failing = false;
+                    while let Err(RecvTimeoutError::Timeout) = stop_rx.recv_timeout(interval) {
+                        let functions =
+                            crate::functions::get_functions_duration_histograms(&exporter.bounds)
+                                .unwrap_or_default();
+                        match exporter.push(&functions) {
+                            Ok(()) => failing = false,
+                            // Report only the first failure of a streak, the collector may be down
+                            Err(e) if !failing => {
+                                failing = true;
+                                eprintln!("[hotpath] Failed to push OTLP metrics: {}", e);
+                            }
+                            Err(_) => {}
+                        }
+                    }
+                })
+                .expect("Failed to spawn hp-otlp thread");
+
+            reporter.stop_tx = Mutex::new(Some(stop_tx));
+            reporter.worker = Mutex::new(Some(worker));
+        }
+
+        reporter
+    }
+}
+
+/// [`Reporter`] that exports metrics to an OpenTelemetry collector over OTLP/HTTP.
+///
+/// Created with [`OtlpReporterBuilder`]. The final report sends the function statistics once
+/// more, periodic pushes stop when the reporter is dropped or [`OtlpReporter::shutdown`] is
+/// called. With `FunctionsGuardBuilder::report_every`, every periodic report pushes the
+/// cumulative statistics, as the exported histograms are cumulative.
+///
+/// Exported metrics:
+///
+/// * `hotpath.function.duration` - cumulative histogram per function (`ns`)
+/// * `hotpath.channel.queued` and `hotpath.channel.queued_bytes` - channel queue depth gauges
+/// * `hotpath.thread.cpu.time` - cumulative CPU time per thread and mode (`s`, `threads` feature)
+/// * `hotpath.thread.cpu.utilization` - CPU usage ratio per thread (`threads` feature)
+pub struct OtlpReporter {
+    exporter: Arc<Exporter>,
+    stop_tx: Mutex<Option<Sender<()>>>,
+    worker: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl OtlpReporter {
+    /// Stops the periodic push thread. Reports can still be pushed afterwards.
+    pub fn shutdown(&self) {
+        if let Some(stop_tx) = self.stop_tx.lock().ok().and_then(|mut tx| tx.take()) {
+            let _ = stop_tx.send(());
+        }
+        if let Some(worker) = self.worker.lock().ok().and_then(|mut w| w.take()) {
+            let _ = worker.join();
+        }
+    }
+}
+
+impl Reporter for OtlpReporter {
+    fn report(
+        &self,
+        metrics_provider: &dyn MetricsProvider<'_>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let functions = metrics_provider.duration_histograms(&self.exporter.bounds);
+        self.exporter.push(&functions)
+    }
+
+    fn report_periodic(
+        &self,
+        _interval: &dyn MetricsProvider<'_>,
+        cumulative: &dyn MetricsProvider<'_>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.report(cumulative)
+    }
+}
+
+impl Drop for OtlpReporter {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+struct Exporter {
+    agent: ureq::Agent,
+    endpoint: String,
+    headers: Vec<(String, String)>,
+    service_name: String,
+    bounds: Vec<u64>,
+    start_time_unix_nano: u64,
+}
+
+impl Exporter {
+    fn push(
+        &self,
+        functions: &[(String, DurationHistogram)],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        #[cfg(feature = "threads")]
+        let threads = Some(crate::threads::get_threads_json());
+        #[cfg(not(feature = "threads"))]
+        let threads = None;
+
+        // Channel stats are relative to the profiler start, which is unset until a guard is built
+        let channels = if crate::channels::START_TIME.get().is_some() {
+            crate::channels::get_channels_json()
+        } else {
+            ChannelsJson {
+                current_elapsed_ns: 0,
+                channels: Vec::new(),
+                queue_warn_threshold: None,
+            }
+        };
+
+        let payload = self.payload(functions, &channels, threads.as_ref(), unix_nanos());
+
+        let mut request = self.agent.post(&self.endpoint);
+        for (key, value) in &self.headers {
+            request = request.header(key, value);
+        }
+        request.send_json(&payload)?;
+        Ok(())
+    }
+
+    fn payload(
+        &self,
+        functions: &[(String, DurationHistogram)],
+        channels: &ChannelsJson,
+        threads: Option<&ThreadsJson>,
+        time_unix_nano: u64,
+    ) -> Value {
+        let start = self.start_time_unix_nano.to_string();
+        let time = time_unix_nano.to_string();
+        let mut metrics = Vec::new();
+
+        let duration_points: Vec<Value> = functions
+            .iter()
+            .map(|(name, histogram)| {
+                json!({
+                    "attributes": [attribute("function", name)],
+                    "startTimeUnixNano": start,
+                    "timeUnixNano": time,
+                    "count": histogram.count.to_string(),
+                    "sum": histogram.sum_ns as f64,
+                    "min": histogram.min_ns as f64,
+                    "max": histogram.max_ns as f64,
+                    "bucketCounts": histogram.bucket_counts.iter().map(u64::to_string).collect::<Vec<_>>(),
+                    "explicitBounds": self.bounds.iter().map(|bound| *bound as f64).collect::<Vec<_>>(),
+                })
+            })
+            .collect();
+        push_metric(
+            &mut metrics,
+            "hotpath.function.duration",
+            "Execution duration of a measured function.",
+            "ns",
+            "histogram",
+            json!({ "aggregationTemporality": AGGREGATION_TEMPORALITY_CUMULATIVE }),
+            duration_points,
+        );
+
+        let channel_points = |value: fn(&crate::json::SerializableChannelStats) -> u64| {
+            channels
+                .channels
+                .iter()
+                .map(|channel| {
+                    json!({
+                        "attributes": [
+                            attribute("channel.id", &channel.id.to_string()),
+                            attribute("channel.label", &channel.label),
+                            attribute("channel.source", &channel.source),
+                            attribute("channel.type", &channel.channel_type.to_string()),
+                        ],
+                        "timeUnixNano": time,
+                        "asInt": value(channel).to_string(),
+                    })
+                })
+                .collect::<Vec<_>>()
+        };
+        push_metric(
+            &mut metrics,
+            "hotpath.channel.queued",
+            "Messages currently queued in an instrumented channel.",
+            "{message}",
+            "gauge",
+            json!({}),
+            channel_points(|channel| channel.queued),
+        );
+        push_metric(
+            &mut metrics,
+            "hotpath.channel.queued_bytes",
+            "Approximate size of messages currently queued in an instrumented channel.",
+            "By",
+            "gauge",
+            json!({}),
+            channel_points(|channel| channel.queued_bytes),
+        );
+
+        if let Some(threads) = threads {
+            let thread_attributes = |thread: &crate::json::ThreadMetrics| {
+                vec![
+                    attribute("thread.id", &thread.os_tid.to_string()),
+                    attribute("thread.name", &thread.name),
+                ]
+            };
+
+            let cpu_time_points = threads
+                .threads
+                .iter()
+                .flat_map(|thread| {
+                    [("user", thread.cpu_user), ("system", thread.cpu_sys)].map(|(mode, value)| {
+                        let mut attributes = thread_attributes(thread);
+                        attributes.push(attribute("cpu.mode", mode));
+                        json!({
+                            "attributes": attributes,
+                            "startTimeUnixNano": start,
+                            "timeUnixNano": time,
+                            "asDouble": value,
+                        })
+                    })
+                })
+                .collect();
+            push_metric(
+                &mut metrics,
+                "hotpath.thread.cpu.time",
+                "CPU time consumed by a thread.",
+                "s",
+                "sum",
+                json!({
+                    "aggregationTemporality": AGGREGATION_TEMPORALITY_CUMULATIVE,
+                    "isMonotonic": true,
+                }),
+                cpu_time_points,
+            );
+
+            let utilization_points = threads
+                .threads
+                .iter()
+                .filter_map(|thread| {
+                    let percent = thread.cpu_percent?;
+                    Some(json!({
+                        "attributes": thread_attributes(thread),
+                        "timeUnixNano": time,
+                        "asDouble": percent / 100.0,
+                    }))
+                })
+                .collect();
+            push_metric(
+                &mut metrics,
+                "hotpath.thread.cpu.utilization",
+                "CPU usage of a thread since the previous sample, 1 is one full core.",
+                "1",
+                "gauge",
+                json!({}),
+                utilization_points,
+            );
+        }
+
+        json!({
+            "resourceMetrics": [{
+                "resource": {
+                    "attributes": [attribute("service.name", &self.service_name)],
+                },
+                "scopeMetrics": [{
+                    "scope": {
+                        "name": "hotpath",
+                        "version": env!("CARGO_PKG_VERSION"),
+                    },
+                    "metrics": metrics,
+                }],
+            }],
+        })
+    }
+}
+
+/// Adds a metric of the given OTLP data `kind`, skipping metrics without data points.
+fn push_metric(
+    metrics: &mut Vec<Value>,
+    name: &str,
+    description: &str,
+    unit: &str,
+    kind: &str,
+    mut data: Value,
+    data_points: Vec<Value>,
+) {
+    if data_points.is_empty() {
+        return;
+    }
+    data["dataPoints"] = Value::Array(data_points);
+    metrics.push(json!({
+        "name": name,
+        "description": description,
+        "unit": unit,
+        kind: data,
+    }));
+}
+
+fn attribute(key: &str, value: &str) -> Value {
+    json!({ "key": key, "value": { "stringValue": value } })
+}
+
+fn unix_nanos() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos() as u64)
+        .unwrap_or_default()
+}
+
+fn parse_headers(headers: &str) -> Vec<(String, String)> {
+    headers
+        .split(',')
+        .filter_map(|pair| {
+            let (key, value) = pair.split_once('=')?;
+            Some((key.trim().to_string(), value.trim().to_string()))
+        })
+        .filter(|(key, _)| !key.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json::{ChannelState, ChannelType, SerializableChannelStats};
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+
+    /// Accepts `requests` requests, one per connection, and returns their headers and bodies.
+    fn mock_collector(requests: usize) -> (String, thread::JoinHandle<Vec<(String, Value)>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}/v1/metrics", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || (0..requests).map(|_| accept(&listener)).collect());
+
+        (endpoint, handle)
+    }
+
+    /// Reads one request and closes the connection after answering it.
+    fn accept(listener: &TcpListener) -> (String, Value) {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+
+        let mut head = String::new();
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line == "\r\n" {
+                break;
+            }
+            if let Some((key, value)) = line.split_once(':') {
+                if key.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+            head.push_str(&line);
+        }
+
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).unwrap();
+        reader
+            .get_mut()
+            .write_all(b"HTTP/1.1 200 OK\r\nConnection: close\r\nContent-Length: 2\r\n\r\n{}")
+            .unwrap();
+
+        (head, serde_json::from_slice(&body).unwrap())
+    }
+
+    fn histogram() -> DurationHistogram {
+        DurationHistogram {
+            count: 3,
+            sum_ns: 2_600,
+            min_ns: 100,
+            max_ns: 2_000,
+            bucket_counts: vec![2, 1, 0],
+        }
+    }
+
+    #[test]
+    fn test_push_to_mock_collector() {
+        let (endpoint, collector) = mock_collector(1);
+        let reporter = OtlpReporterBuilder::new()
+            .endpoint(endpoint)
+            .header("x-api-key", "secret")
+            .service_name("otlp-test")
+            .interval(Duration::ZERO)
+            .bounds(&[10_000, 1_000])
+            .build();
+
+        reporter
+            .exporter
+            .push(&[("app::handle".to_string(), histogram())])
+            .unwrap();
+        let (head, body) = collector.join().unwrap().remove(0);
+
+        assert!(head.starts_with("POST /v1/metrics "), "{head}");
+        assert!(head.to_lowercase().contains("x-api-key: secret"), "{head}");
+
+        let resource = &body["resourceMetrics"][0];
+        assert_eq!(
+            resource["resource"]["attributes"][0],
+            attribute("service.name", "otlp-test")
+        );
+
+        let metric = &resource["scopeMetrics"][0]["metrics"][0];
+        assert_eq!(metric["name"], "hotpath.function.duration");
+        let point = &metric["histogram"]["dataPoints"][0];
+        assert_eq!(point["attributes"][0], attribute("function", "app::handle"));
+        assert_eq!(point["count"], "3");
+        assert_eq!(point["bucketCounts"], json!(["2", "1", "0"]));
+        assert_eq!(point["explicitBounds"], json!([1000.0, 10000.0]));
+    }
+
+    /// Provides a single function histogram with the given call count.
+    struct CallsProvider(u64);
+
+    impl<'a> MetricsProvider<'a> for CallsProvider {
+        fn description(&self) -> String {
+            String::new()
+        }
+
+        fn profiling_mode(&self) -> crate::ProfilingMode {
+            crate::ProfilingMode::Timing
+        }
+
+        fn percentiles(&self) -> Vec<u8> {
+            Vec::new()
+        }
+
+        fn metric_data(&self) -> Vec<(String, Vec<crate::MetricType>)> {
+            Vec::new()
+        }
+
+        fn entry_counts(&self) -> (usize, usize) {
+            (1, 1)
+        }
+
+        fn duration_histograms(&self, bounds: &[u64]) -> Vec<(String, DurationHistogram)> {
+            let mut bucket_counts = vec![0; bounds.len() + 1];
+            bucket_counts[0] = self.0;
+            vec![(
+                "app::handle".to_string(),
+                DurationHistogram {
+                    count: self.0,
+                    sum_ns: self.0 * 100,
+                    min_ns: 100,
+                    max_ns: 100,
+                    bucket_counts,
+                },
+            )]
+        }
+
+        fn new(
+            _stats: &'a std::collections::HashMap<&'static str, crate::FunctionStats>,
+            _total_elapsed: Duration,
+            _percentiles: Vec<u8>,
+            _caller_name: &'static str,
+            _limit: usize,
+        ) -> Self {
+            unreachable!()
+        }
+
+        fn total_elapsed(&self) -> u64 {
+            0
+        }
+
+        fn caller_name(&self) -> &str {
+            "main"
+        }
+    }
+
+    #[test]
+    fn test_periodic_reports_push_cumulative_histograms() {
+        let (endpoint, collector) = mock_collector(2);
+        let reporter = OtlpReporterBuilder::new()
+            .endpoint(endpoint)
+            .interval(Duration::from_secs(3600))
+            .build();
+
+        // Two ticks of 3 and then 2 calls
+        reporter
+            .report_periodic(&CallsProvider(3), &CallsProvider(3))
+            .unwrap();
+        reporter
+            .report_periodic(&CallsProvider(2), &CallsProvider(5))
+            .unwrap();
+
+        let counts: Vec<Value> = collector
+            .join()
+            .unwrap()
+            .into_iter()
+            .map(|(_, body)| {
+                body["resourceMetrics"][0]["scopeMetrics"][0]["metrics"][0]["histogram"]
+                    ["dataPoints"][0]["count"]
+                    .clone()
+            })
+            .collect();
+        assert_eq!(counts, [json!("3"), json!("5")]);
+        assert!(
+            reporter.worker.lock().unwrap().is_some(),
+            "Periodic reports should not stop the push thread"
+        );
+
+        reporter.shutdown();
+        assert!(reporter.worker.lock().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_payload_channels_and_threads() {
+        let reporter = OtlpReporterBuilder::new().interval(Duration::ZERO).build();
+
+        let channels = ChannelsJson {
+            current_elapsed_ns: 0,
+            channels: vec![SerializableChannelStats {
+                id: 7,
+                source: "src/main.rs:10".to_string(),
+                label: "jobs".to_string(),
+                has_custom_label: true,
+                channel_type: ChannelType::Bounded(10),
+                state: ChannelState::Active,
+                sent_count: 5,
+                received_count: 4,
+                queued: 1,
+                type_name: "u64".to_string(),
+                type_size: 8,
+                queued_bytes: 8,
+                iter: 0,
+                sent_rate: 0.0,
+                received_rate: 0.0,
+                sent_rate_instant: 0,
+                received_rate_instant: 0,
+                sent_bytes_rate: 0.0,
+                received_bytes_rate: 0.0,
+                sent_history: Vec::new(),
+                received_history: Vec::new(),
+                subscribers: None,
+                lag: None,
+                queue_wait: None,
+                send_blocked: None,
+                sent_bytes: None,
+                received_bytes: None,
+                max_queued: 0,
+                max_queued_at_ns: 0,
+                queue_warnings: Vec::new(),
+            }],
+            queue_warn_threshold: None,
+        };
+
+        let payload = reporter.exporter.payload(&[], &channels, None, 42);
+        let metrics = &payload["resourceMetrics"][0]["scopeMetrics"][0]["metrics"];
+        let names: Vec<_> = metrics
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|metric| metric["name"].as_str().unwrap())
+            .collect();
+
+        assert_eq!(
+            names,
+            ["hotpath.channel.queued", "hotpath.channel.queued_bytes"]
+        );
+        let point = &metrics[0]["gauge"]["dataPoints"][0];
+        assert_eq!(point["asInt"], "1");
+        assert_eq!(point["timeUnixNano"], "42");
+        assert_eq!(point["attributes"][1], attribute("channel.label", "jobs"));
+    }
+
+    #[test]
+    fn test_parse_headers() {
+        assert_eq!(
+            parse_headers("api-key=abc, x-tenant = team=a,invalid,"),
+            vec![
+                ("api-key".to_string(), "abc".to_string()),
+                ("x-tenant".to_string(), "team=a".to_string()),
+            ]
+        );
+    }
+}
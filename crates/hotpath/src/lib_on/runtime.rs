@@ -0,0 +1,428 @@
+//! This module samples metrics of the tokio runtime that `#[hotpath::main]` runs inside:
+//! workers, alive tasks, queue depths, parks and busy time. Worker busy time is matched
+//! with the CPU usage of the threads the workers run on.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use std::thread::ThreadId;
+use std::time::{Duration, Instant};
+use tokio::runtime::{Handle, RuntimeFlavor, RuntimeMetrics};
+
+use crate::json::ThreadMetrics;
+pub use crate::json::{RuntimeJson, RuntimeWorkerMetrics};
+
+const DEFAULT_SAMPLE_INTERVAL_MS: u64 = 1000;
+
+/// Threads the runtime workers run on
+#[derive(Debug, Clone)]
+enum WorkerThreads {
+    /// A `current_thread` runtime polls its tasks on the thread that calls `block_on`
+    BlockOn(u64),
+    /// `multi_thread` workers are the threads that ran a probe task spawned on the runtime,
+    /// by OS thread id. Blocking pool threads share the worker thread name, but never run
+    /// spawned tasks.
+    Probed(Arc<Mutex<BTreeMap<u64, ThreadId>>>),
+}
+
+impl WorkerThreads {
+    fn matches(&self, thread: &ThreadMetrics) -> bool {
+        match self {
+            WorkerThreads::BlockOn(os_tid) => thread.os_tid == *os_tid,
+            WorkerThreads::Probed(tids) => tids.lock().unwrap().contains_key(&thread.os_tid),
+        }
+    }
+
+    /// OS thread id of the given worker
+    fn os_tid(&self, metrics: &RuntimeMetrics, worker: usize) -> Option<u64> {
+        match self {
+            WorkerThreads::BlockOn(os_tid) => Some(*os_tid),
+            WorkerThreads::Probed(tids) => probed_os_tid(metrics, &tids.lock().unwrap(), worker),
+        }
+    }
+
+    /// Spawns a probe task per worker until a thread was found for each of them. Work
+    /// stealing spreads the probes, so idle workers are found over a few samples.
+    fn probe(&self, handle: &Handle, num_workers: usize) {
+        let WorkerThreads::Probed(tids) = self else {
+            return;
+        };
+        if tids.lock().unwrap().len() >= num_workers {
+            return;
+        }
+
+        for _ in 0..num_workers {
+            let tids = Arc::clone(tids);
+            handle.spawn(async move {
+                tids.lock()
+                    .unwrap()
+                    .insert(crate::tid::current_tid(), std::thread::current().id());
+            });
+        }
+    }
+}
+
+/// Internal state for runtime monitoring
+struct RuntimeState {
+    /// Last sample, without the CPU usage of the worker threads
+    current: RuntimeJson,
+    worker_threads: WorkerThreads,
+    /// Start time for elapsed calculation
+    start_time: Instant,
+}
+
+type RuntimeStateRef = Arc<RwLock<RuntimeState>>;
+
+static RUNTIME_STATE: OnceLock<RuntimeStateRef> = OnceLock::new();
+
+// Initialize monitoring of the tokio runtime the calling thread runs inside
+// Called by #[hotpath::main] and on polls of instrumented futures and async functions
+// Call it from inside the runtime if none of them runs there
+// Only the first runtime is monitored, calls outside of a runtime are ignored
+pub fn init_runtime_monitoring() {
+    if RUNTIME_STATE.get().is_some() {
+        return;
+    }
+    let Ok(handle) = Handle::try_current() else {
+        return;
+    };
+
+    RUNTIME_STATE.get_or_init(|| {
+        let sample_interval_ms = std::env::var("HOTPATH_RUNTIME_INTERVAL")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(DEFAULT_SAMPLE_INTERVAL_MS);
+        let sample_interval = Duration::from_millis(sample_interval_ms);
+
+        let (flavor, worker_threads) = match handle.runtime_flavor() {
+            RuntimeFlavor::CurrentThread => (
+                "current_thread",
+                WorkerThreads::BlockOn(crate::tid::current_tid()),
+            ),
+            flavor => {
+                let flavor = if flavor == RuntimeFlavor::MultiThread {
+                    "multi_thread"
+                } else {
+                    "unknown"
+                };
+                (flavor, WorkerThreads::Probed(Arc::default()))
+            }
+        };
+
+        let metrics = handle.metrics();
+        worker_threads.probe(&handle, metrics.num_workers());
+        let current = sample_runtime(
+            &metrics,
+            flavor,
+            sample_interval_ms,
+            &worker_threads,
+            &busy_durations(&metrics),
+            &[],
+            Duration::ZERO,
+        );
+        let state = Arc::new(RwLock::new(RuntimeState {
+            current,
+            worker_threads: worker_threads.clone(),
+            start_time: Instant::now(),
+        }));
+
+        let state_clone = Arc::clone(&state);
+
+        std::thread::Builder::new()
+            .name("hp-runtime".into())
+            .spawn(move || {
+                collector_loop(handle, state_clone, worker_threads, flavor, sample_interval);
+            })
+            .expect("Failed to spawn runtime-metrics-collector thread");
+
+        state
+    });
+}
+
+fn collector_loop(
+    handle: Handle,
+    state: RuntimeStateRef,
+    worker_threads: WorkerThreads,
+    flavor: &'static str,
+    interval: Duration,
+) {
+    let mut last_sample_time = Instant::now();
+    let mut previous_busy = busy_durations(&handle.metrics());
+
+    loop {
+        std::thread::sleep(interval);
+
+        let metrics = handle.metrics();
+        worker_threads.probe(&handle, metrics.num_workers());
+        let elapsed = last_sample_time.elapsed();
+        last_sample_time = Instant::now();
+
+        let busy = busy_durations(&metrics);
+        let sample = sample_runtime(
+            &metrics,
+            flavor,
+            interval.as_millis() as u64,
+            &worker_threads,
+            &busy,
+            &previous_busy,
+            elapsed,
+        );
+        previous_busy = busy;
+
+        let Ok(mut state_guard) = state.write() else {
+            continue;
+        };
+        state_guard.current = sample;
+    }
+}
+
+fn busy_durations(metrics: &RuntimeMetrics) -> Vec<Duration> {
+    (0..metrics.num_workers())
+        .map(|worker| metrics.worker_total_busy_duration(worker))
+        .collect()
+}
+
+/// Reads the current runtime metrics. Busy percentages are computed from the workers'
+/// `busy` durations and the `previous_busy` ones sampled `elapsed` ago.
+fn sample_runtime(
+    metrics: &RuntimeMetrics,
+    flavor: &str,
+    sample_interval_ms: u64,
+    worker_threads: &WorkerThreads,
+    busy: &[Duration],
+    previous_busy: &[Duration],
+    elapsed: Duration,
+) -> RuntimeJson {
+    let elapsed_secs = elapsed.as_secs_f64();
+
+    let workers: Vec<RuntimeWorkerMetrics> = busy
+        .iter()
+        .enumerate()
+        .map(|(index, busy)| {
+            let busy_percent = previous_busy
+                .get(index)
+                .filter(|_| elapsed_secs > 0.0)
+                .map(|prev| (busy.saturating_sub(*prev).as_secs_f64() / elapsed_secs) * 100.0);
+            let park_count = metrics.worker_park_count(index);
+            let park_unpark_count = metrics.worker_park_unpark_count(index);
+
+            RuntimeWorkerMetrics {
+                index,
+                busy_ns: busy.as_nanos() as u64,
+                busy_percent,
+                park_count,
+                unpark_count: park_unpark_count.saturating_sub(park_count),
+                // An odd park/unpark count means the worker is parked
+                parked: park_unpark_count % 2 == 1,
+                local_queue_depth: local_queue_depth(metrics, index),
+                os_tid: worker_threads.os_tid(metrics, index),
+                cpu_percent: None,
+            }
+        })
+        .collect();
+
+    let busy_percent = workers
+        .iter()
+        .map(|worker| worker.busy_percent)
+        .sum::<Option<f64>>();
+    let (blocking_threads, idle_blocking_threads, blocking_queue_depth) = blocking_pool(metrics);
+
+    RuntimeJson {
+        current_elapsed_ns: 0,
+        sample_interval_ms,
+        flavor: flavor.to_string(),
+        num_workers: metrics.num_workers(),
+        alive_tasks: metrics.num_alive_tasks(),
+        global_queue_depth: metrics.global_queue_depth(),
+        blocking_threads,
+        idle_blocking_threads,
+        blocking_queue_depth,
+        workers,
+        busy_percent,
+        cpu_percent: None,
+        threads: Vec::new(),
+    }
+}
+
+#[cfg(tokio_unstable)]
+fn local_queue_depth(metrics: &RuntimeMetrics, worker: usize) -> Option<usize> {
+    Some(metrics.worker_local_queue_depth(worker))
+}
+
+#[cfg(not(tokio_unstable))]
+fn local_queue_depth(_metrics: &RuntimeMetrics, _worker: usize) -> Option<usize> {
+    None
+}
+
+/// Finds the probed thread a `multi_thread` worker runs on
+#[cfg(tokio_unstable)]
+fn probed_os_tid(
+    metrics: &RuntimeMetrics,
+    tids: &BTreeMap<u64, ThreadId>,
+    worker: usize,
+) -> Option<u64> {
+    let thread_id = metrics.worker_thread_id(worker)?;
+    tids.iter()
+        .find(|(_, id)| **id == thread_id)
+        .map(|(os_tid, _)| *os_tid)
+}
+
+/// Tokio only tells which thread a worker runs on with `--cfg tokio_unstable`
+#[cfg(not(tokio_unstable))]
+fn probed_os_tid(
+    _metrics: &RuntimeMetrics,
+    _tids: &BTreeMap<u64, ThreadId>,
+    _worker: usize,
+) -> Option<u64> {
+    None
+}
+
+/// Blocking pool threads, idle threads and queued tasks
+#[cfg(tokio_unstable)]
+fn blocking_pool(metrics: &RuntimeMetrics) -> (Option<usize>, Option<usize>, Option<usize>) {
+    (
+        Some(metrics.num_blocking_threads()),
+        Some(metrics.num_idle_blocking_threads()),
+        Some(metrics.blocking_queue_depth()),
+    )
+}
+
+#[cfg(not(tokio_unstable))]
+fn blocking_pool(_metrics: &RuntimeMetrics) -> (Option<usize>, Option<usize>, Option<usize>) {
+    (None, None, None)
+}
+
+/// Attaches the worker threads, and their CPU usage, to a runtime sample
+fn correlate_threads(
+    runtime: &mut RuntimeJson,
+    worker_threads: &WorkerThreads,
+    threads: &[ThreadMetrics],
+) {
+    runtime.threads = threads
+        .iter()
+        .filter(|thread| worker_threads.matches(thread))
+        .cloned()
+        .collect();
+
+    for worker in &mut runtime.workers {
+        worker.cpu_percent = worker
+            .os_tid
+            .and_then(|os_tid| runtime.threads.iter().find(|t| t.os_tid == os_tid))
+            .and_then(|thread| thread.cpu_percent);
+    }
+
+    runtime.cpu_percent = runtime
+        .threads
+        .iter()
+        .filter_map(|thread| thread.cpu_percent)
+        .reduce(|a, b| a + b);
+}
+
+/// Get current runtime metrics as JSON
+/// Will return None unless a runtime is monitored
+pub fn get_runtime_json() -> Option<RuntimeJson> {
+    let state = RUNTIME_STATE.get()?;
+    let state_guard = state.read().ok()?;
+
+    let mut runtime = state_guard.current.clone();
+    runtime.current_elapsed_ns = state_guard.start_time.elapsed().as_nanos() as u64;
+
+    #[cfg(feature = "threads")]
+    let threads = crate::threads::get_threads_json().threads;
+    #[cfg(not(feature = "threads"))]
+    let threads = Vec::new();
+    correlate_threads(&mut runtime, &state_guard.worker_threads, &threads);
+
+    Some(runtime)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn thread(os_tid: u64, name: &str, cpu_percent: Option<f64>) -> ThreadMetrics {
+        let mut thread = ThreadMetrics::new(
+            os_tid,
+            name.to_string(),
+            "Running".to_string(),
+            "R".to_string(),
+            1.0,
+            0.5,
+        );
+        thread.cpu_percent = cpu_percent;
+        thread
+    }
+
+    fn worker(index: usize, os_tid: Option<u64>) -> RuntimeWorkerMetrics {
+        RuntimeWorkerMetrics {
+            index,
+            busy_ns: 0,
+            busy_percent: Some(90.0),
+            park_count: 0,
+            unpark_count: 0,
+            parked: false,
+            local_queue_depth: None,
+            os_tid,
+            cpu_percent: None,
+        }
+    }
+
+    fn runtime(workers: Vec<RuntimeWorkerMetrics>) -> RuntimeJson {
+        RuntimeJson {
+            current_elapsed_ns: 0,
+            sample_interval_ms: 1000,
+            flavor: "multi_thread".to_string(),
+            num_workers: workers.len(),
+            alive_tasks: 0,
+            global_queue_depth: 0,
+            blocking_threads: None,
+            idle_blocking_threads: None,
+            blocking_queue_depth: None,
+            workers,
+            busy_percent: None,
+            cpu_percent: None,
+            threads: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_correlate_threads() {
+        let threads = [
+            thread(1, "main", Some(80.0)),
+            thread(2, "tokio-runtime-w", Some(20.0)),
+            thread(3, "tokio-runtime-w", Some(5.0)),
+            thread(4, "tokio", Some(50.0)),
+            thread(5, "hp-threads", None),
+        ];
+
+        let mut current_thread = runtime(vec![worker(0, Some(1))]);
+        correlate_threads(&mut current_thread, &WorkerThreads::BlockOn(1), &threads);
+        assert_eq!(current_thread.workers[0].os_tid, Some(1));
+        assert_eq!(current_thread.workers[0].cpu_percent, Some(80.0));
+        assert_eq!(current_thread.cpu_percent, Some(80.0));
+
+        let tids = Arc::new(Mutex::new(BTreeMap::new()));
+        let probed = WorkerThreads::Probed(Arc::clone(&tids));
+        let mut multi_thread = runtime(vec![worker(0, None), worker(1, None)]);
+        correlate_threads(&mut multi_thread, &probed, &threads);
+        assert!(multi_thread.threads.is_empty());
+        assert_eq!(multi_thread.cpu_percent, None);
+
+        // Thread 3 is a blocking pool thread with the worker name, it never ran a probe
+        let thread_id = std::thread::current().id();
+        tids.lock()
+            .unwrap()
+            .extend([(2, thread_id), (6, thread_id)]);
+        correlate_threads(&mut multi_thread, &probed, &threads);
+        let tids: Vec<u64> = multi_thread.threads.iter().map(|t| t.os_tid).collect();
+        assert_eq!(tids, vec![2]);
+        assert_eq!(multi_thread.cpu_percent, Some(20.0));
+        assert!(multi_thread.workers.iter().all(|w| w.cpu_percent.is_none()));
+
+        // Worker 1 was matched with its probed thread, worker 0 with one not sampled yet
+        multi_thread.workers[0].os_tid = Some(6);
+        multi_thread.workers[1].os_tid = Some(2);
+        correlate_threads(&mut multi_thread, &probed, &threads);
+        assert_eq!(multi_thread.workers[0].cpu_percent, None);
+        assert_eq!(multi_thread.workers[1].cpu_percent, Some(20.0));
+    }
+}
@@ -0,0 +1,231 @@
+//! Single polls that held the thread longer than `HOTPATH_SLOW_POLL_MS`, usually blocking
+//! work inside async code.
+
+use pin_project_lite::pin_project;
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{LazyLock, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+#[cfg(target_os = "linux")]
+use quanta::Instant;
+
+#[cfg(not(target_os = "linux"))]
+use std::time::Instant;
+
+use crate::channels::{resolve_label, timestamp_nanos, START_TIME};
+use crate::format_duration;
+use crate::json::{SlowPoll, SlowPollKind, SlowPollsJson};
+use crate::tid::current_tid;
+
+/// Poll duration reported as slow, disabled if unset or zero.
+pub(crate) static SLOW_POLL_THRESHOLD: LazyLock<Option<Duration>> = LazyLock::new(|| {
+    std::env::var("HOTPATH_SLOW_POLL_MS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .filter(|ms| *ms > 0)
+        .map(Duration::from_millis)
+});
+
+/// Most recent slow polls kept.
+const MAX_SLOW_POLLS: usize = 100;
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum SlowPollTarget {
+    Future {
+        future_id: u64,
+        call_id: u64,
+        source: &'static str,
+    },
+    Function(&'static str),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct SlowPollRecord {
+    target: SlowPollTarget,
+    tid: u64,
+    duration_ns: u64,
+    timestamp_ns: u64,
+}
+
+#[derive(Debug, Default)]
+struct SlowPolls {
+    total: u64,
+    records: VecDeque<SlowPollRecord>,
+}
+
+static SLOW_POLLS: LazyLock<Mutex<SlowPolls>> = LazyLock::new(|| Mutex::new(SlowPolls::default()));
+
+thread_local! {
+    /// Whether a wrapper nested in the one being polled on this thread reported a slow poll
+    static SLOW_POLL_REPORTED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Slow poll state of the enclosing wrapper, handed from [`start_poll`] to [`check_poll`].
+pub(crate) struct PollScope {
+    outer_reported: bool,
+}
+
+/// Called before a wrapper polls its inner future, the poll then being passed to
+/// [`check_poll`].
+pub(crate) fn start_poll() -> PollScope {
+    PollScope {
+        outer_reported: SLOW_POLL_REPORTED.replace(false),
+    }
+}
+
+/// Records a poll of `target` that started at `started` and took `duration`, if it took
+/// longer than `HOTPATH_SLOW_POLL_MS`. Called on the polling thread.
+///
+/// Nested wrappers, e.g. a `future!` awaited inside a measured async function, all time
+/// the same blocking call. Only the innermost one reports it.
+pub(crate) fn check_poll(
+    scope: PollScope,
+    target: SlowPollTarget,
+    started: Instant,
+    duration: Duration,
+) {
+    let Some(threshold) = *SLOW_POLL_THRESHOLD else {
+        return;
+    };
+    let reported = SLOW_POLL_REPORTED.get() || {
+        let slow = duration >= threshold;
+        if slow {
+            report(target, started, duration, threshold);
+        }
+        slow
+    };
+    SLOW_POLL_REPORTED.set(scope.outer_reported || reported);
+}
+
+fn report(target: SlowPollTarget, started: Instant, duration: Duration, threshold: Duration) {
+    START_TIME.get_or_init(|| started);
+    let record = SlowPollRecord {
+        target,
+        tid: current_tid(),
+        duration_ns: duration.as_nanos() as u64,
+        timestamp_ns: timestamp_nanos(started),
+    };
+
+    let name = match target {
+        SlowPollTarget::Future { source, .. } => source,
+        SlowPollTarget::Function(name) => name,
+    };
+    eprintln!(
+        "[hotpath] Slow poll of '{}' blocked thread {} for {} (HOTPATH_SLOW_POLL_MS={})",
+        name,
+        record.tid,
+        format_duration(record.duration_ns),
+        threshold.as_millis()
+    );
+
+    let mut slow_polls = SLOW_POLLS.lock().unwrap();
+    slow_polls.total += 1;
+    if slow_polls.records.len() >= MAX_SLOW_POLLS {
+        slow_polls.records.pop_front();
+    }
+    slow_polls.records.push_back(record);
+}
+
+pub(crate) fn get_slow_polls_json() -> SlowPollsJson {
+    let (total, records) = {
+        let slow_polls = SLOW_POLLS.lock().unwrap();
+        (slow_polls.total, slow_polls.records.clone())
+    };
+
+    // Futures are named like in the futures list, custom labels included
+    let future_labels = crate::futures::future_labels();
+
+    let slow_polls = records
+        .iter()
+        .rev()
+        .map(|record| {
+            let (kind, label, source, future_id, call_id) = match record.target {
+                SlowPollTarget::Future {
+                    future_id,
+                    call_id,
+                    source,
+                } => (
+                    SlowPollKind::Future,
+                    future_labels
+                        .get(&future_id)
+                        .cloned()
+                        .unwrap_or_else(|| resolve_label(source, None, None)),
+                    source,
+                    Some(future_id),
+                    Some(call_id),
+                ),
+                SlowPollTarget::Function(name) => {
+                    (SlowPollKind::Function, name.to_string(), name, None, None)
+                }
+            };
+
+            SlowPoll {
+                kind,
+                label,
+                source: source.to_string(),
+                future_id,
+                call_id,
+                tid: record.tid,
+                duration_ns: record.duration_ns,
+                timestamp_ns: record.timestamp_ns,
+            }
+        })
+        .collect();
+
+    SlowPollsJson {
+        current_elapsed_ns: START_TIME
+            .get()
+            .map(|t| t.elapsed().as_nanos() as u64)
+            .unwrap_or(0),
+        threshold_ms: SLOW_POLL_THRESHOLD.map(|threshold| threshold.as_millis() as u64),
+        total,
+        slow_polls,
+    }
+}
+
+/// Times each poll of an async function measured with `#[hotpath::measure]`.
+///
+/// This function is not intended for direct use.
+#[doc(hidden)]
+pub fn time_polls<F: Future>(name: &'static str, inner: F) -> TimedPolls<F> {
+    TimedPolls { inner, name }
+}
+
+pin_project! {
+    /// Reports polls of the inner future that took longer than `HOTPATH_SLOW_POLL_MS`.
+    #[doc(hidden)]
+    pub struct TimedPolls<F> {
+        #[pin]
+        inner: F,
+        name: &'static str,
+    }
+}
+
+impl<F: Future> Future for TimedPolls<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        #[cfg(feature = "tokio-runtime-metrics")]
+        crate::runtime::init_runtime_monitoring();
+
+        if SLOW_POLL_THRESHOLD.is_none() {
+            this.inner.poll(cx)
+        } else {
+            let scope = start_poll();
+            let started = Instant::now();
+            let result = this.inner.poll(cx);
+            check_poll(
+                scope,
+                SlowPollTarget::Function(this.name),
+                started,
+                Instant::now().duration_since(started),
+            );
+            result
+        }
+    }
+}
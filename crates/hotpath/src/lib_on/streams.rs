@@ -4,6 +4,7 @@ use crossbeam_channel::{unbounded, Sender as CbSender};
 use std::collections::{HashMap, VecDeque};
 use std::sync::atomic::AtomicU64;
 use std::sync::{Arc, OnceLock, RwLock};
+use std::time::Duration;
 
 #[cfg(target_os = "linux")]
 use quanta::Instant;
@@ -16,7 +17,9 @@ pub use guard::{StreamsGuard, StreamsGuardBuilder};
 
 pub(crate) mod wrapper;
 
+use crate::json::StatsWindow;
 pub use crate::json::{ChannelState, LogEntry, SerializableStreamStats, StreamLogs, StreamsJson};
+use crate::lib_on::window::{lifetime_rate, RateHistory, WindowedCounter};
 use crate::metrics_server::METRICS_SERVER_PORT;
 pub use crate::Format;
 
@@ -32,6 +35,11 @@ pub(crate) struct StreamStats {
     pub(crate) type_size: usize,
     pub(crate) logs: VecDeque<LogEntry>,
     pub(crate) iter: u32,
+    yielded_window: WindowedCounter,
+    yielded_history: RateHistory,
+    /// Inline and heap bytes of yielded items, `None` unless instrumented with `size = true`
+    yielded_bytes: Option<u64>,
+    yielded_bytes_window: WindowedCounter,
 }
 
 impl From<&StreamStats> for SerializableStreamStats {
@@ -52,6 +60,11 @@ impl From<&StreamStats> for SerializableStreamStats {
             type_name: stream_stats.type_name.to_string(),
             type_size: stream_stats.type_size,
             iter: stream_stats.iter,
+            yield_rate: 0.0,
+            yield_rate_instant: 0,
+            yield_bytes_rate: 0.0,
+            yield_history: Vec::new(),
+            yielded_bytes: stream_stats.yielded_bytes,
         }
     }
 }
@@ -75,6 +88,29 @@ impl StreamStats {
             type_size,
             logs: VecDeque::new(),
             iter,
+            yielded_window: WindowedCounter::new(),
+            yielded_history: RateHistory::default(),
+            yielded_bytes: None,
+            yielded_bytes_window: WindowedCounter::new(),
+        }
+    }
+
+    /// Yielded items per second over `window`, `now` being the elapsed time.
+    fn yield_rate(&self, window: StatsWindow, now: Duration) -> f64 {
+        self.yielded_window
+            .rate(window, now)
+            .unwrap_or_else(|| lifetime_rate(self.items_yielded, now))
+    }
+
+    /// Yielded bytes per second over `window`. Measured with `size = true`, estimated from
+    /// the item type size otherwise.
+    fn yield_bytes_rate(&self, window: StatsWindow, now: Duration) -> f64 {
+        match self.yielded_bytes {
+            Some(bytes) => self
+                .yielded_bytes_window
+                .rate(window, now)
+                .unwrap_or_else(|| lifetime_rate(bytes, now)),
+            None => self.yield_rate(window, now) * self.type_size as f64,
         }
     }
 }
@@ -88,10 +124,13 @@ pub(crate) enum StreamEvent {
         display_label: Option<String>,
         type_name: &'static str,
         type_size: usize,
+        /// Whether yields report the deep size of their item
+        sized: bool,
     },
     Yielded {
         id: u64,
         log: Option<String>,
+        size: Option<u64>,
         timestamp: Instant,
     },
     Completed {
@@ -130,25 +169,42 @@ pub(crate) fn init_streams_state() -> &'static StreamStatsState {
                             display_label,
                             type_name,
                             type_size,
+                            sized,
                         } => {
                             // Count existing items with the same source location
                             let iter = stats.values().filter(|s| s.source == source).count() as u32;
 
-                            stats.insert(
+                            let mut stream_stats = StreamStats::new(
                                 id,
-                                StreamStats::new(
-                                    id,
-                                    source,
-                                    display_label,
-                                    type_name,
-                                    type_size,
-                                    iter,
-                                ),
+                                source,
+                                display_label,
+                                type_name,
+                                type_size,
+                                iter,
                             );
+                            if sized {
+                                stream_stats.yielded_bytes = Some(0);
+                            }
+                            stats.insert(id, stream_stats);
                         }
-                        StreamEvent::Yielded { id, log, timestamp } => {
+                        StreamEvent::Yielded {
+                            id,
+                            log,
+                            size,
+                            timestamp,
+                        } => {
                             if let Some(stream_stats) = stats.get_mut(&id) {
+                                let timestamp_ns = crate::channels::timestamp_nanos(timestamp);
                                 stream_stats.items_yielded += 1;
+                                let elapsed = Duration::from_nanos(timestamp_ns);
+                                stream_stats.yielded_window.record(elapsed);
+                                stream_stats.yielded_history.record(elapsed);
+                                if let (Some(bytes), Some(size)) =
+                                    (&mut stream_stats.yielded_bytes, size)
+                                {
+                                    *bytes += size;
+                                    stream_stats.yielded_bytes_window.add(elapsed, size);
+                                }
 
                                 let limit = crate::channels::get_log_limit();
                                 if stream_stats.logs.len() >= limit {
@@ -156,7 +212,7 @@ pub(crate) fn init_streams_state() -> &'static StreamStatsState {
                                 }
                                 stream_stats.logs.push_back(LogEntry::new(
                                     stream_stats.items_yielded,
-                                    crate::channels::timestamp_nanos(timestamp),
+                                    timestamp_ns,
                                     log,
                                     None,
                                 ));
@@ -187,6 +243,15 @@ pub trait InstrumentStream {
     fn instrument_stream(self, source: &'static str, label: Option<String>) -> Self::Output;
 }
 
+/// Trait for instrumenting streams with deep item sizes.
+///
+/// This trait is not intended for direct use. Use the `stream!` macro with `size = true` instead.
+#[doc(hidden)]
+pub trait InstrumentStreamSize {
+    type Output;
+    fn instrument_stream_size(self, source: &'static str, label: Option<String>) -> Self::Output;
+}
+
 /// Trait for instrumenting streams with message logging.
 ///
 /// This trait is not intended for direct use. Use the `stream!` macro with `log = true` instead.
@@ -221,6 +286,19 @@ where
     }
 }
 
+// Implement InstrumentStreamSize for all Stream types with HeapSize items
+impl<S> InstrumentStreamSize for S
+where
+    S: futures_util::Stream,
+    S::Item: crate::HeapSize,
+{
+    type Output = crate::streams::wrapper::InstrumentedStreamSize<S>;
+
+    fn instrument_stream_size(self, source: &'static str, label: Option<String>) -> Self::Output {
+        crate::streams::wrapper::InstrumentedStreamSize::new(self, source, label)
+    }
+}
+
 /// Instrument a stream to track its item yields.
 ///
 /// # Examples
@@ -277,6 +355,29 @@ macro_rules! stream {
             Some($label.to_string()),
         )
     }};
+
+    ($expr:expr, size = true) => {{
+        const STREAM_ID: &'static str = concat!(file!(), ":", line!());
+        $crate::InstrumentStreamSize::instrument_stream_size($expr, STREAM_ID, None)
+    }};
+
+    ($expr:expr, label = $label:expr, size = true) => {{
+        const STREAM_ID: &'static str = concat!(file!(), ":", line!());
+        $crate::InstrumentStreamSize::instrument_stream_size(
+            $expr,
+            STREAM_ID,
+            Some($label.to_string()),
+        )
+    }};
+
+    ($expr:expr, size = true, label = $label:expr) => {{
+        const STREAM_ID: &'static str = concat!(file!(), ":", line!());
+        $crate::InstrumentStreamSize::instrument_stream_size(
+            $expr,
+            STREAM_ID,
+            Some($label.to_string()),
+        )
+    }};
 }
 
 fn get_all_stream_stats() -> HashMap<u64, StreamStats> {
@@ -313,16 +414,32 @@ pub(crate) fn get_sorted_stream_stats() -> Vec<StreamStats> {
 }
 
 pub fn get_streams_json() -> StreamsJson {
-    let streams = get_sorted_stream_stats()
-        .iter()
-        .map(SerializableStreamStats::from)
-        .collect();
+    get_streams_json_for(StatsWindow::Lifetime)
+}
 
-    let current_elapsed_ns = crate::channels::START_TIME
+/// Stream statistics with yield rates computed over `window`. Instantaneous rates and
+/// histories always cover the last complete seconds.
+pub(crate) fn get_streams_json_for(window: StatsWindow) -> StreamsJson {
+    let current_elapsed = crate::channels::START_TIME
         .get()
         .expect("START_TIME must be initialized")
-        .elapsed()
-        .as_nanos() as u64;
+        .elapsed();
+    let current_elapsed_ns = current_elapsed.as_nanos() as u64;
+
+    let streams = get_sorted_stream_stats()
+        .iter()
+        .map(|stream_stats| {
+            let yield_rate = stream_stats.yield_rate(window, current_elapsed);
+            let yield_history = stream_stats.yielded_history.counts(current_elapsed);
+            SerializableStreamStats {
+                yield_rate,
+                yield_rate_instant: yield_history.last().copied().unwrap_or(0),
+                yield_bytes_rate: stream_stats.yield_bytes_rate(window, current_elapsed),
+                yield_history,
+                ..SerializableStreamStats::from(stream_stats)
+            }
+        })
+        .collect();
 
     StreamsJson {
         current_elapsed_ns,
@@ -337,7 +454,7 @@ pub fn get_stream_logs(stream_id: &str) -> Option<StreamLogs> {
         let mut yielded_logs: Vec<LogEntry> = stream_stats.logs.iter().cloned().collect();
 
         // Sort by index descending (most recent first)
-        yielded_logs.sort_by(|a, b| b.index.cmp(&a.index));
+        yielded_logs.sort_by_key(|entry| std::cmp::Reverse(entry.index));
 
         StreamLogs {
             id: stream_id.to_string(),
@@ -123,6 +123,12 @@ impl Default for StreamsGuard {
 
 impl Drop for StreamsGuard {
     fn drop(&mut self) {
+        // Folded stacks only cover measured functions, and any other line on stdout would
+        // corrupt them
+        if matches!(self.format, Format::Folded) {
+            return;
+        }
+
         let elapsed = self.start_time.elapsed();
         let streams = get_sorted_stream_stats();
 
@@ -182,6 +188,7 @@ impl Drop for StreamsGuard {
                     Err(e) => eprintln!("Failed to serialize statistics to pretty JSON: {}", e),
                 }
             }
+            Format::Folded => {}
         }
     }
 }
@@ -1,3 +1,4 @@
+use crate::heap_size::{deep_size, HeapSize};
 use crate::output::truncate_result;
 use crate::streams::{init_streams_state, StreamEvent, STREAM_ID_COUNTER};
 use crossbeam_channel::Sender as CbSender;
@@ -47,6 +48,7 @@ impl<S> InstrumentedStream<S> {
             display_label: label,
             type_name: std::any::type_name::<S::Item>(),
             type_size: std::mem::size_of::<S::Item>(),
+            sized: false,
         });
 
         Self {
@@ -68,6 +70,7 @@ impl<S: Stream> Stream for InstrumentedStream<S> {
                 let _ = this.stats_tx.send(StreamEvent::Yielded {
                     id: *this.id,
                     log: None,
+                    size: None,
                     timestamp: Instant::now(),
                 });
                 Poll::Ready(Some(item))
@@ -109,6 +112,7 @@ impl<S> InstrumentedStreamLog<S> {
             display_label: label,
             type_name: std::any::type_name::<S::Item>(),
             type_size: std::mem::size_of::<S::Item>(),
+            sized: false,
         });
 
         Self {
@@ -134,6 +138,74 @@ where
                 let _ = this.stats_tx.send(StreamEvent::Yielded {
                     id: *this.id,
                     log: Some(log_msg),
+                    size: None,
+                    timestamp: Instant::now(),
+                });
+                Poll::Ready(Some(item))
+            }
+            Poll::Ready(None) => {
+                let _ = this.stats_tx.send(StreamEvent::Completed { id: *this.id });
+                Poll::Ready(None)
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+pin_project! {
+    /// Wrapper around a `Stream` that instruments it with deep item sizes.
+    ///
+    /// This variant records the inline and heap size of yielded items.
+    pub struct InstrumentedStreamSize<S> {
+        #[pin]
+        inner: S,
+        stats_tx: CbSender<StreamEvent>,
+        id: u64,
+    }
+}
+
+impl<S> InstrumentedStreamSize<S> {
+    /// Create a new instrumented stream wrapper recording item sizes.
+    pub(crate) fn new(stream: S, source: &'static str, label: Option<String>) -> Self
+    where
+        S: Stream,
+    {
+        let (stats_tx, _) = init_streams_state();
+        let id = STREAM_ID_COUNTER.fetch_add(1, Ordering::Relaxed);
+
+        // Send stream creation event
+        let _ = stats_tx.send(StreamEvent::Created {
+            id,
+            source,
+            display_label: label,
+            type_name: std::any::type_name::<S::Item>(),
+            type_size: std::mem::size_of::<S::Item>(),
+            sized: true,
+        });
+
+        Self {
+            inner: stream,
+            stats_tx: stats_tx.clone(),
+            id,
+        }
+    }
+}
+
+impl<S: Stream> Stream for InstrumentedStreamSize<S>
+where
+    S::Item: HeapSize,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+
+        match this.inner.poll_next(cx) {
+            Poll::Ready(Some(item)) => {
+                let _ = this.stats_tx.send(StreamEvent::Yielded {
+                    id: *this.id,
+                    log: None,
+                    size: Some(deep_size(&item)),
                     timestamp: Instant::now(),
                 });
                 Poll::Ready(Some(item))
@@ -0,0 +1,340 @@
+//! Tasks spawned with `spawn!` and `spawn_blocking!`: where they were spawned from, by which
+//! task, the threads that ran them and how they ended.
+
+use pin_project_lite::pin_project;
+use std::any::Any;
+use std::cell::Cell;
+use std::collections::{BTreeMap, VecDeque};
+use std::future::Future;
+use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::task::{Context, Poll};
+
+#[cfg(target_os = "linux")]
+use quanta::Instant;
+
+#[cfg(not(target_os = "linux"))]
+use std::time::Instant;
+
+use crate::channels::{timestamp_nanos, START_TIME};
+use crate::tid::current_tid;
+
+pub use crate::json::{TaskJson, TaskKind, TaskState, TasksJson};
+
+/// Most recently finished tasks kept.
+const MAX_FINISHED_TASKS: usize = 100;
+
+static TASK_ID_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    /// Tracked task running on this thread, parent of the tasks it spawns
+    static CURRENT_TASK: Cell<Option<u64>> = const { Cell::new(None) };
+}
+
+type TaskRef = Arc<Mutex<TaskJson>>;
+
+#[derive(Default)]
+struct Tasks {
+    total_spawned: u64,
+    live: BTreeMap<u64, TaskRef>,
+    finished: VecDeque<TaskJson>,
+}
+
+static TASKS: LazyLock<Mutex<Tasks>> = LazyLock::new(|| Mutex::new(Tasks::default()));
+
+/// Registered task, marked as cancelled if dropped before it finished.
+struct TaskHandle {
+    id: u64,
+    task: TaskRef,
+    finished: bool,
+}
+
+impl TaskHandle {
+    fn register(kind: TaskKind, source: &'static str, label: String) -> Self {
+        let now = Instant::now();
+        START_TIME.get_or_init(|| now);
+
+        let id = TASK_ID_COUNTER.fetch_add(1, Ordering::Relaxed);
+        let task = Arc::new(Mutex::new(TaskJson {
+            id,
+            kind,
+            label,
+            source: source.to_string(),
+            parent_id: CURRENT_TASK.get(),
+            state: TaskState::Running,
+            panic_message: None,
+            poll_count: 0,
+            busy_ns: 0,
+            threads: Vec::new(),
+            spawned_at_ns: timestamp_nanos(now),
+            finished_at_ns: None,
+        }));
+
+        let mut tasks = TASKS.lock().unwrap();
+        tasks.total_spawned += 1;
+        tasks.live.insert(id, Arc::clone(&task));
+
+        Self {
+            id,
+            task,
+            finished: false,
+        }
+    }
+
+    /// Runs `f` as this task on the current thread and records the time it took. Finishes
+    /// the task as panicked if `f` panics.
+    fn run<T>(&mut self, f: impl FnOnce() -> T) -> T {
+        let previous = CURRENT_TASK.replace(Some(self.id));
+        let started = Instant::now();
+        let result = catch_unwind(AssertUnwindSafe(f));
+        let duration = Instant::now().duration_since(started);
+        CURRENT_TASK.set(previous);
+
+        let tid = current_tid();
+        {
+            let mut task = self.task.lock().unwrap();
+            task.poll_count += 1;
+            task.busy_ns += duration.as_nanos() as u64;
+            if !task.threads.contains(&tid) {
+                task.threads.push(tid);
+            }
+        }
+
+        match result {
+            Ok(result) => result,
+            Err(payload) => {
+                self.finish(TaskState::Panicked, Some(panic_message(&*payload)));
+                resume_unwind(payload)
+            }
+        }
+    }
+
+    fn finish(&mut self, state: TaskState, panic_message: Option<String>) {
+        self.finished = true;
+        let task = {
+            let mut task = self.task.lock().unwrap();
+            task.state = state;
+            task.panic_message = panic_message;
+            task.finished_at_ns = Some(timestamp_nanos(Instant::now()));
+            task.clone()
+        };
+
+        let mut tasks = TASKS.lock().unwrap();
+        tasks.live.remove(&self.id);
+        if tasks.finished.len() >= MAX_FINISHED_TASKS {
+            tasks.finished.pop_front();
+        }
+        tasks.finished.push_back(task);
+    }
+}
+
+impl Drop for TaskHandle {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.finish(TaskState::Cancelled, None);
+        }
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Box<dyn Any>".to_string()
+    }
+}
+
+/// Registers a task spawned by `spawn!` and wraps its future.
+///
+/// This function is not intended for direct use. Use the `spawn!` macro instead.
+#[doc(hidden)]
+pub fn instrument_task<F: Future>(
+    source: &'static str,
+    label: impl Into<String>,
+    inner: F,
+) -> InstrumentedTask<F> {
+    InstrumentedTask {
+        inner,
+        handle: TaskHandle::register(TaskKind::Async, source, label.into()),
+    }
+}
+
+pin_project! {
+    /// Future of a task spawned with `spawn!`.
+    #[doc(hidden)]
+    pub struct InstrumentedTask<F> {
+        #[pin]
+        inner: F,
+        handle: TaskHandle,
+    }
+}
+
+impl<F: Future> Future for InstrumentedTask<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        #[cfg(feature = "tokio-runtime-metrics")]
+        crate::runtime::init_runtime_monitoring();
+
+        let inner = this.inner;
+        let result = this.handle.run(|| inner.poll(cx));
+        if result.is_ready() {
+            this.handle.finish(TaskState::Completed, None);
+        }
+        result
+    }
+}
+
+/// Registers a task spawned by `spawn_blocking!` and wraps its closure.
+///
+/// This function is not intended for direct use. Use the `spawn_blocking!` macro instead.
+#[doc(hidden)]
+pub fn instrument_blocking<T>(
+    source: &'static str,
+    label: impl Into<String>,
+    f: impl FnOnce() -> T,
+) -> impl FnOnce() -> T {
+    let mut handle = TaskHandle::register(TaskKind::Blocking, source, label.into());
+    move || {
+        let result = handle.run(f);
+        handle.finish(TaskState::Completed, None);
+        result
+    }
+}
+
+/// Live tasks in spawn order, then the recently finished ones, newest first
+pub fn get_tasks_json() -> TasksJson {
+    let tasks = TASKS.lock().unwrap();
+    let live: Vec<TaskJson> = tasks
+        .live
+        .values()
+        .map(|task| task.lock().unwrap().clone())
+        .collect();
+
+    TasksJson {
+        current_elapsed_ns: START_TIME
+            .get()
+            .map(|t| t.elapsed().as_nanos() as u64)
+            .unwrap_or(0),
+        total_spawned: tasks.total_spawned,
+        live: live.len(),
+        tasks: live
+            .into_iter()
+            .chain(tasks.finished.iter().rev().cloned())
+            .collect(),
+    }
+}
+
+/// Spawns an instrumented task, listed by the `/tasks` route with its spawn location, parent
+/// task, the threads that polled it and how it ended.
+///
+/// Returns the runtime's join handle. Tasks run on tokio by default, `runtime = smol` spawns
+/// them with `smol::spawn`. The runtime crate must be a dependency of the calling crate.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let handle = hotpath::spawn!("fetch", fetch(url));
+/// let handle = hotpath::spawn!(format!("worker-{}", i), worker(rx));
+/// let task = hotpath::spawn!("fetch", fetch(url), runtime = smol);
+/// ```
+#[macro_export]
+macro_rules! spawn {
+    (@instrument $label:expr, $fut:expr) => {{
+        const TASK_LOC: &'static str = concat!(file!(), ":", line!());
+        $crate::tasks::instrument_task(TASK_LOC, $label, $fut)
+    }};
+
+    ($label:expr, $fut:expr, runtime = tokio) => {
+        ::tokio::spawn($crate::spawn!(@instrument $label, $fut))
+    };
+
+    ($label:expr, $fut:expr, runtime = smol) => {
+        ::smol::spawn($crate::spawn!(@instrument $label, $fut))
+    };
+
+    ($label:expr, $fut:expr) => {
+        $crate::spawn!($label, $fut, runtime = tokio)
+    };
+}
+
+/// Runs a closure on the runtime's blocking thread pool as an instrumented task, listed by
+/// the `/tasks` route.
+///
+/// Uses `tokio::task::spawn_blocking` by default, `runtime = smol` runs the closure with
+/// `smol::unblock`.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let handle = hotpath::spawn_blocking!("compress", move || compress(&data));
+/// let output = hotpath::spawn_blocking!("compress", move || compress(&data), runtime = smol).await;
+/// ```
+#[macro_export]
+macro_rules! spawn_blocking {
+    (@instrument $label:expr, $f:expr) => {{
+        const TASK_LOC: &'static str = concat!(file!(), ":", line!());
+        $crate::tasks::instrument_blocking(TASK_LOC, $label, $f)
+    }};
+
+    ($label:expr, $f:expr, runtime = tokio) => {
+        ::tokio::task::spawn_blocking($crate::spawn_blocking!(@instrument $label, $f))
+    };
+
+    ($label:expr, $f:expr, runtime = smol) => {
+        ::smol::unblock($crate::spawn_blocking!(@instrument $label, $f))
+    };
+
+    ($label:expr, $f:expr) => {
+        $crate::spawn_blocking!($label, $f, runtime = tokio)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find_task(id: u64) -> TaskJson {
+        get_tasks_json()
+            .tasks
+            .into_iter()
+            .find(|task| task.id == id)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_task_lifecycle() {
+        let mut parent = TaskHandle::register(TaskKind::Async, "tasks.rs:1", "parent".into());
+        let (child, cancelled) = parent.run(|| {
+            (
+                TaskHandle::register(TaskKind::Blocking, "tasks.rs:2", "child".into()),
+                TaskHandle::register(TaskKind::Async, "tasks.rs:3", "cancelled".into()),
+            )
+        });
+        assert_eq!(find_task(parent.id).state, TaskState::Running);
+        assert_eq!(find_task(child.id).parent_id, Some(parent.id));
+
+        let mut child = child;
+        let result = catch_unwind(AssertUnwindSafe(|| child.run(|| panic!("child failed"))));
+        assert!(result.is_err());
+        let child = find_task(child.id);
+        assert_eq!(child.state, TaskState::Panicked);
+        assert_eq!(child.panic_message.as_deref(), Some("child failed"));
+        assert_eq!(child.threads, vec![current_tid()]);
+
+        let cancelled_id = cancelled.id;
+        drop(cancelled);
+        assert_eq!(find_task(cancelled_id).state, TaskState::Cancelled);
+
+        parent.finish(TaskState::Completed, None);
+        let parent = find_task(parent.id);
+        assert_eq!(parent.state, TaskState::Completed);
+        assert_eq!(parent.poll_count, 1);
+        assert!(parent.finished_at_ns.is_some());
+    }
+}
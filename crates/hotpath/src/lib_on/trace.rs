@@ -0,0 +1,360 @@
+//! Opt-in Chrome Trace Event recorder.
+//!
+//! When `HOTPATH_TRACE_FILE` is set, every measured function span, instrumented future poll
+//! and channel send/receive is recorded as Chrome Trace Event JSON. A background thread
+//! streams events to that file as they arrive and completes it when the functions guard is
+//! dropped. The file can be opened in Perfetto (<https://ui.perfetto.dev>) or
+//! `chrome://tracing`.
+//!
+//! Functions and future polls are placed on per-thread tracks of the `hotpath` process.
+//! Channel events are placed on a separate `channels` process with one track per channel.
+
+use crossbeam_channel::{bounded, unbounded, Sender};
+use serde::Serialize;
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use std::time::{Duration, Instant};
+
+/// Clock used by the channel wrappers for message timestamps
+#[cfg(target_os = "linux")]
+type ChannelInstant = quanta::Instant;
+
+#[cfg(not(target_os = "linux"))]
+type ChannelInstant = std::time::Instant;
+
+use crate::channels::resolve_label;
+
+const THREADS_PID: u32 = 1;
+const CHANNELS_PID: u32 = 2;
+
+pub(crate) static TRACE_RECORDER: LazyLock<Option<TraceRecorder>> = LazyLock::new(|| {
+    std::env::var("HOTPATH_TRACE_FILE")
+        .ok()
+        .filter(|path| !path.is_empty())
+        .map(|path| TraceRecorder::new(PathBuf::from(path)))
+});
+
+thread_local! {
+    static THREAD_REGISTERED: Cell<bool> = const { Cell::new(false) };
+}
+
+#[derive(Clone, Copy)]
+pub(crate) enum Category {
+    Function,
+    Future,
+    Channel,
+}
+
+impl Category {
+    fn as_str(self) -> &'static str {
+        match self {
+            Category::Function => "function",
+            Category::Future => "future",
+            Category::Channel => "channel",
+        }
+    }
+}
+
+enum EventKind {
+    Span { start: Instant, duration: Duration },
+    Instant { offset: Duration },
+    ThreadName,
+}
+
+pub(crate) struct TraceEvent {
+    name: Cow<'static, str>,
+    category: Category,
+    kind: EventKind,
+    pid: u32,
+    tid: u64,
+    args: Option<serde_json::Value>,
+}
+
+enum TraceMessage {
+    Event(TraceEvent),
+    /// Completes the file and replies with the number of events written
+    Finish(Sender<std::io::Result<usize>>),
+}
+
+pub(crate) struct TraceRecorder {
+    path: PathBuf,
+    channel_start: ChannelInstant,
+    tx: Sender<TraceMessage>,
+}
+
+impl TraceRecorder {
+    fn new(path: PathBuf) -> Self {
+        let start = Instant::now();
+        let (tx, rx) = unbounded::<TraceMessage>();
+        let writer_path = path.clone();
+
+        std::thread::Builder::new()
+            .name("hp-trace".into())
+            .spawn(move || {
+                let mut writer = TraceWriter::create(&writer_path);
+                while let Ok(message) = rx.recv() {
+                    match message {
+                        TraceMessage::Event(event) => {
+                            if let Ok(trace_writer) = &mut writer {
+                                let event = to_chrome_event(start, event);
+                                if let Err(e) = trace_writer.write_event(&event) {
+                                    writer = Err(e);
+                                }
+                            }
+                        }
+                        TraceMessage::Finish(reply) => {
+                            let _ = reply.send(writer.and_then(TraceWriter::finish));
+                            return;
+                        }
+                    }
+                }
+            })
+            .expect("Failed to spawn trace writer thread");
+
+        Self {
+            path,
+            channel_start: ChannelInstant::now(),
+            tx,
+        }
+    }
+
+    fn record(&self, event: TraceEvent) {
+        let _ = self.tx.send(TraceMessage::Event(event));
+    }
+
+    /// Waits for the writer thread to complete the file. Events recorded afterwards are
+    /// dropped.
+    fn finish(&self) -> std::io::Result<usize> {
+        let (reply_tx, reply_rx) = bounded(1);
+        let _ = self.tx.send(TraceMessage::Finish(reply_tx));
+        reply_rx
+            .recv()
+            .unwrap_or_else(|_| Err(std::io::Error::other("trace writer thread is not running")))
+    }
+}
+
+fn to_chrome_event(start: Instant, event: TraceEvent) -> ChromeEvent {
+    let label = match (event.category, event.name) {
+        (Category::Future, Cow::Borrowed(source)) => resolve_label(source, None, None),
+        (_, name) => name.into_owned(),
+    };
+
+    let (name, ph, ts, dur, args) = match event.kind {
+        EventKind::Span {
+            start: span_start,
+            duration,
+        } => (
+            label,
+            "X",
+            span_start.saturating_duration_since(start).as_nanos() as f64 / 1000.0,
+            Some(duration.as_nanos() as f64 / 1000.0),
+            event.args,
+        ),
+        EventKind::Instant { offset } => (
+            label,
+            "i",
+            offset.as_nanos() as f64 / 1000.0,
+            None,
+            event.args,
+        ),
+        EventKind::ThreadName => (
+            "thread_name".to_string(),
+            "M",
+            0.0,
+            None,
+            Some(serde_json::json!({ "name": label })),
+        ),
+    };
+
+    ChromeEvent {
+        name,
+        cat: event.category.as_str(),
+        ph,
+        ts,
+        dur,
+        s: (ph == "i").then_some("t"),
+        pid: event.pid,
+        tid: event.tid,
+        args,
+    }
+}
+
+/// Trace file written one event at a time, so that events are not kept in memory
+struct TraceWriter {
+    writer: BufWriter<File>,
+    count: usize,
+}
+
+impl TraceWriter {
+    fn create(path: &Path) -> std::io::Result<Self> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(b"{\"traceEvents\":[")?;
+        let mut trace_writer = Self { writer, count: 0 };
+
+        for (pid, name) in [(THREADS_PID, "hotpath"), (CHANNELS_PID, "channels")] {
+            trace_writer.write_event(&ChromeEvent {
+                name: "process_name".to_string(),
+                cat: "__metadata",
+                ph: "M",
+                ts: 0.0,
+                dur: None,
+                s: None,
+                pid,
+                tid: 0,
+                args: Some(serde_json::json!({ "name": name })),
+            })?;
+        }
+
+        Ok(trace_writer)
+    }
+
+    fn write_event(&mut self, event: &ChromeEvent) -> std::io::Result<()> {
+        if self.count > 0 {
+            self.writer.write_all(b",")?;
+        }
+        serde_json::to_writer(&mut self.writer, event)?;
+        self.count += 1;
+        Ok(())
+    }
+
+    fn finish(mut self) -> std::io::Result<usize> {
+        self.writer.write_all(b"],\"displayTimeUnit\":\"ms\"}")?;
+        self.writer.flush()?;
+        Ok(self.count)
+    }
+}
+
+#[derive(Serialize)]
+struct ChromeEvent {
+    name: String,
+    cat: &'static str,
+    ph: &'static str,
+    ts: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dur: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    s: Option<&'static str>,
+    pid: u32,
+    tid: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    args: Option<serde_json::Value>,
+}
+
+#[inline]
+pub(crate) fn is_enabled() -> bool {
+    TRACE_RECORDER.is_some()
+}
+
+/// Starts the trace clock. Called when profiling starts so that timestamps are relative to it.
+pub(crate) fn init() {
+    LazyLock::force(&TRACE_RECORDER);
+}
+
+/// Names the current thread's track after the thread, once per thread.
+pub(crate) fn register_current_thread() {
+    let Some(recorder) = TRACE_RECORDER.as_ref() else {
+        return;
+    };
+
+    if THREAD_REGISTERED.with(|registered| registered.replace(true)) {
+        return;
+    }
+
+    let tid = crate::tid::current_tid();
+    let name = std::thread::current()
+        .name()
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("thread-{tid}"));
+
+    recorder.record(TraceEvent {
+        name: Cow::Owned(name),
+        category: Category::Function,
+        kind: EventKind::ThreadName,
+        pid: THREADS_PID,
+        tid,
+        args: None,
+    });
+}
+
+/// Records a span that ended at `end` on a thread track.
+pub(crate) fn record_span(
+    name: &'static str,
+    category: Category,
+    end: Instant,
+    duration: Duration,
+    tid: u64,
+    args: Option<serde_json::Value>,
+) {
+    if let Some(recorder) = TRACE_RECORDER.as_ref() {
+        recorder.record(TraceEvent {
+            name: Cow::Borrowed(name),
+            category,
+            kind: EventKind::Span {
+                start: end.checked_sub(duration).unwrap_or(end),
+                duration,
+            },
+            pid: THREADS_PID,
+            tid,
+            args,
+        });
+    }
+}
+
+/// Channel ids start at 0, which trace viewers treat as a process-wide track.
+fn channel_track(channel_id: u64) -> u64 {
+    channel_id + 1
+}
+
+/// Names the track of an instrumented channel.
+pub(crate) fn record_channel_created(channel_id: u64, label: String) {
+    if let Some(recorder) = TRACE_RECORDER.as_ref() {
+        recorder.record(TraceEvent {
+            name: Cow::Owned(label),
+            category: Category::Channel,
+            kind: EventKind::ThreadName,
+            pid: CHANNELS_PID,
+            tid: channel_track(channel_id),
+            args: None,
+        });
+    }
+}
+
+/// Records a channel send or receive on the channel's track.
+pub(crate) fn record_channel_event(channel_id: u64, name: &'static str, timestamp: ChannelInstant) {
+    if let Some(recorder) = TRACE_RECORDER.as_ref() {
+        recorder.record(TraceEvent {
+            name: Cow::Borrowed(name),
+            category: Category::Channel,
+            kind: EventKind::Instant {
+                offset: timestamp.saturating_duration_since(recorder.channel_start),
+            },
+            pid: CHANNELS_PID,
+            tid: channel_track(channel_id),
+            args: None,
+        });
+    }
+}
+
+/// Completes the trace file once the writer thread has caught up with recorded events.
+pub(crate) fn write_trace_file() {
+    let Some(recorder) = TRACE_RECORDER.as_ref() else {
+        return;
+    };
+
+    match recorder.finish() {
+        Ok(count) => eprintln!(
+            "[hotpath] Trace with {} events written to {}",
+            count,
+            recorder.path.display()
+        ),
+        Err(e) => eprintln!(
+            "[hotpath] Failed to write trace file {}: {}",
+            recorder.path.display(),
+            e
+        ),
+    }
+}
@@ -0,0 +1,344 @@
+//! Sliding time windows over function timings and channel and stream traffic.
+//!
+//! Each window is a ring of [`SLOTS`] time slots. Recording goes to the slot covering the
+//! measurement time, and the oldest slot is dropped once it falls out of the window. A window
+//! query merges the slots that overlap `[now - window, now]`, so its span is between
+//! `(SLOTS - 1) / SLOTS` and one full window length.
+//!
+//! Function durations keep a histogram per slot, so they are only recorded into windows once
+//! a sliding window has been requested.
+
+use hdrhistogram::Histogram;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use crate::json::StatsWindow;
+use crate::lib_on::durations::{new_histogram, record_ns};
+
+const SLOTS: u64 = 5;
+
+/// Set by the first request for a sliding window.
+static DURATIONS_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Starts recording function durations into the sliding windows if `window` is one of them.
+pub(crate) fn enable_durations(window: StatsWindow) {
+    if window != StatsWindow::Lifetime {
+        DURATIONS_ENABLED.store(true, Ordering::Relaxed);
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Ring<T> {
+    slot_ns: u64,
+    slots: VecDeque<(u64, T)>,
+}
+
+impl<T> Ring<T> {
+    fn new(window: Duration) -> Self {
+        Self {
+            slot_ns: (window.as_nanos() as u64 / SLOTS).max(1),
+            slots: VecDeque::with_capacity(SLOTS as usize),
+        }
+    }
+
+    fn slot_index(&self, at: Duration) -> u64 {
+        at.as_nanos() as u64 / self.slot_ns
+    }
+
+    fn slot_mut(&mut self, at: Duration, init: impl FnOnce() -> T) -> &mut T {
+        let index = self.slot_index(at);
+
+        // Measurements from different threads are batched, so they can arrive slightly
+        // out of order. Late ones go to the newest slot.
+        let latest = self.slots.back().map(|(latest, _)| *latest);
+        if latest.is_none_or(|latest| index > latest) {
+            while self
+                .slots
+                .front()
+                .is_some_and(|(oldest, _)| oldest + SLOTS <= index)
+            {
+                self.slots.pop_front();
+            }
+            self.slots.push_back((index, init()));
+        }
+
+        &mut self.slots.back_mut().expect("slot was just pushed").1
+    }
+
+    fn live(&self, now: Duration) -> impl Iterator<Item = &T> {
+        let now_index = self.slot_index(now);
+        self.slots
+            .iter()
+            .filter(move |(index, _)| index + SLOTS > now_index)
+            .map(|(_, slot)| slot)
+    }
+
+    /// Time covered by the live slots at `now`.
+    fn span(&self, now: Duration) -> Duration {
+        let first_index = self.slot_index(now).saturating_sub(SLOTS - 1);
+        now.saturating_sub(Duration::from_nanos(first_index * self.slot_ns))
+    }
+}
+
+fn rings<T>() -> [Ring<T>; 3] {
+    StatsWindow::SLIDING.map(|window| Ring::new(window.duration().expect("sliding window")))
+}
+
+fn ring_index(window: StatsWindow) -> Option<usize> {
+    StatsWindow::SLIDING.iter().position(|w| *w == window)
+}
+
+#[derive(Debug, Clone)]
+struct DurationSlot {
+    count: u64,
+    total_ns: u64,
+    hist: Histogram<u64>,
+}
+
+/// Function durations recorded over each sliding [`StatsWindow`], allocated on the first
+/// measurement after [`enable_durations`].
+#[derive(Debug, Clone)]
+pub(crate) struct WindowedDurations {
+    rings: Option<Box<[Ring<DurationSlot>; 3]>>,
+}
+
+/// Durations merged over a single window.
+pub(crate) struct WindowSnapshot {
+    pub count: u64,
+    pub total_ns: u64,
+    /// Time covered by the snapshot, at most the window length
+    pub span: Duration,
+    hist: Option<Histogram<u64>>,
+}
+
+impl WindowSnapshot {
+    pub fn avg_ns(&self) -> u64 {
+        self.total_ns.checked_div(self.count).unwrap_or(0)
+    }
+
+    pub fn percentile(&self, p: f64) -> u64 {
+        self.hist
+            .as_ref()
+            .map(|hist| hist.value_at_percentile(p.clamp(0.0, 100.0)))
+            .unwrap_or(0)
+    }
+}
+
+impl WindowedDurations {
+    pub fn new() -> Self {
+        Self { rings: None }
+    }
+
+    /// Records a duration measured `elapsed` after the profiler started.
+    pub fn record(&mut self, elapsed: Duration, duration_ns: u64) {
+        if !DURATIONS_ENABLED.load(Ordering::Relaxed) {
+            return;
+        }
+
+        let rings = self.rings.get_or_insert_with(|| Box::new(rings()));
+        for ring in rings.iter_mut() {
+            let slot = ring.slot_mut(elapsed, || DurationSlot {
+                count: 0,
+                total_ns: 0,
+                hist: new_histogram(),
+            });
+            slot.count += 1;
+            slot.total_ns += duration_ns;
+            record_ns(&mut slot.hist, duration_ns);
+        }
+    }
+
+    /// Merges the slots of `window` that are live at `now`. Returns `None` for
+    /// [`StatsWindow::Lifetime`].
+    pub fn snapshot(&self, window: StatsWindow, now: Duration) -> Option<WindowSnapshot> {
+        let index = ring_index(window)?;
+        let mut snapshot = WindowSnapshot {
+            count: 0,
+            total_ns: 0,
+            span: Duration::ZERO,
+            hist: None,
+        };
+        let Some(rings) = &self.rings else {
+            return Some(snapshot);
+        };
+
+        let ring = &rings[index];
+        snapshot.span = ring.span(now);
+        for slot in ring.live(now) {
+            snapshot.count += slot.count;
+            snapshot.total_ns += slot.total_ns;
+            match snapshot.hist {
+                Some(ref mut hist) => hist.add(&slot.hist).expect("auto-resizing histogram"),
+                None => snapshot.hist = Some(slot.hist.clone()),
+            }
+        }
+
+        Some(snapshot)
+    }
+}
+
+/// Event counts over each sliding [`StatsWindow`], used for channel and stream throughput rates.
+#[derive(Debug, Clone)]
+pub(crate) struct WindowedCounter {
+    rings: [Ring<u64>; 3],
+}
+
+impl WindowedCounter {
+    pub fn new() -> Self {
+        Self { rings: rings() }
+    }
+
+    pub fn record(&mut self, elapsed: Duration) {
+        self.add(elapsed, 1);
+    }
+
+    /// Records `amount` at once, e.g. the bytes of a message.
+    pub fn add(&mut self, elapsed: Duration, amount: u64) {
+        for ring in &mut self.rings {
+            *ring.slot_mut(elapsed, || 0) += amount;
+        }
+    }
+
+    /// Events per second over `window`, `None` for [`StatsWindow::Lifetime`].
+    pub fn rate(&self, window: StatsWindow, now: Duration) -> Option<f64> {
+        let ring = &self.rings[ring_index(window)?];
+        let count: u64 = ring.live(now).sum();
+        let span = ring.span(now).as_secs_f64();
+        Some(if span > 0.0 { count as f64 / span } else { 0.0 })
+    }
+}
+
+/// Events per second since the profiler started, used when a [`WindowedCounter`] has no
+/// sliding window to report.
+pub(crate) fn lifetime_rate(count: u64, now: Duration) -> f64 {
+    let secs = now.as_secs_f64();
+    if secs > 0.0 {
+        count as f64 / secs
+    } else {
+        0.0
+    }
+}
+
+/// Seconds of history kept by [`RateHistory`].
+pub(crate) const HISTORY_SECS: u64 = 30;
+
+/// Event counts for each of the last [`HISTORY_SECS`] seconds, for instantaneous rates and
+/// sparklines.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RateHistory {
+    /// Second since the profiler started and its event count, oldest first
+    seconds: VecDeque<(u64, u64)>,
+}
+
+impl RateHistory {
+    pub fn record(&mut self, elapsed: Duration) {
+        let second = elapsed.as_secs();
+        match self.seconds.back_mut() {
+            // Late events go to the newest second, like in the sliding windows
+            Some((latest, count)) if *latest >= second => *count += 1,
+            _ => {
+                while self
+                    .seconds
+                    .front()
+                    .is_some_and(|(oldest, _)| oldest + HISTORY_SECS <= second)
+                {
+                    self.seconds.pop_front();
+                }
+                self.seconds.push_back((second, 1));
+            }
+        }
+    }
+
+    /// Counts of the [`HISTORY_SECS`] complete seconds before `now`, oldest first. The second
+    /// in progress is left out, so the last value is the instantaneous rate.
+    pub fn counts(&self, now: Duration) -> Vec<u64> {
+        let end = now.as_secs();
+        let mut counts = vec![0; HISTORY_SECS as usize];
+        for (second, count) in &self.seconds {
+            if *second < end && second + HISTORY_SECS >= end {
+                counts[(second + HISTORY_SECS - end) as usize] = *count;
+            }
+        }
+        counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECOND: u64 = 1_000_000_000;
+
+    #[test]
+    fn test_windowed_durations_drop_old_slots() {
+        let mut durations = WindowedDurations::new();
+        durations.record(Duration::ZERO, 10);
+        assert!(durations.rings.is_none());
+
+        enable_durations(StatsWindow::Last10s);
+        durations.record(Duration::from_secs(1), 10);
+        durations.record(Duration::from_secs(30), 20);
+        durations.record(Duration::from_secs(31), 30);
+
+        let now = Duration::from_secs(32);
+        let last_10s = durations.snapshot(StatsWindow::Last10s, now).unwrap();
+        assert_eq!(last_10s.count, 2);
+        assert_eq!(last_10s.total_ns, 50);
+        assert_eq!(last_10s.avg_ns(), 25);
+        assert_eq!(last_10s.percentile(100.0), 30);
+
+        let last_1m = durations.snapshot(StatsWindow::Last1m, now).unwrap();
+        assert_eq!(last_1m.count, 3);
+        assert_eq!(last_1m.percentile(0.0), 10);
+
+        assert!(durations.snapshot(StatsWindow::Lifetime, now).is_none());
+
+        durations.record(Duration::from_secs(32), 5 * SECOND);
+        let last_10s = durations.snapshot(StatsWindow::Last10s, now).unwrap();
+        assert!(last_10s.percentile(100.0).abs_diff(5 * SECOND) < SECOND / 20);
+
+        let later = durations
+            .snapshot(StatsWindow::Last10s, Duration::from_secs(60))
+            .unwrap();
+        assert_eq!(later.count, 0);
+        assert_eq!(later.percentile(50.0), 0);
+    }
+
+    #[test]
+    fn test_rate_history_counts_complete_seconds() {
+        let mut history = RateHistory::default();
+        for ms in [100, 200, 1_500, 3_100, 3_200, 3_300, 4_000] {
+            history.record(Duration::from_millis(ms));
+        }
+
+        let counts = history.counts(Duration::from_millis(4_500));
+        assert_eq!(counts.len(), HISTORY_SECS as usize);
+        assert_eq!(counts[counts.len() - 4..], [2, 1, 0, 3]);
+        assert_eq!(counts.iter().sum::<u64>(), 6);
+
+        // Seconds older than the history are dropped
+        history.record(Duration::from_secs(40));
+        let counts = history.counts(Duration::from_secs(41));
+        assert_eq!(counts.iter().sum::<u64>(), 1);
+    }
+
+    #[test]
+    fn test_windowed_counter_rate() {
+        let mut counter = WindowedCounter::new();
+        for i in 0..100 {
+            counter.record(Duration::from_nanos(20 * SECOND + i * 10_000_000));
+        }
+
+        // 10s window at 21s covers slots starting at 12s
+        let rate = counter
+            .rate(StatsWindow::Last10s, Duration::from_secs(21))
+            .unwrap();
+        assert!((rate - 100.0 / 9.0).abs() < 1e-9);
+
+        let rate = counter
+            .rate(StatsWindow::Last10s, Duration::from_secs(40))
+            .unwrap();
+        assert_eq!(rate, 0.0);
+    }
+}
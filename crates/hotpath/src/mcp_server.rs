@@ -16,14 +16,17 @@ use std::sync::{Arc, LazyLock, OnceLock};
 use std::time::Duration;
 use tokio_util::sync::CancellationToken;
 
-use crate::channels::{get_channel_logs, get_channels_json};
+use crate::channels::{get_channel_logs, get_channels_graph, get_channels_json};
 use crate::functions::{
     get_function_logs_alloc, get_function_logs_timing, get_functions_alloc_json,
     get_functions_timing_json,
 };
 use crate::futures::{get_future_calls, get_futures_json};
+use crate::json::StatsWindow;
+use crate::lib_on::slow_polls::get_slow_polls_json;
 use crate::mcp_server::output::FunctionsMCPJson;
 use crate::streams::{get_stream_logs, get_streams_json};
+use crate::tasks::get_tasks_json;
 use crate::threads::get_threads_json;
 
 #[derive(Debug, Deserialize, JsonSchema)]
@@ -84,7 +87,7 @@ Use this first to identify performance hotspots. Look for high p95/p99 values in
     async fn functions_timing(&self) -> Result<CallToolResult, McpError> {
         log_debug("Tool called: functions_timing");
 
-        let metrics = get_functions_timing_json();
+        let metrics = get_functions_timing_json(StatsWindow::Lifetime);
         let mcp_json = FunctionsMCPJson::from(&metrics);
         Ok(CallToolResult::success(vec![Content::text(to_json(
             &mcp_json,
@@ -118,7 +121,7 @@ Returns error if hotpath-alloc feature is not enabled. Cross-reference with func
     }
 
     #[tool(
-        description = r#"Get metrics for all monitored async channels (tokio, crossbeam, std, futures-channel).
+        description = r#"Get metrics for all monitored async channels (tokio, crossbeam, std, futures-channel, async-channel, flume).
 
 Returns JSON array with:
 - id: channel identifier
@@ -127,8 +130,12 @@ Returns JSON array with:
 - sent/received: message counts
 - queue_size: current pending messages (high values indicate backpressure)
 - state: "active", "closed", "full"
+- queue_wait: time between send and receive in nanoseconds (count, avg_ns, p50_ns, p95_ns, p99_ns, max_ns), present once a message was received
+- send_blocked: sends that found a bounded channel full, with the time spent waiting for capacity and a per-thread (tid) breakdown for channels created with proxy = false
+- max_queued / max_queued_at_ns: high-water mark of the queue and when it was reached
+- queue_warnings: times the queue reached queue_warn_threshold (HOTPATH_CHANNEL_QUEUE_WARN), with the queued count and timestamp_ns
 
-Look for channels with growing queue_size or "full" state to identify bottlenecks."#
+Look for channels with growing queue_size or "full" state to identify bottlenecks. A high queue_wait p99 points to a slow consumer, and send_blocked shows which producer threads it throttles. Unbounded channels with queue_warnings or a max_queued far above queue_size are likely memory leaks."#
     )]
     async fn channels(&self) -> Result<CallToolResult, McpError> {
         log_debug("Tool called: channels");
@@ -139,6 +146,24 @@ Look for channels with growing queue_size or "full" state to identify bottleneck
         )?)]))
     }
 
+    #[tool(
+        description = r#"Get the producer -> channel -> consumer graph of monitored channels.
+
+Returns JSON with:
+- nodes: channels, plus the functions and threads that send to or receive from them (id, kind, label, tids)
+- edges: from a producer to a channel or from a channel to a consumer, with message counts
+
+Producers and consumers are only known for channels created with proxy = false. Calls inside a #[hotpath::measure] function are grouped by function name, other calls by thread id. Use to see how a pipeline is wired and which stage feeds which channel."#
+    )]
+    async fn channels_graph(&self) -> Result<CallToolResult, McpError> {
+        log_debug("Tool called: channels_graph");
+
+        let graph = get_channels_graph();
+        Ok(CallToolResult::success(vec![Content::text(to_json(
+            &graph,
+        )?)]))
+    }
+
     #[tool(description = r#"Get metrics for all monitored async streams.
 
 Returns JSON array with:
@@ -164,8 +189,11 @@ Returns JSON array with:
 - label: optional custom label
 - poll_count: number of times polled (high counts may indicate inefficient futures)
 - state: "active", "completed", or "cancelled"
+- busy, idle, total: per-call time spent inside poll, waiting between polls, and from creation until completion or drop, in nanoseconds (count, avg_ns, p50_ns, p95_ns, p99_ns, max_ns), present once a call finished
+- longest_poll: the longest single poll (call_id, duration_ns, started_at_ns)
+- wakes: wake counts (wakes, self_wakes, wakes_after_completion, lost_wakeups)
 
-High poll counts with "active" state suggest futures that wake frequently without progress."#)]
+High poll counts with "active" state suggest futures that wake frequently without progress. A high busy time or longest_poll points to blocking work inside poll that stalls the executor thread, while a high idle time means the future mostly waits on I/O, timers or other tasks. lost_wakeups counts polls that returned Pending without waking or keeping the waker, so the future can hang; wakes_after_completion points to stale wakers left registered, and many self_wakes to a busy loop."#)]
     async fn futures(&self) -> Result<CallToolResult, McpError> {
         log_debug("Tool called: futures");
 
@@ -175,6 +203,43 @@ High poll counts with "active" state suggest futures that wake frequently withou
         )?)]))
     }
 
+    #[tool(
+        description = r#"Get polls that blocked the thread longer than HOTPATH_SLOW_POLL_MS.
+
+Returns JSON with:
+- threshold_ms: the configured threshold, null if slow poll detection is disabled
+- total: slow polls recorded so far
+- slow_polls: the most recent ones, newest first, with kind ("future" or "function"), label, source, future_id and call_id (futures only), tid of the polling thread, duration_ns and timestamp_ns
+
+A slow poll means synchronous work, such as blocking I/O, a mutex wait or heavy computation, ran inside async code and held an executor thread. Move it to spawn_blocking or a dedicated thread. Use future_calls with future_id to inspect the affected call."#
+    )]
+    async fn slow_polls(&self) -> Result<CallToolResult, McpError> {
+        log_debug("Tool called: slow_polls");
+
+        let slow_polls = get_slow_polls_json();
+        Ok(CallToolResult::success(vec![Content::text(to_json(
+            &slow_polls,
+        )?)]))
+    }
+
+    #[tool(
+        description = r#"Get tasks spawned with the spawn! and spawn_blocking! macros.
+
+Returns JSON with:
+- total_spawned: tasks spawned so far, live: tasks not finished yet
+- tasks: live tasks in spawn order, then the most recently finished ones, newest first, with id, kind ("async" or "blocking"), label, source (spawn location), parent_id (task it was spawned from), state ("running", "completed", "cancelled" or "panicked"), panic_message, poll_count, busy_ns, threads (OS thread ids that ran it), spawned_at_ns and finished_at_ns
+
+Cancelled tasks were dropped before completion, aborted or left behind when their runtime shut down. Follow parent_id to find which task keeps spawning others, and compare threads with the threads tool to see where a task runs."#
+    )]
+    async fn tasks(&self) -> Result<CallToolResult, McpError> {
+        log_debug("Tool called: tasks");
+
+        let tasks = get_tasks_json();
+        Ok(CallToolResult::success(vec![Content::text(to_json(
+            &tasks,
+        )?)]))
+    }
+
     #[tool(description = r#"Get CPU usage metrics for all monitored threads.
 
 Returns JSON array with:
@@ -191,6 +256,37 @@ Sampled at configurable interval (HOTPATH_THREADS_INTERVAL env var, default 1000
         )?)]))
     }
 
+    #[tool(
+        description = r#"Get metrics of the tokio runtime #[hotpath::main] runs inside (requires tokio-runtime-metrics feature).
+
+Returns JSON with:
+- flavor: "current_thread" or "multi_thread"
+- num_workers, alive_tasks, global_queue_depth
+- blocking_threads, idle_blocking_threads, blocking_queue_depth: blocking pool, only if the app is built with --cfg tokio_unstable
+- workers: per worker index, busy_percent over the last sample, park_count, unpark_count, parked, local_queue_depth (tokio_unstable only), and os_tid and cpu_percent of its thread (multi_thread runtimes need tokio_unstable)
+- busy_percent: summed worker busy percentages
+- cpu_percent: summed CPU usage of the threads the workers run on, listed in threads (blocking pool threads excluded, multi_thread runtimes only report this aggregate)
+
+Sampled at configurable interval (HOTPATH_RUNTIME_INTERVAL env var, default 1000ms). Workers busy close to 100% mean the runtime is saturated, a growing global_queue_depth means tasks wait for a worker. Busy time well above the CPU usage of the worker threads means polls block on something other than CPU, such as blocking I/O, sleeps or locks, check slow_polls."#
+    )]
+    async fn runtime(&self) -> Result<CallToolResult, McpError> {
+        log_debug("Tool called: runtime");
+
+        #[cfg(feature = "tokio-runtime-metrics")]
+        let runtime = crate::runtime::get_runtime_json();
+        #[cfg(not(feature = "tokio-runtime-metrics"))]
+        let runtime: Option<crate::json::RuntimeJson> = None;
+
+        match runtime {
+            Some(runtime) => Ok(CallToolResult::success(vec![Content::text(to_json(
+                &runtime,
+            )?)])),
+            None => Ok(CallToolResult::error(vec![Content::text(
+                "Runtime metrics not available - enable tokio-runtime-metrics feature and run #[hotpath::main] inside a tokio runtime",
+            )])),
+        }
+    }
+
     #[tool(description = r#"Get detailed timing logs for a specific function.
 
 Returns JSON array of recent execution logs with timestamps and duration. Use functions_timing first to get function names, then use this tool to get detailed logs."#)]
@@ -282,7 +378,7 @@ Returns JSON array of recent yield events with timestamps. Use streams first to
 
     #[tool(description = r#"Get detailed call/poll logs for a specific future.
 
-Returns JSON array of poll events and completion status. Use futures first to get future IDs, then use this tool to get detailed logs."#)]
+Returns JSON array of poll events and completion status, with created_at_ns, first_poll_at_ns and ended_at_ns timestamps, busy_ns spent inside poll, max_poll_ns and wake counts of each call. Calls instrumented with tags(...) carry them as a tags object, e.g. {"request_id": "42"}, to find the call of a specific request. Use futures first to get future IDs, then use this tool to get detailed logs."#)]
     async fn future_calls(
         &self,
         params: Parameters<FutureIdParam>,
@@ -12,6 +12,8 @@ pub(crate) struct FunctionMCPData {
     pub percentiles: HashMap<String, String>,
     pub total: String,
     pub percent_total: String,
+    #[serde(rename = "self", skip_serializing_if = "Option::is_none")]
+    pub self_total: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -46,23 +48,25 @@ impl From<&FunctionsJson> for FunctionsMCPJson {
                 };
                 let avg = format_value(&metrics[1]);
 
+                let total_idx = 2 + json.percentiles.len();
+                let percent_idx = total_idx + 1;
+                let self_idx = total_idx + 2;
+
                 let mut percentiles = HashMap::new();
                 for (i, &p) in json.percentiles.iter().enumerate() {
                     let metric_idx = 2 + i;
-                    if metric_idx < metrics.len() - 2 {
+                    if metric_idx < total_idx {
                         percentiles.insert(format!("p{}", p), format_value(&metrics[metric_idx]));
                     }
                 }
 
-                let total_idx = metrics.len() - 2;
-                let percent_idx = metrics.len() - 1;
-
                 let total = format_value(&metrics[total_idx]);
                 let percent_total = match &metrics[percent_idx] {
                     MetricType::Percentage(bp) => format!("{:.2}%", *bp as f64 / 100.0),
                     MetricType::Unsupported => "N/A".to_string(),
                     _ => "0%".to_string(),
                 };
+                let self_total = metrics.get(self_idx).map(format_value);
 
                 FunctionMCPData {
                     name: name.clone(),
@@ -71,6 +75,7 @@ impl From<&FunctionsJson> for FunctionsMCPJson {
                     percentiles,
                     total,
                     percent_total,
+                    self_total,
                 }
             })
             .collect();
@@ -115,8 +120,10 @@ mod tests {
                     MetricType::Alloc(60447, 0),
                     MetricType::Alloc(312947932, 0),
                     MetricType::Percentage(3884),
+                    MetricType::Alloc(1024, 0),
                 ],
             )],
+            call_tree: Vec::new(),
         };
 
         let formatted = FunctionsMCPJson::from(&raw);
@@ -134,5 +141,6 @@ mod tests {
         assert_eq!(formatted.data[0].percentiles.get("p95").unwrap(), "59.0 KB");
         assert_eq!(formatted.data[0].total, "298.5 MB");
         assert_eq!(formatted.data[0].percent_total, "38.84%");
+        assert_eq!(formatted.data[0].self_total.as_deref(), Some("1.0 KB"));
     }
 }
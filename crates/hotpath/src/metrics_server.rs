@@ -1,8 +1,8 @@
 use crate::functions::{
     get_function_logs_alloc, get_function_logs_timing, get_functions_alloc_json,
-    get_functions_timing_json,
+    get_functions_timing_json, get_functions_timing_tree_json,
 };
-use crate::json::Route;
+use crate::json::{GraphFormat, Route};
 use std::sync::LazyLock;
 
 pub(crate) static METRICS_SERVER_PORT: LazyLock<u16> = LazyLock::new(|| {
@@ -21,9 +21,11 @@ pub(crate) static METRICS_SERVER_DISABLED: LazyLock<bool> = LazyLock::new(|| {
 
 pub(crate) static RECV_TIMEOUT_MS: u64 = 250;
 
-use crate::channels::{get_channel_logs, get_channels_json};
+use crate::channels::{get_channel_logs, get_channels_graph, get_channels_json_for};
 use crate::futures::{get_future_calls, get_futures_json};
-use crate::streams::{get_stream_logs, get_streams_json};
+use crate::lib_on::slow_polls::get_slow_polls_json;
+use crate::streams::{get_stream_logs, get_streams_json_for};
+use crate::tasks::get_tasks_json;
 use serde::Serialize;
 use std::fmt::Display;
 use std::sync::OnceLock;
@@ -72,10 +74,14 @@ fn handle_request(request: Request) {
     let path = request.url();
 
     match path.parse::<Route>() {
-        Ok(Route::FunctionsTiming) => {
-            let metrics = get_functions_timing_json();
+        Ok(Route::FunctionsTiming { window }) => {
+            let metrics = get_functions_timing_json(window);
             respond_json(request, &metrics);
         }
+        Ok(Route::FunctionsTimingTree) => {
+            let tree = get_functions_timing_tree_json();
+            respond_json(request, &tree);
+        }
         Ok(Route::FunctionsAlloc) => match get_functions_alloc_json() {
             Some(metrics) => respond_json(request, &metrics),
             None => respond_error(
@@ -84,12 +90,29 @@ fn handle_request(request: Request) {
                 "Memory profiling not available - enable hotpath-alloc feature",
             ),
         },
-        Ok(Route::Channels) => {
-            let channels = get_channels_json();
+        Ok(Route::Channels { window }) => {
+            let channels = get_channels_json_for(window);
             respond_json(request, &channels);
         }
-        Ok(Route::Streams) => {
-            let streams = get_streams_json();
+        Ok(Route::ChannelsGraph { format }) => {
+            let graph = get_channels_graph();
+            match format {
+                GraphFormat::Json => respond_json(request, &graph),
+                GraphFormat::Dot => {
+                    let mut response = Response::from_string(graph.to_dot());
+                    response.add_header(
+                        Header::from_bytes(
+                            b"Content-Type".as_slice(),
+                            b"text/vnd.graphviz".as_slice(),
+                        )
+                        .unwrap(),
+                    );
+                    let _ = request.respond(response);
+                }
+            }
+        }
+        Ok(Route::Streams { window }) => {
+            let streams = get_streams_json_for(window);
             respond_json(request, &streams);
         }
         Ok(Route::Futures) => {
@@ -128,6 +151,14 @@ fn handle_request(request: Request) {
             Some(calls) => respond_json(request, &calls),
             None => respond_error(request, 404, "Future not found"),
         },
+        Ok(Route::SlowPolls) => {
+            let slow_polls = get_slow_polls_json();
+            respond_json(request, &slow_polls);
+        }
+        Ok(Route::Tasks) => {
+            let tasks = get_tasks_json();
+            respond_json(request, &tasks);
+        }
         #[cfg(feature = "threads")]
         Ok(Route::Threads) => {
             let threads = crate::threads::get_threads_json();
@@ -141,6 +172,34 @@ fn handle_request(request: Request) {
                 "Thread monitoring not available - enable threads feature",
             );
         }
+        #[cfg(feature = "tokio-runtime-metrics")]
+        Ok(Route::Runtime) => match crate::runtime::get_runtime_json() {
+            Some(runtime) => respond_json(request, &runtime),
+            None => respond_error(
+                request,
+                404,
+                "Runtime not monitored - #[hotpath::main] must run inside a tokio runtime",
+            ),
+        },
+        #[cfg(not(feature = "tokio-runtime-metrics"))]
+        Ok(Route::Runtime) => {
+            respond_error(
+                request,
+                404,
+                "Runtime metrics not available - enable tokio-runtime-metrics feature",
+            );
+        }
+        Ok(Route::Metrics) => {
+            let mut response = Response::from_string(crate::openmetrics::get_openmetrics());
+            response.add_header(
+                Header::from_bytes(
+                    b"Content-Type".as_slice(),
+                    crate::openmetrics::CONTENT_TYPE.as_bytes(),
+                )
+                .unwrap(),
+            );
+            let _ = request.respond(response);
+        }
         Err(_) => respond_error(request, 404, "Not found"),
     }
 }
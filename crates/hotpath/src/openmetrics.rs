@@ -0,0 +1,429 @@
+//! OpenMetrics text exposition for the `/metrics` route of the metrics server.
+//!
+//! Renders the same data as the JSON routes, so that Prometheus-compatible scrapers can
+//! collect it directly: function calls and durations (plus allocations with `hotpath-alloc`),
+//! channel, stream and future counters, and per-thread CPU time with the `threads` feature.
+
+use std::fmt::{Display, Write};
+
+use crate::json::{ChannelsJson, FuturesJson, StreamsJson, ThreadsJson};
+use crate::{FunctionsJson, MetricType};
+
+pub(crate) const CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+#[derive(Clone, Copy)]
+enum MetricKind {
+    Counter,
+    Gauge,
+}
+
+struct Family {
+    name: &'static str,
+    kind: MetricKind,
+    help: &'static str,
+    samples: Vec<(Vec<(&'static str, String)>, String)>,
+}
+
+impl Family {
+    fn new(name: &'static str, kind: MetricKind, help: &'static str) -> Self {
+        Self {
+            name,
+            kind,
+            help,
+            samples: Vec::new(),
+        }
+    }
+
+    fn sample(&mut self, labels: Vec<(&'static str, String)>, value: impl Display) {
+        self.samples.push((labels, value.to_string()));
+    }
+
+    fn render(&self, out: &mut String) {
+        if self.samples.is_empty() {
+            return;
+        }
+
+        let (kind, suffix) = match self.kind {
+            MetricKind::Counter => ("counter", "_total"),
+            MetricKind::Gauge => ("gauge", ""),
+        };
+        let _ = writeln!(out, "# TYPE {} {}", self.name, kind);
+        let _ = writeln!(out, "# HELP {} {}", self.name, self.help);
+
+        for (labels, value) in &self.samples {
+            out.push_str(self.name);
+            out.push_str(suffix);
+            if !labels.is_empty() {
+                out.push('{');
+                for (idx, (key, label_value)) in labels.iter().enumerate() {
+                    if idx > 0 {
+                        out.push(',');
+                    }
+                    let _ = write!(out, "{}=\"{}\"", key, escape_label_value(label_value));
+                }
+                out.push('}');
+            }
+            let _ = writeln!(out, " {}", value);
+        }
+    }
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Formats a percentile as a quantile label value, e.g. 95 -> "0.95".
+fn quantile(percentile: u8) -> String {
+    (percentile as f64 / 100.0).to_string()
+}
+
+fn metric_value(metric: &MetricType) -> Option<u64> {
+    match metric {
+        MetricType::CallsCount(value)
+        | MetricType::DurationNs(value)
+        | MetricType::Alloc(value, _)
+        | MetricType::Percentage(value) => Some(*value),
+        MetricType::Unsupported => None,
+    }
+}
+
+/// Adds call counts, totals and percentile gauges from a functions report.
+/// Rows are laid out as Calls, Avg, percentiles..., Total, % Total, Self.
+fn push_functions(
+    functions: &FunctionsJson,
+    mut calls: Option<&mut Family>,
+    total: &mut Family,
+    quantiles: &mut Family,
+) {
+    let percentiles = &functions.percentiles;
+
+    for (name, row) in &functions.data {
+        let labels = || vec![("function", name.clone())];
+
+        if let Some(calls) = calls.as_deref_mut() {
+            if let Some(value) = row.first().and_then(metric_value) {
+                calls.sample(labels(), value);
+            }
+        }
+
+        for (idx, &percentile) in percentiles.iter().enumerate() {
+            if let Some(value) = row.get(2 + idx).and_then(metric_value) {
+                let mut labels = labels();
+                labels.push(("quantile", quantile(percentile)));
+                quantiles.sample(labels, value);
+            }
+        }
+
+        if let Some(value) = row.get(2 + percentiles.len()).and_then(metric_value) {
+            total.sample(labels(), value);
+        }
+    }
+}
+
+/// Renders all metrics in OpenMetrics text format.
+pub(crate) fn render(
+    functions_timing: &FunctionsJson,
+    functions_alloc: Option<&FunctionsJson>,
+    channels: &ChannelsJson,
+    streams: &StreamsJson,
+    futures: &FuturesJson,
+    threads: Option<&ThreadsJson>,
+) -> String {
+    let mut families = Vec::new();
+
+    let mut function_calls = Family::new(
+        "hotpath_function_calls",
+        MetricKind::Counter,
+        "Number of calls of a measured function.",
+    );
+    let mut function_duration = Family::new(
+        "hotpath_function_duration_nanoseconds",
+        MetricKind::Counter,
+        "Total time spent in a measured function.",
+    );
+    let mut function_duration_quantiles = Family::new(
+        "hotpath_function_duration_quantile_nanoseconds",
+        MetricKind::Gauge,
+        "Duration percentiles of a measured function.",
+    );
+    push_functions(
+        functions_timing,
+        Some(&mut function_calls),
+        &mut function_duration,
+        &mut function_duration_quantiles,
+    );
+    families.extend([
+        function_calls,
+        function_duration,
+        function_duration_quantiles,
+    ]);
+
+    if let Some(functions_alloc) = functions_alloc {
+        let mut alloc_bytes = Family::new(
+            "hotpath_function_alloc_bytes",
+            MetricKind::Counter,
+            "Total bytes allocated by a measured function.",
+        );
+        let mut alloc_quantiles = Family::new(
+            "hotpath_function_alloc_quantile_bytes",
+            MetricKind::Gauge,
+            "Allocated bytes percentiles of a measured function.",
+        );
+        push_functions(
+            functions_alloc,
+            None,
+            &mut alloc_bytes,
+            &mut alloc_quantiles,
+        );
+        families.extend([alloc_bytes, alloc_quantiles]);
+    }
+
+    let mut channel_sent = Family::new(
+        "hotpath_channel_sent",
+        MetricKind::Counter,
+        "Messages sent to an instrumented channel.",
+    );
+    let mut channel_received = Family::new(
+        "hotpath_channel_received",
+        MetricKind::Counter,
+        "Messages received from an instrumented channel.",
+    );
+    let mut channel_queued = Family::new(
+        "hotpath_channel_queued",
+        MetricKind::Gauge,
+        "Messages currently queued in an instrumented channel.",
+    );
+    let mut channel_queued_bytes = Family::new(
+        "hotpath_channel_queued_bytes",
+        MetricKind::Gauge,
+        "Approximate size of messages currently queued in an instrumented channel.",
+    );
+    for channel in &channels.channels {
+        let labels = || {
+            vec![
+                ("id", channel.id.to_string()),
+                ("channel", channel.label.clone()),
+                ("source", channel.source.clone()),
+                ("type", channel.channel_type.to_string()),
+            ]
+        };
+        channel_sent.sample(labels(), channel.sent_count);
+        channel_received.sample(labels(), channel.received_count);
+        channel_queued.sample(labels(), channel.queued);
+        channel_queued_bytes.sample(labels(), channel.queued_bytes);
+    }
+    families.extend([
+        channel_sent,
+        channel_received,
+        channel_queued,
+        channel_queued_bytes,
+    ]);
+
+    let mut stream_yielded = Family::new(
+        "hotpath_stream_yielded",
+        MetricKind::Counter,
+        "Items yielded by an instrumented stream.",
+    );
+    for stream in &streams.streams {
+        stream_yielded.sample(
+            vec![
+                ("id", stream.id.to_string()),
+                ("stream", stream.label.clone()),
+                ("source", stream.source.clone()),
+            ],
+            stream.items_yielded,
+        );
+    }
+    families.push(stream_yielded);
+
+    let mut future_calls = Family::new(
+        "hotpath_future_calls",
+        MetricKind::Counter,
+        "Calls of an instrumented future.",
+    );
+    let mut future_polls = Family::new(
+        "hotpath_future_polls",
+        MetricKind::Counter,
+        "Polls of an instrumented future across all calls.",
+    );
+    for future in &futures.futures {
+        let labels = || {
+            vec![
+                ("id", future.id.to_string()),
+                ("future", future.label.clone()),
+                ("source", future.source.clone()),
+            ]
+        };
+        future_calls.sample(labels(), future.call_count);
+        future_polls.sample(labels(), future.total_polls);
+    }
+    families.extend([future_calls, future_polls]);
+
+    if let Some(threads) = threads {
+        let mut thread_cpu = Family::new(
+            "hotpath_thread_cpu_seconds",
+            MetricKind::Counter,
+            "CPU time consumed by a thread.",
+        );
+        for thread in &threads.threads {
+            for (mode, value) in [("user", thread.cpu_user), ("system", thread.cpu_sys)] {
+                thread_cpu.sample(
+                    vec![
+                        ("tid", thread.os_tid.to_string()),
+                        ("thread", thread.name.clone()),
+                        ("mode", mode.to_string()),
+                    ],
+                    value,
+                );
+            }
+        }
+
+        let mut resident_memory = Family::new(
+            "hotpath_process_resident_memory_bytes",
+            MetricKind::Gauge,
+            "Resident set size of the process.",
+        );
+        if let Some(rss_bytes) = threads.rss_bytes {
+            resident_memory.sample(Vec::new(), rss_bytes);
+        }
+        families.extend([thread_cpu, resident_memory]);
+    }
+
+    let mut out = String::new();
+    for family in &families {
+        family.render(&mut out);
+    }
+    out.push_str("# EOF\n");
+    out
+}
+
+/// Collects current metrics from all instrumented sources and renders them.
+pub(crate) fn get_openmetrics() -> String {
+    #[cfg(feature = "threads")]
+    let threads = Some(crate::threads::get_threads_json());
+    #[cfg(not(feature = "threads"))]
+    let threads = None;
+
+    // Scrapers need every function, not only the `limit` slowest shown in reports
+    let (timing, alloc) = crate::functions::get_all_functions_json();
+
+    render(
+        &timing,
+        alloc.as_ref(),
+        &crate::channels::get_channels_json(),
+        &crate::streams::get_streams_json(),
+        &crate::futures::get_futures_json(),
+        threads.as_ref(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json::{ChannelState, ChannelType, SerializableChannelStats};
+    use crate::ProfilingMode;
+
+    fn empty_functions() -> FunctionsJson {
+        FunctionsJson {
+            hotpath_profiling_mode: ProfilingMode::Timing,
+            total_elapsed: 0,
+            description: String::new(),
+            caller_name: "main".to_string(),
+            percentiles: vec![50, 95],
+            data: Vec::new(),
+            call_tree: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_render_functions_and_channels() {
+        let mut functions = empty_functions();
+        functions.data.push((
+            "app::handle".to_string(),
+            vec![
+                MetricType::CallsCount(3),
+                MetricType::DurationNs(100),
+                MetricType::DurationNs(90),
+                MetricType::DurationNs(150),
+                MetricType::DurationNs(300),
+                MetricType::Percentage(10000),
+                MetricType::DurationNs(300),
+            ],
+        ));
+
+        let channels = ChannelsJson {
+            current_elapsed_ns: 0,
+            channels: vec![SerializableChannelStats {
+                id: 7,
+                source: "src/main.rs:10".to_string(),
+                label: "jobs \"main\"".to_string(),
+                has_custom_label: true,
+                channel_type: ChannelType::Bounded(10),
+                state: ChannelState::Active,
+                sent_count: 5,
+                received_count: 4,
+                queued: 1,
+                type_name: "u64".to_string(),
+                type_size: 8,
+                queued_bytes: 8,
+                iter: 0,
+                sent_rate: 0.0,
+                received_rate: 0.0,
+                sent_rate_instant: 0,
+                received_rate_instant: 0,
+                sent_bytes_rate: 0.0,
+                received_bytes_rate: 0.0,
+                sent_history: Vec::new(),
+                received_history: Vec::new(),
+                subscribers: None,
+                lag: None,
+                queue_wait: None,
+                send_blocked: None,
+                sent_bytes: None,
+                received_bytes: None,
+                max_queued: 0,
+                max_queued_at_ns: 0,
+                queue_warnings: Vec::new(),
+            }],
+            queue_warn_threshold: None,
+        };
+        let streams = StreamsJson {
+            current_elapsed_ns: 0,
+            streams: Vec::new(),
+        };
+        let futures = FuturesJson {
+            current_elapsed_ns: 0,
+            futures: Vec::new(),
+        };
+
+        let output = render(&functions, None, &channels, &streams, &futures, None);
+
+        let expected = [
+            "# TYPE hotpath_function_calls counter\n",
+            "hotpath_function_calls_total{function=\"app::handle\"} 3\n",
+            "hotpath_function_duration_nanoseconds_total{function=\"app::handle\"} 300\n",
+            "hotpath_function_duration_quantile_nanoseconds{function=\"app::handle\",quantile=\"0.5\"} 90\n",
+            "hotpath_function_duration_quantile_nanoseconds{function=\"app::handle\",quantile=\"0.95\"} 150\n",
+            "# TYPE hotpath_channel_queued gauge\n",
+            "hotpath_channel_sent_total{id=\"7\",channel=\"jobs \\\"main\\\"\",source=\"src/main.rs:10\",type=\"bounded[10]\"} 5\n",
+            "hotpath_channel_queued{id=\"7\",channel=\"jobs \\\"main\\\"\",source=\"src/main.rs:10\",type=\"bounded[10]\"} 1\n",
+        ];
+        for line in expected {
+            assert!(output.contains(line), "Expected:\n{line}\nGot:\n{output}");
+        }
+
+        assert!(!output.contains("hotpath_stream_yielded"));
+        assert!(!output.contains("hotpath_function_alloc_bytes"));
+        assert!(output.ends_with("# EOF\n"));
+    }
+
+    #[test]
+    fn test_escape_label_value() {
+        assert_eq!(escape_label_value("a\\b\"c\nd"), "a\\\\b\\\"c\\nd");
+        assert_eq!(quantile(99), "0.99");
+        assert_eq!(quantile(100), "1");
+    }
+}
@@ -169,6 +169,19 @@ pub trait Reporter: Send + Sync {
         &self,
         metrics_provider: &dyn MetricsProvider<'_>,
     ) -> Result<(), Box<dyn std::error::Error>>;
+
+    /// Called while profiling is running when `FunctionsGuardBuilder::report_every` is set.
+    ///
+    /// `interval` covers the measurements since the previous periodic report and
+    /// `cumulative` everything since the guard was built. Reports the interval
+    /// snapshot with [`Reporter::report`] by default.
+    fn report_periodic(
+        &self,
+        interval: &dyn MetricsProvider<'_>,
+        _cumulative: &dyn MetricsProvider<'_>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.report(interval)
+    }
 }
 
 /// Profiling mode indicating what type of measurements were collected.
@@ -181,7 +194,7 @@ pub trait Reporter: Send + Sync {
 /// * `Timing` - Time-based profiling (execution duration)
 /// * `Alloc` - Combined allocation profiling (both bytes and count)
 #[allow(dead_code)]
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub enum ProfilingMode {
     Timing,
@@ -227,6 +240,42 @@ pub struct FunctionLogsJson {
     pub count: usize,
 }
 
+/// A measured function at a specific call path.
+///
+/// Children are the measured functions called from this one on the same thread.
+/// `self_ns` excludes the time spent in children.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallTreeNode {
+    pub name: String,
+    pub calls: u64,
+    /// Total time spent in this call path in nanoseconds
+    pub total_ns: u64,
+    /// Time spent in this call path excluding nested measured functions
+    pub self_ns: u64,
+    pub children: Vec<CallTreeNode>,
+}
+
+/// Duration distribution of a measured function over explicit bucket bounds.
+///
+/// `bucket_counts` has one more entry than the bounds it was built from: bucket `i`
+/// counts calls in `(bounds[i - 1], bounds[i]]`, and the last one everything above.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DurationHistogram {
+    pub count: u64,
+    pub sum_ns: u64,
+    pub min_ns: u64,
+    pub max_ns: u64,
+    pub bucket_counts: Vec<u64>,
+}
+
+/// Response for the /functions_timing/tree endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionsTreeJson {
+    pub total_elapsed: u64,
+    pub caller_name: String,
+    pub tree: Vec<CallTreeNode>,
+}
+
 /// JSON representation of profiling metrics.
 #[derive(Debug, Clone)]
 pub struct FunctionsJson {
@@ -236,6 +285,8 @@ pub struct FunctionsJson {
     pub caller_name: String,
     pub percentiles: Vec<u8>,
     pub data: FunctionsDataJson,
+    /// Call tree of measured functions (timing mode only, empty otherwise)
+    pub call_tree: Vec<CallTreeNode>,
 }
 
 #[derive(Deserialize)]
@@ -245,6 +296,8 @@ struct MetricsJsonRaw {
     description: String,
     caller_name: String,
     data: serde_json::Value,
+    #[serde(default)]
+    call_tree: Vec<CallTreeNode>,
 }
 
 impl TryFrom<MetricsJsonRaw> for FunctionsJson {
@@ -264,6 +317,7 @@ impl TryFrom<MetricsJsonRaw> for FunctionsJson {
             caller_name: raw.caller_name,
             percentiles,
             data,
+            call_tree: raw.call_tree,
         })
     }
 }
@@ -294,6 +348,7 @@ fn build_headers(percentiles: &[u8]) -> Vec<String> {
 
     headers.push("Total".to_string());
     headers.push("% Total".to_string());
+    headers.push("Self".to_string());
 
     headers
 }
@@ -331,7 +386,7 @@ impl Serialize for FunctionsJson {
         use serde::ser::SerializeStruct;
 
         let headers = build_headers(&self.percentiles);
-        let mut state = serializer.serialize_struct("MetricsJson", 5)?;
+        let mut state = serializer.serialize_struct("MetricsJson", 6)?;
 
         state.serialize_field("hotpath_profiling_mode", &self.hotpath_profiling_mode)?;
         state.serialize_field("total_elapsed", &self.total_elapsed)?;
@@ -344,6 +399,12 @@ impl Serialize for FunctionsJson {
         };
         state.serialize_field("data", &data_serializer)?;
 
+        if self.call_tree.is_empty() {
+            state.skip_field("call_tree")?;
+        } else {
+            state.serialize_field("call_tree", &self.call_tree)?;
+        }
+
         state.end()
     }
 }
@@ -441,7 +502,7 @@ fn create_metric_type(field_name: &str, value: u64, profiling_mode: &ProfilingMo
                 ProfilingMode::Alloc => MetricType::Alloc(value, 0),
             }
         }
-        "avg" | "total" => match profiling_mode {
+        "avg" | "total" | "self" => match profiling_mode {
             ProfilingMode::Timing => MetricType::DurationNs(value),
             ProfilingMode::Alloc => MetricType::Alloc(value, 0),
         },
@@ -504,6 +565,7 @@ pub trait MetricsProvider<'a> {
 
         headers.push("Total".to_string());
         headers.push("% Total".to_string());
+        headers.push("Self".to_string());
 
         headers
     }
@@ -512,11 +574,14 @@ pub trait MetricsProvider<'a> {
     fn metric_data(&self) -> Vec<(String, Vec<MetricType>)>;
 
     fn sort_key(&self, metrics: &[MetricType]) -> f64 {
-        if let Some(MetricType::Percentage(basis_points)) = metrics.last() {
-            *basis_points as f64 / 100.0
-        } else {
-            0.0
-        }
+        metrics
+            .iter()
+            .rev()
+            .find_map(|metric| match metric {
+                MetricType::Percentage(basis_points) => Some(*basis_points as f64 / 100.0),
+                _ => None,
+            })
+            .unwrap_or(0.0)
     }
 
     fn has_unsupported_async(&self) -> bool {
@@ -525,6 +590,23 @@ pub trait MetricsProvider<'a> {
 
     fn entry_counts(&self) -> (usize, usize);
 
+    /// Measured functions aggregated by call path. Empty unless call paths are tracked.
+    fn call_tree(&self) -> Vec<CallTreeNode> {
+        Vec::new()
+    }
+
+    /// Folded stack lines with their self weight: nanoseconds in timing mode and bytes
+    /// in alloc mode. Empty unless call paths are tracked.
+    fn folded_stacks(&self) -> Vec<(String, u64)> {
+        Vec::new()
+    }
+
+    /// Duration histograms of measured functions, bucketed over `bounds` in nanoseconds.
+    /// Bounds must be sorted ascending.
+    fn duration_histograms(&self, _bounds: &[u64]) -> Vec<(String, DurationHistogram)> {
+        Vec::new()
+    }
+
     #[cfg(feature = "hotpath")]
     fn new(
         stats: &'a HashMap<&'static str, FunctionStats>,
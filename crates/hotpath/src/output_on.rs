@@ -188,6 +188,29 @@ impl Reporter for JsonPrettyReporter {
     }
 }
 
+pub(crate) struct FoldedReporter;
+
+impl Reporter for FoldedReporter {
+    fn report(
+        &self,
+        metrics_provider: &dyn MetricsProvider<'_>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let stacks = metrics_provider.folded_stacks();
+        if stacks.is_empty() {
+            display_no_measurements_message(
+                Duration::from_nanos(metrics_provider.total_elapsed()),
+                metrics_provider.caller_name(),
+            );
+            return Ok(());
+        }
+
+        for (stack, weight) in stacks {
+            println!("{stack} {weight}");
+        }
+        Ok(())
+    }
+}
+
 impl From<&dyn MetricsProvider<'_>> for FunctionsJson {
     fn from(metrics: &dyn MetricsProvider<'_>) -> Self {
         let hotpath_profiling_mode = metrics.profiling_mode();
@@ -201,6 +224,7 @@ impl From<&dyn MetricsProvider<'_>> for FunctionsJson {
             caller_name: metrics.caller_name().to_string(),
             percentiles,
             data,
+            call_tree: metrics.call_tree(),
         }
     }
 }
@@ -0,0 +1,370 @@
+#[cfg(test)]
+pub mod tests {
+    use std::process::Command;
+
+    fn path_sep() -> &'static str {
+        if cfg!(windows) {
+            "\\"
+        } else {
+            "/"
+        }
+    }
+
+    // cargo run -p test-channels-async-channel --example basic_async_channel --features hotpath
+    #[test]
+    fn test_basic_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "test-channels-async-channel",
+                "--example",
+                "basic_async_channel",
+                "--features",
+                "hotpath",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        assert!(!output.stderr.is_empty(), "Stderr is empty");
+        let sep = path_sep();
+        let basic_async_channel_path = format!("examples{sep}basic_async_channel.rs");
+        let all_expected = [
+            basic_async_channel_path.as_str(),
+            "hello-there",
+            "unbounded",
+            "bounded[10]",
+            "bounded[1]",
+        ];
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        for expected in all_expected {
+            assert!(
+                stdout.contains(expected),
+                "Expected:\n{expected}\n\nGot:\n{stdout}",
+            );
+        }
+    }
+
+    // cargo run -p test-channels-async-channel --example closed_async_channel --features hotpath
+    #[test]
+    fn test_closed_channels_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "test-channels-async-channel",
+                "--example",
+                "closed_async_channel",
+                "--features",
+                "hotpath",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        // Check that all three channels have "closed" state
+        assert!(
+            stdout.contains("closed-sender"),
+            "Expected closed-sender channel in output"
+        );
+        assert!(
+            stdout.contains("closed-receiver"),
+            "Expected closed-receiver channel in output"
+        );
+        assert!(
+            stdout.contains("closed-unbounded"),
+            "Expected closed-unbounded channel in output"
+        );
+    }
+
+    // cargo run -p test-channels-async-channel --example basic_json_async_channel --features hotpath
+    #[test]
+    fn test_basic_json_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "test-channels-async-channel",
+                "--example",
+                "basic_json_async_channel",
+                "--features",
+                "hotpath",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let all_expected = ["\"label\": \"bounded\"", "\"label\": \"unbounded\""];
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        for expected in all_expected {
+            assert!(
+                stdout.contains(expected),
+                "Expected:\n{expected}\n\nGot:\n{stdout}",
+            );
+        }
+    }
+
+    // cargo run -p test-channels-async-channel --example iter_async_channel --features hotpath
+    #[test]
+    fn test_iter_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "test-channels-async-channel",
+                "--example",
+                "iter_async_channel",
+                "--features",
+                "hotpath",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        let sep = path_sep();
+        let iter_path = format!("examples{sep}iter_async_channel.rs:12");
+        let iter_path_2 = format!("examples{sep}iter_async_channel.rs:12-2");
+        let iter_path_3 = format!("examples{sep}iter_async_channel.rs:12-3");
+        let all_expected = [
+            "bounded",
+            "bounded-2",
+            "bounded-3",
+            iter_path.as_str(),
+            iter_path_2.as_str(),
+            iter_path_3.as_str(),
+        ];
+
+        for expected in all_expected {
+            assert!(
+                stdout.contains(expected),
+                "Expected:\n{expected}\n\nGot:\n{stdout}",
+            );
+        }
+    }
+
+    // cargo run -p test-channels-async-channel --example mpmc_async_channel --features hotpath
+    #[test]
+    fn test_mpmc_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "test-channels-async-channel",
+                "--example",
+                "mpmc_async_channel",
+                "--features",
+                "hotpath",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let all_expected = [
+            "Processed 20 jobs",
+            "\"label\": \"jobs\"",
+            "\"channel_type\": \"bounded[4]\"",
+            "\"sent_count\": 20",
+            "\"received_count\": 20",
+        ];
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        for expected in all_expected {
+            assert!(
+                stdout.contains(expected),
+                "Expected:\n{expected}\n\nGot:\n{stdout}",
+            );
+        }
+    }
+
+    // cargo run -p test-channels-async-channel --example slow_consumer_async_channel --features hotpath
+    #[test]
+    fn test_slow_consumer_no_panic() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "test-channels-async-channel",
+                "--example",
+                "slow_consumer_async_channel",
+                "--features",
+                "hotpath",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}\nStdout:\n{}\nStderr:\n{}",
+            output.status,
+            stdout,
+            stderr
+        );
+
+        assert!(
+            stdout.contains("Slow consumer example completed!"),
+            "Expected completion message not found.\nOutput:\n{}",
+            stdout
+        );
+    }
+
+    // HOTPATH_METRICS_PORT=6778 TEST_SLEEP_SECONDS=10 cargo run -p test-channels-async-channel --example basic_async_channel --features hotpath
+    #[test]
+    fn test_data_endpoints() {
+        use hotpath::json::ChannelsJson;
+        use std::{thread::sleep, time::Duration};
+
+        let mut child = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "test-channels-async-channel",
+                "--example",
+                "basic_async_channel",
+                "--features",
+                "hotpath",
+            ])
+            .env("HOTPATH_METRICS_PORT", "6778")
+            .env("TEST_SLEEP_SECONDS", "10")
+            .spawn()
+            .expect("Failed to spawn command");
+
+        let mut json_text = String::new();
+        let mut last_error = None;
+
+        // Test /channels endpoint
+        // Give the server some time to start up
+
+        for _attempt in 0..12 {
+            sleep(Duration::from_millis(500));
+
+            match ureq::get("http://localhost:6778/channels").call() {
+                Ok(mut response) => {
+                    json_text = response
+                        .body_mut()
+                        .read_to_string()
+                        .expect("Failed to read response body");
+                    last_error = None;
+                    break;
+                }
+                Err(e) => {
+                    last_error = Some(format!("Request error: {}", e));
+                }
+            }
+        }
+
+        if let Some(error) = last_error {
+            let _ = child.kill();
+            panic!("Failed after 12 retries: {}", error);
+        }
+
+        let all_expected = ["basic_async_channel.rs", "unbounded", "hello-there"];
+        for expected in all_expected {
+            assert!(
+                json_text.contains(expected),
+                "Expected:\n{expected}\n\nGot:\n{json_text}",
+            );
+        }
+
+        // Test /channels/:id/logs endpoint
+        let channels_response: ChannelsJson =
+            serde_json::from_str(&json_text).expect("Failed to parse channels JSON");
+
+        if let Some(first_channel) = channels_response.channels.first() {
+            let logs_url = format!("http://localhost:6778/channels/{}/logs", first_channel.id);
+            let response = ureq::get(&logs_url)
+                .call()
+                .expect("Failed to call /channels/:id/logs endpoint");
+
+            assert_eq!(
+                response.status(),
+                200,
+                "Expected status 200 for /channels/:id/logs endpoint"
+            );
+        }
+
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+
+    // cargo run -p test-channels-async-channel --example blocked_async_channel --features hotpath
+    #[test]
+    fn test_send_blocked_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "test-channels-async-channel",
+                "--example",
+                "blocked_async_channel",
+                "--features",
+                "hotpath",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let json_start = stdout.find('{').expect("JSON output not found");
+        let channels: hotpath::json::ChannelsJson =
+            serde_json::from_str(stdout[json_start..].trim()).expect("Failed to parse JSON");
+
+        let channel = channels
+            .channels
+            .iter()
+            .find(|c| c.label == "blocked")
+            .unwrap_or_else(|| panic!("Channel blocked not found in:\n{stdout}"));
+        assert_eq!(channel.sent_count, 20);
+        assert_eq!(channel.received_count, 20);
+
+        // The forwarder sees the channel full while the consumer stalls, but not who is blocked
+        let blocked = channel
+            .send_blocked
+            .as_ref()
+            .expect("Missing send blocked stats");
+        assert!(blocked.durations.count >= 1);
+        assert!(blocked.total_ns >= 50_000_000, "Got {}", blocked.total_ns);
+        assert!(blocked.threads.is_empty());
+    }
+}
@@ -255,4 +255,49 @@ pub mod tests {
         let _ = child.kill();
         let _ = child.wait();
     }
+
+    // cargo run -p test-channels-std --example blocked_std --features hotpath
+    #[test]
+    fn test_send_blocked_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "test-channels-std",
+                "--example",
+                "blocked_std",
+                "--features",
+                "hotpath",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let json_start = stdout.find('{').expect("JSON output not found");
+        let channels: hotpath::json::ChannelsJson =
+            serde_json::from_str(stdout[json_start..].trim()).expect("Failed to parse JSON");
+
+        let channel = channels
+            .channels
+            .iter()
+            .find(|c| c.label == "blocked")
+            .unwrap_or_else(|| panic!("Channel blocked not found in:\n{stdout}"));
+        assert_eq!(channel.sent_count, 20);
+        assert_eq!(channel.received_count, 20);
+
+        // The forwarder sees the channel full while the consumer stalls, but not who is blocked
+        let blocked = channel
+            .send_blocked
+            .as_ref()
+            .expect("Missing send blocked stats");
+        assert!(blocked.durations.count >= 1);
+        assert!(blocked.total_ns >= 50_000_000, "Got {}", blocked.total_ns);
+        assert!(blocked.threads.is_empty());
+    }
 }
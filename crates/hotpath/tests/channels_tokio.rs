@@ -76,7 +76,54 @@ pub mod tests {
 
         let sep = path_sep();
         let json_path = format!("\"label\": \"examples{sep}basic_json_tokio.rs:");
-        let all_expected = [json_path.as_str(), "\"label\": \"hello-there\""];
+        let all_expected = [
+            json_path.as_str(),
+            "\"label\": \"hello-there\"",
+            "\"queue_wait\"",
+            "\"p99_ns\"",
+        ];
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        for expected in all_expected {
+            assert!(
+                stdout.contains(expected),
+                "Expected:\n{expected}\n\nGot:\n{stdout}",
+            );
+        }
+    }
+
+    // cargo run -p test-channels-tokio --example broadcast_watch_tokio --features hotpath
+    #[test]
+    fn test_broadcast_watch_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "test-channels-tokio",
+                "--example",
+                "broadcast_watch_tokio",
+                "--features",
+                "hotpath",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let all_expected = [
+            "\"label\": \"events\"",
+            "\"channel_type\": \"broadcast[4]\"",
+            "\"label\": \"config\"",
+            "\"channel_type\": \"watch\"",
+            "\"subscribers\": 2",
+            "\"lag\": 4",
+            "\"sent_count\": 3",
+        ];
 
         let stdout = String::from_utf8_lossy(&output.stdout);
 
@@ -88,6 +135,206 @@ pub mod tests {
         }
     }
 
+    // cargo run -p test-channels-tokio --example direct_tokio --features hotpath
+    #[test]
+    fn test_direct_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "test-channels-tokio",
+                "--example",
+                "direct_tokio",
+                "--features",
+                "hotpath",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let json_start = stdout.find('{').expect("JSON output not found");
+        let channels: hotpath::json::ChannelsJson =
+            serde_json::from_str(stdout[json_start..].trim()).expect("Failed to parse JSON");
+
+        assert!(
+            stdout.contains("Third try_send rejected"),
+            "Expected unchanged capacity.\n\nGot:\n{stdout}",
+        );
+
+        let channel = |label: &str| {
+            channels
+                .channels
+                .iter()
+                .find(|c| c.label == label)
+                .unwrap_or_else(|| panic!("Channel {label} not found in:\n{stdout}"))
+        };
+
+        let bounded = channel("direct-bounded");
+        assert_eq!(bounded.channel_type.to_string(), "bounded[2]");
+        assert_eq!(bounded.sent_count, 10);
+        assert_eq!(bounded.received_count, 10);
+        let wait = bounded.queue_wait.expect("Missing queue wait stats");
+        assert_eq!(wait.count, 10);
+        assert!(wait.p50_ns <= wait.p99_ns && wait.avg_ns <= wait.max_ns);
+
+        // The rejected try_send hit the full condition
+        let bounded_blocked = bounded.send_blocked.as_ref().expect("Missing send blocked");
+        assert!(bounded_blocked.durations.count >= 1);
+
+        let blocked = channel("direct-blocked")
+            .send_blocked
+            .as_ref()
+            .expect("Missing send blocked stats");
+        assert!(blocked.durations.count >= 1);
+        assert!(blocked.total_ns > 0);
+        assert!(blocked.durations.max_ns <= blocked.total_ns);
+        assert_eq!(
+            blocked.threads.iter().map(|t| t.count).sum::<u64>(),
+            blocked.durations.count
+        );
+
+        let unbounded = channel("direct-unbounded");
+        assert_eq!(unbounded.sent_count, 3);
+        assert_eq!(unbounded.received_count, 3);
+
+        // Messages are counted where they sit, without a proxy slot
+        let queued = channel("direct-queued");
+        assert_eq!(queued.sent_count, 2);
+        assert_eq!(queued.queued, 2);
+        assert!(queued.queue_wait.is_none());
+        assert!(queued.send_blocked.is_none());
+    }
+
+    // cargo run -p test-channels-tokio --example size_tokio --features hotpath
+    #[test]
+    fn test_size_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "test-channels-tokio",
+                "--example",
+                "size_tokio",
+                "--features",
+                "hotpath",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let json_start = stdout.find('{').expect("JSON output not found");
+        let channels: hotpath::json::ChannelsJson =
+            serde_json::from_str(stdout[json_start..].trim()).expect("Failed to parse JSON");
+
+        let channel = |label: &str| {
+            channels
+                .channels
+                .iter()
+                .find(|c| c.label == label)
+                .unwrap_or_else(|| panic!("Channel {label} not found in:\n{stdout}"))
+        };
+
+        // Frame is a u64 and a Vec header inline, plus a 1 KiB payload
+        let frame_size = 32 + 1024;
+        let frames = channel("sized-frames");
+        assert_eq!(frames.queued, 3);
+        assert_eq!(frames.sent_bytes, Some(4 * frame_size));
+        assert_eq!(frames.received_bytes, Some(frame_size));
+        assert_eq!(frames.queued_bytes, 3 * frame_size);
+
+        let unsized_frames = channel("unsized-frames");
+        assert_eq!(unsized_frames.queued, 3);
+        assert_eq!(unsized_frames.queued_bytes, 3 * 24);
+        assert!(unsized_frames.sent_bytes.is_none());
+        assert!(unsized_frames.received_bytes.is_none());
+
+        let text = channel("sized-text");
+        assert_eq!(text.sent_count, 3);
+        assert_eq!(text.sent_bytes, Some(3 * (24 + 100)));
+        assert_eq!(text.received_bytes, text.sent_bytes);
+        assert_eq!(text.queued_bytes, 0);
+    }
+
+    // HOTPATH_CHANNEL_QUEUE_WARN=10 cargo run -p test-channels-tokio --example growth_tokio --features hotpath
+    #[test]
+    fn test_queue_growth_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "test-channels-tokio",
+                "--example",
+                "growth_tokio",
+                "--features",
+                "hotpath",
+            ])
+            .env("HOTPATH_CHANNEL_QUEUE_WARN", "10")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let json_start = stdout.find('{').expect("JSON output not found");
+        let channels: hotpath::json::ChannelsJson =
+            serde_json::from_str(stdout[json_start..].trim()).expect("Failed to parse JSON");
+
+        assert_eq!(channels.queue_warn_threshold, Some(10));
+        let channel = channels
+            .channels
+            .iter()
+            .find(|c| c.label == "growing-queue")
+            .unwrap_or_else(|| panic!("Channel growing-queue not found in:\n{stdout}"));
+
+        assert_eq!(channel.queued, 0);
+        assert_eq!(channel.max_queued, 50);
+        assert!(channel.max_queued_at_ns > 0);
+
+        // One warning per burst, the queue being drained in between
+        assert_eq!(channel.queue_warnings.len(), 2);
+        for warning in &channel.queue_warnings {
+            assert_eq!(warning.queued, 10);
+            assert_eq!(warning.threshold, 10);
+        }
+
+        let source = format!("examples{}growth_tokio.rs", path_sep());
+        let warnings = stderr
+            .lines()
+            .filter(|line| line.starts_with("[hotpath] Channel 'growing-queue' at "))
+            .filter(|line| line.contains(&source))
+            .count();
+        assert_eq!(warnings, 2, "Expected two queue warnings in:\n{stderr}");
+
+        let proxied = channels
+            .channels
+            .iter()
+            .find(|c| c.label == "proxied-queue")
+            .unwrap_or_else(|| panic!("Channel proxied-queue not found in:\n{stdout}"));
+        assert_eq!(proxied.sent_count, 50);
+        assert_eq!(proxied.received_count, 50);
+        assert_eq!(proxied.queued, 0);
+        assert!(proxied.max_queued >= 49, "Got {}", proxied.max_queued);
+        assert_eq!(proxied.queue_warnings.len(), 1);
+        assert!(stderr.contains("[hotpath] Channel 'proxied-queue' at "));
+    }
+
     // cargo run -p test-channels-tokio --example closed_tokio --features hotpath
     #[test]
     fn test_closed_channels_output() {
@@ -312,6 +559,29 @@ pub mod tests {
         let channels_response: ChannelsJson =
             serde_json::from_str(&json_text).expect("Failed to parse channels JSON");
 
+        // Test /channels?window= endpoint
+        let windowed: ChannelsJson = ureq::get("http://localhost:6773/channels?window=1m")
+            .call()
+            .expect("Failed to call /channels?window=1m endpoint")
+            .body_mut()
+            .read_json()
+            .expect("Failed to parse windowed channels JSON");
+        assert!(
+            windowed
+                .channels
+                .iter()
+                .any(|channel| channel.sent_rate > 0.0),
+            "Expected a non-zero send rate within the last 1m: {:?}",
+            windowed.channels
+        );
+        for channel in &windowed.channels {
+            assert_eq!(channel.sent_history.len(), 30);
+            assert_eq!(channel.received_history.len(), 30);
+            assert!(channel.sent_history.iter().sum::<u64>() <= channel.sent_count);
+            let estimated = channel.sent_rate * channel.type_size as f64;
+            assert!((channel.sent_bytes_rate - estimated).abs() <= estimated * 1e-9);
+        }
+
         if let Some(first_channel) = channels_response.channels.first() {
             let logs_url = format!("http://localhost:6773/channels/{}/logs", first_channel.id);
             let response = ureq::get(&logs_url)
@@ -328,4 +598,139 @@ pub mod tests {
         let _ = child.kill();
         let _ = child.wait();
     }
+
+    // HOTPATH_METRICS_PORT=6780 TEST_SLEEP_SECONDS=10 cargo run -p test-channels-tokio --example graph_tokio --features hotpath
+    #[test]
+    fn test_graph_endpoint() {
+        use hotpath::json::{ChannelsGraphJson, GraphNodeKind};
+        use std::{thread::sleep, time::Duration};
+
+        let mut child = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "test-channels-tokio",
+                "--example",
+                "graph_tokio",
+                "--features",
+                "hotpath",
+            ])
+            .env("HOTPATH_METRICS_PORT", "6780")
+            .env("TEST_SLEEP_SECONDS", "10")
+            .spawn()
+            .expect("Failed to spawn command");
+
+        // Wait until the pipeline has drained into the last channel
+        let mut graph = None;
+        for _attempt in 0..20 {
+            sleep(Duration::from_millis(500));
+
+            let Ok(mut response) = ureq::get("http://localhost:6780/channels/graph").call() else {
+                continue;
+            };
+            let current: ChannelsGraphJson = response
+                .body_mut()
+                .read_json()
+                .expect("Failed to parse graph JSON");
+            if current.edges.iter().map(|e| e.messages).sum::<u64>() == 80 {
+                graph = Some(current);
+                break;
+            }
+        }
+
+        let Some(graph) = graph else {
+            let _ = child.kill();
+            panic!("Graph endpoint did not report the finished pipeline");
+        };
+
+        let node_id = |label: &str| {
+            graph
+                .nodes
+                .iter()
+                .find(|n| n.label == label)
+                .unwrap_or_else(|| panic!("Missing node {label}: {graph:?}"))
+                .id
+                .clone()
+        };
+        let edge = |from: &str, to: &str| {
+            graph
+                .edges
+                .iter()
+                .find(|e| e.from == node_id(from) && e.to == node_id(to))
+                .map(|e| e.messages)
+        };
+
+        assert_eq!(edge("graph_tokio::source", "graph-raw"), Some(20));
+        assert_eq!(edge("graph-raw", "graph_tokio::parse"), Some(20));
+        assert_eq!(edge("graph_tokio::parse", "graph-parsed"), Some(20));
+        assert_eq!(edge("graph-parsed", "graph_tokio::main"), Some(20));
+
+        let parse = graph
+            .nodes
+            .iter()
+            .find(|n| n.label == "graph_tokio::parse")
+            .unwrap();
+        assert_eq!(parse.kind, GraphNodeKind::Function);
+        assert_eq!(parse.tids.len(), 2);
+
+        let dot = ureq::get("http://localhost:6780/channels/graph?format=dot")
+            .call()
+            .expect("Failed to call /channels/graph?format=dot endpoint")
+            .body_mut()
+            .read_to_string()
+            .expect("Failed to read DOT body");
+        assert!(dot.starts_with("digraph channels {"), "Got:\n{dot}");
+        assert!(
+            dot.contains("\"fn:graph_tokio::source\" -> "),
+            "Got:\n{dot}"
+        );
+
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+
+    // cargo run -p test-channels-tokio --example blocked_tokio --features hotpath
+    #[test]
+    fn test_send_blocked_output() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "test-channels-tokio",
+                "--example",
+                "blocked_tokio",
+                "--features",
+                "hotpath",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let json_start = stdout.find('{').expect("JSON output not found");
+        let channels: hotpath::json::ChannelsJson =
+            serde_json::from_str(stdout[json_start..].trim()).expect("Failed to parse JSON");
+
+        let channel = channels
+            .channels
+            .iter()
+            .find(|c| c.label == "blocked")
+            .unwrap_or_else(|| panic!("Channel blocked not found in:\n{stdout}"));
+        assert_eq!(channel.sent_count, 20);
+        assert_eq!(channel.received_count, 20);
+
+        // The forwarder sees the channel full while the consumer stalls, but not who is blocked
+        let blocked = channel
+            .send_blocked
+            .as_ref()
+            .expect("Missing send blocked stats");
+        assert!(blocked.durations.count >= 1);
+        assert!(blocked.total_ns >= 50_000_000, "Got {}", blocked.total_ns);
+        assert!(blocked.threads.is_empty());
+    }
 }
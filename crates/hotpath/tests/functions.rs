@@ -198,6 +198,56 @@ pub mod tests {
         }
     }
 
+    // cargo run -p test-tokio-async --example report_every --features hotpath
+    #[test]
+    fn test_report_every_param() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "test-tokio-async",
+                "--example",
+                "report_every",
+                "--features",
+                "hotpath",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Process did not exit successfully.\n\nstderr:\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let calls: Vec<u64> = stdout
+            .lines()
+            .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+            .map(|report| {
+                report["data"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .find(|row| row["name"] == "report_every::example_function")
+                    .and_then(|row| row["calls"].as_u64())
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        // Periodic reports cover only their interval, the final one is cumulative
+        let (last, periodic) = calls.split_last().expect("No reports printed");
+        assert!(
+            !periodic.is_empty(),
+            "Expected periodic reports before the final one\n\nGot:\n{stdout}"
+        );
+        assert_eq!(*last, 10, "Got:\n{stdout}");
+        assert!(
+            periodic.iter().all(|calls| *calls < 10) && periodic.iter().sum::<u64>() <= 10,
+            "Expected interval call counts, got {periodic:?}"
+        );
+    }
+
     // cargo run -p test-tokio-async --example main_format --features hotpath
     #[test]
     fn test_main_format_param() {
@@ -273,6 +323,308 @@ pub mod tests {
         }
     }
 
+    // cargo run -p test-tokio-async --example call_tree --features hotpath
+    #[test]
+    fn test_call_tree_output() {
+        use hotpath::json::{CallTreeNode, FunctionsJson};
+
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "test-tokio-async",
+                "--example",
+                "call_tree",
+                "--features",
+                "hotpath",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Process did not exit successfully.\n\nstderr:\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let metrics: FunctionsJson =
+            serde_json::from_str(stdout.trim()).expect("Failed to parse JSON output");
+
+        fn find<'a>(nodes: &'a [CallTreeNode], name: &str) -> &'a CallTreeNode {
+            nodes
+                .iter()
+                .find(|n| n.name == name)
+                .unwrap_or_else(|| panic!("Expected node {name} in {nodes:#?}"))
+        }
+
+        let main = find(&metrics.call_tree, "call_tree::main");
+        let handler = find(&main.children, "call_tree::api_handler");
+        let worker = find(&main.children, "call_tree::worker_sync");
+        let handler_query = find(&handler.children, "call_tree::db_query");
+        let worker_query = find(&worker.children, "call_tree::db_query");
+
+        assert_eq!(handler.calls, 3);
+        assert_eq!(handler_query.calls, 3);
+        assert_eq!(worker_query.calls, 3);
+        assert!(handler_query.total_ns > worker_query.total_ns);
+        assert_eq!(
+            handler.self_ns,
+            handler.total_ns - handler_query.total_ns,
+            "Self time should exclude nested measured calls"
+        );
+    }
+
+    // cargo run -p test-tokio-async --example call_tree_async --features hotpath
+    #[test]
+    fn test_call_tree_interleaved_async_output() {
+        use hotpath::json::{CallTreeNode, FunctionsJson};
+
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "test-tokio-async",
+                "--example",
+                "call_tree_async",
+                "--features",
+                "hotpath",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Process did not exit successfully.\n\nstderr:\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let metrics: FunctionsJson =
+            serde_json::from_str(stdout.trim()).expect("Failed to parse JSON output");
+
+        fn find<'a>(nodes: &'a [CallTreeNode], name: &str) -> &'a CallTreeNode {
+            nodes
+                .iter()
+                .find(|n| n.name == name)
+                .unwrap_or_else(|| panic!("Expected node {name} in {nodes:#?}"))
+        }
+
+        let main = find(&metrics.call_tree, "call_tree_async::main");
+        let users = find(&main.children, "call_tree_async::fetch_users");
+        let orders = find(&main.children, "call_tree_async::fetch_orders");
+        assert_eq!(metrics.call_tree.len(), 1, "{:#?}", metrics.call_tree);
+
+        for (node, parse_ms) in [(users, 3), (orders, 1)] {
+            assert_eq!(node.calls, 3);
+            // Only the sync call made inside the function, not the other function
+            assert_eq!(node.children.len(), 1, "{node:#?}");
+            let parse = find(&node.children, "call_tree_async::parse");
+            assert_eq!(parse.calls, 3);
+            assert!(parse.total_ns >= 3 * parse_ms * 1_000_000);
+            assert_eq!(node.self_ns, node.total_ns - parse.total_ns);
+        }
+    }
+
+    // cargo run -p test-tokio-async --example folded --features hotpath
+    #[test]
+    fn test_folded_output() {
+        let features = ["hotpath", "hotpath,hotpath-alloc"];
+
+        for features_arg in features {
+            let output = Command::new("cargo")
+                .args([
+                    "run",
+                    "-p",
+                    "test-tokio-async",
+                    "--example",
+                    "folded",
+                    "--features",
+                    features_arg,
+                ])
+                .output()
+                .expect("Failed to execute command");
+
+            assert!(
+                output.status.success(),
+                "Process did not exit successfully.\n\nstderr:\n{}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let stacks: Vec<(&str, u64)> = stdout
+                .lines()
+                .map(|line| {
+                    let (stack, weight) = line
+                        .rsplit_once(' ')
+                        .unwrap_or_else(|| panic!("Invalid folded line: {line}"));
+                    (stack, weight.parse().expect("Invalid folded weight"))
+                })
+                .collect();
+
+            let weight = |stack: &str| {
+                stacks
+                    .iter()
+                    .find(|(s, _)| *s == stack)
+                    .map(|(_, w)| *w)
+                    .unwrap_or_else(|| panic!("Expected {stack} in:\n{stdout}"))
+            };
+
+            let nested = weight("folded::main;folded::api_handler;folded::db_query");
+            let direct = weight("folded::main;folded::db_query");
+
+            if features_arg.contains("hotpath-alloc") {
+                assert_eq!(nested, 3 * 1024);
+                assert_eq!(direct, 3 * 1024);
+            } else {
+                assert!(nested >= 3 * 2_000_000, "Expected 3 x 2ms nested queries");
+                assert!(direct >= 3 * 1_000_000, "Expected 3 x 1ms direct queries");
+                assert!(weight("folded::main;folded::api_handler") >= 3 * 1_000_000);
+            }
+        }
+    }
+
+    // HOTPATH_TRACE_FILE=trace.json cargo run -p test-tokio-async --example trace_file --features hotpath
+    #[test]
+    fn test_trace_file_output() {
+        let trace_path = std::env::temp_dir().join("hotpath_test_trace.json");
+        let _ = std::fs::remove_file(&trace_path);
+
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "test-tokio-async",
+                "--example",
+                "trace_file",
+                "--features",
+                "hotpath",
+            ])
+            .env("HOTPATH_TRACE_FILE", &trace_path)
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Process did not exit successfully.\n\nstderr:\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        let content = std::fs::read_to_string(&trace_path).expect("Trace file was not written");
+        let trace: serde_json::Value =
+            serde_json::from_str(&content).expect("Failed to parse trace file");
+        let events = trace["traceEvents"]
+            .as_array()
+            .expect("Expected traceEvents array");
+
+        let count = |ph: &str, name: &str| {
+            events
+                .iter()
+                .filter(|e| e["ph"] == ph && e["name"] == name)
+                .count()
+        };
+
+        assert_eq!(count("X", "trace_file::parse_job"), 3);
+        assert_eq!(count("X", "trace_file::handle_request"), 3);
+        assert_eq!(count("X", "trace_file::main"), 1);
+        assert!(
+            events
+                .iter()
+                .any(|e| e["ph"] == "X" && e["cat"] == "future"),
+            "Expected future poll spans in:\n{content}"
+        );
+        assert!(
+            events
+                .iter()
+                .any(|e| e["ph"] == "M" && e["cat"] == "channel" && e["args"]["name"] == "jobs"),
+            "Expected a named channel track in:\n{content}"
+        );
+        assert!(
+            count("i", "send") > 0,
+            "Expected channel sends in:\n{content}"
+        );
+        assert!(
+            events
+                .iter()
+                .filter(|e| e["ph"] == "X" && e["cat"] == "function")
+                .all(|e| e["tid"] != 0),
+            "Function spans should be on the track of a real thread in:\n{content}"
+        );
+
+        let parse_job = events
+            .iter()
+            .find(|e| e["name"] == "trace_file::parse_job")
+            .unwrap();
+        let handle_request = events
+            .iter()
+            .find(|e| e["name"] == "trace_file::handle_request")
+            .unwrap();
+        assert_eq!(
+            parse_job["tid"], handle_request["tid"],
+            "Nested spans should share a thread track"
+        );
+        assert!(parse_job["dur"].as_f64().unwrap() >= 1000.0);
+
+        let _ = std::fs::remove_file(&trace_path);
+    }
+
+    // cargo run -p test-tokio-async --example call_tree --features hotpath
+    #[test]
+    fn test_self_column_output() {
+        use hotpath::json::FunctionsJson;
+        use hotpath::MetricType;
+
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "test-tokio-async",
+                "--example",
+                "call_tree",
+                "--features",
+                "hotpath",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Process did not exit successfully.\n\nstderr:\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(
+            stdout.contains(r#""self":"#),
+            "Expected self key in JSON output"
+        );
+
+        let metrics: FunctionsJson =
+            serde_json::from_str(stdout.trim()).expect("Failed to parse JSON output");
+
+        let durations = |name: &str| {
+            let (_, row) = metrics
+                .data
+                .iter()
+                .find(|(n, _)| n == name)
+                .unwrap_or_else(|| panic!("Expected {name} in output"));
+            match (&row[row.len() - 3], &row[row.len() - 1]) {
+                (MetricType::DurationNs(total), MetricType::DurationNs(self_ns)) => {
+                    (*total, *self_ns)
+                }
+                other => panic!("Unexpected Total/Self metrics: {other:?}"),
+            }
+        };
+
+        let (query_total, query_self) = durations("call_tree::db_query");
+        let (handler_total, handler_self) = durations("call_tree::api_handler");
+        let (main_total, main_self) = durations("call_tree::main");
+
+        assert_eq!(query_self, query_total, "Leaf self time should equal total");
+        assert!(handler_self < handler_total);
+        assert!(main_self < main_total);
+    }
+
     // cargo run -p test-smol-async --example basic_smol --features hotpath,hotpath-alloc -- --nocapture
     #[test]
     fn test_async_smol_alloc_profiling_output() {
@@ -379,7 +731,7 @@ pub mod tests {
         let report_content = fs::read_to_string(report_path).expect("Failed to read report file");
 
         let expected_content = [
-            "Function, Calls, Avg, P50, P90, P95, Total, % Total",
+            "Function, Calls, Avg, P50, P90, P95, Total, % Total, Self\n",
             "Functions measured: 4",
             "csv_file_reporter::async_function, 100",
             "csv_file_reporter::sync_function, 100",
@@ -673,7 +1025,7 @@ pub mod tests {
     // HOTPATH_METRICS_PORT=6775 TEST_SLEEP_SECONDS=10 cargo run -p test-tokio-async --example basic --features hotpath,hotpath-alloc
     #[test]
     fn test_data_endpoints() {
-        use hotpath::json::FunctionsJson;
+        use hotpath::json::{FunctionsJson, FunctionsTreeJson};
         use std::{thread::sleep, time::Duration};
 
         let mut child = Command::new("cargo")
@@ -767,6 +1119,60 @@ pub mod tests {
         let _alloc_response: FunctionsJson =
             serde_json::from_str(&alloc_json).expect("Failed to parse alloc JSON");
 
+        // Test /functions_timing/tree endpoint
+        let mut tree_response = ureq::get("http://localhost:6775/functions_timing/tree")
+            .call()
+            .expect("Failed to call /functions_timing/tree endpoint");
+
+        assert_eq!(
+            tree_response.status(),
+            200,
+            "Expected status 200 for /functions_timing/tree endpoint"
+        );
+
+        let tree_json = tree_response
+            .body_mut()
+            .read_to_string()
+            .expect("Failed to read tree response body");
+
+        let tree: FunctionsTreeJson =
+            serde_json::from_str(&tree_json).expect("Failed to parse tree JSON");
+        assert!(
+            tree.tree
+                .iter()
+                .flat_map(|root| root.children.iter())
+                .any(|node| node.name == "basic::sync_function"),
+            "Expected basic::sync_function nested under main:\n{tree_json}"
+        );
+
+        // Test /functions_timing?window= endpoint
+        let windowed_json = ureq::get("http://localhost:6775/functions_timing?window=10s")
+            .call()
+            .expect("Failed to call /functions_timing?window=10s endpoint")
+            .body_mut()
+            .read_to_string()
+            .expect("Failed to read windowed response body");
+
+        let windowed: FunctionsJson =
+            serde_json::from_str(&windowed_json).expect("Failed to parse windowed JSON");
+        assert!(
+            windowed.description.contains("last 10s"),
+            "Expected windowed description:\n{windowed_json}"
+        );
+        // Windowed durations are recorded from the first windowed request on, and the example
+        // is idle by now
+        assert!(
+            windowed.data.is_empty(),
+            "Expected no functions recorded before the first windowed request:\n{windowed_json}"
+        );
+
+        let invalid_window = ureq::get("http://localhost:6775/functions_timing?window=2h").call();
+        assert!(
+            matches!(invalid_window, Err(ureq::Error::StatusCode(404))),
+            "Expected 404 for an unsupported window, got {:?}",
+            invalid_window.map(|r| r.status())
+        );
+
         if let Some((first_function_name, _)) = timing_response.data.first() {
             use base64::Engine;
             let encoded_name =
@@ -807,6 +1213,152 @@ pub mod tests {
         let _ = child.wait();
     }
 
+    // HOTPATH_METRICS_PORT=6777 TEST_SLEEP_SECONDS=10 cargo run -p test-tokio-async --example basic --features hotpath,hotpath-alloc
+    #[test]
+    fn test_openmetrics_endpoint() {
+        use std::{thread::sleep, time::Duration};
+
+        let mut child = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "test-tokio-async",
+                "--example",
+                "basic",
+                "--features",
+                "hotpath,hotpath-alloc",
+            ])
+            .env("HOTPATH_METRICS_PORT", "6777")
+            .env("TEST_SLEEP_SECONDS", "10")
+            .spawn()
+            .expect("Failed to spawn command");
+
+        let mut metrics_text = String::new();
+        let mut content_type = None;
+        let mut last_error = None;
+
+        // Give the server some time to start up and functions to be called
+        for _attempt in 0..18 {
+            sleep(Duration::from_millis(500));
+
+            match ureq::get("http://localhost:6777/metrics").call() {
+                Ok(mut response) => {
+                    content_type = response
+                        .headers()
+                        .get("Content-Type")
+                        .and_then(|value| value.to_str().ok())
+                        .map(str::to_string);
+                    metrics_text = response
+                        .body_mut()
+                        .read_to_string()
+                        .expect("Failed to read response body");
+                    last_error = None;
+                    if metrics_text.contains("basic::sync_function") {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    last_error = Some(format!("Request error: {}", e));
+                }
+            }
+        }
+
+        let _ = child.kill();
+        let _ = child.wait();
+
+        if let Some(error) = last_error {
+            panic!("Failed to connect to /metrics after 18 retries: {}", error);
+        }
+
+        assert!(
+            content_type
+                .as_deref()
+                .is_some_and(|value| value.starts_with("application/openmetrics-text")),
+            "Unexpected Content-Type: {content_type:?}"
+        );
+
+        let expected = [
+            "# TYPE hotpath_function_calls counter",
+            "hotpath_function_calls_total{function=\"basic::sync_function\"} ",
+            "hotpath_function_duration_nanoseconds_total{function=\"basic::async_function\"} ",
+            "hotpath_function_duration_quantile_nanoseconds{function=\"custom_block\",quantile=\"0.95\"} ",
+            "hotpath_function_alloc_bytes_total{function=\"basic::sync_function\"} ",
+        ];
+        for line in expected {
+            assert!(
+                metrics_text.contains(line),
+                "Expected:\n{line}\n\nGot:\n{metrics_text}",
+            );
+        }
+        assert!(
+            metrics_text.ends_with("# EOF\n"),
+            "Expected # EOF terminator:\n{metrics_text}"
+        );
+    }
+
+    // HOTPATH_METRICS_PORT=6784 TEST_SLEEP_SECONDS=10 cargo run -p test-tokio-async --example openmetrics_limit --features hotpath
+    #[test]
+    fn test_openmetrics_ignores_limit() {
+        use std::{thread::sleep, time::Duration};
+
+        let mut child = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "test-tokio-async",
+                "--example",
+                "openmetrics_limit",
+                "--features",
+                "hotpath",
+            ])
+            .env("HOTPATH_METRICS_PORT", "6784")
+            .env("TEST_SLEEP_SECONDS", "10")
+            .spawn()
+            .expect("Failed to spawn command");
+
+        let mut metrics_text = String::new();
+        let mut last_error = None;
+
+        for _attempt in 0..18 {
+            sleep(Duration::from_millis(500));
+
+            match ureq::get("http://localhost:6784/metrics").call() {
+                Ok(mut response) => {
+                    metrics_text = response
+                        .body_mut()
+                        .read_to_string()
+                        .expect("Failed to read response body");
+                    last_error = None;
+                    if metrics_text.contains("openmetrics_limit::fast_function") {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    last_error = Some(format!("Request error: {}", e));
+                }
+            }
+        }
+
+        let _ = child.kill();
+        let _ = child.wait();
+
+        if let Some(error) = last_error {
+            panic!("Failed to connect to /metrics after 18 retries: {}", error);
+        }
+
+        // limit = 1 truncates reports, but not the exposition
+        for function in [
+            "openmetrics_limit::slow_function",
+            "openmetrics_limit::fast_function",
+        ] {
+            let line = format!("hotpath_function_calls_total{{function=\"{function}\"}} ");
+            assert!(
+                metrics_text.contains(&line),
+                "Expected:\n{line}\n\nGot:\n{metrics_text}",
+            );
+        }
+    }
+
     // cargo run -p test-tokio-async --example main_timeout --features hotpath
     #[test]
     fn test_main_timeout_output() {
@@ -915,4 +1467,128 @@ pub mod tests {
         let _ = child.kill();
         let _ = child.wait();
     }
+
+    // cargo run -p test-tokio-async --example otlp --features otlp
+    #[test]
+    fn test_otlp_reporter_pushes_to_collector() {
+        use std::io::{BufRead, BufReader, Read, Write};
+        use std::net::TcpListener;
+        use std::sync::mpsc;
+
+        // Mock OTLP collector that forwards every request body to the test
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind mock collector");
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let (body_tx, body_rx) = mpsc::channel::<serde_json::Value>();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let body_tx = body_tx.clone();
+                std::thread::spawn(move || {
+                    let mut reader = BufReader::new(stream);
+                    loop {
+                        let mut content_length = 0;
+                        loop {
+                            let mut line = String::new();
+                            if reader.read_line(&mut line).unwrap_or(0) == 0 {
+                                return;
+                            }
+                            if line == "\r\n" {
+                                break;
+                            }
+                            if let Some((key, value)) = line.split_once(':') {
+                                if key.eq_ignore_ascii_case("content-length") {
+                                    content_length = value.trim().parse().unwrap_or(0);
+                                }
+                            }
+                        }
+
+                        let mut body = vec![0; content_length];
+                        if reader.read_exact(&mut body).is_err() {
+                            return;
+                        }
+                        let _ = reader
+                            .get_mut()
+                            .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\n{}");
+                        if let Ok(body) = serde_json::from_slice(&body) {
+                            let _ = body_tx.send(body);
+                        }
+                    }
+                });
+            }
+        });
+
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "test-tokio-async",
+                "--example",
+                "otlp",
+                "--features",
+                "otlp",
+            ])
+            .env("OTEL_EXPORTER_OTLP_ENDPOINT", &endpoint)
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Process did not exit successfully.\n\nstderr:\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        let bodies: Vec<serde_json::Value> = body_rx.try_iter().collect();
+        assert!(
+            bodies.len() >= 2,
+            "Expected periodic and final pushes, got {} requests",
+            bodies.len()
+        );
+
+        let metrics = |body: &serde_json::Value| -> Vec<serde_json::Value> {
+            body["resourceMetrics"][0]["scopeMetrics"][0]["metrics"]
+                .as_array()
+                .cloned()
+                .unwrap_or_default()
+        };
+        let find_metric = |body: &serde_json::Value, name: &str| {
+            metrics(body)
+                .into_iter()
+                .find(|metric| metric["name"] == name)
+        };
+
+        assert!(
+            bodies.iter().any(|body| {
+                find_metric(body, "hotpath.channel.queued").is_some_and(|metric| {
+                    metric["gauge"]["dataPoints"][0]["attributes"]
+                        .to_string()
+                        .contains("jobs")
+                })
+            }),
+            "Expected a channel queue depth gauge, got:\n{:#?}",
+            bodies
+        );
+
+        // The final flush happens when the guard drops and includes every call
+        let last = bodies.last().unwrap();
+        assert_eq!(
+            last["resourceMetrics"][0]["resource"]["attributes"][0]["value"]["stringValue"],
+            "otlp-example"
+        );
+        let duration = find_metric(last, "hotpath.function.duration")
+            .expect("Missing function duration histogram in final push");
+        let point = duration["histogram"]["dataPoints"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|point| point["attributes"][0]["value"]["stringValue"] == "otlp::handle_request")
+            .expect("Missing otlp::handle_request data point");
+        assert_eq!(point["count"], "20");
+        let bucket_total: u64 = point["bucketCounts"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|count| count.as_str().unwrap().parse::<u64>().unwrap())
+            .sum();
+        assert_eq!(bucket_total, 20);
+    }
 }
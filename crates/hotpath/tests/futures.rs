@@ -104,10 +104,67 @@ pub mod tests {
         );
     }
 
+    // cargo run -p test-futures --example wakers_futures --features hotpath
+    #[test]
+    fn test_wake_warnings() {
+        use hotpath::json::{FuturesJson, WakeStats, WakeWarning};
+
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "test-futures",
+                "--example",
+                "wakers_futures",
+                "--features",
+                "hotpath",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}",
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let json_start = stdout.find('{').expect("JSON output not found");
+        let futures: FuturesJson =
+            serde_json::from_str(stdout[json_start..].trim()).expect("Failed to parse JSON");
+        let wakes = |label: &str| -> WakeStats {
+            futures
+                .futures
+                .iter()
+                .find(|future| future.label == label)
+                .unwrap_or_else(|| panic!("Expected future labeled '{label}'.\nGot:\n{stdout}"))
+                .wakes
+        };
+
+        let yielding = wakes("yielding");
+        assert_eq!(yielding.wakes, 3);
+        assert_eq!(yielding.self_wakes, 3);
+        assert_eq!(yielding.warnings(), vec![(WakeWarning::SelfWake, 3)]);
+
+        let sleeping = wakes("sleeping");
+        assert_eq!(sleeping.wakes, 1);
+        assert!(sleeping.warnings().is_empty(), "{sleeping:?}");
+
+        let forgetful = wakes("forgetful");
+        assert_eq!(forgetful.wakes, 0);
+        assert_eq!(forgetful.warnings(), vec![(WakeWarning::LostWakeup, 1)]);
+
+        let stashing = wakes("stashing");
+        assert_eq!(
+            stashing.warnings(),
+            vec![(WakeWarning::WokenAfterCompletion, 1)]
+        );
+    }
+
     // HOTPATH_METRICS_PORT=6775 TEST_SLEEP_SECONDS=10 cargo run -p test-futures --example basic_futures --features hotpath
     #[test]
     fn test_data_endpoints() {
-        use hotpath::json::FuturesJson;
+        use hotpath::json::{FutureCalls, FuturesJson};
         use std::{thread::sleep, time::Duration};
 
         let mut child = Command::new("cargo")
@@ -189,7 +246,161 @@ pub mod tests {
             );
         }
 
+        // Labels name the future, tags are kept per call
+        for (label, expected_tags) in [
+            (
+                "tagged_request",
+                vec![
+                    vec![("kind", "demo"), ("request_id", "7")],
+                    vec![("kind", "demo"), ("request_id", "8")],
+                ],
+            ),
+            ("attributed_tagged", vec![vec![("request_id", "9")]]),
+        ] {
+            let future = futures_response
+                .futures
+                .iter()
+                .find(|future| future.label == label)
+                .unwrap_or_else(|| panic!("Expected future labeled '{label}'.\nGot:\n{json_text}"));
+
+            let calls_url = format!("http://localhost:6775/futures/{}/calls", future.id);
+            let calls_text = ureq::get(&calls_url)
+                .call()
+                .expect("Failed to call /futures/{id}/calls endpoint")
+                .body_mut()
+                .read_to_string()
+                .expect("Failed to read calls response");
+            let calls: FutureCalls =
+                serde_json::from_str(&calls_text).expect("Failed to parse calls JSON");
+
+            let mut tags: Vec<Vec<(String, String)>> = calls
+                .calls
+                .iter()
+                .map(|call| call.tags.clone().into_iter().collect())
+                .collect();
+            tags.sort();
+            let expected_tags: Vec<Vec<(String, String)>> = expected_tags
+                .into_iter()
+                .map(|tags| {
+                    tags.into_iter()
+                        .map(|(k, v)| (k.to_string(), v.to_string()))
+                        .collect()
+                })
+                .collect();
+            assert_eq!(tags, expected_tags, "Unexpected tags for '{label}'");
+
+            if label == "tagged_request" {
+                // Both calls sleep for 10ms, waiting on the timer between polls
+                let total = future.total.expect("Expected total timing");
+                let idle = future.idle.expect("Expected idle timing");
+                let busy = future.busy.expect("Expected busy timing");
+                assert_eq!(total.count, 2);
+                assert!(total.p50_ns >= 10_000_000, "Unexpected total: {total:?}");
+                assert!(idle.avg_ns >= 9_000_000, "Unexpected idle: {idle:?}");
+                assert!(busy.max_ns <= total.max_ns);
+
+                let longest = future.longest_poll.expect("Expected longest poll");
+                let call = calls
+                    .calls
+                    .iter()
+                    .find(|call| call.id == longest.call_id)
+                    .expect("Expected the longest poll to belong to a logged call");
+                assert_eq!(call.max_poll_ns, longest.duration_ns);
+
+                for call in &calls.calls {
+                    assert_eq!(call.poll_count, 2);
+                    let first_poll_at_ns = call.first_poll_at_ns.expect("Expected first poll");
+                    let ended_at_ns = call.ended_at_ns.expect("Expected end timestamp");
+                    assert!(call.created_at_ns <= first_poll_at_ns);
+                    assert!(first_poll_at_ns < ended_at_ns);
+                    assert!(call.lifetime_ns().unwrap() >= 10_000_000);
+                    assert!(call.busy_ns > 0 && call.busy_ns >= call.max_poll_ns);
+                }
+            }
+        }
+
         let _ = child.kill();
         let _ = child.wait();
     }
+
+    // HOTPATH_SLOW_POLL_MS=20 HOTPATH_METRICS_PORT=6781 TEST_SLEEP_SECONDS=10 cargo run -p test-futures --example slow_polls --features hotpath
+    #[test]
+    fn test_slow_polls_endpoint() {
+        use hotpath::json::{SlowPollKind, SlowPollsJson};
+        use std::{thread::sleep, time::Duration};
+
+        let mut child = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "test-futures",
+                "--example",
+                "slow_polls",
+                "--features",
+                "hotpath",
+            ])
+            .env("HOTPATH_SLOW_POLL_MS", "20")
+            .env("HOTPATH_METRICS_PORT", "6781")
+            .env("TEST_SLEEP_SECONDS", "10")
+            .spawn()
+            .expect("Failed to spawn command");
+
+        // Wait for the server, and for all blocking calls to finish
+        let mut slow_polls = None;
+        let mut last_error = None;
+        for _attempt in 0..20 {
+            sleep(Duration::from_millis(500));
+
+            match ureq::get("http://localhost:6781/slow_polls").call() {
+                Ok(mut response) => {
+                    let json_text = response
+                        .body_mut()
+                        .read_to_string()
+                        .expect("Failed to read response body");
+                    let parsed: SlowPollsJson =
+                        serde_json::from_str(&json_text).expect("Failed to parse slow polls JSON");
+                    last_error = None;
+                    if parsed.total >= 3 {
+                        slow_polls = Some(parsed);
+                        break;
+                    }
+                }
+                Err(e) => {
+                    last_error = Some(format!("Request error: {}", e));
+                }
+            }
+        }
+
+        let _ = child.kill();
+        let _ = child.wait();
+
+        if let Some(error) = last_error {
+            panic!("Failed after 20 retries: {}", error);
+        }
+        let slow_polls = slow_polls.expect("Expected 3 slow polls");
+
+        assert_eq!(slow_polls.threshold_ms, Some(20));
+        assert_eq!(slow_polls.total, 3, "{slow_polls:?}");
+
+        // Newest first, the measured function around the nested future doesn't report again
+        let nested = &slow_polls.slow_polls[0];
+        assert_eq!(nested.kind, SlowPollKind::Future);
+        assert_eq!(nested.label, "nested_future");
+        assert!(nested.duration_ns >= 40_000_000);
+
+        let function = &slow_polls.slow_polls[1];
+        assert_eq!(function.kind, SlowPollKind::Function);
+        assert_eq!(function.label, "slow_polls::blocking_function");
+        assert!(function.duration_ns >= 40_000_000);
+        assert!(function.call_id.is_none());
+
+        let future = &slow_polls.slow_polls[2];
+        assert_eq!(future.kind, SlowPollKind::Future);
+        assert_eq!(future.label, "blocking_future");
+        assert!(future.source.contains("slow_polls.rs"));
+        assert!(future.duration_ns >= 50_000_000);
+        assert!(future.future_id.is_some() && future.call_id.is_some());
+        assert!(future.tid > 0);
+        assert!(future.timestamp_ns < function.timestamp_ns);
+    }
 }
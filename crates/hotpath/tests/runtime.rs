@@ -0,0 +1,105 @@
+#[cfg(test)]
+pub mod tests {
+    use hotpath::json::RuntimeJson;
+    use std::process::Command;
+    use std::thread::sleep;
+    use std::time::Duration;
+
+    // HOTPATH_METRICS_PORT=6782 TEST_SLEEP_SECONDS=10 cargo run -p test-tokio-async --example runtime_metrics --features tokio-runtime-metrics
+    #[test]
+    fn test_runtime_endpoint() {
+        let mut child = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "test-tokio-async",
+                "--example",
+                "runtime_metrics",
+                "--features",
+                "tokio-runtime-metrics",
+            ])
+            .env("HOTPATH_METRICS_PORT", "6782")
+            .env("TEST_SLEEP_SECONDS", "10")
+            .spawn()
+            .expect("Failed to spawn command");
+
+        let mut runtime = None;
+        let mut last_error = None;
+
+        // Wait until both the runtime and the threads were sampled twice, and both workers
+        // ran a probe task before the last runtime sample
+        for _attempt in 0..30 {
+            sleep(Duration::from_millis(1000));
+
+            match ureq::get("http://localhost:6782/runtime").call() {
+                Ok(mut response) => {
+                    let json_text = response
+                        .body_mut()
+                        .read_to_string()
+                        .expect("Failed to read response body");
+                    let parsed: RuntimeJson =
+                        serde_json::from_str(&json_text).expect("Failed to parse runtime JSON");
+                    last_error = None;
+                    let workers_matched = !cfg!(tokio_unstable)
+                        || parsed.workers.iter().all(|w| w.cpu_percent.is_some());
+                    if parsed.busy_percent.is_some()
+                        && parsed.cpu_percent.is_some()
+                        && parsed.threads.len() >= 2
+                        && workers_matched
+                    {
+                        runtime = Some(parsed);
+                        break;
+                    }
+                }
+                Err(e) => {
+                    last_error = Some(format!("Request error: {}", e));
+                }
+            }
+        }
+
+        let _ = child.kill();
+        let _ = child.wait();
+
+        if let Some(error) = last_error {
+            panic!("Failed after 30 retries: {}", error);
+        }
+        let runtime = runtime.expect("Expected busy and CPU percentages");
+
+        assert_eq!(runtime.flavor, "multi_thread");
+        assert_eq!(runtime.num_workers, 2);
+        assert_eq!(runtime.workers.len(), 2);
+        assert!(runtime.alive_tasks >= 2, "{runtime:?}");
+
+        for (index, worker) in runtime.workers.iter().enumerate() {
+            assert_eq!(worker.index, index);
+            assert!(worker.busy_ns > 0);
+            assert!(worker.busy_percent.is_some());
+            assert!(worker.park_count > 0);
+        }
+
+        // The blocking pool thread shares the worker name, but is left out
+        assert_eq!(runtime.threads.len(), 2, "{:?}", runtime.threads);
+        assert!(runtime.threads.iter().all(|t| t.name.starts_with("tokio-")));
+
+        // Tokio only tells which thread a multi_thread worker runs on with tokio_unstable
+        if cfg!(tokio_unstable) {
+            let mut os_tids: Vec<u64> = runtime
+                .workers
+                .iter()
+                .map(|w| w.os_tid.expect("Missing worker thread"))
+                .collect();
+            os_tids.sort_unstable();
+            let mut threads: Vec<u64> = runtime.threads.iter().map(|t| t.os_tid).collect();
+            threads.sort_unstable();
+            assert_eq!(os_tids, threads);
+            assert!(runtime.workers.iter().all(|w| w.cpu_percent.is_some()));
+        } else {
+            assert!(runtime.workers.iter().all(|w| w.os_tid.is_none()));
+        }
+
+        // Half of the busy time is spent in std::thread::sleep
+        let busy = runtime.busy_percent.unwrap();
+        let cpu = runtime.cpu_percent.unwrap();
+        assert!(busy > cpu, "busy {busy}% should exceed CPU {cpu}%");
+    }
+}
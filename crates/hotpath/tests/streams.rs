@@ -138,6 +138,40 @@ pub mod tests {
             );
         }
 
+        // Test /streams?window= endpoint
+        let windowed: StreamsJson = ureq::get("http://localhost:6774/streams?window=1m")
+            .call()
+            .expect("Failed to call /streams?window=1m endpoint")
+            .body_mut()
+            .read_json()
+            .expect("Failed to parse windowed streams JSON");
+        assert!(
+            windowed
+                .streams
+                .iter()
+                .any(|stream| stream.yield_rate > 0.0),
+            "Expected a non-zero yield rate within the last 1m: {:?}",
+            windowed.streams
+        );
+        for stream in &windowed.streams {
+            assert_eq!(stream.yield_history.len(), 30);
+            assert!(stream.yield_history.iter().sum::<u64>() <= stream.items_yielded);
+            if stream.yielded_bytes.is_none() {
+                let estimated = stream.yield_rate * stream.type_size as f64;
+                assert!((stream.yield_bytes_rate - estimated).abs() <= estimated * 1e-9);
+            }
+        }
+
+        // Items of size = true streams are measured including their heap allocations
+        let sized = windowed
+            .streams
+            .iter()
+            .find(|stream| stream.label == "sized-stream")
+            .expect("sized-stream not found");
+        assert_eq!(sized.items_yielded, 3);
+        assert_eq!(sized.yielded_bytes, Some(3 * (24 + 64)));
+        assert!(sized.yield_bytes_rate > sized.yield_rate * sized.type_size as f64);
+
         // Test /streams/:id/logs endpoint
         let streams_response: StreamsJson =
             serde_json::from_str(&json_text).expect("Failed to parse streams JSON");
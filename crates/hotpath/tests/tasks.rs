@@ -0,0 +1,211 @@
+#[cfg(test)]
+pub mod tests {
+    use hotpath::json::{TaskKind, TaskState, TasksJson};
+    use std::process::Command;
+    use std::thread::sleep;
+    use std::time::Duration;
+
+    // HOTPATH_METRICS_PORT=6783 TEST_SLEEP_SECONDS=10 cargo run -p test-tokio-async --example spawned_tasks --features hotpath
+    #[test]
+    fn test_tasks_endpoint() {
+        let mut child = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "test-tokio-async",
+                "--example",
+                "spawned_tasks",
+                "--features",
+                "hotpath",
+            ])
+            .env("HOTPATH_METRICS_PORT", "6783")
+            .env("TEST_SLEEP_SECONDS", "10")
+            .spawn()
+            .expect("Failed to spawn command");
+
+        let mut tasks = None;
+        let mut last_error = None;
+
+        // Wait until the supervisor task finished
+        for _attempt in 0..30 {
+            sleep(Duration::from_millis(1000));
+
+            match ureq::get("http://localhost:6783/tasks").call() {
+                Ok(mut response) => {
+                    let json_text = response
+                        .body_mut()
+                        .read_to_string()
+                        .expect("Failed to read response body");
+                    let parsed: TasksJson =
+                        serde_json::from_str(&json_text).expect("Failed to parse tasks JSON");
+                    last_error = None;
+                    if parsed.total_spawned == 7 {
+                        tasks = Some(parsed);
+                        break;
+                    }
+                }
+                Err(e) => {
+                    last_error = Some(format!("Request error: {}", e));
+                }
+            }
+        }
+
+        let _ = child.kill();
+        let _ = child.wait();
+
+        if let Some(error) = last_error {
+            panic!("Failed after 30 retries: {}", error);
+        }
+        let tasks = tasks.expect("Expected 7 spawned tasks");
+
+        let labels: Vec<&str> = tasks.tasks.iter().map(|t| t.label.as_str()).collect();
+        assert_eq!(
+            labels,
+            vec![
+                "listener-0",
+                "listener-1",
+                "supervisor",
+                "stuck",
+                "failing",
+                "checksum",
+                "worker"
+            ]
+        );
+        assert_eq!(tasks.live, 2);
+
+        let task = |label: &str| tasks.tasks.iter().find(|t| t.label == label).unwrap();
+        let supervisor = task("supervisor");
+
+        for listener in &tasks.tasks[..2] {
+            assert_eq!(listener.state, TaskState::Running);
+            assert_eq!(listener.parent_id, None);
+            assert!(listener.finished_at_ns.is_none());
+        }
+
+        assert_eq!(supervisor.state, TaskState::Completed);
+        assert_eq!(supervisor.parent_id, None);
+        assert!(supervisor.source.contains("spawned_tasks.rs:"));
+        assert!(supervisor.poll_count > 1);
+        assert!(!supervisor.threads.is_empty());
+
+        for label in ["worker", "checksum", "failing", "stuck"] {
+            assert_eq!(task(label).parent_id, Some(supervisor.id), "{label}");
+        }
+
+        let worker = task("worker");
+        assert_eq!(worker.kind, TaskKind::Async);
+        assert_eq!(worker.state, TaskState::Completed);
+        assert!(worker.poll_count >= 4);
+
+        let checksum = task("checksum");
+        assert_eq!(checksum.kind, TaskKind::Blocking);
+        assert_eq!(checksum.state, TaskState::Completed);
+        assert_eq!(checksum.poll_count, 1);
+        assert!(checksum.busy_ns >= 20_000_000);
+
+        let failing = task("failing");
+        assert_eq!(failing.state, TaskState::Panicked);
+        assert_eq!(failing.panic_message.as_deref(), Some("connection reset"));
+
+        let stuck = task("stuck");
+        assert_eq!(stuck.state, TaskState::Cancelled);
+        assert!(stuck.finished_at_ns.is_some());
+    }
+
+    // HOTPATH_METRICS_PORT=6785 TEST_SLEEP_SECONDS=10 cargo run -p test-smol-async --example spawned_tasks_smol --features hotpath
+    #[test]
+    fn test_tasks_endpoint_smol() {
+        let mut child = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "test-smol-async",
+                "--example",
+                "spawned_tasks_smol",
+                "--features",
+                "hotpath",
+            ])
+            .env("HOTPATH_METRICS_PORT", "6785")
+            .env("TEST_SLEEP_SECONDS", "10")
+            .spawn()
+            .expect("Failed to spawn command");
+
+        let mut tasks = None;
+        let mut last_error = None;
+
+        // Wait until the supervisor task finished
+        for _attempt in 0..30 {
+            sleep(Duration::from_millis(1000));
+
+            match ureq::get("http://localhost:6785/tasks").call() {
+                Ok(mut response) => {
+                    let json_text = response
+                        .body_mut()
+                        .read_to_string()
+                        .expect("Failed to read response body");
+                    let parsed: TasksJson =
+                        serde_json::from_str(&json_text).expect("Failed to parse tasks JSON");
+                    last_error = None;
+                    if parsed.total_spawned == 4 && parsed.live == 0 {
+                        tasks = Some(parsed);
+                        break;
+                    }
+                }
+                Err(e) => {
+                    last_error = Some(format!("Request error: {}", e));
+                }
+            }
+        }
+
+        let _ = child.kill();
+        let _ = child.wait();
+
+        if let Some(error) = last_error {
+            panic!("Failed after 30 retries: {}", error);
+        }
+        let tasks = tasks.expect("Expected 4 finished tasks");
+
+        let task = |label: &str| tasks.tasks.iter().find(|t| t.label == label).unwrap();
+        let supervisor = task("supervisor");
+        assert_eq!(supervisor.state, TaskState::Completed);
+        assert!(supervisor.source.contains("spawned_tasks_smol.rs:"));
+
+        for label in ["worker", "checksum", "stuck"] {
+            assert_eq!(task(label).parent_id, Some(supervisor.id), "{label}");
+        }
+
+        let worker = task("worker");
+        assert_eq!(worker.kind, TaskKind::Async);
+        assert_eq!(worker.state, TaskState::Completed);
+        assert!(!worker.threads.is_empty());
+
+        let checksum = task("checksum");
+        assert_eq!(checksum.kind, TaskKind::Blocking);
+        assert_eq!(checksum.state, TaskState::Completed);
+
+        // Cancelled with Task::cancel
+        assert_eq!(task("stuck").state, TaskState::Cancelled);
+    }
+
+    // cargo run -p test-smol-async --example spawned_tasks_smol
+    #[test]
+    fn test_spawn_smol_disabled() {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "-p",
+                "test-smol-async",
+                "--example",
+                "spawned_tasks_smol",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Command failed with status: {}\n{}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+}
@@ -0,0 +1,38 @@
+use std::thread;
+use std::time::Duration;
+
+#[allow(unused_mut)]
+fn main() {
+    let _channels_guard =
+        hotpath::channels::ChannelsGuard::new().format(hotpath::Format::JsonPretty);
+
+    let (txa, mut _rxa) = hotpath::channel!(async_channel::unbounded::<i32>(), label = "unbounded");
+
+    let (txb, rxb) = hotpath::channel!(async_channel::bounded::<i32>(10), label = "bounded");
+
+    let sender_handle = thread::spawn(move || {
+        for i in 1..=3 {
+            println!("[Sender] Sending to unbounded: {}", i);
+            txa.send_blocking(i).expect("Failed to send");
+            thread::sleep(Duration::from_millis(100));
+        }
+
+        for i in 1..=3 {
+            println!("[Sender] Sending to bounded: {}", i);
+            txb.send_blocking(i).expect("Failed to send");
+            thread::sleep(Duration::from_millis(250));
+        }
+
+        println!("[Sender] Done sending messages");
+    });
+
+    sender_handle.join().unwrap();
+
+    smol::block_on(async {
+        while let Ok(msg) = rxb.recv().await {
+            println!("[Receiver] Received message: {}", msg);
+        }
+    });
+
+    println!("\nExample completed!");
+}
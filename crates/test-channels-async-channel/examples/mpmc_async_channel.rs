@@ -0,0 +1,60 @@
+use std::thread;
+use std::time::Duration;
+
+fn main() {
+    let _channels_guard =
+        hotpath::channels::ChannelsGuard::new().format(hotpath::Format::JsonPretty);
+
+    let (tx, rx) = hotpath::channel!(async_channel::bounded::<u32>(4), label = "jobs");
+
+    // Sync and async consumers share the same channel through receiver clones
+    let mut handles = vec![];
+    for worker in 0..2 {
+        let rx = rx.clone();
+        handles.push(thread::spawn(move || {
+            let mut processed = 0;
+            while let Ok(job) = rx.recv_blocking() {
+                println!("[Sync worker {}] Processing job {}", worker, job);
+                processed += 1;
+            }
+            processed
+        }));
+    }
+
+    let async_rx = rx;
+    handles.push(thread::spawn(move || {
+        smol::block_on(async move {
+            let mut processed = 0;
+            while let Ok(job) = async_rx.recv().await {
+                println!("[Async worker] Processing job {}", job);
+                smol::Timer::after(Duration::from_millis(1)).await;
+                processed += 1;
+            }
+            processed
+        })
+    }));
+
+    // Producers on both ends of the API
+    let async_tx = tx.clone();
+    let async_producer = thread::spawn(move || {
+        smol::block_on(async move {
+            for job in 0..10 {
+                async_tx.send(job).await.expect("Failed to send");
+            }
+        })
+    });
+    for job in 10..20 {
+        tx.send_blocking(job).expect("Failed to send");
+    }
+    async_producer.join().unwrap();
+    drop(tx);
+
+    let processed: u32 = handles.into_iter().map(|h| h.join().unwrap()).sum();
+    println!("Processed {} jobs", processed);
+
+    thread::sleep(Duration::from_millis(100));
+
+    drop(_channels_guard);
+
+    println!("\nExample completed!");
+}
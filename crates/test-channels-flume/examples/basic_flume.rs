@@ -0,0 +1,56 @@
+#[allow(unused_mut)]
+fn main() {
+    let _channels_guard = hotpath::channels::ChannelsGuard::new();
+
+    let (txa, _rxa) = hotpath::channel!(flume::unbounded::<i32>(), log = true);
+
+    let (txb, rxb) = hotpath::channel!(flume::bounded::<i32>(10));
+
+    let (txc, rxc) = hotpath::channel!(flume::bounded::<String>(1), label = "hello-there");
+
+    let sender_handle = std::thread::spawn(move || {
+        smol::block_on(async {
+            for i in 1..=3 {
+                println!("[Sender] Sending message: {}", i);
+                txa.send_async(i).await.expect("Failed to send");
+                smol::Timer::after(std::time::Duration::from_millis(100)).await;
+            }
+
+            for i in 1..=3 {
+                println!("[Sender] Sending message: {}", i);
+                txb.send_async(i).await.expect("Failed to send");
+                smol::Timer::after(std::time::Duration::from_millis(250)).await;
+            }
+        });
+
+        println!("[Sender] Done sending messages");
+    });
+
+    let bounded_receiver_handle = std::thread::spawn(move || match rxc.recv() {
+        Ok(msg) => println!("[Bounded-1] Received: {}", msg),
+        Err(_) => println!("[Bounded-1] Sender dropped"),
+    });
+
+    println!("[Bounded-1] Sending message");
+    txc.send("Hello from bounded channel!".to_string())
+        .expect("Failed to send");
+
+    sender_handle.join().expect("Sender thread failed");
+    bounded_receiver_handle
+        .join()
+        .expect("Bounded receiver thread failed");
+
+    drop(_channels_guard);
+
+    while let Ok(msg) = rxb.recv() {
+        println!("[Receiver] Received message: {}", msg);
+    }
+
+    if let Ok(secs) = std::env::var("TEST_SLEEP_SECONDS") {
+        if let Ok(duration) = secs.parse::<u64>() {
+            std::thread::sleep(std::time::Duration::from_secs(duration));
+        }
+    }
+
+    println!("\nExample completed!");
+}
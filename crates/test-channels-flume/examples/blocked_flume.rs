@@ -0,0 +1,26 @@
+use std::thread;
+use std::time::Duration;
+
+fn main() {
+    let _channels_guard =
+        hotpath::channels::ChannelsGuard::new().format(hotpath::Format::JsonPretty);
+
+    let (tx, rx) = hotpath::channel!(flume::bounded::<u64>(4), label = "blocked", capacity = 4);
+
+    // The consumer stalls, so the producer fills the channel and blocks
+    let producer = thread::spawn(move || {
+        for i in 0..20 {
+            tx.send(i).expect("Failed to send");
+        }
+    });
+
+    thread::sleep(Duration::from_millis(100));
+    for _ in 0..20 {
+        rx.recv().expect("Failed to receive");
+    }
+    producer.join().unwrap();
+
+    thread::sleep(Duration::from_millis(50));
+
+    println!("\nExample completed!");
+}
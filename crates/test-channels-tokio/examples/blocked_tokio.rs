@@ -0,0 +1,29 @@
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+
+#[tokio::main]
+async fn main() {
+    let _channels_guard = hotpath::channels::ChannelsGuardBuilder::new()
+        .format(hotpath::Format::JsonPretty)
+        .build();
+
+    let (tx, mut rx) = hotpath::channel!(mpsc::channel::<u64>(4), label = "blocked");
+
+    // The consumer stalls, so the producer fills the channel and blocks
+    let producer = tokio::spawn(async move {
+        for i in 0..20 {
+            tx.send(i).await.expect("Failed to send");
+        }
+    });
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    for _ in 0..20 {
+        rx.recv().await.expect("Failed to receive");
+    }
+    producer.await.expect("Producer task failed");
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    println!("\nExample completed!");
+}
@@ -0,0 +1,62 @@
+use tokio::sync::{broadcast, watch};
+
+#[tokio::main]
+async fn main() {
+    let _channels_guard = hotpath::channels::ChannelsGuardBuilder::new()
+        .format(hotpath::Format::JsonPretty)
+        .build();
+
+    let (events_tx, mut fast_rx) =
+        hotpath::channel!(broadcast::channel::<u32>(4), label = "events", capacity = 4);
+    let mut slow_rx = events_tx.subscribe();
+
+    // Instrumenting doesn't keep the channel open once every receiver is gone
+    let (alerts_tx, alerts_rx) = hotpath::channel!(broadcast::channel::<u32>(4), label = "alerts");
+    drop(alerts_rx);
+    assert!(
+        alerts_tx.send(1).is_err(),
+        "Expected send to fail without receivers"
+    );
+    assert_eq!(alerts_tx.receiver_count(), 0);
+
+    let (config_tx, mut config_rx) = hotpath::channel!(watch::channel::<u32>(0), label = "config");
+
+    let fast_handle = tokio::spawn(async move {
+        while let Ok(event) = fast_rx.recv().await {
+            println!("[Fast] Received event: {}", event);
+        }
+    });
+
+    let config_handle = tokio::spawn(async move {
+        while config_rx.changed().await.is_ok() {
+            println!("[Config] Version: {}", *config_rx.borrow_and_update());
+        }
+    });
+
+    for i in 1..=10 {
+        events_tx.send(i).expect("Failed to broadcast");
+        tokio::time::sleep(tokio::time::Duration::from_millis(20)).await;
+    }
+
+    for version in 1..=3 {
+        config_tx.send(version).expect("Failed to update config");
+        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+    }
+
+    // Let the monitors sample the slow receiver lag
+    tokio::time::sleep(tokio::time::Duration::from_millis(300)).await;
+
+    match slow_rx.recv().await {
+        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+            println!("[Slow] Lagged by {} events", skipped)
+        }
+        other => println!("[Slow] {:?}", other),
+    }
+
+    drop(events_tx);
+    drop(config_tx);
+    fast_handle.await.expect("Fast receiver task failed");
+    config_handle.await.expect("Config task failed");
+
+    println!("\nExample completed!");
+}
@@ -0,0 +1,80 @@
+use hotpath::channels::direct::tokio::Sender;
+use tokio::sync::mpsc;
+
+async fn produce(tx: Sender<u32>, jobs: std::ops::RangeInclusive<u32>) {
+    for job in jobs {
+        tx.send(job).await.expect("Failed to send");
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let _channels_guard = hotpath::channels::ChannelsGuardBuilder::new()
+        .format(hotpath::Format::JsonPretty)
+        .build();
+
+    let (tx, mut rx) = hotpath::channel!(
+        mpsc::channel::<u32>(2),
+        label = "direct-bounded",
+        proxy = false
+    );
+
+    // Capacity is the same as without instrumentation, the third message doesn't fit
+    tx.try_send(1).expect("Failed to send");
+    tx.try_send(2).expect("Failed to send");
+    if tx.try_send(3).is_err() {
+        println!("[Bounded] Third try_send rejected, channel full");
+    }
+
+    let producer = tokio::spawn(produce(tx.clone(), 3..=10));
+    drop(tx);
+
+    let mut total = 0;
+    while let Some(job) = rx.recv().await {
+        total += job;
+    }
+    producer.await.expect("Producer task failed");
+    println!("[Bounded] Received total: {}", total);
+
+    let (log_tx, mut log_rx) = hotpath::channel!(
+        mpsc::unbounded_channel::<String>(),
+        label = "direct-unbounded",
+        log = true,
+        proxy = false
+    );
+    for i in 0..3 {
+        log_tx.send(format!("event-{}", i)).expect("Failed to send");
+    }
+    let mut events = Vec::new();
+    log_rx.recv_many(&mut events, 10).await;
+    println!("[Unbounded] Received {} events", events.len());
+
+    // Producer outpaces the consumer and waits for capacity
+    let (blocked_tx, mut blocked_rx) = hotpath::channel!(
+        mpsc::channel::<u32>(1),
+        label = "direct-blocked",
+        proxy = false
+    );
+    let consumer = tokio::spawn(async move {
+        while blocked_rx.recv().await.is_some() {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+    });
+    for i in 0..5 {
+        blocked_tx.send(i).await.expect("Failed to send");
+    }
+    drop(blocked_tx);
+    consumer.await.expect("Consumer task failed");
+
+    let (queued_tx, _queued_rx) = hotpath::channel!(
+        mpsc::channel::<u32>(4),
+        label = "direct-queued",
+        proxy = false
+    );
+    queued_tx.send(1).await.expect("Failed to send");
+    queued_tx.send(2).await.expect("Failed to send");
+
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+    println!("\nExample completed!");
+}
@@ -0,0 +1,70 @@
+use hotpath::channels::direct::tokio::{Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+#[hotpath::measure]
+fn source(tx: Sender<u32>) {
+    for job in 0..20 {
+        tx.blocking_send(job).expect("Failed to send");
+    }
+}
+
+#[hotpath::measure]
+fn parse(rx: Arc<Mutex<Receiver<u32>>>, tx: Sender<String>) {
+    loop {
+        let job = rx.lock().unwrap().blocking_recv();
+        match job {
+            Some(job) => tx
+                .blocking_send(format!("job-{}", job))
+                .expect("Failed to send"),
+            None => break,
+        }
+        // Let the other worker take the next job
+        thread::sleep(Duration::from_millis(5));
+    }
+}
+
+#[hotpath::main]
+fn main() {
+    let (raw_tx, raw_rx) =
+        hotpath::channel!(mpsc::channel::<u32>(4), label = "graph-raw", proxy = false);
+    let (parsed_tx, mut parsed_rx) = hotpath::channel!(
+        mpsc::channel::<String>(4),
+        label = "graph-parsed",
+        proxy = false
+    );
+
+    let source_handle = thread::spawn(move || source(raw_tx));
+
+    let raw_rx = Arc::new(Mutex::new(raw_rx));
+    let workers: Vec<_> = (0..2)
+        .map(|_| {
+            let raw_rx = raw_rx.clone();
+            let parsed_tx = parsed_tx.clone();
+            thread::spawn(move || parse(raw_rx, parsed_tx))
+        })
+        .collect();
+    drop(parsed_tx);
+
+    // Consumed by the measured main function
+    let mut parsed = 0;
+    while parsed_rx.blocking_recv().is_some() {
+        parsed += 1;
+    }
+
+    source_handle.join().expect("Source thread panicked");
+    for worker in workers {
+        worker.join().expect("Parse thread panicked");
+    }
+    println!("[Graph] Parsed {} jobs", parsed);
+
+    let sleep_secs: u64 = std::env::var("TEST_SLEEP_SECONDS")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0);
+    thread::sleep(Duration::from_secs(sleep_secs));
+
+    println!("\nExample completed!");
+}
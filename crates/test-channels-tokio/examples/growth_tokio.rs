@@ -0,0 +1,39 @@
+use tokio::sync::mpsc;
+
+#[tokio::main]
+async fn main() {
+    let _channels_guard = hotpath::channels::ChannelsGuardBuilder::new()
+        .format(hotpath::Format::JsonPretty)
+        .build();
+
+    let (tx, mut rx) = hotpath::channel!(
+        mpsc::unbounded_channel::<u64>(),
+        label = "growing-queue",
+        proxy = false
+    );
+
+    // Two bursts with a drained queue in between
+    for burst in [50, 20] {
+        for i in 0..burst {
+            tx.send(i).expect("Failed to send");
+        }
+        for _ in 0..burst {
+            rx.recv().await.expect("Failed to receive");
+        }
+    }
+
+    // Messages behind the proxy are sampled while the consumer is stalled
+    let (proxied_tx, mut proxied_rx) =
+        hotpath::channel!(mpsc::channel::<u64>(100), label = "proxied-queue");
+    for i in 0..50 {
+        proxied_tx.send(i).await.expect("Failed to send");
+    }
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    for _ in 0..50 {
+        proxied_rx.recv().await.expect("Failed to receive");
+    }
+
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+    println!("\nExample completed!");
+}
@@ -0,0 +1,67 @@
+use tokio::sync::mpsc;
+
+#[derive(hotpath::HeapSize)]
+struct Frame {
+    id: u64,
+    payload: Vec<u8>,
+}
+
+#[tokio::main]
+async fn main() {
+    let _channels_guard = hotpath::channels::ChannelsGuardBuilder::new()
+        .format(hotpath::Format::JsonPretty)
+        .build();
+
+    // Three frames stay queued, each holding a 1 KiB payload on the heap
+    let (frames_tx, mut frames_rx) = hotpath::channel!(
+        mpsc::channel::<Frame>(8),
+        label = "sized-frames",
+        proxy = false,
+        size = true
+    );
+    for id in 0..4 {
+        frames_tx
+            .send(Frame {
+                id,
+                payload: vec![0; 1024],
+            })
+            .await
+            .expect("Failed to send");
+    }
+    let frame = frames_rx.recv().await.expect("Failed to receive");
+    println!(
+        "[Frames] Received frame {} with {} bytes",
+        frame.id,
+        frame.payload.len()
+    );
+    // The derived impl exists with profiling disabled too
+    assert_eq!(hotpath::HeapSize::heap_size(&frame), 1024);
+
+    // Same payloads without size = true only count the Vec header
+    let (unsized_tx, _unsized_rx) = hotpath::channel!(
+        mpsc::channel::<Vec<u8>>(8),
+        label = "unsized-frames",
+        proxy = false
+    );
+    for _ in 0..3 {
+        unsized_tx
+            .send(vec![0; 1024])
+            .await
+            .expect("Failed to send");
+    }
+
+    let (text_tx, mut text_rx) = hotpath::channel!(
+        mpsc::unbounded_channel::<String>(),
+        label = "sized-text",
+        size = true
+    );
+    for _ in 0..3 {
+        text_tx.send("x".repeat(100)).expect("Failed to send");
+    }
+    drop(text_tx);
+    while text_rx.recv().await.is_some() {}
+
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+    println!("\nExample completed!");
+}
@@ -85,6 +85,16 @@ async fn main() {
     let _result = attributed_no_log().await;
     let _result = attributed_with_log().await;
 
+    for request_id in [7, 8] {
+        let _result = future!(
+            slow_operation(),
+            label = "tagged_request",
+            tags(request_id = request_id, kind = "demo")
+        )
+        .await;
+    }
+    let _result = attributed_with_tags(9).await;
+
     tokio::time::sleep(Duration::from_millis(10)).await;
 
     // For testing: allow configurable sleep to keep server running
@@ -94,3 +104,9 @@ async fn main() {
         }
     }
 }
+
+#[hotpath::future_fn(label = "attributed_tagged", tags(request_id = request_id))]
+async fn attributed_with_tags(request_id: u64) -> u64 {
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    request_id
+}
@@ -0,0 +1,62 @@
+//! Example demonstrating slow poll detection of blocking calls inside async code.
+//!
+//! Run with: HOTPATH_SLOW_POLL_MS=20 cargo run -p test-futures --example slow_polls --features hotpath
+
+use hotpath::future;
+use std::time::Duration;
+
+#[hotpath::measure]
+async fn blocking_function() {
+    // Blocks the executor thread instead of yielding
+    std::thread::sleep(Duration::from_millis(40));
+    tokio::time::sleep(Duration::from_millis(5)).await;
+}
+
+#[hotpath::measure]
+async fn yielding_function() {
+    tokio::time::sleep(Duration::from_millis(40)).await;
+}
+
+#[hotpath::measure]
+async fn nested_function() {
+    // Reported once, by the innermost future
+    future!(
+        async {
+            std::thread::sleep(Duration::from_millis(40));
+        },
+        label = "nested_future"
+    )
+    .await;
+}
+
+#[tokio::main(flavor = "current_thread")]
+#[hotpath::main]
+async fn main() {
+    future!(
+        async {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+            std::thread::sleep(Duration::from_millis(50));
+        },
+        label = "blocking_future"
+    )
+    .await;
+
+    future!(
+        async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        },
+        label = "sleeping_future"
+    )
+    .await;
+
+    blocking_function().await;
+    yielding_function().await;
+    nested_function().await;
+
+    // For testing: allow configurable sleep to keep server running
+    if let Ok(secs) = std::env::var("TEST_SLEEP_SECONDS") {
+        if let Ok(secs) = secs.parse::<u64>() {
+            tokio::time::sleep(Duration::from_secs(secs)).await;
+        }
+    }
+}
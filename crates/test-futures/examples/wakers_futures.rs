@@ -0,0 +1,89 @@
+//! Example demonstrating waker instrumentation: self-wakes, wakes after completion and
+//! futures returning `Pending` without retaining the waker.
+//!
+//! Run with: cargo run -p test-futures --example wakers_futures --features hotpath
+
+use hotpath::future;
+use hotpath::futures::{Format, FuturesGuardBuilder};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+
+/// Returns `Pending` on the first poll without registering the waker anywhere.
+struct ForgetsWaker {
+    polled: bool,
+}
+
+impl Future for ForgetsWaker {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+        if self.polled {
+            return Poll::Ready(());
+        }
+        self.polled = true;
+        Poll::Pending
+    }
+}
+
+/// Completes right away, leaving a clone of its waker behind.
+struct StashesWaker {
+    slot: Arc<Mutex<Option<Waker>>>,
+}
+
+impl Future for StashesWaker {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        *self.slot.lock().unwrap() = Some(cx.waker().clone());
+        Poll::Ready(())
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let _guard = FuturesGuardBuilder::new()
+        .format(Format::JsonPretty)
+        .build();
+
+    future!(
+        async {
+            for _ in 0..3 {
+                tokio::task::yield_now().await;
+            }
+        },
+        label = "yielding"
+    )
+    .await;
+
+    future!(
+        async {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        },
+        label = "sleeping"
+    )
+    .await;
+
+    // Only completes because the timeout polls it again once its own timer fires
+    let _ = tokio::time::timeout(
+        Duration::from_millis(20),
+        future!(ForgetsWaker { polled: false }, label = "forgetful"),
+    )
+    .await;
+
+    let slot = Arc::new(Mutex::new(None));
+    future!(
+        StashesWaker {
+            slot: Arc::clone(&slot),
+        },
+        label = "stashing"
+    )
+    .await;
+    if let Some(waker) = slot.lock().unwrap().take() {
+        waker.wake();
+    }
+
+    tokio::time::sleep(Duration::from_millis(10)).await;
+}
@@ -0,0 +1,52 @@
+//! Example demonstrating task tracking with `spawn!` and `spawn_blocking!` on smol.
+//!
+//! Run with: cargo run -p test-smol-async --example spawned_tasks_smol --features hotpath
+//! Then: curl localhost:6770/tasks
+
+use std::time::Duration;
+
+#[hotpath::main]
+fn main() {
+    smol::block_on(async {
+        let supervisor = hotpath::spawn!(
+            "supervisor",
+            async {
+                let worker = hotpath::spawn!(
+                    "worker",
+                    async {
+                        smol::Timer::after(Duration::from_millis(10)).await;
+                        42
+                    },
+                    runtime = smol
+                );
+                assert_eq!(worker.await, 42);
+
+                let checksum = hotpath::spawn_blocking!(
+                    "checksum",
+                    || (0..1000u64).sum::<u64>(),
+                    runtime = smol
+                );
+                assert_eq!(checksum.await, 499_500);
+
+                let stuck = hotpath::spawn!(
+                    "stuck",
+                    async {
+                        smol::Timer::after(Duration::from_secs(3600)).await;
+                    },
+                    runtime = smol
+                );
+                smol::Timer::after(Duration::from_millis(10)).await;
+                assert!(stuck.cancel().await.is_none());
+            },
+            runtime = smol
+        );
+        supervisor.await;
+
+        // For testing: allow configurable sleep to keep server running
+        if let Ok(secs) = std::env::var("TEST_SLEEP_SECONDS") {
+            if let Ok(secs) = secs.parse::<u64>() {
+                smol::Timer::after(Duration::from_secs(secs)).await;
+            }
+        }
+    })
+}
@@ -14,27 +14,38 @@ fn main() {
         let numbers: Vec<i32> = stream.collect().await;
         println!("[Stream 1] Collected: {:?}", numbers);
 
-        // Example 2: Stream with logging enabled
+        // Example 2: Stream with heap sizes of yielded items
         let stream2 = hotpath::stream!(
+            stream::iter((0..3).map(|_| "x".repeat(64))),
+            label = "sized-stream",
+            size = true
+        );
+
+        println!("\n[Stream 2] Collecting strings...");
+        let strings: Vec<String> = stream2.collect().await;
+        println!("[Stream 2] Collected {} strings", strings.len());
+
+        // Example 3: Stream with logging enabled
+        let stream3 = hotpath::stream!(
             stream::iter(vec!["hello", "world", "from", "streams"]),
             label = "text-stream",
             log = true
         );
 
-        println!("\n[Stream 2] Processing text...");
-        stream2
+        println!("\n[Stream 3] Processing text...");
+        stream3
             .for_each(|text| async move {
-                println!("[Stream 2] Yielded: {}", text);
+                println!("[Stream 3] Yielded: {}", text);
                 Timer::after(Duration::from_millis(100)).await;
             })
             .await;
 
-        // Example 3: Infinite stream (take first 3)
-        let stream3 = hotpath::stream!(stream::repeat(42).take(3), label = "repeat-stream");
+        // Example 4: Infinite stream (take first 3)
+        let stream4 = hotpath::stream!(stream::repeat(42).take(3), label = "repeat-stream");
 
-        println!("\n[Stream 3] Taking from infinite stream...");
-        let repeated: Vec<i32> = stream3.collect().await;
-        println!("[Stream 3] Collected: {:?}", repeated);
+        println!("\n[Stream 4] Taking from infinite stream...");
+        let repeated: Vec<i32> = stream4.collect().await;
+        println!("[Stream 4] Collected: {:?}", repeated);
 
         println!("\nStream example completed!");
 
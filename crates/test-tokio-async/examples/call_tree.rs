@@ -0,0 +1,25 @@
+use std::time::Duration;
+
+#[hotpath::measure]
+fn db_query(sleep_ms: u64) {
+    std::thread::sleep(Duration::from_millis(sleep_ms));
+}
+
+#[hotpath::measure]
+fn api_handler() {
+    db_query(5);
+    std::thread::sleep(Duration::from_millis(1));
+}
+
+#[hotpath::measure]
+fn worker_sync() {
+    db_query(1);
+}
+
+#[hotpath::main(format = "json")]
+fn main() {
+    for _ in 0..3 {
+        api_handler();
+        worker_sync();
+    }
+}
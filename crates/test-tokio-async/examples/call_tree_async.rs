@@ -0,0 +1,29 @@
+use std::time::Duration;
+
+#[hotpath::measure]
+fn parse(sleep_ms: u64) {
+    std::thread::sleep(Duration::from_millis(sleep_ms));
+}
+
+#[hotpath::measure]
+async fn fetch_users() {
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    parse(3);
+    tokio::time::sleep(Duration::from_millis(10)).await;
+}
+
+#[hotpath::measure]
+async fn fetch_orders() {
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    parse(1);
+    tokio::time::sleep(Duration::from_millis(10)).await;
+}
+
+// Both functions are polled in turns on the same thread
+#[tokio::main(flavor = "current_thread")]
+#[hotpath::main(format = "json")]
+async fn main() {
+    for _ in 0..3 {
+        tokio::join!(fetch_users(), fetch_orders());
+    }
+}
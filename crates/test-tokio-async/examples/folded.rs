@@ -0,0 +1,22 @@
+use std::time::Duration;
+
+#[hotpath::measure]
+fn db_query(sleep_ms: u64) {
+    let buffer = Vec::<u8>::with_capacity(1024);
+    std::hint::black_box(&buffer);
+    std::thread::sleep(Duration::from_millis(sleep_ms));
+}
+
+#[hotpath::measure]
+fn api_handler() {
+    db_query(2);
+    std::thread::sleep(Duration::from_millis(1));
+}
+
+#[hotpath::main(format = "folded")]
+fn main() {
+    for _ in 0..3 {
+        api_handler();
+        db_query(1);
+    }
+}
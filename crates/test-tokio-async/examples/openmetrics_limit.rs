@@ -0,0 +1,28 @@
+//! `limit` only truncates reports, the `/metrics` exposition lists every function.
+//!
+//! Run with: cargo run -p test-tokio-async --example openmetrics_limit --features hotpath
+//! Then: curl localhost:6770/metrics
+
+#[hotpath::measure]
+fn slow_function() {
+    std::thread::sleep(std::time::Duration::from_millis(2));
+}
+
+#[hotpath::measure]
+fn fast_function() {}
+
+#[hotpath::main(limit = 1)]
+fn main() {
+    // Two full batches of measurements, so they reach the worker before main returns
+    for _ in 0..64 {
+        slow_function();
+        fast_function();
+    }
+
+    // For testing: allow configurable sleep to keep server running
+    if let Ok(secs) = std::env::var("TEST_SLEEP_SECONDS") {
+        if let Ok(secs) = secs.parse::<u64>() {
+            std::thread::sleep(std::time::Duration::from_secs(secs));
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+#[hotpath::measure]
+fn parse_job(job: u32) -> u32 {
+    std::thread::sleep(Duration::from_millis(1));
+    job * 2
+}
+
+#[hotpath::measure]
+async fn handle_request(job: u32) -> u32 {
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    parse_job(job)
+}
+
+// Reads the collector address from OTEL_EXPORTER_OTLP_ENDPOINT (default http://localhost:4318)
+#[tokio::main]
+async fn main() {
+    let reporter = hotpath::OtlpReporterBuilder::new()
+        .service_name("otlp-example")
+        .interval(Duration::from_millis(200))
+        .build();
+
+    let _hotpath = hotpath::FunctionsGuardBuilder::new("main")
+        .reporter(Box::new(reporter))
+        .build();
+
+    let (tx, mut rx) = hotpath::channel!(mpsc::channel::<u32>(32), label = "jobs");
+
+    for job in 0..20 {
+        tx.send(job).await.unwrap();
+    }
+    drop(tx);
+
+    let mut total = 0;
+    while let Some(job) = rx.recv().await {
+        total += handle_request(job).await;
+    }
+    std::hint::black_box(total);
+}
@@ -0,0 +1,13 @@
+use std::time::Duration;
+
+#[hotpath::measure]
+fn example_function() {
+    std::thread::sleep(Duration::from_millis(30));
+}
+
+#[hotpath::main(format = "json", report_every = 100)]
+fn main() {
+    for _ in 0..10 {
+        example_function();
+    }
+}
@@ -0,0 +1,57 @@
+//! Example demonstrating tokio runtime metrics: worker busy time, parks and queues, next to
+//! the CPU usage of the worker threads.
+//!
+//! Run with: cargo run -p test-tokio-async --example runtime_metrics --features tokio-runtime-metrics
+//! Then: curl localhost:6770/runtime
+
+use std::time::{Duration, Instant};
+
+#[hotpath::measure]
+async fn spin(duration: Duration) {
+    // Keeps the worker busy and on the CPU
+    let start = Instant::now();
+    while start.elapsed() < duration {
+        std::hint::black_box(0u64.wrapping_add(1));
+    }
+    tokio::task::yield_now().await;
+}
+
+#[hotpath::measure]
+async fn block(duration: Duration) {
+    // Keeps the worker busy, but off the CPU
+    std::thread::sleep(duration);
+    tokio::task::yield_now().await;
+}
+
+#[tokio::main(worker_threads = 2)]
+#[hotpath::main]
+async fn main() {
+    let sleep_secs = std::env::var("TEST_SLEEP_SECONDS")
+        .ok()
+        .and_then(|secs| secs.parse::<u64>().ok())
+        .unwrap_or(3);
+    let deadline = Instant::now() + Duration::from_secs(sleep_secs);
+
+    let spinning = tokio::spawn(async move {
+        while Instant::now() < deadline {
+            spin(Duration::from_millis(20)).await;
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    });
+
+    let blocking = tokio::spawn(async move {
+        while Instant::now() < deadline {
+            block(Duration::from_millis(20)).await;
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    });
+
+    // Runs on a blocking pool thread, named like the workers but not one of them
+    let blocking_pool = tokio::task::spawn_blocking(move || {
+        while Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(20));
+        }
+    });
+
+    let _ = tokio::join!(spinning, blocking, blocking_pool);
+}
@@ -0,0 +1,57 @@
+//! Example demonstrating task tracking with `spawn!` and `spawn_blocking!`: spawn locations,
+//! parent tasks, worker threads and how each task ended.
+//!
+//! Run with: cargo run -p test-tokio-async --example spawned_tasks --features hotpath
+//! Then: curl localhost:6770/tasks
+
+use std::time::Duration;
+
+#[tokio::main(worker_threads = 2)]
+#[hotpath::main]
+async fn main() {
+    let supervisor = hotpath::spawn!("supervisor", async {
+        let worker = hotpath::spawn!("worker", async {
+            for _ in 0..3 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+            42
+        });
+        assert_eq!(worker.await.unwrap(), 42);
+
+        let checksum = hotpath::spawn_blocking!("checksum", || {
+            std::thread::sleep(Duration::from_millis(20));
+            (0..1000u64).sum::<u64>()
+        });
+        assert_eq!(checksum.await.unwrap(), 499_500);
+
+        let failing = hotpath::spawn!("failing", async {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            panic!("connection reset");
+        });
+        assert!(failing.await.unwrap_err().is_panic());
+
+        let stuck = hotpath::spawn!("stuck", async {
+            tokio::time::sleep(Duration::from_secs(3600)).await;
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        stuck.abort();
+        assert!(stuck.await.unwrap_err().is_cancelled());
+    });
+    supervisor.await.unwrap();
+
+    // Still running when the program exits
+    for i in 0..2 {
+        hotpath::spawn!(format!("listener-{}", i), async {
+            loop {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        });
+    }
+
+    // For testing: allow configurable sleep to keep server running
+    if let Ok(secs) = std::env::var("TEST_SLEEP_SECONDS") {
+        if let Ok(secs) = secs.parse::<u64>() {
+            tokio::time::sleep(Duration::from_secs(secs)).await;
+        }
+    }
+}
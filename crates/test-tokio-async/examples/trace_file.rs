@@ -0,0 +1,40 @@
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+#[hotpath::measure]
+fn parse_job(job: u32) -> u32 {
+    std::thread::sleep(Duration::from_millis(1));
+    job * 2
+}
+
+#[hotpath::measure]
+async fn handle_request(job: u32) -> u32 {
+    let parsed = parse_job(job);
+    hotpath::future!(async {
+        tokio::time::sleep(Duration::from_millis(1)).await;
+    })
+    .await;
+    parsed
+}
+
+#[tokio::main]
+#[hotpath::main]
+async fn main() {
+    let (tx, mut rx) = hotpath::channel!(mpsc::channel::<u32>(10), label = "jobs");
+
+    let consumer = tokio::spawn(async move {
+        let mut total = 0;
+        while let Some(job) = rx.recv().await {
+            total += handle_request(job).await;
+        }
+        total
+    });
+
+    for job in 0..3 {
+        tx.send(job).await.unwrap();
+    }
+    drop(tx);
+
+    let total = consumer.await.unwrap();
+    std::hint::black_box(total);
+}